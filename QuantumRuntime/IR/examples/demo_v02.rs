@@ -138,7 +138,7 @@ fn demo_runtime_execution() {
         100,  // shots
         Priority::Normal,
         JobMetadata::new().with_user("demo_user"),
-    );
+    ).unwrap();
     
     println!("   Job ID: {}", job_id);
     println!("   Status: {:?}", runtime.get_job_status(job_id));
@@ -2,9 +2,18 @@
 //! 
 //! 定义异步后端适配器接口
 
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
 use crate::circuit::CircuitDag;
+use crate::future::{block_on, block_on_timeout, poll_until, JobFuture};
 use crate::job::{Job, JobId, JobResult, JobStatus};
-use crate::qubit::QubitMapping;
+use crate::observable::Observable;
+use crate::operation::Operation;
+use crate::params::ParamBinding;
+use crate::qubit::{LogicalQubitId, QubitMapping};
+use crate::statevector::{evolve, final_state, strip_measurements, StateVector};
 use crate::{Result, IrError};
 
 // ============================================================================
@@ -44,6 +53,8 @@ pub struct BackendCapabilities {
     pub coupling_map: Option<CouplingMap>,
     /// 错误模型（可选）
     pub error_model: Option<ErrorModel>,
+    /// 门时长（可选，用于时序调度）
+    pub gate_durations: Option<GateDurations>,
 }
 
 /// 后端类型
@@ -164,6 +175,93 @@ impl ErrorModel {
     }
 }
 
+/// 门时长表（纳秒）
+/// 
+/// 按门的比特数给出默认时长，可按门名称单独覆盖
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateDurations {
+    /// 单比特门时长
+    pub single_qubit_ns: f64,
+    /// 双比特门时长
+    pub two_qubit_ns: f64,
+    /// 三比特门时长
+    pub three_qubit_ns: f64,
+    /// 测量时长
+    pub measurement_ns: f64,
+    /// 重置时长
+    pub reset_ns: f64,
+    /// 按门名称覆盖的时长
+    pub overrides: std::collections::HashMap<String, f64>,
+}
+
+impl GateDurations {
+    pub fn new(
+        single_qubit_ns: f64,
+        two_qubit_ns: f64,
+        three_qubit_ns: f64,
+        measurement_ns: f64,
+        reset_ns: f64,
+    ) -> Self {
+        Self {
+            single_qubit_ns,
+            two_qubit_ns,
+            three_qubit_ns,
+            measurement_ns,
+            reset_ns,
+            overrides: std::collections::HashMap::new(),
+        }
+    }
+    
+    /// 超导设备典型时长
+    pub fn superconducting() -> Self {
+        Self::new(35.0, 300.0, 900.0, 1_000.0, 1_000.0)
+    }
+    
+    /// 离子阱设备典型时长
+    pub fn ion_trap() -> Self {
+        Self::new(10_000.0, 200_000.0, 600_000.0, 100_000.0, 50_000.0)
+    }
+    
+    /// 所有操作单位时长（等价于按层计数）
+    pub fn unit() -> Self {
+        Self::new(1.0, 1.0, 1.0, 1.0, 1.0)
+    }
+    
+    /// 按门名称覆盖时长
+    pub fn with_override(mut self, gate_name: impl Into<String>, duration_ns: f64) -> Self {
+        self.overrides.insert(gate_name.into(), duration_ns);
+        self
+    }
+    
+    /// 获取操作时长
+    pub fn duration(&self, op: &Operation) -> f64 {
+        if let Some(&d) = self.overrides.get(op.name()) {
+            return d;
+        }
+        match op {
+            Operation::Gate1 { .. } => self.single_qubit_ns,
+            Operation::Gate2 { .. } => self.two_qubit_ns,
+            Operation::Gate3 { .. } => self.three_qubit_ns,
+            Operation::Measure { .. } => self.measurement_ns,
+            Operation::Reset { .. } => self.reset_ns,
+            Operation::Barrier { .. } => 0.0,
+            Operation::Delay { duration_ns, .. } => *duration_ns,
+            Operation::Symbolic { op, .. } => self.duration(op),
+            Operation::Custom(custom) => match custom.qubits.len() {
+                0 | 1 => self.single_qubit_ns,
+                2 => self.two_qubit_ns,
+                _ => self.three_qubit_ns,
+            },
+        }
+    }
+}
+
+impl Default for GateDurations {
+    fn default() -> Self {
+        Self::superconducting()
+    }
+}
+
 impl BackendCapabilities {
     /// 理想模拟器能力
     pub fn ideal_simulator() -> Self {
@@ -183,6 +281,7 @@ impl BackendCapabilities {
             native_gates: vec!["X", "Y", "Z", "H", "S", "T", "CNOT", "Rx", "Ry", "Rz"],
             coupling_map: Some(CouplingMap::fully_connected(32)),
             error_model: Some(ErrorModel::ideal()),
+            gate_durations: None,
         }
     }
     
@@ -204,15 +303,62 @@ impl BackendCapabilities {
             native_gates: vec!["X", "Y", "Z", "H", "S", "T", "CNOT", "Rz"],
             coupling_map: Some(CouplingMap::linear_chain(100)),
             error_model: Some(ErrorModel::new(0.001, 0.01, 0.02, 100_000.0, 50_000.0)),
+            gate_durations: Some(GateDurations::superconducting()),
         }
     }
     
+    /// 门时长，未提供时使用默认值
+    pub fn durations(&self) -> GateDurations {
+        self.gate_durations.clone().unwrap_or_default()
+    }
+    
     /// 检查是否支持某个操作
     pub fn supports_gate(&self, gate_name: &str) -> bool {
         self.supported_1q_gates.contains(&gate_name)
             || self.supported_2q_gates.contains(&gate_name)
             || self.supported_3q_gates.contains(&gate_name)
     }
+    
+    /// 检查电路能否以 shots 次采样在此后端运行
+    pub fn check_circuit(&self, circuit: &CircuitDag, shots: u32) -> Result<()> {
+        if circuit.num_qubits() > self.num_qubits {
+            return Err(IrError::UnsupportedOperation(format!(
+                "Circuit has {} qubits, {} supports {}",
+                circuit.num_qubits(), self.name, self.num_qubits
+            )));
+        }
+        if shots > self.max_shots {
+            return Err(IrError::UnsupportedOperation(format!(
+                "{} shots exceed the limit of {} ({})",
+                shots, self.max_shots, self.name
+            )));
+        }
+        circuit.nodes().iter().try_for_each(|node| self.check_operation(&node.op))
+    }
+    
+    /// 检查作业的所有电路（含批量附加电路）
+    pub fn check_job(&self, job: &Job) -> Result<()> {
+        std::iter::once(&job.circuit)
+            .chain(&job.batch_circuits)
+            .try_for_each(|circuit| self.check_circuit(circuit, job.shots))
+    }
+    
+    fn check_operation(&self, op: &Operation) -> Result<()> {
+        let supported = match op {
+            Operation::Gate1 { .. } | Operation::Gate2 { .. } | Operation::Gate3 { .. } => self.supports_gate(op.name()),
+            Operation::Measure { .. } => self.supports_measurement,
+            Operation::Reset { .. } => self.supports_reset,
+            Operation::Barrier { .. } => self.supports_barrier,
+            Operation::Delay { .. } => true,
+            Operation::Custom(_) => self.supports_custom,
+            Operation::Symbolic { op, .. } => return self.check_operation(op),
+        };
+        if supported {
+            Ok(())
+        } else {
+            Err(IrError::UnsupportedOperation(format!("{} does not support {}", self.name, op.name())))
+        }
+    }
 }
 
 // ============================================================================
@@ -230,6 +376,10 @@ pub struct BackendCircuit {
     pub metadata: std::collections::HashMap<String, String>,
     /// Qubit 映射
     pub qubit_mapping: QubitMapping,
+    /// 按执行顺序排列的操作（复合门已展开），参数门可以仍含符号参数
+    pub operations: Vec<Operation>,
+    /// `operations` 中含符号参数的位置（参数槽）
+    pub slots: Vec<usize>,
 }
 
 impl BackendCircuit {
@@ -239,15 +389,15 @@ impl BackendCircuit {
             data: Vec::new(),
             metadata: std::collections::HashMap::new(),
             qubit_mapping: QubitMapping::new(),
+            operations: Vec::new(),
+            slots: Vec::new(),
         }
     }
     
     pub fn with_data(backend_name: impl Into<String>, data: Vec<u8>) -> Self {
         Self {
-            backend_name: backend_name.into(),
             data,
-            metadata: std::collections::HashMap::new(),
-            qubit_mapping: QubitMapping::new(),
+            ..Self::new(backend_name)
         }
     }
     
@@ -256,11 +406,100 @@ impl BackendCircuit {
         mapping: QubitMapping,
     ) -> Self {
         Self {
-            backend_name: backend_name.into(),
-            data: Vec::new(),
-            metadata: std::collections::HashMap::new(),
             qubit_mapping: mapping,
+            ..Self::new(backend_name)
+        }
+    }
+    
+    /// 设置按执行顺序排列的操作，并记录其中的参数槽
+    pub fn with_operations(mut self, operations: Vec<Operation>) -> Self {
+        self.slots = operations
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| op.is_symbolic())
+            .map(|(i, _)| i)
+            .collect();
+        self.operations = operations;
+        self
+    }
+    
+    /// 在后端格式上代入参数：只替换参数槽中的操作，不重新翻译
+    /// 
+    /// 仍有参数未绑定时返回错误。
+    pub fn bind(&self, values: &ParamBinding) -> Result<BackendCircuit> {
+        let mut bound = self.clone();
+        for &slot in &self.slots {
+            bound.operations[slot] = self.operations[slot].bind(values)?;
         }
+        let unbound: std::collections::BTreeSet<_> = self.slots
+            .iter()
+            .flat_map(|&slot| bound.operations[slot].free_parameters())
+            .collect();
+        if !unbound.is_empty() {
+            return Err(IrError::InvalidOperation(format!(
+                "Backend circuit has unbound parameters: {:?}",
+                unbound.iter().map(|p| p.name()).collect::<Vec<_>>()
+            )));
+        }
+        bound.slots.clear();
+        Ok(bound)
+    }
+    
+    /// 操作涉及的 qubit（按 ID 升序）
+    pub fn qubits(&self) -> Vec<LogicalQubitId> {
+        let qubits: std::collections::BTreeSet<_> = self.operations.iter().flat_map(Operation::qubits).collect();
+        qubits.into_iter().collect()
+    }
+}
+
+/// 批量作业各执行点的后端电路
+/// 
+/// templates 为 `translate_batch` 的结果：扫描点依次在 `job.circuit` 的模板上代入参数
+/// （扫描点的取值覆盖 `job.parameters` 中的同名参数，无扫描点时执行一次），随后是附加电路的模板。
+/// 顺序与 `Job::point_circuits` 相同。
+pub fn bind_points(job: &Job, templates: &[BackendCircuit]) -> Result<Vec<BackendCircuit>> {
+    let Some((template, extras)) = templates.split_first() else {
+        return Err(IrError::InvalidOperation(format!("Job {} has no translated circuits", job.id)));
+    };
+    let base = job.parameters.clone().unwrap_or_default();
+    let mut points = Vec::with_capacity(job.num_points());
+    if job.sweep.is_empty() {
+        points.push(template.bind(&base)?);
+    } else {
+        for point in &job.sweep {
+            let mut binding = base.clone();
+            binding.extend(point.iter().map(|(p, &v)| (p.clone(), v)));
+            points.push(template.bind(&binding)?);
+        }
+    }
+    for extra in extras {
+        points.push(extra.bind(&base)?);
+    }
+    Ok(points)
+}
+
+// ============================================================================
+// Submitted Job
+// ============================================================================
+
+/// 异步执行的作业：后端作业 ID 与作业结束时就绪的 future
+#[derive(Debug)]
+pub struct SubmittedJob {
+    /// 后端分配的作业 ID，取消时使用；作业已在调用线程上执行完毕时为 `None`
+    pub backend_job_id: Option<JobId>,
+    /// 作业结果
+    pub future: JobFuture,
+}
+
+impl SubmittedJob {
+    /// 已提交到后端、尚未结束的作业
+    pub fn submitted(backend_job_id: JobId, future: JobFuture) -> Self {
+        Self { backend_job_id: Some(backend_job_id), future }
+    }
+
+    /// 已在调用线程上执行完毕的作业
+    pub fn ready(result: Result<JobResult>) -> Self {
+        Self { backend_job_id: None, future: JobFuture::ready(result) }
     }
 }
 
@@ -277,7 +516,11 @@ impl BackendCircuit {
 /// - get_job_status: 查询作业状态
 /// - get_job_result: 获取作业结果（阻塞直到完成）
 /// - cancel_job: 取消作业
-pub trait BackendAdapter: Send + Sync {
+/// - completion: 作业结束时就绪的 `JobFuture`
+/// 
+/// 作业在 `submit_job` 返回后才结束的后端（远程设备等）应覆盖 `completion`，
+/// 在作业结束时通过 `JobCompleter` 唤醒等待者。
+pub trait BackendAdapter: Send + Sync + 'static {
     /// 获取后端标识
     fn id(&self) -> &str;
     
@@ -287,6 +530,17 @@ pub trait BackendAdapter: Send + Sync {
     /// 将 IR 电路转换为后端格式
     fn translate_circuit(&self, circuit: &CircuitDag) -> Result<BackendCircuit>;
     
+    /// 翻译批量作业的模板电路
+    /// 
+    /// 参数扫描的各点共用一次 `job.circuit` 的翻译，附加电路各翻译一次；
+    /// 执行扫描点时由 `bind_points` 在后端格式上代入参数（`BackendCircuit::bind`），不再重新翻译。
+    fn translate_batch(&self, job: &Job) -> Result<Vec<BackendCircuit>> {
+        std::iter::once(&job.circuit)
+            .chain(&job.batch_circuits)
+            .map(|circuit| self.translate_circuit(circuit))
+            .collect()
+    }
+    
     /// 验证电路是否可在此后端执行
     fn validate_circuit(&self, circuit: &CircuitDag) -> Result<()> {
         let caps = self.capabilities();
@@ -326,26 +580,48 @@ pub trait BackendAdapter: Send + Sync {
     /// 取消作业（异步）
     fn cancel_job(&self, job_id: JobId) -> Result<()>;
     
+    /// 已提交作业结束时就绪的 future
+    /// 
+    /// 默认返回尚未就绪的 future：被轮询时按退避间隔检查 `get_job_status`，
+    /// 两次检查之间由共享计时线程唤醒，作业结束后取回结果；
+    /// 能主动通知作业结束的后端应覆盖此方法。
+    fn completion(self: Arc<Self>, job_id: JobId) -> JobFuture {
+        JobFuture::from_future(poll_until(
+            move || job_outcome(&*self, job_id),
+            POLL_INITIAL_INTERVAL,
+            POLL_MAX_INTERVAL,
+        ))
+    }
+    
+    /// 异步执行作业
+    /// 
+    /// 默认在调用线程上执行 `execute` 并返回已就绪的 future；
+    /// 原生异步的后端应覆盖此方法，提交后立即返回 `submit_job` 分配的 ID 与 `completion`。
+    /// 超时后运行时丢弃 future，并以返回的后端作业 ID 调用 `cancel_job`。
+    fn execute_async(self: Arc<Self>, job: &Job) -> SubmittedJob {
+        SubmittedJob::ready(self.execute(job))
+    }
+    
     // ========================================================================
     // 同步执行接口（可选实现）
     // ========================================================================
     
-    /// 同步执行作业（可选，用于模拟器）
+    /// 同步执行作业：提交后在当前线程上轮询 `get_job_status` 直到作业结束
+    /// 
+    /// 作业设置了超时（`JobMetadata::timeout`）时最多等待该时长，
+    /// 超时后取消后端作业并返回 `Timeout`。
     fn execute(&self, job: &Job) -> Result<JobResult> {
-        // 默认实现：提交 + 轮询
         let job_id = self.submit_job(job)?;
-        
-        // 简单轮询（实际实现应该更高效）
-        loop {
-            let status = self.get_job_status(job_id)?;
-            match status {
-                JobStatus::Completed => return self.get_job_result(job_id),
-                JobStatus::Failed | JobStatus::Cancelled => {
-                    return self.get_job_result(job_id);
-                }
-                _ => {
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                }
+        let Some(timeout) = job.metadata.timeout else {
+            return block_on(poll_job(self, job_id));
+        };
+        match block_on_timeout(poll_job(self, job_id), timeout) {
+            Some(result) => result,
+            None => {
+                let _ = self.cancel_job(job_id);
+                Err(IrError::Timeout(format!(
+                    "Job {} did not finish within {:?} on backend {}", job.id, timeout, self.id()
+                )))
             }
         }
     }
@@ -354,17 +630,68 @@ pub trait BackendAdapter: Send + Sync {
     fn is_available(&self) -> bool {
         true
     }
+    
+    /// 精确期望捷径（仅态矢量模拟器提供）
+    /// 
+    /// 返回 `None` 表示后端只能通过采样估计期望。
+    fn exact_expectation(&self, circuit: &CircuitDag, observable: &dyn Observable) -> Option<Result<f64>> {
+        None
+    }
+}
+
+/// 轮询后端作业状态的初始间隔
+const POLL_INITIAL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// 轮询后端作业状态的最大间隔
+const POLL_MAX_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 检查一次后端作业：结束时取回结果，仍在运行时返回 `None`
+fn job_outcome<B: BackendAdapter + ?Sized>(backend: &B, job_id: JobId) -> Option<Result<JobResult>> {
+    match backend.get_job_status(job_id) {
+        Ok(status) if status.is_terminal() => Some(backend.get_job_result(job_id)),
+        Ok(_) => None,
+        Err(e) => Some(Err(e)),
+    }
+}
+
+/// 轮询后端作业状态直到结束，再取回结果
+fn poll_job<B: BackendAdapter + ?Sized>(backend: &B, job_id: JobId) -> impl Future<Output = Result<JobResult>> + '_ {
+    poll_until(move || job_outcome(backend, job_id), POLL_INITIAL_INTERVAL, POLL_MAX_INTERVAL)
 }
 
 // ============================================================================
 // Mock Backend (for testing)
 // ============================================================================
 
+/// 生成确定性的伪测量结果
+fn sampled_result(job_id: JobId, point: usize, qubits: &[LogicalQubitId], shots: u32) -> JobResult {
+    let mut result = JobResult::success(job_id);
+    result.execution_time_ms = Some(1);
+    for &qubit in qubits {
+        let counts: Vec<u8> = (0..shots)
+            .map(|i| ((job_id + point as u64 + i as u64 + qubit.value()) % 2) as u8)
+            .collect();
+        result.add_counts(qubit, counts);
+    }
+    result
+}
+
+/// 批量作业的逐点结果（各点由翻译后的模板代入参数得到）
+fn sampled_batch(job_id: JobId, job: &Job, points: &[BackendCircuit]) -> JobResult {
+    let points = points
+        .iter()
+        .enumerate()
+        .map(|(point, circuit)| sampled_result(job_id, point, &circuit.qubits(), job.shots))
+        .collect();
+    JobResult::batch(job_id, points)
+}
+
 /// 模拟后端适配器（用于测试）
 pub struct MockBackendAdapter {
     capabilities: BackendCapabilities,
     jobs: std::sync::Mutex<std::collections::HashMap<JobId, MockJobState>>,
     next_job_id: std::sync::atomic::AtomicU64,
+    translations: std::sync::atomic::AtomicU64,
 }
 
 struct MockJobState {
@@ -378,6 +705,7 @@ impl MockBackendAdapter {
             capabilities: BackendCapabilities::ideal_simulator(),
             jobs: std::sync::Mutex::new(std::collections::HashMap::new()),
             next_job_id: std::sync::atomic::AtomicU64::new(1000),
+            translations: std::sync::atomic::AtomicU64::new(0),
         }
     }
     
@@ -386,8 +714,14 @@ impl MockBackendAdapter {
             capabilities,
             jobs: std::sync::Mutex::new(std::collections::HashMap::new()),
             next_job_id: std::sync::atomic::AtomicU64::new(1000),
+            translations: std::sync::atomic::AtomicU64::new(0),
         }
     }
+    
+    /// 已执行的电路翻译次数
+    pub fn translation_count(&self) -> u64 {
+        self.translations.load(std::sync::atomic::Ordering::SeqCst)
+    }
 }
 
 impl Default for MockBackendAdapter {
@@ -407,26 +741,23 @@ impl BackendAdapter for MockBackendAdapter {
     
     fn translate_circuit(&self, circuit: &CircuitDag) -> Result<BackendCircuit> {
         self.validate_circuit(circuit)?;
+        self.translations.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         Ok(BackendCircuit::with_data(
             self.id(),
             format!("{:?}", circuit).into_bytes(),
-        ))
+        ).with_operations(circuit.resolved_operations()?))
     }
     
     fn submit_job(&self, job: &Job) -> Result<JobId> {
         let job_id = self.next_job_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         
         // 模拟立即完成
-        let mut result = JobResult::success(job_id);
-        result.execution_time_ms = Some(1);
-        
-        // 生成随机测量结果
-        for &qubit in &job.circuit.all_qubits() {
-            let counts: Vec<u8> = (0..job.shots)
-                .map(|i| ((job_id + i as u64 + qubit.value()) % 2) as u8)
-                .collect();
-            result.add_counts(qubit, counts);
-        }
+        let result = if job.is_batch() {
+            let templates = self.translate_batch(job)?;
+            sampled_batch(job_id, job, &bind_points(job, &templates)?)
+        } else {
+            sampled_result(job_id, 0, &job.resolved_circuit()?.all_qubits(), job.shots)
+        };
         
         let mut jobs = self.jobs.lock().unwrap();
         jobs.insert(
@@ -471,10 +802,13 @@ impl BackendAdapter for MockBackendAdapter {
 // ============================================================================
 
 /// 理想模拟器后端
+/// 
+/// 用态矢量精确模拟到测量前的末态，再按计算基概率采样（测量须位于电路末尾）。
 pub struct IdealSimulatorBackend {
     capabilities: BackendCapabilities,
     jobs: std::sync::Mutex<std::collections::HashMap<JobId, MockJobState>>,
     next_job_id: std::sync::atomic::AtomicU64,
+    seed: u64,
 }
 
 impl IdealSimulatorBackend {
@@ -483,10 +817,41 @@ impl IdealSimulatorBackend {
             capabilities: BackendCapabilities::ideal_simulator(),
             jobs: std::sync::Mutex::new(std::collections::HashMap::new()),
             next_job_id: std::sync::atomic::AtomicU64::new(2000),
+            seed: 0x5eed,
+        }
+    }
+    
+    /// 设置采样随机种子
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+    
+    /// 对模拟得到的末态采样；模拟失败时返回失败结果
+    fn simulate(&self, job_id: JobId, point: usize, simulated: Result<(Vec<LogicalQubitId>, StateVector)>, shots: u32) -> JobResult {
+        let (qubits, state) = match simulated {
+            Ok(simulated) => simulated,
+            Err(e) => return JobResult::failure(job_id, e.to_string()),
+        };
+        let seed = self.seed ^ job_id.wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ point as u64;
+        let samples = state.sample(shots as usize, seed);
+        
+        let mut result = JobResult::success(job_id);
+        result.execution_time_ms = Some(1);
+        for (bit, &qubit) in qubits.iter().enumerate() {
+            result.add_counts(qubit, samples.iter().map(|&s| (s >> bit & 1) as u8).collect());
         }
+        result
     }
 }
 
+/// 从 |0…0⟩ 模拟后端电路到测量前的末态，qubit 按 ID 升序
+fn backend_state(circuit: BackendCircuit) -> Result<(Vec<LogicalQubitId>, StateVector)> {
+    let qubits = circuit.qubits();
+    let state = evolve(&qubits, &strip_measurements(circuit.operations)?)?;
+    Ok((qubits, state))
+}
+
 impl Default for IdealSimulatorBackend {
     fn default() -> Self {
         Self::new()
@@ -504,27 +869,29 @@ impl BackendAdapter for IdealSimulatorBackend {
     
     fn translate_circuit(&self, circuit: &CircuitDag) -> Result<BackendCircuit> {
         self.validate_circuit(circuit)?;
-        Ok(BackendCircuit::new(self.id()))
+        Ok(BackendCircuit::new(self.id()).with_operations(circuit.resolved_operations()?))
     }
     
     fn submit_job(&self, job: &Job) -> Result<JobId> {
         let job_id = self.next_job_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         
-        let mut result = JobResult::success(job_id);
-        result.execution_time_ms = Some(1);
-        
-        for &qubit in &job.circuit.all_qubits() {
-            let counts: Vec<u8> = (0..job.shots)
-                .map(|i| ((job_id + i as u64 + qubit.value()) % 2) as u8)
+        let result = if job.is_batch() {
+            let templates = self.translate_batch(job)?;
+            let points = bind_points(job, &templates)?
+                .into_iter()
+                .enumerate()
+                .map(|(point, circuit)| self.simulate(job_id, point, backend_state(circuit), job.shots))
                 .collect();
-            result.add_counts(qubit, counts);
-        }
+            JobResult::batch(job_id, points)
+        } else {
+            self.simulate(job_id, 0, final_state(&job.resolved_circuit()?), job.shots)
+        };
         
         let mut jobs = self.jobs.lock().unwrap();
         jobs.insert(
             job_id,
             MockJobState {
-                status: JobStatus::Completed,
+                status: result.status,
                 result: Some(result),
             },
         );
@@ -556,6 +923,10 @@ impl BackendAdapter for IdealSimulatorBackend {
         }
         Err(IrError::JobExecutionFailed("Job cannot be cancelled".to_string()))
     }
+    
+    fn exact_expectation(&self, circuit: &CircuitDag, observable: &dyn Observable) -> Option<Result<f64>> {
+        Some(final_state(circuit).and_then(|(qubits, state)| observable.exact_expectation(&state, &qubits)))
+    }
 }
 
 // ============================================================================
@@ -566,6 +937,7 @@ impl BackendAdapter for IdealSimulatorBackend {
 mod tests {
     use super::*;
     use crate::circuit::bell_state_dag;
+    use crate::params::ParamBinding;
 
     #[test]
     fn test_backend_capabilities() {
@@ -575,6 +947,24 @@ mod tests {
         assert!(caps.supported_1q_gates.contains(&"H"));
     }
 
+    #[test]
+    fn test_check_circuit() {
+        let bell = bell_state_dag();
+        let caps = BackendCapabilities::ideal_simulator();
+        assert!(caps.check_circuit(&bell, 1000).is_ok());
+        assert!(caps.check_circuit(&bell, 2_000_000).is_err());
+
+        let mut no_measure = BackendCapabilities::nisq_device();
+        no_measure.supports_measurement = false;
+        assert!(no_measure.check_circuit(&bell, 100).is_err());
+
+        let mut tiny = BackendCapabilities::ideal_simulator();
+        tiny.num_qubits = 1;
+        tiny.supported_2q_gates.clear();
+        let job = Job::new(bell, 100, "tiny");
+        assert!(matches!(tiny.check_job(&job), Err(IrError::UnsupportedOperation(_))));
+    }
+
     #[test]
     fn test_coupling_map() {
         let fully = CouplingMap::fully_connected(4);
@@ -602,13 +992,167 @@ mod tests {
         assert_eq!(result.status, JobStatus::Completed);
     }
 
+    #[test]
+    fn test_batch_job() {
+        use crate::operation::cnot;
+        use crate::params::{ry_param, Param};
+        use crate::qubit::LogicalQubitId;
+        
+        let (q0, q1) = (LogicalQubitId::new(0), LogicalQubitId::new(1));
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(ry_param(q0, Param::new("theta")));
+        circuit.add_node(Operation::measure(q0));
+        let mut extra = CircuitDag::new();
+        extra.add_node(cnot(q0, q1));
+        
+        let sweep = (0..5).map(|i| [(Param::new("theta"), i as f64 * 0.1)].into_iter().collect());
+        let job = Job::new(circuit, 10, "mock_backend").with_sweep(sweep).with_circuits(vec![extra]);
+        assert_eq!(job.num_points(), 6);
+        assert_eq!(job.allocated_qubits.len(), 2);
+        
+        let backend = MockBackendAdapter::new();
+        let result = backend.execute(&job).unwrap();
+        assert_eq!(result.status, JobStatus::Completed);
+        assert_eq!(result.points.len(), 6);
+        assert_eq!(result.point(5).unwrap().counts.len(), 2);
+        // 模板电路与附加电路各翻译一次
+        assert_eq!(backend.translation_count(), 2);
+        
+        // 扫描点缺少参数时整个作业被拒绝
+        let bad = Job::new(job.circuit.clone(), 10, "mock_backend").with_sweep(vec![ParamBinding::new()]);
+        assert!(backend.submit_job(&bad).is_err());
+        
+        // 扫描点只在后端格式的参数槽上代入
+        let template = backend.translate_circuit(&job.circuit).unwrap();
+        assert_eq!(template.slots, vec![0]);
+        let bound = template.bind(&job.sweep[2]).unwrap();
+        assert!(bound.slots.is_empty());
+        assert_eq!(bound.operations[0], Operation::gate1(crate::operation::SingleQubitGate::Ry(0.2), q0));
+        assert_eq!(bound.operations[1], template.operations[1]);
+        assert!(template.bind(&ParamBinding::new()).is_err());
+        
+        // 理想模拟器按绑定后的后端电路模拟各点
+        let flip = [0.0, std::f64::consts::PI].map(|v| [(Param::new("theta"), v)].into_iter().collect());
+        let job = Job::new(job.circuit.clone(), 20, "ideal_simulator").with_sweep(flip);
+        let result = IdealSimulatorBackend::new().execute(&job).unwrap();
+        assert_eq!(result.point(0).unwrap().get_probability(q0), Some(0.0));
+        assert_eq!(result.point(1).unwrap().get_probability(q0), Some(1.0));
+    }
+
+    /// 作业永不结束的后端
+    struct StuckBackend {
+        inner: MockBackendAdapter,
+        pending: std::sync::Mutex<Vec<crate::future::JobCompleter>>,
+        cancelled: std::sync::Mutex<Vec<JobId>>,
+    }
+
+    impl BackendAdapter for StuckBackend {
+        fn id(&self) -> &str { "stuck" }
+        fn capabilities(&self) -> BackendCapabilities { self.inner.capabilities() }
+        fn translate_circuit(&self, circuit: &CircuitDag) -> Result<BackendCircuit> { self.inner.translate_circuit(circuit) }
+        fn submit_job(&self, job: &Job) -> Result<JobId> { self.inner.submit_job(job) }
+        fn get_job_status(&self, job_id: JobId) -> Result<JobStatus> { Ok(JobStatus::Running) }
+        fn get_job_result(&self, job_id: JobId) -> Result<JobResult> { self.inner.get_job_result(job_id) }
+
+        fn cancel_job(&self, job_id: JobId) -> Result<()> {
+            self.cancelled.lock().unwrap().push(job_id);
+            Ok(())
+        }
+
+        fn completion(self: Arc<Self>, job_id: JobId) -> JobFuture {
+            let (completer, future) = crate::future::completion();
+            self.pending.lock().unwrap().push(completer);
+            future.into()
+        }
+    }
+
+    #[test]
+    fn test_execute_timeout() {
+        let backend = StuckBackend {
+            inner: MockBackendAdapter::new(),
+            pending: std::sync::Mutex::new(Vec::new()),
+            cancelled: std::sync::Mutex::new(Vec::new()),
+        };
+        let job = Job::new(bell_state_dag(), 10, "stuck").with_timeout(std::time::Duration::from_millis(20));
+        assert!(matches!(backend.execute(&job), Err(IrError::Timeout(_))));
+        // 以后端自己的作业 ID 取消
+        assert_eq!(*backend.cancelled.lock().unwrap(), vec![1000]);
+    }
+
+    /// 前几次查询报告作业仍在运行的后端
+    struct PollingBackend {
+        inner: MockBackendAdapter,
+        running_polls: usize,
+        polls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl BackendAdapter for PollingBackend {
+        fn id(&self) -> &str { "polling" }
+        fn capabilities(&self) -> BackendCapabilities { self.inner.capabilities() }
+        fn translate_circuit(&self, circuit: &CircuitDag) -> Result<BackendCircuit> { self.inner.translate_circuit(circuit) }
+        fn submit_job(&self, job: &Job) -> Result<JobId> { self.inner.submit_job(job) }
+        fn get_job_result(&self, job_id: JobId) -> Result<JobResult> { self.inner.get_job_result(job_id) }
+        fn cancel_job(&self, job_id: JobId) -> Result<()> { self.inner.cancel_job(job_id) }
+
+        fn get_job_status(&self, job_id: JobId) -> Result<JobStatus> {
+            let polls = self.polls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            if polls <= self.running_polls {
+                Ok(JobStatus::Running)
+            } else {
+                self.inner.get_job_status(job_id)
+            }
+        }
+    }
+
+    #[test]
+    fn test_execute_polls_until_terminal() {
+        use std::sync::atomic::Ordering;
+
+        let backend = PollingBackend { inner: MockBackendAdapter::new(), running_polls: 3, polls: Default::default() };
+        let job = Job::new(bell_state_dag(), 10, "polling");
+        let result = backend.execute(&job).unwrap();
+        assert_eq!(result.status, JobStatus::Completed);
+        assert_eq!(backend.polls.load(Ordering::SeqCst), 4);
+
+        // 设置超时时同样轮询，并在期限内完成
+        backend.polls.store(0, Ordering::SeqCst);
+        let job = Job::new(bell_state_dag(), 10, "polling").with_timeout(Duration::from_secs(5));
+        assert_eq!(backend.execute(&job).unwrap().status, JobStatus::Completed);
+        assert_eq!(backend.polls.load(Ordering::SeqCst), 4);
+
+        // 默认的异步接口同样经轮询得到结果
+        let backend = Arc::new(backend);
+        backend.polls.store(0, Ordering::SeqCst);
+        let submitted = Arc::clone(&backend).execute_async(&job);
+        assert_eq!(submitted.backend_job_id, None);
+        assert_eq!(block_on(submitted.future).unwrap().status, JobStatus::Completed);
+
+        // 默认的 completion 不在调用时轮询，返回的 future 被轮询时才检查作业状态
+        backend.polls.store(0, Ordering::SeqCst);
+        let job_id = backend.submit_job(&job).unwrap();
+        let future = Arc::clone(&backend).completion(job_id);
+        assert!(!future.is_ready());
+        assert_eq!(backend.polls.load(Ordering::SeqCst), 0);
+        assert_eq!(block_on(future).unwrap().status, JobStatus::Completed);
+        assert_eq!(backend.polls.load(Ordering::SeqCst), 4);
+    }
+
     #[test]
     fn test_ideal_simulator() {
+        use crate::qubit::LogicalQubitId;
+        
         let backend = IdealSimulatorBackend::new();
         assert_eq!(backend.id(), "ideal_simulator");
         
         let caps = backend.capabilities();
         assert_eq!(caps.num_qubits, 32);
+        
+        // Bell 态：两个 qubit 的结果逐次一致
+        let result = backend.execute(&Job::new(bell_state_dag(), 200, "ideal_simulator")).unwrap();
+        assert_eq!(result.status, JobStatus::Completed);
+        let (q0, q1) = (LogicalQubitId::new(0), LogicalQubitId::new(1));
+        assert_eq!(result.get_counts(q0), result.get_counts(q1));
+        assert!((result.get_probability(q0).unwrap() - 0.5).abs() < 0.15);
     }
 
     #[test]
@@ -621,4 +1165,19 @@ mod tests {
         assert_eq!(noisy.single_qubit_error_rate, 0.001);
         assert_eq!(noisy.two_qubit_error_rate, 0.01);
     }
+
+    #[test]
+    fn test_gate_durations() {
+        use crate::operation::{h, cnot, measure};
+        use crate::qubit::LogicalQubitId;
+        
+        let q0 = LogicalQubitId::new(0);
+        let q1 = LogicalQubitId::new(1);
+        let durations = GateDurations::superconducting().with_override("H", 20.0);
+        
+        assert_eq!(durations.duration(&h(q0)), 20.0);
+        assert_eq!(durations.duration(&cnot(q0, q1)), 300.0);
+        assert_eq!(durations.duration(&measure(q0)), 1_000.0);
+        assert_eq!(durations.duration(&Operation::barrier(vec![q0, q1])), 0.0);
+    }
 }
@@ -5,6 +5,7 @@
 
 use crate::qubit::LogicalQubitId;
 use crate::operation::Operation;
+use crate::composite::{GateDefinition, GateLibrary};
 use crate::{Result, IrError};
 
 // ============================================================================
@@ -85,6 +86,8 @@ pub struct CircuitDag {
     outputs: Vec<LogicalQubitId>,
     /// 电路元数据
     metadata: CircuitMetadata,
+    /// 复合门定义库
+    library: GateLibrary,
     /// 缓存的深度值
     cached_depth: Option<usize>,
 }
@@ -107,6 +110,7 @@ impl CircuitDag {
             inputs: Vec::new(),
             outputs: Vec::new(),
             metadata: CircuitMetadata::default(),
+            library: GateLibrary::default(),
             cached_depth: None,
         }
     }
@@ -124,6 +128,22 @@ impl CircuitDag {
         self
     }
     
+    /// 获取输入 qubit
+    pub fn inputs(&self) -> &[LogicalQubitId] {
+        &self.inputs
+    }
+    
+    /// 设置输出 qubit
+    pub fn with_outputs(mut self, outputs: Vec<LogicalQubitId>) -> Self {
+        self.outputs = outputs;
+        self
+    }
+    
+    /// 获取输出 qubit
+    pub fn outputs(&self) -> &[LogicalQubitId] {
+        &self.outputs
+    }
+    
     /// 添加操作节点
     pub fn add_node(&mut self, op: Operation) -> usize {
         let id = self.nodes.len();
@@ -199,10 +219,8 @@ impl CircuitDag {
         
         // 查找所有从 start 出发的边
         for &(from, to) in &self.edges {
-            if from == start {
-                if self.has_path(to, end, visited) {
-                    return true;
-                }
+            if from == start && self.has_path(to, end, visited) {
+                return true;
             }
         }
         
@@ -281,6 +299,25 @@ impl CircuitDag {
         max_depth
     }
     
+    /// 带权关键路径长度
+    /// 
+    /// 每个节点的权重由 weight 给出，返回所有路径中权重和的最大值
+    pub fn longest_path<F>(&self, weight: F) -> f64
+    where
+        F: Fn(&Operation) -> f64,
+    {
+        let mut finish = vec![0.0f64; self.nodes.len()];
+        for node_id in self.topological_sort() {
+            let node = &self.nodes[node_id];
+            let start = node.depends_on
+                .iter()
+                .map(|&pred| finish[pred])
+                .fold(0.0, f64::max);
+            finish[node_id] = start + weight(&node.op);
+        }
+        finish.into_iter().fold(0.0, f64::max)
+    }
+    
     /// 拓扑排序
     pub fn topological_sort(&self) -> Vec<usize> {
         let mut result = Vec::with_capacity(self.nodes.len());
@@ -321,6 +358,33 @@ impl CircuitDag {
         result
     }
     
+    /// 稳定的拓扑顺序：每步取可执行节点中 ID 最小者，无依赖约束时保持插入顺序
+    fn program_order(&self) -> Vec<usize> {
+        let mut in_degree = vec![0usize; self.nodes.len()];
+        for &(_, to) in &self.edges {
+            in_degree[to] += 1;
+        }
+        let mut ready: std::collections::BinaryHeap<std::cmp::Reverse<usize>> = in_degree
+            .iter()
+            .enumerate()
+            .filter(|(_, &deg)| deg == 0)
+            .map(|(i, _)| std::cmp::Reverse(i))
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(std::cmp::Reverse(node)) = ready.pop() {
+            order.push(node);
+            for &(from, to) in &self.edges {
+                if from == node {
+                    in_degree[to] -= 1;
+                    if in_degree[to] == 0 {
+                        ready.push(std::cmp::Reverse(to));
+                    }
+                }
+            }
+        }
+        order
+    }
+    
     /// 计算可并行执行的节点组
     pub fn compute_parallel_groups(&mut self) -> Vec<Vec<usize>> {
         let order = self.topological_sort();
@@ -400,6 +464,44 @@ impl CircuitDag {
             .collect()
     }
     
+    /// 按拓扑顺序列出所有操作，无依赖约束时保持插入顺序
+    pub fn operations_in_order(&self) -> Vec<Operation> {
+        self.program_order()
+            .into_iter()
+            .map(|id| self.nodes[id].op.clone())
+            .collect()
+    }
+    
+    /// 用新的操作序列重建 DAG
+    /// 
+    /// 保留元数据、输入 qubit 和复合门定义库，依赖关系按 qubit 使用顺序自动推导。
+    /// 编译通道（pass）统一通过此方法产出新电路。
+    pub fn rebuild_with(&self, ops: impl IntoIterator<Item = Operation>) -> CircuitDag {
+        let mut builder = CircuitDagBuilder::new();
+        for op in ops {
+            builder.add_op(op);
+        }
+        let mut dag = builder.build();
+        dag.inputs = self.inputs.clone();
+        dag.outputs = self.outputs.clone();
+        dag.metadata = self.metadata.clone();
+        dag.library = self.library.clone();
+        dag
+    }
+    
+    /// 逆电路 U†：反转操作顺序并逐个取逆
+    /// 
+    /// 复合门先按定义展开；含测量或重置时返回错误。
+    pub fn inverse(&self) -> Result<CircuitDag> {
+        let ops = self
+            .resolved_operations()?
+            .into_iter()
+            .rev()
+            .map(|op| op.inverse())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.rebuild_with(ops))
+    }
+    
     /// 清除所有节点
     pub fn clear(&mut self) {
         self.nodes.clear();
@@ -407,6 +509,21 @@ impl CircuitDag {
         self.cached_depth = None;
     }
     
+    /// 获取复合门定义库
+    pub fn gate_library(&self) -> &GateLibrary {
+        &self.library
+    }
+    
+    /// 获取可变的复合门定义库
+    pub fn gate_library_mut(&mut self) -> &mut GateLibrary {
+        &mut self.library
+    }
+    
+    /// 注册复合门定义，返回同名的旧定义
+    pub fn define_gate(&mut self, definition: GateDefinition) -> Option<GateDefinition> {
+        self.library.define(definition)
+    }
+    
     /// 获取元数据
     pub fn metadata(&self) -> &CircuitMetadata {
         &self.metadata
@@ -458,6 +575,12 @@ impl CircuitDagBuilder {
         self
     }
     
+    /// 注册复合门定义
+    pub fn define_gate(&mut self, definition: GateDefinition) -> &mut Self {
+        self.dag.library.define(definition);
+        self
+    }
+    
     /// 添加操作（自动处理依赖）
     pub fn add_op(&mut self, op: Operation) -> usize {
         let qubits = op.qubits();
@@ -614,6 +737,19 @@ mod tests {
         let order = dag.topological_sort();
         assert_eq!(order, vec![n1, n2, n3]);
     }
+    
+    #[test]
+    fn test_operations_in_insertion_order() {
+        let (q0, q1, q2) = (LogicalQubitId::new(0), LogicalQubitId::new(1), LogicalQubitId::new(2));
+        let mut dag = CircuitDag::new();
+        let n1 = dag.add_node(h(q0));
+        dag.add_node(x(q1));
+        dag.add_node(z(q2));
+        let n4 = dag.add_node(h(q1));
+        // 相互独立的操作按插入顺序，依赖只推迟后继
+        dag.add_edge(n4, n1).unwrap();
+        assert_eq!(dag.operations_in_order(), vec![x(q1), z(q2), h(q1), h(q0)]);
+    }
 
     #[test]
     fn test_depth_calculation() {
@@ -687,4 +823,25 @@ mod tests {
         let total_nodes: usize = groups.iter().map(|g| g.len()).sum();
         assert_eq!(total_nodes, 3);
     }
+
+    #[test]
+    fn test_inverse_circuit() {
+        let q0 = LogicalQubitId::new(0);
+        let q1 = LogicalQubitId::new(1);
+        let mut dag = CircuitDag::with_name("prep");
+        dag.add_node(h(q0));
+        dag.add_node(crate::operation::t(q0));
+        dag.add_node(cnot(q0, q1));
+
+        let inverse = dag.inverse().unwrap();
+        let names: Vec<String> = inverse.operations_in_order().iter().map(|op| op.name().to_string()).collect();
+        assert_eq!(names, vec!["CNOT", "Tdg", "H"]);
+        assert_eq!(inverse.metadata().name.as_deref(), Some("prep"));
+
+        let ops = dag.operations_in_order().into_iter().chain(inverse.operations_in_order());
+        let roundtrip = dag.rebuild_with(ops);
+        assert!(roundtrip.unitary().unwrap().equal_up_to_phase(&crate::linalg::Matrix::identity(4), 1e-12));
+
+        assert!(bell_state_dag().inverse().is_err());
+    }
 }
@@ -0,0 +1,413 @@
+//! 电路清理模块
+//!
+//! 前端生成的电路常带有无用操作。本通道依次执行：
+//! 1. 去除 barrier（目标后端不支持时）
+//! 2. 删除紧邻测量之前的对角门（不影响测量统计）
+//! 3. 删除结果永远不会到达测量的门（反向活跃性分析）
+//! 4. 去除只被 barrier 触及的 qubit
+//! 5. 压缩逻辑 qubit 编号，使 `num_qubits()` 反映真正使用的 qubit
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::backend::BackendCapabilities;
+use crate::circuit::CircuitDag;
+use crate::operation::Operation;
+use crate::qubit::LogicalQubitId;
+
+// ============================================================================
+// Report
+// ============================================================================
+
+/// 清理报告
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CleanupReport {
+    /// 删除的无效门数量
+    pub removed_dead_gates: usize,
+    /// 删除的测量前对角门数量
+    pub removed_diagonal_gates: usize,
+    /// 删除的 barrier 数量
+    pub removed_barriers: usize,
+    /// 被去除的 qubit（原编号）
+    pub removed_qubits: Vec<LogicalQubitId>,
+    /// qubit 重编号：原编号 → 新编号
+    pub qubit_remap: BTreeMap<LogicalQubitId, LogicalQubitId>,
+}
+
+impl CleanupReport {
+    /// 删除的操作总数
+    pub fn total_removed(&self) -> usize {
+        self.removed_dead_gates + self.removed_diagonal_gates + self.removed_barriers
+    }
+}
+
+// ============================================================================
+// Cleanup Pass
+// ============================================================================
+
+/// 电路清理通道
+#[derive(Debug, Clone)]
+pub struct CircuitCleanup {
+    /// 删除结果不会到达测量的门
+    pub remove_dead_gates: bool,
+    /// 删除紧邻测量之前的对角门
+    pub remove_trailing_diagonal: bool,
+    /// 去除所有 barrier
+    pub strip_barriers: bool,
+    /// 压缩 qubit 编号
+    pub compact_qubits: bool,
+}
+
+impl Default for CircuitCleanup {
+    fn default() -> Self {
+        Self {
+            remove_dead_gates: true,
+            remove_trailing_diagonal: true,
+            strip_barriers: false,
+            compact_qubits: true,
+        }
+    }
+}
+
+impl CircuitCleanup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 根据后端能力配置（不支持 barrier 时去除 barrier）
+    pub fn for_backend(capabilities: &BackendCapabilities) -> Self {
+        Self {
+            strip_barriers: !capabilities.supports_barrier,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_strip_barriers(mut self, strip: bool) -> Self {
+        self.strip_barriers = strip;
+        self
+    }
+
+    pub fn with_compact_qubits(mut self, compact: bool) -> Self {
+        self.compact_qubits = compact;
+        self
+    }
+
+    pub fn with_dead_gate_removal(mut self, enabled: bool) -> Self {
+        self.remove_dead_gates = enabled;
+        self
+    }
+
+    pub fn with_trailing_diagonal_removal(mut self, enabled: bool) -> Self {
+        self.remove_trailing_diagonal = enabled;
+        self
+    }
+
+    /// 执行清理
+    pub fn run(&self, circuit: &CircuitDag) -> (CircuitDag, CleanupReport) {
+        let mut report = CleanupReport::default();
+        let mut ops = circuit.operations_in_order();
+        let original_qubits: BTreeSet<LogicalQubitId> =
+            ops.iter().flat_map(|op| op.qubits()).collect();
+
+        if self.strip_barriers {
+            let before = ops.len();
+            ops.retain(|op| !op.is_barrier());
+            report.removed_barriers += before - ops.len();
+        }
+
+        if self.remove_trailing_diagonal {
+            let (kept, removed) = remove_trailing_diagonal(ops);
+            ops = kept;
+            report.removed_diagonal_gates = removed;
+        }
+
+        if self.remove_dead_gates {
+            let (kept, removed) = remove_dead_gates(ops);
+            ops = kept;
+            report.removed_dead_gates = removed;
+        }
+
+        // 只被 barrier 触及的 qubit 从 barrier 中移除
+        let touched: BTreeSet<LogicalQubitId> = ops
+            .iter()
+            .filter(|op| !op.is_barrier())
+            .flat_map(|op| op.qubits())
+            .collect();
+        ops = ops
+            .into_iter()
+            .filter_map(|op| match op {
+                Operation::Barrier { qubits } => {
+                    let qubits: Vec<_> = qubits.into_iter().filter(|q| touched.contains(q)).collect();
+                    if qubits.is_empty() {
+                        report.removed_barriers += 1;
+                        None
+                    } else {
+                        Some(Operation::Barrier { qubits })
+                    }
+                }
+                other => Some(other),
+            })
+            .collect();
+        report.removed_qubits = original_qubits.difference(&touched).copied().collect();
+
+        let (inputs, outputs): (Vec<LogicalQubitId>, Vec<LogicalQubitId>) = if self.compact_qubits {
+            report.qubit_remap = touched
+                .iter()
+                .enumerate()
+                .map(|(i, &q)| (q, LogicalQubitId::new(i as u64)))
+                .collect();
+            let remap = &report.qubit_remap;
+            ops = ops
+                .iter()
+                .map(|op| op.map_qubits(|q| remap[&q]))
+                .collect();
+            let map = |qs: &[LogicalQubitId]| qs.iter().filter_map(|q| remap.get(q).copied()).collect();
+            (map(circuit.inputs()), map(circuit.outputs()))
+        } else {
+            let keep = |qs: &[LogicalQubitId]| qs.iter().copied().filter(|q| touched.contains(q)).collect();
+            (keep(circuit.inputs()), keep(circuit.outputs()))
+        };
+
+        let out = circuit.rebuild_with(ops).with_inputs(inputs).with_outputs(outputs);
+        (out, report)
+    }
+}
+
+/// 删除紧邻测量之前的对角门（反向扫描，可连锁删除）
+fn remove_trailing_diagonal(ops: Vec<Operation>) -> (Vec<Operation>, usize) {
+    let mut measured_next: HashSet<LogicalQubitId> = HashSet::new();
+    let mut kept = Vec::with_capacity(ops.len());
+    let mut removed = 0;
+
+    for op in ops.into_iter().rev() {
+        let qubits = op.qubits();
+        if op.is_measurement() {
+            measured_next.extend(qubits);
+        } else if op.is_delay() {
+            // 延时不改变“下一个操作是测量”的状态
+        } else if op.is_diagonal() && qubits.iter().all(|q| measured_next.contains(q)) {
+            removed += 1;
+            continue;
+        } else {
+            for q in &qubits {
+                measured_next.remove(q);
+            }
+        }
+        kept.push(op);
+    }
+
+    kept.reverse();
+    (kept, removed)
+}
+
+/// 删除结果永远不会到达测量的操作
+///
+/// 电路中没有测量时视为所有 qubit 在末尾都被观测，不删除任何操作。
+/// 自定义操作语义未知，始终保留。
+fn remove_dead_gates(ops: Vec<Operation>) -> (Vec<Operation>, usize) {
+    if !ops.iter().any(|op| op.is_measurement()) {
+        return (ops, 0);
+    }
+
+    let mut live: HashSet<LogicalQubitId> = HashSet::new();
+    let mut kept = Vec::with_capacity(ops.len());
+    let mut removed = 0;
+
+    for op in ops.into_iter().rev() {
+        let qubits = op.qubits();
+        let keep = match &op {
+            Operation::Measure { qubit, .. } => {
+                live.insert(*qubit);
+                true
+            }
+            Operation::Reset { qubit } => {
+                // 重置之前的状态不会影响之后的测量
+                live.remove(qubit)
+            }
+            Operation::Barrier { .. } => true,
+            Operation::Custom(_) => {
+                live.extend(qubits);
+                true
+            }
+            _ => {
+                if qubits.iter().any(|q| live.contains(q)) {
+                    live.extend(qubits);
+                    true
+                } else {
+                    false
+                }
+            }
+        };
+
+        if keep {
+            kept.push(op);
+        } else {
+            removed += 1;
+        }
+    }
+
+    kept.reverse();
+    (kept, removed)
+}
+
+impl CircuitDag {
+    /// 使用默认配置清理电路
+    pub fn cleaned(&self) -> CircuitDag {
+        CircuitCleanup::default().run(self).0
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::CircuitDagBuilder;
+    use crate::operation::{cnot, cz, h, measure, rz, s, t, x};
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    fn names(circuit: &CircuitDag) -> Vec<String> {
+        circuit
+            .operations_in_order()
+            .iter()
+            .map(|op| op.name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_remove_dead_gates() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(0)));
+        builder.add_op(h(q(1)));
+        builder.add_op(x(q(2)));
+        builder.add_op(cnot(q(0), q(1)));
+        builder.add_op(measure(q(1)));
+        // 测量之后对 q0 的操作不会被观测
+        builder.add_op(x(q(0)));
+        let (out, report) = CircuitCleanup::new().with_compact_qubits(false).run(&builder.build());
+
+        assert_eq!(report.removed_dead_gates, 2);
+        assert_eq!(names(&out), vec!["H", "H", "CNOT", "M"]);
+        assert_eq!(report.removed_qubits, vec![q(2)]);
+    }
+
+    #[test]
+    fn test_gate_before_reset_is_dead() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(0)));
+        builder.add_op(Operation::reset(q(0)));
+        builder.add_op(x(q(0)));
+        builder.add_op(measure(q(0)));
+        let (out, report) = CircuitCleanup::new().run(&builder.build());
+        assert_eq!(report.removed_dead_gates, 1);
+        assert_eq!(names(&out), vec!["Reset", "X", "M"]);
+    }
+
+    #[test]
+    fn test_trailing_diagonal_gates() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(0)));
+        builder.add_op(h(q(1)));
+        builder.add_op(t(q(0)));
+        builder.add_op(s(q(0)));
+        builder.add_op(cz(q(0), q(1)));
+        builder.add_op(rz(q(1), 0.3));
+        builder.add_op(measure(q(0)));
+        builder.add_op(measure(q(1)));
+        let (out, report) = CircuitCleanup::new().run(&builder.build());
+
+        assert_eq!(report.removed_diagonal_gates, 4);
+        assert_eq!(names(&out), vec!["H", "H", "M", "M"]);
+    }
+
+    #[test]
+    fn test_diagonal_gate_followed_by_non_diagonal_is_kept() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(t(q(0)));
+        builder.add_op(h(q(0)));
+        builder.add_op(measure(q(0)));
+        let (out, report) = CircuitCleanup::new().run(&builder.build());
+        assert_eq!(report.total_removed(), 0);
+        assert_eq!(out.num_operations(), 3);
+    }
+
+    #[test]
+    fn test_barrier_only_qubits_and_compaction() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(3)));
+        builder.add_op(Operation::barrier(vec![q(1), q(3), q(7)]));
+        builder.add_op(x(q(5)));
+        builder.add_op(cnot(q(3), q(5)));
+        builder.add_op(Operation::barrier(vec![q(9)]));
+        builder.add_op(measure(q(5)));
+        let circuit = builder.build();
+        assert_eq!(circuit.num_qubits(), 5);
+
+        let (out, report) = CircuitCleanup::new().run(&circuit);
+        assert_eq!(out.num_qubits(), 2);
+        assert_eq!(report.removed_qubits, vec![q(1), q(7), q(9)]);
+        assert_eq!(report.removed_barriers, 1);
+        assert_eq!(report.qubit_remap.get(&q(3)), Some(&q(0)));
+        assert_eq!(report.qubit_remap.get(&q(5)), Some(&q(1)));
+        let barrier = out.operations_in_order().into_iter().find(|op| op.is_barrier()).unwrap();
+        assert_eq!(barrier.qubits(), vec![q(0)]);
+    }
+
+    #[test]
+    fn test_inputs_and_outputs_follow_compaction() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(2)));
+        builder.add_op(x(q(4)));
+        builder.add_op(cnot(q(2), q(6)));
+        builder.add_op(measure(q(2)));
+        builder.add_op(measure(q(6)));
+        let circuit = builder
+            .build()
+            .with_inputs(vec![q(2), q(4), q(6)])
+            .with_outputs(vec![q(2), q(4), q(6)]);
+
+        // q4 上的 X 从未被测量，整条线被删除
+        let (out, report) = CircuitCleanup::new().run(&circuit);
+        assert_eq!(report.removed_qubits, vec![q(4)]);
+        assert_eq!(out.inputs(), &[q(0), q(1)]);
+        assert_eq!(out.outputs(), &[q(0), q(1)]);
+        let measured: Vec<_> = out
+            .operations_in_order()
+            .into_iter()
+            .filter(|op| op.is_measurement())
+            .flat_map(|op| op.qubits())
+            .collect();
+        assert!(out.outputs().iter().all(|q| measured.contains(q)));
+
+        let (out, _) = CircuitCleanup::new().with_compact_qubits(false).run(&circuit);
+        assert_eq!(out.outputs(), &[q(2), q(6)]);
+    }
+
+    #[test]
+    fn test_strip_barriers_for_backend() {
+        let mut caps = BackendCapabilities::ideal_simulator();
+        caps.supports_barrier = false;
+
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(0)));
+        builder.add_op(Operation::barrier(vec![q(0)]));
+        builder.add_op(measure(q(0)));
+        let (out, report) = CircuitCleanup::for_backend(&caps).run(&builder.build());
+
+        assert_eq!(report.removed_barriers, 1);
+        assert!(out.nodes().iter().all(|n| !n.op.is_barrier()));
+    }
+
+    #[test]
+    fn test_no_measurement_keeps_everything() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(0)));
+        builder.add_op(cnot(q(0), q(1)));
+        let circuit = builder.build();
+        assert_eq!(circuit.cleaned().num_operations(), 2);
+    }
+}
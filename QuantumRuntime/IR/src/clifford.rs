@@ -0,0 +1,333 @@
+//! Clifford 模块
+//!
+//! Aaronson-Gottesman 稳定子 tableau，用于 Clifford 电路的多项式时间模拟与比较。
+//!
+//! 前 n 行为 destabilizer（X_i 的像），后 n 行为 stabilizer（Z_i 的像）。
+//! 两个 Clifford 电路在忽略全局相位时等价，当且仅当它们的 tableau 完全相同。
+
+use std::f64::consts::FRAC_PI_2;
+
+use crate::circuit::CircuitDag;
+use crate::operation::{Operation, SingleQubitGate, TwoQubitGate};
+use crate::qubit::LogicalQubitId;
+use crate::{Result, IrError};
+
+/// 角度判定为 π/2 整数倍的容差
+const ANGLE_TOLERANCE: f64 = 1e-9;
+
+/// 若 theta 为 π/2 的整数倍，返回倍数（模 4）
+fn quarter_turns(theta: f64) -> Option<usize> {
+    let k = (theta / FRAC_PI_2).round();
+    ((theta - k * FRAC_PI_2).abs() < ANGLE_TOLERANCE).then(|| k.rem_euclid(4.0) as usize)
+}
+
+/// 判断操作是否为 Clifford 操作（barrier 与延时视为 Clifford）
+pub fn is_clifford(op: &Operation) -> bool {
+    match op {
+        Operation::Gate1 { gate, .. } => match *gate {
+            SingleQubitGate::T | SingleQubitGate::Tdg => false,
+            SingleQubitGate::Rx(a) | SingleQubitGate::Ry(a) | SingleQubitGate::Rz(a) | SingleQubitGate::P(a) => {
+                quarter_turns(a).is_some()
+            }
+            SingleQubitGate::U(theta, phi, lam) => [theta, phi, lam].iter().all(|&a| quarter_turns(a).is_some()),
+            _ => true,
+        },
+        Operation::Gate2 { gate, .. } => match *gate {
+            TwoQubitGate::CNOT | TwoQubitGate::CZ | TwoQubitGate::SWAP | TwoQubitGate::ISWAP => true,
+            TwoQubitGate::CP(phi) => quarter_turns(phi).is_some_and(|k| k % 2 == 0),
+            TwoQubitGate::MS(theta) => quarter_turns(theta).is_some(),
+            TwoQubitGate::SqrtSWAP => false,
+        },
+        Operation::Barrier { .. } | Operation::Delay { .. } => true,
+        _ => false,
+    }
+}
+
+// ============================================================================
+// Clifford Tableau
+// ============================================================================
+
+/// 稳定子 tableau
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliffordTableau {
+    num_qubits: usize,
+    x: Vec<Vec<bool>>,
+    z: Vec<Vec<bool>>,
+    r: Vec<bool>,
+}
+
+impl CliffordTableau {
+    /// 恒等 tableau
+    pub fn identity(num_qubits: usize) -> Self {
+        let n = num_qubits;
+        let mut x = vec![vec![false; n]; 2 * n];
+        let mut z = vec![vec![false; n]; 2 * n];
+        for i in 0..n {
+            x[i][i] = true;
+            z[n + i][i] = true;
+        }
+        Self {
+            num_qubits: n,
+            x,
+            z,
+            r: vec![false; 2 * n],
+        }
+    }
+
+    /// 按 qubits 的顺序模拟 Clifford 电路
+    pub fn from_circuit(circuit: &CircuitDag, qubits: &[LogicalQubitId]) -> Result<Self> {
+        Self::from_operations(&circuit.operations_in_order(), qubits)
+    }
+
+    /// 由操作序列构建
+    pub fn from_operations(ops: &[Operation], qubits: &[LogicalQubitId]) -> Result<Self> {
+        let mut tableau = Self::identity(qubits.len());
+        for op in ops {
+            tableau.apply_operation(op, |q| qubits.iter().position(|&x| x == q))?;
+        }
+        Ok(tableau)
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    pub fn apply_h(&mut self, a: usize) {
+        for i in 0..2 * self.num_qubits {
+            self.r[i] ^= self.x[i][a] & self.z[i][a];
+            std::mem::swap(&mut self.x[i][a], &mut self.z[i][a]);
+        }
+    }
+
+    pub fn apply_s(&mut self, a: usize) {
+        for i in 0..2 * self.num_qubits {
+            self.r[i] ^= self.x[i][a] & self.z[i][a];
+            self.z[i][a] ^= self.x[i][a];
+        }
+    }
+
+    /// S^k（k 取模 4）
+    pub fn apply_s_power(&mut self, a: usize, k: usize) {
+        for _ in 0..k % 4 {
+            self.apply_s(a);
+        }
+    }
+
+    pub fn apply_x(&mut self, a: usize) {
+        for i in 0..2 * self.num_qubits {
+            self.r[i] ^= self.z[i][a];
+        }
+    }
+
+    pub fn apply_y(&mut self, a: usize) {
+        for i in 0..2 * self.num_qubits {
+            self.r[i] ^= self.x[i][a] ^ self.z[i][a];
+        }
+    }
+
+    pub fn apply_z(&mut self, a: usize) {
+        for i in 0..2 * self.num_qubits {
+            self.r[i] ^= self.x[i][a];
+        }
+    }
+
+    pub fn apply_cnot(&mut self, control: usize, target: usize) {
+        let (a, b) = (control, target);
+        for i in 0..2 * self.num_qubits {
+            self.r[i] ^= self.x[i][a] & self.z[i][b] & !(self.x[i][b] ^ self.z[i][a]);
+            self.x[i][b] ^= self.x[i][a];
+            self.z[i][a] ^= self.z[i][b];
+        }
+    }
+
+    pub fn apply_cz(&mut self, a: usize, b: usize) {
+        self.apply_h(b);
+        self.apply_cnot(a, b);
+        self.apply_h(b);
+    }
+
+    pub fn apply_swap(&mut self, a: usize, b: usize) {
+        self.apply_cnot(a, b);
+        self.apply_cnot(b, a);
+        self.apply_cnot(a, b);
+    }
+
+    /// Rx(k·π/2) = H S^k H（忽略全局相位）
+    fn apply_rx_quarter(&mut self, a: usize, k: usize) {
+        self.apply_h(a);
+        self.apply_s_power(a, k);
+        self.apply_h(a);
+    }
+
+    /// Ry(k·π/2) = S · Rx(k·π/2) · S†
+    fn apply_ry_quarter(&mut self, a: usize, k: usize) {
+        self.apply_s_power(a, 3);
+        self.apply_rx_quarter(a, k);
+        self.apply_s(a);
+    }
+
+    /// 作用一个 Clifford 操作，index_of 把逻辑 qubit 映射到 tableau 列
+    pub fn apply_operation<F>(&mut self, op: &Operation, index_of: F) -> Result<()>
+    where
+        F: Fn(LogicalQubitId) -> Option<usize>,
+    {
+        let non_clifford = || IrError::UnsupportedOperation(format!("{} is not a Clifford operation", op.name()));
+        if !is_clifford(op) {
+            return Err(non_clifford());
+        }
+        let idx = |q: LogicalQubitId| {
+            index_of(q).ok_or_else(|| IrError::QubitNotFound(format!("{} is not in the tableau", q)))
+        };
+        let turns = |a: f64| quarter_turns(a).ok_or_else(non_clifford);
+
+        match op {
+            Operation::Gate1 { gate, target } => {
+                let a = idx(*target)?;
+                match *gate {
+                    SingleQubitGate::X => self.apply_x(a),
+                    SingleQubitGate::Y => self.apply_y(a),
+                    SingleQubitGate::Z => self.apply_z(a),
+                    SingleQubitGate::H => self.apply_h(a),
+                    SingleQubitGate::S => self.apply_s(a),
+                    SingleQubitGate::Sdg => self.apply_s_power(a, 3),
+                    SingleQubitGate::Rz(phi) | SingleQubitGate::P(phi) => self.apply_s_power(a, turns(phi)?),
+                    SingleQubitGate::Rx(theta) => self.apply_rx_quarter(a, turns(theta)?),
+                    SingleQubitGate::Ry(theta) => self.apply_ry_quarter(a, turns(theta)?),
+                    // U(θ,φ,λ) = Rz(φ)·Ry(θ)·Rz(λ)（忽略全局相位）
+                    SingleQubitGate::U(theta, phi, lam) => {
+                        self.apply_s_power(a, turns(lam)?);
+                        self.apply_ry_quarter(a, turns(theta)?);
+                        self.apply_s_power(a, turns(phi)?);
+                    }
+                    SingleQubitGate::T | SingleQubitGate::Tdg => return Err(non_clifford()),
+                }
+            }
+            Operation::Gate2 { gate, control, target } => {
+                let (a, b) = (idx(*control)?, idx(*target)?);
+                match *gate {
+                    TwoQubitGate::CNOT => self.apply_cnot(a, b),
+                    TwoQubitGate::CZ => self.apply_cz(a, b),
+                    TwoQubitGate::SWAP => self.apply_swap(a, b),
+                    // iSWAP = SWAP · CZ · (S ⊗ S)
+                    TwoQubitGate::ISWAP => {
+                        self.apply_s(a);
+                        self.apply_s(b);
+                        self.apply_cz(a, b);
+                        self.apply_swap(a, b);
+                    }
+                    TwoQubitGate::CP(phi) => {
+                        if turns(phi)? % 4 == 2 {
+                            self.apply_cz(a, b);
+                        }
+                    }
+                    // MS(k·π/2) = (H⊗H) · CNOT · (I⊗S^k) · CNOT · (H⊗H)
+                    TwoQubitGate::MS(theta) => {
+                        let k = turns(theta)?;
+                        self.apply_h(a);
+                        self.apply_h(b);
+                        self.apply_cnot(a, b);
+                        self.apply_s_power(b, k);
+                        self.apply_cnot(a, b);
+                        self.apply_h(a);
+                        self.apply_h(b);
+                    }
+                    TwoQubitGate::SqrtSWAP => return Err(non_clifford()),
+                }
+            }
+            Operation::Barrier { .. } | Operation::Delay { .. } => {}
+            _ => return Err(non_clifford()),
+        }
+        Ok(())
+    }
+
+    /// 第 row 行的带符号 Pauli 串（如 "+XZI"，按 qubit 顺序）
+    pub fn row_string(&self, row: usize) -> String {
+        let mut s = String::with_capacity(self.num_qubits + 1);
+        s.push(if self.r[row] { '-' } else { '+' });
+        for q in 0..self.num_qubits {
+            s.push(match (self.x[row][q], self.z[row][q]) {
+                (false, false) => 'I',
+                (true, false) => 'X',
+                (true, true) => 'Y',
+                (false, true) => 'Z',
+            });
+        }
+        s
+    }
+
+    /// 第 row 行对应的生成元名称（X_i 或 Z_i）
+    pub fn generator_name(&self, row: usize) -> String {
+        if row < self.num_qubits {
+            format!("X{}", row)
+        } else {
+            format!("Z{}", row - self.num_qubits)
+        }
+    }
+
+    /// 第一个不同的行
+    pub fn first_difference(&self, other: &CliffordTableau) -> Option<usize> {
+        (0..2 * self.num_qubits).find(|&i| {
+            self.r[i] != other.r[i] || self.x[i] != other.x[i] || self.z[i] != other.z[i]
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation::{cnot, h, rz, s, t};
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    #[test]
+    fn test_h_conjugation() {
+        let mut tab = CliffordTableau::identity(1);
+        tab.apply_h(0);
+        assert_eq!(tab.row_string(0), "+Z");
+        assert_eq!(tab.row_string(1), "+X");
+        tab.apply_h(0);
+        assert_eq!(tab, CliffordTableau::identity(1));
+    }
+
+    #[test]
+    fn test_bell_stabilizers() {
+        let qubits = [q(0), q(1)];
+        let tab = CliffordTableau::from_operations(&[h(q(0)), cnot(q(0), q(1))], &qubits).unwrap();
+        // Z0 → XX, Z1 → ZZ
+        assert_eq!(tab.row_string(2), "+XX");
+        assert_eq!(tab.row_string(3), "+ZZ");
+    }
+
+    #[test]
+    fn test_s_squared_is_z() {
+        let qubits = [q(0)];
+        let ss = CliffordTableau::from_operations(&[s(q(0)), s(q(0))], &qubits).unwrap();
+        let z = CliffordTableau::from_operations(&[Operation::gate1(SingleQubitGate::Z, q(0))], &qubits).unwrap();
+        let rz_pi = CliffordTableau::from_operations(&[rz(q(0), std::f64::consts::PI)], &qubits).unwrap();
+        assert_eq!(ss, z);
+        assert_eq!(rz_pi, z);
+        assert_eq!(z.row_string(0), "-X");
+    }
+
+    #[test]
+    fn test_iswap_and_ms_decompositions_are_clifford() {
+        let qubits = [q(0), q(1)];
+        let iswap = Operation::gate2(TwoQubitGate::ISWAP, q(0), q(1));
+        let ms = Operation::gate2(TwoQubitGate::MS(FRAC_PI_2), q(0), q(1));
+        assert!(CliffordTableau::from_operations(&[iswap, ms], &qubits).is_ok());
+    }
+
+    #[test]
+    fn test_non_clifford_rejected() {
+        assert!(!is_clifford(&t(q(0))));
+        assert!(!is_clifford(&rz(q(0), 0.3)));
+        assert!(is_clifford(&rz(q(0), -FRAC_PI_2)));
+        assert!(CliffordTableau::from_operations(&[t(q(0))], &[q(0)]).is_err());
+    }
+}
@@ -0,0 +1,523 @@
+//! 复合门模块
+//!
+//! 具名、可带参数的子电路定义（如 QFT、加法器、oracle），保存在 DAG 的门定义库中。
+//! 电路中以引用定义名的 `CustomOp` 节点实例化，由 `CompositeInliner` 递归展开。
+//!
+//! 定义体使用形式 qubit：第 i 个操作数对应 `LogicalQubitId::new(i)`（见 [`formal`]）。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use crate::circuit::CircuitDag;
+use crate::operation::{CustomOp, Operation, SingleQubitGate, TwoQubitGate};
+use crate::qubit::LogicalQubitId;
+use crate::{Result, IrError};
+
+/// 第 i 个形式 qubit
+pub fn formal(index: usize) -> LogicalQubitId {
+    LogicalQubitId::new(index as u64)
+}
+
+// ============================================================================
+// Gate Definition
+// ============================================================================
+
+/// 定义体生成函数：输入参数值，输出作用在形式 qubit 上的操作序列
+pub type DefinitionBody = Arc<dyn Fn(&[f64]) -> Vec<Operation> + Send + Sync>;
+
+/// 复合门定义
+#[derive(Clone)]
+pub struct GateDefinition {
+    name: String,
+    num_qubits: usize,
+    param_names: Vec<String>,
+    description: Option<String>,
+    body: DefinitionBody,
+}
+
+impl std::fmt::Debug for GateDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GateDefinition")
+            .field("name", &self.name)
+            .field("num_qubits", &self.num_qubits)
+            .field("param_names", &self.param_names)
+            .field("description", &self.description)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GateDefinition {
+    /// 由生成函数创建定义
+    pub fn new<F>(name: impl Into<String>, num_qubits: usize, body: F) -> Self
+    where
+        F: Fn(&[f64]) -> Vec<Operation> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            num_qubits,
+            param_names: Vec::new(),
+            description: None,
+            body: Arc::new(body),
+        }
+    }
+
+    /// 由固定电路创建无参定义，qubit 按 ID 升序映射到形式 qubit
+    pub fn from_circuit(name: impl Into<String>, circuit: &CircuitDag) -> Self {
+        let mut qubits = circuit.all_qubits();
+        qubits.sort();
+        let ops: Vec<Operation> = circuit
+            .operations_in_order()
+            .iter()
+            .map(|op| op.map_qubits(|q| formal(qubits.iter().position(|&x| x == q).unwrap_or_default())))
+            .collect();
+        Self::new(name, qubits.len(), move |_| ops.clone())
+    }
+
+    pub fn with_params(mut self, names: Vec<impl Into<String>>) -> Self {
+        self.param_names = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    pub fn param_names(&self) -> &[String] {
+        &self.param_names
+    }
+
+    pub fn num_params(&self) -> usize {
+        self.param_names.len()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// 定义体（作用在形式 qubit 上）
+    pub fn body(&self, params: &[f64]) -> Vec<Operation> {
+        (self.body)(params)
+    }
+
+    fn check_arity(&self, qubits: &[LogicalQubitId], params: &[f64]) -> Result<()> {
+        if qubits.len() != self.num_qubits {
+            return Err(IrError::InvalidOperation(format!(
+                "{} expects {} qubits, got {}", self.name, self.num_qubits, qubits.len()
+            )));
+        }
+        if params.len() != self.num_params() {
+            return Err(IrError::InvalidOperation(format!(
+                "{} expects {} parameters, got {}", self.name, self.num_params(), params.len()
+            )));
+        }
+        let distinct: BTreeSet<_> = qubits.iter().collect();
+        if distinct.len() != qubits.len() {
+            return Err(IrError::InvalidOperation(format!(
+                "{} is applied to duplicate qubits", self.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// 创建引用本定义的实例节点
+    pub fn instantiate(&self, qubits: Vec<LogicalQubitId>, params: Vec<f64>) -> Result<Operation> {
+        self.check_arity(&qubits, &params)?;
+        Ok(Operation::Custom(
+            CustomOp::new(self.name.clone()).with_qubits(qubits).with_params(params),
+        ))
+    }
+
+    /// 展开一层：定义体映射到实际 qubit
+    pub fn expand(&self, qubits: &[LogicalQubitId], params: &[f64]) -> Result<Vec<Operation>> {
+        self.check_arity(qubits, params)?;
+        let body = self.body(params);
+        for op in &body {
+            if let Some(q) = op.qubits().into_iter().find(|q| q.value() as usize >= self.num_qubits) {
+                return Err(IrError::InvalidOperation(format!(
+                    "Definition {} uses formal qubit {} outside its {} operands", self.name, q, self.num_qubits
+                )));
+            }
+        }
+        Ok(body
+            .iter()
+            .map(|op| op.map_qubits(|q| qubits[q.value() as usize]))
+            .collect())
+    }
+}
+
+/// n 比特 QFT 定义（形式 qubit 0 为最高位，末尾含反转顺序的 SWAP）
+pub fn qft_definition(n: usize) -> GateDefinition {
+    GateDefinition::new(format!("QFT{}", n), n, move |_| {
+        let mut ops = Vec::new();
+        for i in 0..n {
+            ops.push(Operation::gate1(SingleQubitGate::H, formal(i)));
+            for j in i + 1..n {
+                let angle = std::f64::consts::PI / (1u64 << (j - i)) as f64;
+                ops.push(Operation::gate2(TwoQubitGate::CP(angle), formal(j), formal(i)));
+            }
+        }
+        for i in 0..n / 2 {
+            ops.push(Operation::gate2(TwoQubitGate::SWAP, formal(i), formal(n - 1 - i)));
+        }
+        ops
+    })
+    .with_description("Quantum Fourier transform")
+}
+
+// ============================================================================
+// Gate Library
+// ============================================================================
+
+/// 复合门定义库（按名称索引）
+#[derive(Debug, Clone, Default)]
+pub struct GateLibrary {
+    definitions: BTreeMap<String, GateDefinition>,
+}
+
+impl GateLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册定义，返回同名的旧定义
+    pub fn define(&mut self, definition: GateDefinition) -> Option<GateDefinition> {
+        self.definitions.insert(definition.name.clone(), definition)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&GateDefinition> {
+        self.definitions.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.definitions.contains_key(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<GateDefinition> {
+        self.definitions.remove(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.definitions.keys().map(String::as_str).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+
+    /// 合并另一个库，同名定义以 other 为准
+    pub fn merge(&mut self, other: &GateLibrary) {
+        for def in other.definitions.values() {
+            self.define(def.clone());
+        }
+    }
+
+    /// 操作若为库中定义的实例，返回对应定义
+    pub fn definition_of(&self, op: &Operation) -> Option<&GateDefinition> {
+        match op {
+            Operation::Custom(custom) => self.get(&custom.name),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Inline Pass
+// ============================================================================
+
+/// 复合门展开报告
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InlineReport {
+    /// 每个定义被展开的次数
+    pub expanded: BTreeMap<String, usize>,
+    /// 实际达到的最大嵌套深度
+    pub max_depth: usize,
+}
+
+impl InlineReport {
+    pub fn total_expanded(&self) -> usize {
+        self.expanded.values().sum()
+    }
+}
+
+/// 复合门展开（inline / unroll）通道
+#[derive(Debug, Clone)]
+pub struct CompositeInliner {
+    /// 最大嵌套深度，超过视为递归定义
+    pub max_depth: usize,
+    /// 只展开这些定义（None 表示全部）
+    pub only: Option<BTreeSet<String>>,
+}
+
+impl Default for CompositeInliner {
+    fn default() -> Self {
+        Self { max_depth: 32, only: None }
+    }
+}
+
+impl CompositeInliner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn with_only<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.only = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    fn selected(&self, name: &str) -> bool {
+        self.only.as_ref().is_none_or(|only| only.contains(name))
+    }
+
+    fn expand_into(
+        &self,
+        library: &GateLibrary,
+        op: Operation,
+        depth: usize,
+        out: &mut Vec<Operation>,
+        report: &mut InlineReport,
+    ) -> Result<()> {
+        let (definition, custom) = match (&op, library.definition_of(&op)) {
+            (Operation::Custom(custom), Some(def)) if self.selected(&custom.name) => (def, custom),
+            _ => {
+                out.push(op);
+                return Ok(());
+            }
+        };
+        if depth >= self.max_depth {
+            return Err(IrError::CyclicDependency(format!(
+                "Composite gate {} exceeds the maximum nesting depth {}", custom.name, self.max_depth
+            )));
+        }
+        *report.expanded.entry(custom.name.clone()).or_insert(0) += 1;
+        report.max_depth = report.max_depth.max(depth + 1);
+        for child in definition.expand(&custom.qubits, &custom.params)? {
+            self.expand_into(library, child, depth + 1, out, report)?;
+        }
+        Ok(())
+    }
+
+    /// 运行展开，返回新电路和报告（定义库保留）
+    pub fn run(&self, circuit: &CircuitDag) -> Result<(CircuitDag, InlineReport)> {
+        let mut report = InlineReport::default();
+        let mut ops = Vec::new();
+        for op in circuit.operations_in_order() {
+            self.expand_into(circuit.gate_library(), op, 0, &mut ops, &mut report)?;
+        }
+        Ok((circuit.rebuild_with(ops), report))
+    }
+}
+
+impl CircuitDag {
+    /// 添加复合门实例节点
+    pub fn add_composite(&mut self, name: &str, qubits: Vec<LogicalQubitId>, params: Vec<f64>) -> Result<usize> {
+        let op = self
+            .gate_library()
+            .get(name)
+            .ok_or_else(|| IrError::UnsupportedOperation(format!("Composite gate {} is not defined", name)))?
+            .instantiate(qubits, params)?;
+        Ok(self.add_node(op))
+    }
+
+    /// 递归展开全部复合门
+    pub fn inlined(&self) -> Result<CircuitDag> {
+        CompositeInliner::new().run(self).map(|(dag, _)| dag)
+    }
+
+    /// 按拓扑顺序列出操作，复合门实例经定义库展开
+    ///
+    /// 模拟、酉矩阵、取逆与加控制都经由此方法，复合门无需调用方预先展开。
+    pub(crate) fn resolved_operations(&self) -> Result<Vec<Operation>> {
+        if self.gate_library().is_empty() {
+            Ok(self.operations_in_order())
+        } else {
+            Ok(self.inlined()?.operations_in_order())
+        }
+    }
+
+    /// 按定义名统计复合门实例数
+    pub fn composite_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for node in self.nodes() {
+            if let Some(def) = self.gate_library().definition_of(&node.op) {
+                *counts.entry(def.name().to_string()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equivalence::EquivalenceChecker;
+    use crate::linalg::{Complex, Matrix};
+    use crate::operation::{cnot, h, rz, x};
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    /// exp(-iθ/2·Z⊗Z)
+    fn rzz_definition() -> GateDefinition {
+        GateDefinition::new("RZZ", 2, |p| vec![cnot(formal(0), formal(1)), rz(formal(1), p[0]), cnot(formal(0), formal(1))])
+            .with_params(vec!["theta"])
+    }
+
+    #[test]
+    fn test_instantiate_and_inline() {
+        let mut dag = CircuitDag::with_name("ising");
+        dag.define_gate(rzz_definition());
+        dag.add_composite("RZZ", vec![q(0), q(1)], vec![0.3]).unwrap();
+        dag.add_composite("RZZ", vec![q(1), q(2)], vec![0.5]).unwrap();
+        assert_eq!(dag.num_nodes(), 2);
+        assert_eq!(dag.composite_counts().get("RZZ"), Some(&2));
+
+        let (flat, report) = CompositeInliner::new().run(&dag).unwrap();
+        assert_eq!(flat.num_nodes(), 6);
+        assert_eq!(report.expanded.get("RZZ"), Some(&2));
+        assert!(flat.composite_counts().is_empty());
+        assert!(flat.gate_library().contains("RZZ"));
+        assert_eq!(flat.metadata().name.as_deref(), Some("ising"));
+
+        let ops = flat.operations_in_order();
+        assert_eq!(ops[1], rz(q(1), 0.3));
+        assert_eq!(ops[4], rz(q(2), 0.5));
+    }
+
+    #[test]
+    fn test_arity_validation() {
+        let mut dag = CircuitDag::new();
+        dag.define_gate(rzz_definition());
+        assert!(dag.add_composite("RZZ", vec![q(0)], vec![0.3]).is_err());
+        assert!(dag.add_composite("RZZ", vec![q(0), q(1)], vec![]).is_err());
+        assert!(dag.add_composite("RZZ", vec![q(0), q(0)], vec![0.3]).is_err());
+        assert!(dag.add_composite("missing", vec![q(0)], vec![]).is_err());
+    }
+
+    #[test]
+    fn test_nested_definitions() {
+        let mut dag = CircuitDag::new();
+        dag.define_gate(rzz_definition());
+        dag.define_gate(
+            GateDefinition::new("LAYER", 3, |p| vec![
+                Operation::Custom(CustomOp::new("RZZ").with_qubits(vec![formal(0), formal(1)]).with_params(vec![p[0]])),
+                Operation::Custom(CustomOp::new("RZZ").with_qubits(vec![formal(1), formal(2)]).with_params(vec![p[0]])),
+            ])
+            .with_params(vec!["gamma"]),
+        );
+        dag.add_composite("LAYER", vec![q(2), q(1), q(0)], vec![0.7]).unwrap();
+
+        let (flat, report) = CompositeInliner::new().run(&dag).unwrap();
+        assert_eq!(flat.num_nodes(), 6);
+        assert_eq!(report.max_depth, 2);
+        assert_eq!(report.total_expanded(), 3);
+
+        // 只展开外层
+        let (partial, _) = CompositeInliner::new().with_only(["LAYER"]).run(&dag).unwrap();
+        assert_eq!(partial.composite_counts().get("RZZ"), Some(&2));
+    }
+
+    #[test]
+    fn test_recursive_definition_rejected() {
+        let mut dag = CircuitDag::new();
+        dag.define_gate(GateDefinition::new("LOOP", 1, |_| {
+            vec![x(formal(0)), Operation::Custom(CustomOp::new("LOOP").with_qubits(vec![formal(0)]))]
+        }));
+        dag.add_composite("LOOP", vec![q(0)], vec![]).unwrap();
+        assert!(matches!(dag.inlined(), Err(IrError::CyclicDependency(_))));
+    }
+
+    #[test]
+    fn test_qft_and_from_circuit() {
+        let mut dag = CircuitDag::new();
+        dag.define_gate(qft_definition(3));
+        dag.add_composite("QFT3", vec![q(0), q(1), q(2)], vec![]).unwrap();
+        let flat = dag.inlined().unwrap();
+        assert_eq!(flat.num_nodes(), 3 + 3 + 1);
+        assert!(flat.unitary().unwrap().is_unitary(1e-10));
+
+        let mut bell = CircuitDag::new();
+        bell.add_node(h(q(4)));
+        bell.add_node(cnot(q(4), q(7)));
+        let def = GateDefinition::from_circuit("BELL", &bell);
+        assert_eq!(def.num_qubits(), 2);
+        assert_eq!(def.body(&[]), vec![h(formal(0)), cnot(formal(0), formal(1))]);
+
+        let mut dag = CircuitDag::new();
+        dag.define_gate(def);
+        dag.add_composite("BELL", vec![q(4), q(7)], vec![]).unwrap();
+        let verdict = EquivalenceChecker::new().check(&dag.inlined().unwrap(), &bell).unwrap();
+        assert!(verdict.is_equivalent());
+    }
+
+    #[test]
+    fn test_simulate_composite_without_inlining() {
+        use crate::backend::{BackendAdapter, IdealSimulatorBackend};
+        use crate::job::{Job, JobStatus};
+        use crate::operation::measure;
+
+        let mut dag = CircuitDag::new();
+        dag.define_gate(rzz_definition());
+        dag.define_gate(GateDefinition::new("FLIP", 1, |_| vec![x(formal(0))]));
+        dag.add_node(h(q(0)));
+        dag.add_composite("RZZ", vec![q(0), q(1)], vec![0.7]).unwrap();
+        dag.add_composite("FLIP", vec![q(1)], vec![]).unwrap();
+        let flat = dag.inlined().unwrap();
+        assert!(dag.unitary().unwrap().approx_eq(&flat.unitary().unwrap(), 1e-12));
+
+        dag.add_node(measure(q(1)));
+        let result = IdealSimulatorBackend::new().execute(&Job::new(dag, 50, "ideal_simulator")).unwrap();
+        assert_eq!(result.status, JobStatus::Completed);
+        assert_eq!(result.get_probability(q(1)), Some(1.0));
+    }
+
+    #[test]
+    fn test_invert_and_control_composite() {
+        let mut dag = CircuitDag::new();
+        dag.define_gate(rzz_definition());
+        dag.add_node(h(q(0)));
+        dag.add_composite("RZZ", vec![q(0), q(1)], vec![0.4]).unwrap();
+        dag.add_node(rz(q(1), 0.2));
+
+        let u = dag.unitary().unwrap();
+        let inverse = dag.inverse().unwrap();
+        assert!(inverse.composite_counts().is_empty());
+        assert!(inverse.unitary().unwrap().matmul(&u).approx_eq(&Matrix::identity(4), 1e-10));
+
+        // 控制 qubit q2 为最高位：控制为 1 的子块等于 U
+        let controlled = dag.control(1).unwrap().unitary().unwrap();
+        for row in 0..4 {
+            for col in 0..4 {
+                let identity = if row == col { Complex::ONE } else { Complex::ZERO };
+                assert!(controlled.get(row, col).approx_eq(identity, 1e-10));
+                assert!(controlled.get(row + 4, col + 4).approx_eq(u.get(row, col), 1e-10));
+            }
+        }
+    }
+}
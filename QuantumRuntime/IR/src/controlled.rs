@@ -0,0 +1,449 @@
+//! 受控操作模块
+//!
+//! 把门和子电路变换为多控制版本（controlled-U）。
+//! 有原生受控形式时直接使用（CNOT、CZ、CP、Toffoli、CCZ、Fredkin），否则分解为
+//! CNOT/Toffoli 与单比特旋转：
+//! - 单控制：ZYZ 欧拉分解 + ABC 构造（Nielsen & Chuang 定理 4.3）
+//! - 多控制：V = √U 递归（Barenco et al. 引理 7.5）
+//! - 双比特门：先写成 `V·W·V†` 形式，只对中间的 W 加控制
+
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, FRAC_PI_8};
+
+use crate::circuit::CircuitDag;
+use crate::linalg::{Complex, Matrix};
+use crate::operation::{Operation, SingleQubitGate, ThreeQubitGate, TwoQubitGate};
+use crate::qubit::LogicalQubitId;
+use crate::{Result, IrError};
+
+/// 判定矩阵相等与角度为零的容差
+const TOLERANCE: f64 = 1e-12;
+
+// ============================================================================
+// Single Qubit Decomposition
+// ============================================================================
+
+/// 单比特酉矩阵的 ZYZ 欧拉分解
+///
+/// 返回 (α, β, γ, δ)，满足 U = e^{iα}·Rz(β)·Ry(γ)·Rz(δ)
+pub fn euler_zyz(u: &Matrix) -> (f64, f64, f64, f64) {
+    let det = u.get(0, 0) * u.get(1, 1) - u.get(0, 1) * u.get(1, 0);
+    let alpha = det.arg() / 2.0;
+    let v = u.scale(Complex::cis(-alpha));
+    let (v00, v10, v11) = (v.get(0, 0), v.get(1, 0), v.get(1, 1));
+
+    let gamma = 2.0 * v10.abs().atan2(v00.abs());
+    let sum = if v11.abs() > TOLERANCE { 2.0 * v11.arg() } else { 0.0 };
+    let diff = if v10.abs() > TOLERANCE { 2.0 * v10.arg() } else { 0.0 };
+    (alpha, (sum + diff) / 2.0, gamma, (sum - diff) / 2.0)
+}
+
+/// 2×2 矩阵的主平方根：√M = (M + s·I) / √(tr M + 2s)，s = ±√det M
+fn sqrt_2x2(m: &Matrix) -> Matrix {
+    let det = m.get(0, 0) * m.get(1, 1) - m.get(0, 1) * m.get(1, 0);
+    let trace = m.trace();
+    let mut s = Complex::from_polar(det.abs().sqrt(), det.arg() / 2.0);
+    let mut t2 = trace + s * 2.0;
+    if t2.abs() < TOLERANCE {
+        s = -s;
+        t2 = trace + s * 2.0;
+    }
+    let t = Complex::from_polar(t2.abs().sqrt(), t2.arg() / 2.0);
+    let inv_t = t.conj() / t.norm_sqr();
+    let mut root = m.clone();
+    for i in 0..2 {
+        root.set(i, i, root.get(i, i) + s);
+    }
+    root.scale(inv_t)
+}
+
+fn push_rotation(ops: &mut Vec<Operation>, gate: fn(f64) -> SingleQubitGate, angle: f64, target: LogicalQubitId) {
+    if angle.abs() > TOLERANCE {
+        ops.push(Operation::gate1(gate(angle), target));
+    }
+}
+
+// ============================================================================
+// Multi-Controlled Single Qubit Unitary
+// ============================================================================
+
+/// 所有控制位为 1 时施加全局相位 e^{iφ}
+fn controlled_phase(controls: &[LogicalQubitId], phase: f64) -> Vec<Operation> {
+    if phase.abs() <= TOLERANCE {
+        return Vec::new();
+    }
+    match controls.split_last() {
+        None => Vec::new(),
+        Some((&last, rest)) => {
+            multi_controlled_unitary(rest, last, &SingleQubitGate::P(phase).matrix())
+        }
+    }
+}
+
+/// 多控制单比特酉门 C^n(U)
+pub fn multi_controlled_unitary(controls: &[LogicalQubitId], target: LogicalQubitId, u: &Matrix) -> Vec<Operation> {
+    let is = |gate: SingleQubitGate| u.approx_eq(&gate.matrix(), TOLERANCE);
+    let diagonal = u.get(0, 1).abs() <= TOLERANCE && u.get(1, 0).abs() <= TOLERANCE;
+
+    match controls {
+        [] => {
+            let (_, beta, gamma, delta) = euler_zyz(u);
+            vec![Operation::gate1(SingleQubitGate::U(gamma, beta, delta), target)]
+        }
+        [c] if is(SingleQubitGate::X) => vec![Operation::gate2(TwoQubitGate::CNOT, *c, target)],
+        [c] if is(SingleQubitGate::Z) => vec![Operation::gate2(TwoQubitGate::CZ, *c, target)],
+        [c1, c2] if is(SingleQubitGate::X) => vec![Operation::gate3(ThreeQubitGate::Toffoli, *c1, *c2, target)],
+        [c1, c2] if is(SingleQubitGate::Z) => vec![Operation::gate3(ThreeQubitGate::CCZ, *c1, *c2, target)],
+        // diag(a, b) = a·P(arg(b/a))
+        [c] if diagonal => {
+            let (a, b) = (u.get(0, 0), u.get(1, 1));
+            let mut ops = vec![Operation::gate2(TwoQubitGate::CP(b.arg() - a.arg()), *c, target)];
+            ops.extend(controlled_phase(controls, a.arg()));
+            ops
+        }
+        // U = e^{iα}·A·X·B·X·C，A·B·C = I
+        [c] => {
+            let (alpha, beta, gamma, delta) = euler_zyz(u);
+            let mut ops = Vec::new();
+            push_rotation(&mut ops, SingleQubitGate::Rz, (delta - beta) / 2.0, target);
+            ops.push(Operation::gate2(TwoQubitGate::CNOT, *c, target));
+            push_rotation(&mut ops, SingleQubitGate::Rz, -(delta + beta) / 2.0, target);
+            push_rotation(&mut ops, SingleQubitGate::Ry, -gamma / 2.0, target);
+            ops.push(Operation::gate2(TwoQubitGate::CNOT, *c, target));
+            push_rotation(&mut ops, SingleQubitGate::Ry, gamma / 2.0, target);
+            push_rotation(&mut ops, SingleQubitGate::Rz, beta, target);
+            ops.extend(controlled_phase(controls, alpha));
+            ops
+        }
+        // C^n(U) = C(V)·C^{n-1}(X)·C(V†)·C^{n-1}(X)·C^{n-1}(V)，V² = U
+        _ => {
+            let (&last, rest) = controls.split_last().expect("at least two controls");
+            let v = sqrt_2x2(u);
+            let x = SingleQubitGate::X.matrix();
+            let mut ops = multi_controlled_unitary(&[last], target, &v);
+            ops.extend(multi_controlled_unitary(rest, last, &x));
+            ops.extend(multi_controlled_unitary(&[last], target, &v.dagger()));
+            ops.extend(multi_controlled_unitary(rest, last, &x));
+            ops.extend(multi_controlled_unitary(rest, target, &v));
+            ops
+        }
+    }
+}
+
+// ============================================================================
+// Two Qubit Gates
+// ============================================================================
+
+/// 分解片段：Fixed 成对出现（V 与 V†），加控制时保持不变；Controlled 需要加控制
+#[derive(Debug, Clone)]
+enum Segment {
+    Fixed(Operation),
+    Controlled(Operation),
+}
+
+fn rzz_segments(a: LogicalQubitId, b: LogicalQubitId, theta: f64) -> Vec<Segment> {
+    vec![
+        Segment::Fixed(Operation::gate2(TwoQubitGate::CNOT, a, b)),
+        Segment::Controlled(Operation::gate1(SingleQubitGate::Rz(theta), b)),
+        Segment::Fixed(Operation::gate2(TwoQubitGate::CNOT, a, b)),
+    ]
+}
+
+/// exp(-iθ/2·X⊗X) = (H⊗H)·Rzz(θ)·(H⊗H)
+fn rxx_segments(a: LogicalQubitId, b: LogicalQubitId, theta: f64) -> Vec<Segment> {
+    let h = |q| Segment::Fixed(Operation::gate1(SingleQubitGate::H, q));
+    let mut segments = vec![h(a), h(b)];
+    segments.extend(rzz_segments(a, b, theta));
+    segments.extend([h(a), h(b)]);
+    segments
+}
+
+/// exp(-iθ/2·Y⊗Y) = (S⊗S)·Rxx(θ)·(S†⊗S†)
+fn ryy_segments(a: LogicalQubitId, b: LogicalQubitId, theta: f64) -> Vec<Segment> {
+    let gate = |g, q| Segment::Fixed(Operation::gate1(g, q));
+    let mut segments = vec![gate(SingleQubitGate::Sdg, a), gate(SingleQubitGate::Sdg, b)];
+    segments.extend(rxx_segments(a, b, theta));
+    segments.extend([gate(SingleQubitGate::S, a), gate(SingleQubitGate::S, b)]);
+    segments
+}
+
+/// 双比特门的分解片段及全局相位
+fn two_qubit_segments(gate: TwoQubitGate, a: LogicalQubitId, b: LogicalQubitId) -> (Vec<Segment>, f64) {
+    match gate {
+        TwoQubitGate::SWAP => (
+            vec![
+                Segment::Fixed(Operation::gate2(TwoQubitGate::CNOT, b, a)),
+                Segment::Controlled(Operation::gate2(TwoQubitGate::CNOT, a, b)),
+                Segment::Fixed(Operation::gate2(TwoQubitGate::CNOT, b, a)),
+            ],
+            0.0,
+        ),
+        // iSWAP = exp(iπ/4·(XX + YY))
+        TwoQubitGate::ISWAP => {
+            let mut segments = rxx_segments(a, b, -FRAC_PI_2);
+            segments.extend(ryy_segments(a, b, -FRAC_PI_2));
+            (segments, 0.0)
+        }
+        // √SWAP = e^{iπ/8}·exp(-iπ/8·(XX + YY + ZZ))
+        TwoQubitGate::SqrtSWAP => {
+            let mut segments = rxx_segments(a, b, FRAC_PI_4);
+            segments.extend(ryy_segments(a, b, FRAC_PI_4));
+            segments.extend(rzz_segments(a, b, FRAC_PI_4));
+            (segments, FRAC_PI_8)
+        }
+        TwoQubitGate::MS(theta) => (rxx_segments(a, b, theta), 0.0),
+        TwoQubitGate::CNOT | TwoQubitGate::CZ | TwoQubitGate::CP(_) => {
+            (vec![Segment::Controlled(Operation::gate2(gate, a, b))], 0.0)
+        }
+    }
+}
+
+/// 逆分解：反转顺序、逐段取逆、相位取反
+fn invert_segments(segments: Vec<Segment>, phase: f64) -> Result<(Vec<Segment>, f64)> {
+    let inverted = segments
+        .into_iter()
+        .rev()
+        .map(|segment| match segment {
+            Segment::Fixed(op) => op.inverse().map(Segment::Fixed),
+            Segment::Controlled(op) => op.inverse().map(Segment::Controlled),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((inverted, -phase))
+}
+
+fn control_segments(controls: &[LogicalQubitId], segments: Vec<Segment>, phase: f64) -> Result<Vec<Operation>> {
+    let mut ops = Vec::new();
+    for segment in segments {
+        match segment {
+            Segment::Fixed(op) => ops.push(op),
+            Segment::Controlled(op) => ops.extend(op.control(controls)?),
+        }
+    }
+    ops.extend(controlled_phase(controls, phase));
+    Ok(ops)
+}
+
+fn with_controls(controls: &[LogicalQubitId], extra: &[LogicalQubitId]) -> Vec<LogicalQubitId> {
+    controls.iter().chain(extra).copied().collect()
+}
+
+// ============================================================================
+// Operation / Circuit Transforms
+// ============================================================================
+
+impl Operation {
+    /// 多控制版本：所有 controls 为 |1⟩ 时才作用本操作
+    ///
+    /// 返回等价的门序列；测量、重置和未附带单比特矩阵的自定义操作无法加控制。
+    pub fn control(&self, controls: &[LogicalQubitId]) -> Result<Vec<Operation>> {
+        let targets = self.qubits();
+        for (i, c) in controls.iter().enumerate() {
+            if targets.contains(c) || controls[..i].contains(c) {
+                return Err(IrError::InvalidOperation(format!(
+                    "Control qubit {} overlaps with the operation or other controls", c
+                )));
+            }
+        }
+        if controls.is_empty() {
+            return Ok(vec![self.clone()]);
+        }
+
+        match self {
+            Operation::Gate1 { gate, target } => Ok(multi_controlled_unitary(controls, *target, &gate.matrix())),
+            Operation::Gate2 { gate, control, target } => match gate {
+                TwoQubitGate::CNOT | TwoQubitGate::CZ | TwoQubitGate::CP(_) => {
+                    let base = match gate {
+                        TwoQubitGate::CNOT => SingleQubitGate::X,
+                        TwoQubitGate::CZ => SingleQubitGate::Z,
+                        _ => SingleQubitGate::P(gate.parameters()[0]),
+                    };
+                    Ok(multi_controlled_unitary(&with_controls(controls, &[*control]), *target, &base.matrix()))
+                }
+                TwoQubitGate::SWAP if controls.len() == 1 => {
+                    Ok(vec![Operation::gate3(ThreeQubitGate::Fredkin, controls[0], *control, *target)])
+                }
+                _ => {
+                    let (segments, phase) = two_qubit_segments(*gate, *control, *target);
+                    control_segments(controls, segments, phase)
+                }
+            },
+            Operation::Gate3 { gate, controls: inner, target } => {
+                let all = with_controls(controls, inner);
+                match gate {
+                    ThreeQubitGate::Toffoli => Ok(multi_controlled_unitary(&all, *target, &SingleQubitGate::X.matrix())),
+                    ThreeQubitGate::CCZ => Ok(multi_controlled_unitary(&all, *target, &SingleQubitGate::Z.matrix())),
+                    ThreeQubitGate::Fredkin => {
+                        let (segments, phase) = two_qubit_segments(TwoQubitGate::SWAP, inner[1], *target);
+                        control_segments(&with_controls(controls, &inner[..1]), segments, phase)
+                    }
+                }
+            }
+            Operation::Barrier { qubits } => Ok(vec![Operation::barrier(with_controls(controls, qubits))]),
+            Operation::Delay { .. } => Ok(vec![self.clone()]),
+            Operation::Custom(op) => {
+                let gate = op.metadata.get("adjoint_of").and_then(|name| TwoQubitGate::from_adjoint_name(name));
+                match (gate, op.qubits.as_slice(), &op.matrix) {
+                    (Some(gate), [a, b], _) => {
+                        let (segments, phase) = two_qubit_segments(gate, *a, *b);
+                        let (segments, phase) = invert_segments(segments, phase)?;
+                        control_segments(controls, segments, phase)
+                    }
+                    (None, [target], Some(matrix)) if matrix.dim() == 2 => {
+                        Ok(multi_controlled_unitary(controls, *target, matrix))
+                    }
+                    _ => Err(IrError::UnsupportedOperation(format!(
+                        "Custom operation {} cannot be controlled", op.name
+                    ))),
+                }
+            }
+            Operation::Symbolic { .. } => Err(IrError::UnsupportedOperation(format!(
+                "{} has unbound parameters and cannot be controlled", self.name()
+            ))),
+            Operation::Measure { .. } | Operation::Reset { .. } => Err(IrError::InvalidOperation(format!(
+                "{} cannot be controlled", self.name()
+            ))),
+        }
+    }
+}
+
+impl CircuitDag {
+    /// 多控制版本，控制 qubit 由调用方指定；控制 qubit 排在输入列表最前
+    /// 
+    /// 复合门先按定义展开。
+    pub fn control_on(&self, controls: &[LogicalQubitId]) -> Result<CircuitDag> {
+        let mut ops = Vec::new();
+        for op in self.resolved_operations()? {
+            ops.extend(op.control(controls)?);
+        }
+        let inputs = with_controls(controls, self.inputs());
+        Ok(self.rebuild_with(ops).with_inputs(inputs))
+    }
+
+    /// 加 n 个新的控制 qubit，编号接在电路现有最大 qubit ID 之后
+    pub fn control(&self, n: usize) -> Result<CircuitDag> {
+        let next = self
+            .all_qubits()
+            .into_iter()
+            .chain(self.inputs().iter().copied())
+            .map(|q| q.value() + 1)
+            .max()
+            .unwrap_or(0);
+        let controls: Vec<LogicalQubitId> = (0..n as u64).map(|i| LogicalQubitId::new(next + i)).collect();
+        self.control_on(&controls)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equivalence::EquivalenceChecker;
+    use crate::operation::{cnot, h, measure, x, CustomOp};
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    fn circuit(ops: Vec<Operation>) -> CircuitDag {
+        let mut dag = CircuitDag::new();
+        for op in ops {
+            dag.add_node(op);
+        }
+        dag
+    }
+
+    /// 期望的受控矩阵：控制位在高位，右下角为 U
+    fn expected(op: &Operation, controls: &[LogicalQubitId]) -> CircuitDag {
+        let u = op.matrix().unwrap();
+        let dim = u.dim() << controls.len();
+        let offset = dim - u.dim();
+        let mut m = Matrix::identity(dim);
+        for i in 0..u.dim() {
+            for j in 0..u.dim() {
+                m.set(offset + i, offset + j, u.get(i, j));
+            }
+        }
+        let qubits = with_controls(controls, &op.qubits());
+        circuit(vec![Operation::Custom(CustomOp::new("expected").with_qubits(qubits).with_matrix(m))])
+    }
+
+    fn assert_controlled(op: Operation, n: u64) {
+        let controls: Vec<LogicalQubitId> = (10..10 + n).map(q).collect();
+        let decomposed = circuit(op.control(&controls).unwrap());
+        let verdict = EquivalenceChecker::new().check(&decomposed, &expected(&op, &controls)).unwrap();
+        assert!(verdict.is_equivalent(), "C^{}({}) differs: {:?}", n, op.name(), verdict);
+    }
+
+    #[test]
+    fn test_native_controlled_forms() {
+        assert_eq!(x(q(0)).control(&[q(1)]).unwrap(), vec![cnot(q(1), q(0))]);
+        assert_eq!(
+            cnot(q(0), q(1)).control(&[q(2)]).unwrap(),
+            vec![Operation::gate3(ThreeQubitGate::Toffoli, q(2), q(0), q(1))]
+        );
+        assert_eq!(
+            Operation::gate2(TwoQubitGate::SWAP, q(0), q(1)).control(&[q(2)]).unwrap(),
+            vec![Operation::gate3(ThreeQubitGate::Fredkin, q(2), q(0), q(1))]
+        );
+        assert!(x(q(0)).control(&[q(0)]).is_err());
+    }
+
+    #[test]
+    fn test_controlled_single_qubit_gates() {
+        let gates = [
+            SingleQubitGate::H, SingleQubitGate::Y, SingleQubitGate::T, SingleQubitGate::Rx(0.7),
+            SingleQubitGate::Rz(-1.3), SingleQubitGate::U(0.4, 1.1, -0.6),
+        ];
+        for gate in gates {
+            for n in 1..=3 {
+                assert_controlled(Operation::gate1(gate, q(0)), n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_controlled_multi_qubit_gates() {
+        let gates = [
+            TwoQubitGate::CZ, TwoQubitGate::CP(0.9), TwoQubitGate::SWAP, TwoQubitGate::ISWAP,
+            TwoQubitGate::SqrtSWAP, TwoQubitGate::MS(0.5),
+        ];
+        for gate in gates {
+            for n in 1..=2 {
+                assert_controlled(Operation::gate2(gate, q(0), q(1)), n);
+            }
+        }
+        for gate in [ThreeQubitGate::Toffoli, ThreeQubitGate::Fredkin, ThreeQubitGate::CCZ] {
+            assert_controlled(Operation::gate3(gate, q(0), q(1), q(2)), 1);
+        }
+    }
+
+    #[test]
+    fn test_controlled_inverse_custom() {
+        for gate in [TwoQubitGate::ISWAP, TwoQubitGate::SqrtSWAP] {
+            let inverse = Operation::gate2(gate, q(0), q(1)).inverse().unwrap();
+            assert_controlled(inverse, 1);
+        }
+    }
+
+    #[test]
+    fn test_control_circuit() {
+        let bell = circuit(vec![h(q(0)), cnot(q(0), q(1))]);
+        let controlled = bell.control(1).unwrap();
+        assert_eq!(controlled.inputs(), &[q(2)]);
+
+        // 控制位为 |0⟩ 时不作用，为 |1⟩ 时制备 Bell 态
+        let u = controlled.unitary().unwrap();
+        let bell_u = bell.unitary().unwrap();
+        for i in 0..4 {
+            for j in 0..4 {
+                let identity = if i == j { Complex::ONE } else { Complex::ZERO };
+                assert!(u.get(i, j).approx_eq(identity, 1e-9));
+                assert!(u.get(4 + i, 4 + j).approx_eq(bell_u.get(i, j), 1e-9));
+            }
+        }
+
+        assert!(circuit(vec![measure(q(0))]).control(1).is_err());
+    }
+}
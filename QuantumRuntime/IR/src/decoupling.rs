@@ -0,0 +1,409 @@
+//! 动态解耦（Dynamical Decoupling）模块
+//!
+//! 在时序调度之后，用 XX / XY4 / CPMG 脉冲序列填充每个 qubit 的空闲窗口。
+//! 窗口必须足够容纳脉冲（按后端门时长计算），且不跨越 barrier 或测量边界。
+
+use std::collections::{HashMap, HashSet};
+
+use crate::backend::{BackendCapabilities, ErrorModel, GateDurations};
+use crate::circuit::CircuitDag;
+use crate::operation::{rz, Operation, SingleQubitGate};
+use crate::qubit::LogicalQubitId;
+use crate::rng::SplitMix64;
+use crate::statevector::{final_state, StateVector};
+use crate::timing::{IdleWindow, ScheduleMode, TimingSchedule};
+use crate::Result;
+
+// ============================================================================
+// DD Sequences
+// ============================================================================
+
+/// 动态解耦脉冲序列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DdSequence {
+    /// X-X，等间距
+    XX,
+    /// X-Y-X-Y，对称间距
+    XY4,
+    /// Carr-Purcell-Meiboom-Gill：X-X，τ/4 - τ/2 - τ/4 间距
+    CPMG,
+}
+
+impl DdSequence {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DdSequence::XX => "XX",
+            DdSequence::XY4 => "XY4",
+            DdSequence::CPMG => "CPMG",
+        }
+    }
+
+    /// 脉冲序列（乘积为单位元，忽略全局相位）
+    pub fn pulses(&self) -> Vec<SingleQubitGate> {
+        use SingleQubitGate::{X, Y};
+        match self {
+            DdSequence::XX | DdSequence::CPMG => vec![X, X],
+            DdSequence::XY4 => vec![X, Y, X, Y],
+        }
+    }
+
+    /// 脉冲之间空闲时间的分配比例（长度为脉冲数 + 1，和为 1）
+    pub fn spacing(&self) -> Vec<f64> {
+        match self {
+            DdSequence::XX => vec![1.0 / 3.0; 3],
+            DdSequence::CPMG => vec![0.25, 0.5, 0.25],
+            DdSequence::XY4 => vec![0.125, 0.25, 0.25, 0.25, 0.125],
+        }
+    }
+}
+
+// ============================================================================
+// Report
+// ============================================================================
+
+/// 动态解耦报告
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecouplingReport {
+    /// 考察的空闲窗口数量
+    pub windows_considered: usize,
+    /// 插入了解耦序列的窗口数量
+    pub windows_filled: usize,
+    /// 插入的脉冲数量
+    pub pulses_inserted: usize,
+    /// 受保护的空闲时间（纳秒，所有 qubit 合计）
+    pub protected_idle_ns: f64,
+    /// 未受保护的空闲时间（纳秒，所有 qubit 合计）
+    pub unprotected_idle_ns: f64,
+}
+
+impl DecouplingReport {
+    /// 空闲退相干的一阶保真度估算
+    ///
+    /// 未保护的空闲按 T2 衰减；DD 抑制纯退相位，受保护空闲只剩 T1 限制（2·T1）
+    pub fn idle_fidelity(&self, model: &ErrorModel) -> f64 {
+        (-self.unprotected_idle_ns / model.t2_time_ns
+            - self.protected_idle_ns / (2.0 * model.t1_time_ns))
+            .exp()
+    }
+}
+
+/// 不做动态解耦时空闲退相干的一阶保真度估算
+pub fn idle_fidelity(schedule: &TimingSchedule, model: &ErrorModel) -> f64 {
+    (-schedule.total_idle_ns() / model.t2_time_ns).exp()
+}
+
+/// 在准静态退相位噪声下模拟电路，返回末态相对无噪声末态的平均保真度
+///
+/// 每个采样为每个 qubit 抽取一个固定的频率失谐 δ ~ N(0, 2/T2²)，
+/// 使单个 qubit 的相干性按 exp(-(t/T2)²) 衰减。按 ASAP 调度，qubit 在两个操作之间的空闲
+/// 以及显式 `Delay` 期间累积相位 Rz(δ·t)；门视为瞬时且无噪声，末尾测量被忽略。
+/// 解耦脉冲翻转累积相位的符号，因此能抵消这类噪声。
+pub fn simulate_idle_fidelity(
+    circuit: &CircuitDag,
+    durations: &GateDurations,
+    model: &ErrorModel,
+    samples: usize,
+    seed: u64,
+) -> Result<f64> {
+    let (qubits, ideal) = final_state(circuit)?;
+    let schedule = TimingSchedule::compute(circuit, durations, ScheduleMode::Asap);
+    let timeline: Vec<_> = schedule
+        .timeline()
+        .into_iter()
+        .filter(|op| !circuit.nodes()[op.node_id].op.is_measurement())
+        .collect();
+    let sigma = std::f64::consts::SQRT_2 / model.t2_time_ns;
+    let index_of = |q: LogicalQubitId| qubits.binary_search(&q).ok();
+
+    let mut rng = SplitMix64::new(seed);
+    let mut total = 0.0;
+    for _ in 0..samples.max(1) {
+        let detuning: Vec<f64> = qubits.iter().map(|_| sigma * rng.next_gaussian()).collect();
+        let mut last_end: HashMap<LogicalQubitId, f64> = HashMap::new();
+        let mut state = StateVector::new(qubits.len());
+        for scheduled in &timeline {
+            let op = &circuit.nodes()[scheduled.node_id].op;
+            for &qubit in &scheduled.qubits {
+                let idle = last_end.get(&qubit).map_or(0.0, |&end| scheduled.start_ns - end)
+                    + if scheduled.is_delay { scheduled.duration_ns } else { 0.0 };
+                let index = index_of(qubit).expect("scheduled qubit is simulated");
+                if idle > 0.0 {
+                    state.apply_operation(&rz(qubit, detuning[index] * idle), index_of)?;
+                }
+                last_end.insert(qubit, scheduled.end_ns());
+            }
+            state.apply_operation(op, index_of)?;
+        }
+        total += state.fidelity(&ideal);
+    }
+    Ok(total / samples.max(1) as f64)
+}
+
+// ============================================================================
+// DD Pass
+// ============================================================================
+
+/// 动态解耦插入通道
+///
+/// 空闲窗口按 ASAP 调度计算；输出电路用显式 `Delay` 固定脉冲位置，
+/// 重新按 ASAP 调度时总时长保持不变。
+#[derive(Debug, Clone)]
+pub struct DynamicalDecoupling {
+    sequence: DdSequence,
+    durations: GateDurations,
+    /// 脉冲之间的最小间隔（纳秒）
+    min_spacing_ns: f64,
+    /// 测量之后的窗口不做解耦（qubit 已处于经典态）
+    skip_after_measurement: bool,
+    /// 只对这些 qubit 做解耦（None 表示全部）
+    qubits: Option<HashSet<LogicalQubitId>>,
+}
+
+impl DynamicalDecoupling {
+    pub fn new(sequence: DdSequence, durations: GateDurations) -> Self {
+        Self {
+            sequence,
+            durations,
+            min_spacing_ns: 0.0,
+            skip_after_measurement: true,
+            qubits: None,
+        }
+    }
+
+    /// 使用后端门时长
+    pub fn for_backend(sequence: DdSequence, capabilities: &BackendCapabilities) -> Self {
+        Self::new(sequence, capabilities.durations())
+    }
+
+    pub fn with_min_spacing(mut self, min_spacing_ns: f64) -> Self {
+        self.min_spacing_ns = min_spacing_ns;
+        self
+    }
+
+    pub fn with_skip_after_measurement(mut self, skip: bool) -> Self {
+        self.skip_after_measurement = skip;
+        self
+    }
+
+    pub fn with_qubits(mut self, qubits: Vec<LogicalQubitId>) -> Self {
+        self.qubits = Some(qubits.into_iter().collect());
+        self
+    }
+
+    /// 窗口能否容纳序列，能则返回每段空闲时长
+    fn fit(&self, window: &IdleWindow) -> Option<Vec<f64>> {
+        let pulse_total: f64 = self
+            .sequence
+            .pulses()
+            .into_iter()
+            .map(|g| self.durations.duration(&Operation::gate1(g, window.qubit)))
+            .sum();
+        let free = window.duration_ns() - pulse_total;
+        if free < 0.0 {
+            return None;
+        }
+        let gaps: Vec<f64> = self.sequence.spacing().into_iter().map(|f| free * f).collect();
+        if gaps.iter().any(|&gap| gap < self.min_spacing_ns) {
+            return None;
+        }
+        Some(gaps)
+    }
+
+    /// 在空闲窗口中插入解耦序列
+    pub fn run(&self, circuit: &CircuitDag) -> (CircuitDag, DecouplingReport) {
+        let schedule = TimingSchedule::compute(circuit, &self.durations, ScheduleMode::Asap);
+        let nodes = circuit.nodes();
+        let mut report = DecouplingReport::default();
+
+        let mut insertions: HashMap<usize, Vec<Operation>> = HashMap::new();
+        let mut replaced_delays: HashSet<usize> = HashSet::new();
+
+        for window in schedule.idle_windows() {
+            report.windows_considered += 1;
+
+            let selected = self.qubits.as_ref().is_none_or(|qs| qs.contains(&window.qubit));
+            let after_measurement = nodes[window.after_node].op.is_measurement();
+            let gaps = if selected && !(after_measurement && self.skip_after_measurement) {
+                self.fit(&window)
+            } else {
+                None
+            };
+
+            let Some(gaps) = gaps else {
+                report.unprotected_idle_ns += window.duration_ns();
+                continue;
+            };
+
+            // 窗口内原有的显式 Delay 被解耦序列取代
+            for op in schedule.qubit_timeline(window.qubit) {
+                if op.is_delay
+                    && op.start_ns >= window.start_ns - 1e-9
+                    && op.end_ns() <= window.end_ns + 1e-9
+                {
+                    replaced_delays.insert(op.node_id);
+                }
+            }
+
+            let ops = insertions.entry(window.after_node).or_default();
+            let pulses = self.sequence.pulses();
+            for (i, gap) in gaps.iter().enumerate() {
+                if *gap > 0.0 {
+                    ops.push(Operation::delay(window.qubit, *gap));
+                }
+                if let Some(&pulse) = pulses.get(i) {
+                    ops.push(Operation::gate1(pulse, window.qubit));
+                }
+            }
+
+            report.windows_filled += 1;
+            report.pulses_inserted += pulses.len();
+            report.protected_idle_ns += window.duration_ns();
+        }
+
+        let mut ops = Vec::with_capacity(circuit.num_nodes() + report.pulses_inserted * 2);
+        for id in circuit.topological_sort() {
+            if replaced_delays.contains(&id) {
+                continue;
+            }
+            ops.push(nodes[id].op.clone());
+            if let Some(extra) = insertions.remove(&id) {
+                ops.extend(extra);
+            }
+        }
+
+        (circuit.rebuild_with(ops), report)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::CircuitDagBuilder;
+    use crate::operation::{cnot, delay, h, measure, x};
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    fn durations() -> GateDurations {
+        GateDurations::new(10.0, 100.0, 300.0, 500.0, 500.0)
+    }
+
+    /// q1 在 H 之后空闲 1000ns，等待 q0 上的延时结束
+    fn idle_heavy_circuit() -> CircuitDag {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(0)));
+        builder.add_op(h(q(1)));
+        builder.add_op(delay(q(0), 1_000.0));
+        builder.add_op(cnot(q(0), q(1)));
+        builder.add_op(measure(q(0)));
+        builder.add_op(measure(q(1)));
+        builder.build()
+    }
+
+    fn pulses_on(circuit: &CircuitDag, qubit: LogicalQubitId) -> Vec<SingleQubitGate> {
+        circuit
+            .operations_in_order()
+            .into_iter()
+            .filter_map(|op| match op {
+                Operation::Gate1 { gate, target } if target == qubit => Some(gate),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_xy4_fills_idle_window() {
+        let circuit = idle_heavy_circuit();
+        let (out, report) = DynamicalDecoupling::new(DdSequence::XY4, durations()).run(&circuit);
+
+        assert_eq!(report.windows_filled, 2);
+        assert_eq!(report.pulses_inserted, 8);
+        use SingleQubitGate::{H, X, Y};
+        assert_eq!(pulses_on(&out, q(1)), vec![H, X, Y, X, Y]);
+
+        // 时长保持不变，且 q1 的空闲只剩脉冲间隔
+        let before = circuit.schedule_timing(&durations(), ScheduleMode::Asap);
+        let after = out.schedule_timing(&durations(), ScheduleMode::Asap);
+        assert_eq!(before.total_duration_ns, after.total_duration_ns);
+        assert!((report.protected_idle_ns - 2_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_window_too_short() {
+        let circuit = idle_heavy_circuit();
+        let slow = GateDurations::new(10.0, 100.0, 300.0, 500.0, 500.0).with_override("X", 600.0);
+        let (_, report) = DynamicalDecoupling::new(DdSequence::XX, slow).run(&circuit);
+        assert_eq!(report.windows_filled, 0);
+        assert_eq!(report.unprotected_idle_ns, 2_000.0);
+    }
+
+    #[test]
+    fn test_min_spacing() {
+        let circuit = idle_heavy_circuit();
+        let pass = DynamicalDecoupling::new(DdSequence::CPMG, durations()).with_min_spacing(300.0);
+        // CPMG 最短间隔为 (1000 - 20) / 4 = 245ns < 300ns
+        assert_eq!(pass.run(&circuit).1.windows_filled, 0);
+    }
+
+    #[test]
+    fn test_respects_barrier_and_measurement() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(0)));
+        builder.add_op(measure(q(1)));
+        builder.add_op(delay(q(0), 1_000.0));
+        builder.add_op(Operation::barrier(vec![q(0), q(1)]));
+        builder.add_op(x(q(1)));
+        let circuit = builder.build();
+
+        // q1 的窗口在测量之后，不做解耦
+        let (_, report) = DynamicalDecoupling::new(DdSequence::XX, durations()).run(&circuit);
+        assert_eq!(report.windows_considered, 2);
+        assert_eq!(report.windows_filled, 1);
+
+        let pass = DynamicalDecoupling::new(DdSequence::XX, durations())
+            .with_skip_after_measurement(false);
+        let (out, report) = pass.run(&circuit);
+        assert_eq!(report.windows_filled, 2);
+
+        // 所有解耦脉冲都位于 barrier 之前
+        let ops = out.operations_in_order();
+        let barrier_pos = ops.iter().position(|op| op.is_barrier()).unwrap();
+        let pulses_before = ops[..barrier_pos]
+            .iter()
+            .filter(|op| matches!(op, Operation::Gate1 { gate: SingleQubitGate::X, .. }))
+            .count();
+        assert_eq!(pulses_before, 4);
+        assert_eq!(pulses_on(&out, q(1)).last(), Some(&SingleQubitGate::X));
+        assert_eq!(pulses_on(&out, q(1)).len(), 3);
+    }
+
+    #[test]
+    fn test_fidelity_improves() {
+        let circuit = idle_heavy_circuit();
+        // T2 与空闲窗口同量级，未保护时保真度明显下降
+        let model = ErrorModel::new(0.001, 0.01, 0.02, 100_000.0, 1_000.0);
+
+        let baseline = simulate_idle_fidelity(&circuit, &durations(), &model, 400, 7).unwrap();
+        assert!(baseline < 0.6, "baseline {}", baseline);
+
+        for sequence in [DdSequence::XX, DdSequence::XY4, DdSequence::CPMG] {
+            let (padded, _) = DynamicalDecoupling::new(sequence, durations()).run(&circuit);
+            let fidelity = simulate_idle_fidelity(&padded, &durations(), &model, 400, 7).unwrap();
+            assert!(fidelity > baseline + 0.3, "{}: {} vs baseline {}", sequence.name(), fidelity, baseline);
+            // 对称间距的序列完全抵消准静态失谐；XX 等间距只抵消三分之二
+            if sequence != DdSequence::XX {
+                assert!(fidelity > 0.999, "{}: {}", sequence.name(), fidelity);
+            }
+        }
+
+        // 一阶估算与模拟的改进方向一致
+        let (_, report) = DynamicalDecoupling::new(DdSequence::XY4, durations()).run(&circuit);
+        let estimate = idle_fidelity(&circuit.schedule_timing(&durations(), ScheduleMode::Asap), &model);
+        assert!(report.idle_fidelity(&model) > estimate);
+    }
+}
@@ -0,0 +1,594 @@
+//! 电路等价性检查模块
+//!
+//! 判断两个电路在忽略全局相位时是否实现相同的变换：
+//! - 小电路：比较完整酉矩阵
+//! - Clifford 电路：比较稳定子 tableau
+//! - 中等电路：随机输入态上的态矢量比较
+//!
+//! 末尾测量会被剥离并单独比较（qubit 与经典寄存器必须一致），barrier 与延时被忽略；
+//! 电路中途的测量和重置不受支持。
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::circuit::CircuitDag;
+use crate::clifford::{is_clifford, CliffordTableau};
+use crate::linalg::{Complex, Matrix};
+use crate::operation::{swap, Operation};
+use crate::qubit::{LogicalQubitId, QubitMapping};
+use crate::statevector::StateVector;
+use crate::{Result, IrError};
+
+// ============================================================================
+// Verdict
+// ============================================================================
+
+/// 使用的检查方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquivalenceMethod {
+    /// 完整酉矩阵比较
+    Unitary,
+    /// 随机输入态比较
+    RandomStimulus,
+    /// Clifford tableau 比较
+    CliffordTableau,
+}
+
+impl EquivalenceMethod {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EquivalenceMethod::Unitary => "unitary",
+            EquivalenceMethod::RandomStimulus => "random-stimulus",
+            EquivalenceMethod::CliffordTableau => "clifford-tableau",
+        }
+    }
+}
+
+/// 不等价的反例
+#[derive(Debug, Clone, PartialEq)]
+pub enum Counterexample {
+    /// 输入态（qubits[0] 为最低位）及两电路输出的保真度
+    State {
+        qubits: Vec<LogicalQubitId>,
+        input: Vec<Complex>,
+        fidelity: f64,
+    },
+    /// 生成元（如 "Z1"）在两电路下的共轭像
+    Pauli {
+        generator: String,
+        left: String,
+        right: String,
+    },
+    /// 末尾测量不一致
+    Measurements {
+        left: Vec<(LogicalQubitId, Option<usize>)>,
+        right: Vec<(LogicalQubitId, Option<usize>)>,
+    },
+}
+
+/// 等价性检查结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum EquivalenceVerdict {
+    /// 等价；global_phase 为 φ 使 right ≈ e^{iφ}·left（tableau 方法无法给出相位）
+    Equivalent {
+        method: EquivalenceMethod,
+        global_phase: Option<f64>,
+    },
+    /// 不等价
+    NotEquivalent {
+        method: EquivalenceMethod,
+        counterexample: Counterexample,
+    },
+}
+
+impl EquivalenceVerdict {
+    pub fn is_equivalent(&self) -> bool {
+        matches!(self, EquivalenceVerdict::Equivalent { .. })
+    }
+
+    pub fn method(&self) -> EquivalenceMethod {
+        match self {
+            EquivalenceVerdict::Equivalent { method, .. } => *method,
+            EquivalenceVerdict::NotEquivalent { method, .. } => *method,
+        }
+    }
+
+    pub fn counterexample(&self) -> Option<&Counterexample> {
+        match self {
+            EquivalenceVerdict::NotEquivalent { counterexample, .. } => Some(counterexample),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Prepared Circuit
+// ============================================================================
+
+/// 剥离末尾测量后的酉部分
+struct Prepared {
+    ops: Vec<Operation>,
+    measured: Vec<(LogicalQubitId, Option<usize>)>,
+}
+
+impl Prepared {
+    fn from_circuit(circuit: &CircuitDag) -> Result<Self> {
+        let mut touched = BTreeSet::new();
+        let mut ops = Vec::new();
+        let mut measured = Vec::new();
+
+        for op in circuit.operations_in_order().into_iter().rev() {
+            match op {
+                Operation::Measure { qubit, classical_reg } => {
+                    if !touched.insert(qubit) {
+                        return Err(IrError::UnsupportedOperation(format!(
+                            "Mid-circuit measurement on {} is not supported", qubit
+                        )));
+                    }
+                    measured.push((qubit, classical_reg));
+                }
+                Operation::Reset { qubit } => {
+                    return Err(IrError::UnsupportedOperation(format!(
+                        "Reset on {} is not supported", qubit
+                    )));
+                }
+                Operation::Barrier { .. } | Operation::Delay { .. } => {}
+                op => {
+                    touched.extend(op.qubits());
+                    ops.push(op);
+                }
+            }
+        }
+        ops.reverse();
+        measured.sort();
+        Ok(Self { ops, measured })
+    }
+
+    fn qubits(&self) -> impl Iterator<Item = LogicalQubitId> + '_ {
+        self.ops
+            .iter()
+            .flat_map(|op| op.qubits())
+            .chain(self.measured.iter().map(|&(q, _)| q))
+    }
+
+    fn simulate(&self, qubits: &[LogicalQubitId], mut state: StateVector) -> Result<StateVector> {
+        for op in &self.ops {
+            state.apply_operation(op, |q| qubits.iter().position(|&x| x == q))?;
+        }
+        Ok(state)
+    }
+
+    fn is_clifford(&self) -> bool {
+        self.ops.iter().all(is_clifford)
+    }
+}
+
+/// 物理 qubit 在电路中以同值的 LogicalQubitId 表示
+fn physical_wire(mapping: &QubitMapping, q: LogicalQubitId, which: &str) -> Result<LogicalQubitId> {
+    mapping
+        .get_physical(q)
+        .map(|p| LogicalQubitId::new(p.value()))
+        .ok_or_else(|| IrError::QubitNotFound(format!("{} is not in the {} mapping", q, which)))
+}
+
+// ============================================================================
+// Equivalence Checker
+// ============================================================================
+
+/// 电路等价性检查器
+#[derive(Debug, Clone)]
+pub struct EquivalenceChecker {
+    /// 不超过该 qubit 数时比较完整酉矩阵
+    pub unitary_max_qubits: usize,
+    /// 随机激励方法允许的最大 qubit 数
+    pub statevector_max_qubits: usize,
+    /// 随机输入态数量
+    pub trials: usize,
+    /// 数值容差
+    pub tolerance: f64,
+    /// 随机种子
+    pub seed: u64,
+    /// 强制使用的方法（None 时自动选择）
+    pub method: Option<EquivalenceMethod>,
+}
+
+impl Default for EquivalenceChecker {
+    fn default() -> Self {
+        Self {
+            unitary_max_qubits: 6,
+            statevector_max_qubits: 20,
+            trials: 4,
+            tolerance: 1e-9,
+            seed: 0x5EED,
+            method: None,
+        }
+    }
+}
+
+impl EquivalenceChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_unitary_max_qubits(mut self, n: usize) -> Self {
+        self.unitary_max_qubits = n;
+        self
+    }
+
+    pub fn with_statevector_max_qubits(mut self, n: usize) -> Self {
+        self.statevector_max_qubits = n;
+        self
+    }
+
+    pub fn with_trials(mut self, trials: usize) -> Self {
+        self.trials = trials.max(1);
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_method(mut self, method: EquivalenceMethod) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// 检查两个电路是否等价
+    pub fn check(&self, left: &CircuitDag, right: &CircuitDag) -> Result<EquivalenceVerdict> {
+        self.compare(&Prepared::from_circuit(left)?, &Prepared::from_circuit(right)?)
+    }
+
+    /// 检查路由前后的电路是否等价
+    ///
+    /// routed 中的 qubit 为物理 qubit（以同值 LogicalQubitId 表示）。original 先按
+    /// initial 映射重标记，再追加 SWAP 把每个逻辑 qubit 从 initial 位置移到 final 位置。
+    /// 映射中未出现的物理 qubit 视为空闲线路。
+    pub fn check_mapped(
+        &self,
+        original: &CircuitDag,
+        routed: &CircuitDag,
+        initial: &QubitMapping,
+        final_mapping: &QubitMapping,
+    ) -> Result<EquivalenceVerdict> {
+        let logical = Prepared::from_circuit(original)?;
+
+        let mut ops = Vec::with_capacity(logical.ops.len());
+        for op in &logical.ops {
+            for q in op.qubits() {
+                physical_wire(initial, q, "initial")?;
+            }
+            ops.push(op.map_qubits(|q| physical_wire(initial, q, "initial").expect("checked above")));
+        }
+
+        // 把 initial 布局置换成 final 布局
+        let mut logicals: Vec<LogicalQubitId> = initial.all_mappings().into_iter().map(|(l, _)| l).collect();
+        logicals.sort();
+        let mut location = HashMap::new();
+        let mut occupant = HashMap::new();
+        for &q in &logicals {
+            let wire = physical_wire(initial, q, "initial")?;
+            location.insert(q, wire);
+            occupant.insert(wire, q);
+        }
+        for &q in &logicals {
+            let target = physical_wire(final_mapping, q, "final")?;
+            let current = location[&q];
+            if current == target {
+                continue;
+            }
+            ops.push(swap(current, target));
+            let displaced = occupant.insert(target, q);
+            location.insert(q, target);
+            match displaced {
+                Some(other) => {
+                    location.insert(other, current);
+                    occupant.insert(current, other);
+                }
+                None => {
+                    occupant.remove(&current);
+                }
+            }
+        }
+
+        let mut measured = logical
+            .measured
+            .iter()
+            .map(|&(q, reg)| physical_wire(final_mapping, q, "final").map(|w| (w, reg)))
+            .collect::<Result<Vec<_>>>()?;
+        measured.sort();
+
+        let relabeled = Prepared { ops, measured };
+        self.compare(&relabeled, &Prepared::from_circuit(routed)?)
+    }
+
+    fn choose_method(&self, n: usize, left: &Prepared, right: &Prepared) -> Result<EquivalenceMethod> {
+        if let Some(method) = self.method {
+            return Ok(method);
+        }
+        if n <= self.unitary_max_qubits {
+            Ok(EquivalenceMethod::Unitary)
+        } else if left.is_clifford() && right.is_clifford() {
+            Ok(EquivalenceMethod::CliffordTableau)
+        } else if n <= self.statevector_max_qubits {
+            Ok(EquivalenceMethod::RandomStimulus)
+        } else {
+            Err(IrError::UnsupportedOperation(format!(
+                "Equivalence check of {} non-Clifford qubits exceeds the state vector limit of {}",
+                n, self.statevector_max_qubits
+            )))
+        }
+    }
+
+    fn compare(&self, left: &Prepared, right: &Prepared) -> Result<EquivalenceVerdict> {
+        let qubits: Vec<LogicalQubitId> = left
+            .qubits()
+            .chain(right.qubits())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let method = self.choose_method(qubits.len(), left, right)?;
+
+        if left.measured != right.measured {
+            return Ok(EquivalenceVerdict::NotEquivalent {
+                method,
+                counterexample: Counterexample::Measurements {
+                    left: left.measured.clone(),
+                    right: right.measured.clone(),
+                },
+            });
+        }
+
+        match method {
+            EquivalenceMethod::Unitary => self.compare_unitary(&qubits, left, right),
+            EquivalenceMethod::RandomStimulus => self.compare_random(&qubits, left, right),
+            EquivalenceMethod::CliffordTableau => self.compare_tableau(&qubits, left, right),
+        }
+    }
+
+    fn state_counterexample(
+        &self,
+        qubits: &[LogicalQubitId],
+        left: &Prepared,
+        right: &Prepared,
+        input: StateVector,
+    ) -> Result<EquivalenceVerdict> {
+        let fidelity = left
+            .simulate(qubits, input.clone())?
+            .fidelity(&right.simulate(qubits, input.clone())?);
+        Ok(EquivalenceVerdict::NotEquivalent {
+            method: EquivalenceMethod::Unitary,
+            counterexample: Counterexample::State {
+                qubits: qubits.to_vec(),
+                input: input.into_amplitudes(),
+                fidelity,
+            },
+        })
+    }
+
+    fn compare_unitary(&self, qubits: &[LogicalQubitId], left: &Prepared, right: &Prepared) -> Result<EquivalenceVerdict> {
+        let n = qubits.len();
+        let dim = 1usize << n;
+        let mut u_left = Matrix::zeros(dim);
+        let mut u_right = Matrix::zeros(dim);
+        let mut column_overlaps = Vec::with_capacity(dim);
+        for col in 0..dim {
+            let a = left.simulate(qubits, StateVector::basis(n, col))?;
+            let b = right.simulate(qubits, StateVector::basis(n, col))?;
+            column_overlaps.push(a.inner(&b));
+            for row in 0..dim {
+                u_left.set(row, col, a.amplitudes()[row]);
+                u_right.set(row, col, b.amplitudes()[row]);
+            }
+        }
+
+        if let Some(phase) = u_right.global_phase_to(&u_left, self.tolerance) {
+            return Ok(EquivalenceVerdict::Equivalent {
+                method: EquivalenceMethod::Unitary,
+                global_phase: Some(phase),
+            });
+        }
+
+        // 优先找能直接区分的计算基态
+        if let Some(col) = column_overlaps.iter().position(|o| o.norm_sqr() < 1.0 - self.tolerance) {
+            return self.state_counterexample(qubits, left, right, StateVector::basis(n, col));
+        }
+
+        // 各列仅差相对相位：叠加态 (|0⟩ + |k⟩)/√2 可区分
+        let reference = column_overlaps[0];
+        let col = (1..dim)
+            .find(|&k| !column_overlaps[k].approx_eq(reference, self.tolerance.sqrt()))
+            .unwrap_or(dim - 1);
+        let mut amplitudes = vec![Complex::ZERO; dim];
+        amplitudes[0] = Complex::ONE;
+        amplitudes[col] = Complex::ONE;
+        self.state_counterexample(qubits, left, right, StateVector::from_amplitudes(amplitudes)?)
+    }
+
+    fn compare_random(&self, qubits: &[LogicalQubitId], left: &Prepared, right: &Prepared) -> Result<EquivalenceVerdict> {
+        let n = qubits.len();
+        let mut global_phase = None;
+        for trial in 0..self.trials.max(1) {
+            let input = StateVector::random(n, self.seed.wrapping_add(trial as u64));
+            let overlap = left
+                .simulate(qubits, input.clone())?
+                .inner(&right.simulate(qubits, input.clone())?);
+            let phase_mismatch = global_phase
+                .is_some_and(|phase: f64| !overlap.approx_eq(Complex::cis(phase), self.tolerance.sqrt()));
+            if overlap.norm_sqr() < 1.0 - self.tolerance || phase_mismatch {
+                return Ok(EquivalenceVerdict::NotEquivalent {
+                    method: EquivalenceMethod::RandomStimulus,
+                    counterexample: Counterexample::State {
+                        qubits: qubits.to_vec(),
+                        input: input.into_amplitudes(),
+                        fidelity: overlap.norm_sqr(),
+                    },
+                });
+            }
+            global_phase.get_or_insert(overlap.arg());
+        }
+        Ok(EquivalenceVerdict::Equivalent {
+            method: EquivalenceMethod::RandomStimulus,
+            global_phase,
+        })
+    }
+
+    fn compare_tableau(&self, qubits: &[LogicalQubitId], left: &Prepared, right: &Prepared) -> Result<EquivalenceVerdict> {
+        let a = CliffordTableau::from_operations(&left.ops, qubits)?;
+        let b = CliffordTableau::from_operations(&right.ops, qubits)?;
+        Ok(match a.first_difference(&b) {
+            None => EquivalenceVerdict::Equivalent {
+                method: EquivalenceMethod::CliffordTableau,
+                global_phase: None,
+            },
+            Some(row) => EquivalenceVerdict::NotEquivalent {
+                method: EquivalenceMethod::CliffordTableau,
+                counterexample: Counterexample::Pauli {
+                    generator: a.generator_name(row),
+                    left: a.row_string(row),
+                    right: b.row_string(row),
+                },
+            },
+        })
+    }
+}
+
+impl CircuitDag {
+    /// 使用默认设置检查与另一个电路是否等价
+    pub fn equivalent_to(&self, other: &CircuitDag) -> Result<EquivalenceVerdict> {
+        EquivalenceChecker::new().check(self, other)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation::{cnot, cz, h, measure, rz, s, t, x, SingleQubitGate};
+    use crate::qubit::PhysicalQubitId;
+    use std::f64::consts::PI;
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    fn circuit(ops: Vec<Operation>) -> CircuitDag {
+        let mut dag = CircuitDag::new();
+        for op in ops {
+            dag.add_node(op);
+        }
+        dag
+    }
+
+    #[test]
+    fn test_unitary_equivalent_up_to_phase() {
+        // Rz(π) = -i·Z
+        let a = circuit(vec![rz(q(0), PI)]);
+        let b = circuit(vec![Operation::gate1(SingleQubitGate::Z, q(0))]);
+        match a.equivalent_to(&b).unwrap() {
+            EquivalenceVerdict::Equivalent { method, global_phase } => {
+                assert_eq!(method, EquivalenceMethod::Unitary);
+                assert!((global_phase.unwrap() - PI / 2.0).abs() < 1e-9);
+            }
+            other => panic!("expected equivalence, got {:?}", other),
+        }
+
+        // CZ = H·CNOT·H
+        let a = circuit(vec![cz(q(0), q(1))]);
+        let b = circuit(vec![h(q(1)), cnot(q(0), q(1)), h(q(1))]);
+        assert!(a.equivalent_to(&b).unwrap().is_equivalent());
+    }
+
+    #[test]
+    fn test_relative_phase_counterexample() {
+        // S 与 I 在每个计算基态上都只差相位，反例必须是叠加态
+        let a = circuit(vec![s(q(0))]);
+        let b = circuit(vec![x(q(0)), x(q(0))]);
+        let verdict = a.equivalent_to(&b).unwrap();
+        match verdict.counterexample() {
+            Some(Counterexample::State { input, fidelity, .. }) => {
+                assert!(input[0].abs() > 0.5 && input[1].abs() > 0.5);
+                assert!(*fidelity < 0.99);
+            }
+            other => panic!("unexpected counterexample {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_clifford_tableau_method() {
+        let a = circuit(vec![h(q(0)), s(q(0)), s(q(0)), h(q(0))]);
+        let b = circuit(vec![x(q(0))]);
+        let checker = EquivalenceChecker::new().with_unitary_max_qubits(0);
+        let verdict = checker.check(&a, &b).unwrap();
+        assert_eq!(verdict.method(), EquivalenceMethod::CliffordTableau);
+        assert!(verdict.is_equivalent());
+
+        let c = circuit(vec![h(q(0))]);
+        match checker.check(&a, &c).unwrap().counterexample() {
+            Some(Counterexample::Pauli { generator, left, right }) => {
+                assert_eq!(generator, "X0");
+                assert_eq!(left, "+X");
+                assert_eq!(right, "+Z");
+            }
+            other => panic!("unexpected counterexample {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_random_stimulus_method() {
+        let checker = EquivalenceChecker::new().with_unitary_max_qubits(0);
+        let a = circuit(vec![t(q(0)), t(q(0)), cnot(q(0), q(1))]);
+        let b = circuit(vec![s(q(0)), cnot(q(0), q(1))]);
+        let verdict = checker.check(&a, &b).unwrap();
+        assert_eq!(verdict.method(), EquivalenceMethod::RandomStimulus);
+        assert!(verdict.is_equivalent());
+
+        let c = circuit(vec![t(q(0)), cnot(q(0), q(1))]);
+        assert!(!checker.check(&a, &c).unwrap().is_equivalent());
+    }
+
+    #[test]
+    fn test_terminal_measurements() {
+        let a = circuit(vec![h(q(0)), measure(q(0))]);
+        let b = circuit(vec![h(q(0)), Operation::barrier(vec![q(0)]), measure(q(0))]);
+        assert!(a.equivalent_to(&b).unwrap().is_equivalent());
+
+        let c = circuit(vec![h(q(0))]);
+        assert!(matches!(
+            a.equivalent_to(&c).unwrap().counterexample(),
+            Some(Counterexample::Measurements { .. })
+        ));
+
+        let mid = circuit(vec![measure(q(0)), h(q(0))]);
+        assert!(mid.equivalent_to(&a).is_err());
+    }
+
+    #[test]
+    fn test_check_mapped_with_routing_swap() {
+        // 逻辑 CNOT(0, 2)，布局 0→P0, 1→P1, 2→P2，线性耦合需要一次 SWAP
+        let original = circuit(vec![h(q(0)), cnot(q(0), q(2))]);
+        let routed = circuit(vec![h(q(0)), swap(q(0), q(1)), cnot(q(1), q(2))]);
+
+        let mut initial = QubitMapping::new();
+        let mut final_mapping = QubitMapping::new();
+        for i in 0..3 {
+            initial.map(q(i), PhysicalQubitId::new(i));
+        }
+        final_mapping.map(q(0), PhysicalQubitId::new(1));
+        final_mapping.map(q(1), PhysicalQubitId::new(0));
+        final_mapping.map(q(2), PhysicalQubitId::new(2));
+
+        let checker = EquivalenceChecker::new();
+        assert!(checker.check_mapped(&original, &routed, &initial, &final_mapping).unwrap().is_equivalent());
+        // 错误的 final 映射应被识别
+        assert!(!checker.check_mapped(&original, &routed, &initial, &initial).unwrap().is_equivalent());
+    }
+}
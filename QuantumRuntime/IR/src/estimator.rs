@@ -0,0 +1,634 @@
+//! 期望值估计模块
+//!
+//! 把 `PauliSum` 的各项按对易关系分组，每组追加一段 Clifford 基变换使组内各项同时对角化，
+//! 测量后由逐次结果的奇偶性恢复各项取值。所有测量电路作为一个批量作业经运行时执行。
+//!
+//! 分组方式：
+//! - 逐比特对易：每个 qubit 上单独旋转（X→H，Y→S†·H），电路最浅；
+//! - 一般对易：用 H/S/CNOT/CZ 构造组内公共对角化电路，组数更少。
+
+use crate::backend::BackendAdapter;
+use crate::circuit::CircuitDag;
+use crate::job::{Job, JobResult, JobStatus};
+use crate::observable::{append_measurements, shot_parities, Observable};
+use crate::operation::{cnot, cz, h, s, Operation, SingleQubitGate};
+use crate::pauli::{Pauli, PauliString, PauliSum};
+use crate::qubit::LogicalQubitId;
+use crate::runtime::QuantumRuntime;
+use crate::statevector::StateVector;
+use crate::{Result, IrError};
+
+// ============================================================================
+// Grouping
+// ============================================================================
+
+/// 对易分组方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Grouping {
+    /// 每项单独测量
+    None,
+    /// 逐比特对易
+    #[default]
+    QubitWise,
+    /// 一般对易
+    Commuting,
+}
+
+impl Grouping {
+    fn compatible(self, a: &PauliString, b: &PauliString) -> bool {
+        match self {
+            Grouping::None => false,
+            Grouping::QubitWise => a.qubitwise_commutes_with(b),
+            Grouping::Commuting => a.commutes_with(b),
+        }
+    }
+}
+
+/// 一组同时测量的 Pauli 项
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeasurementGroup {
+    /// 项在 `PauliSum::terms` 中的下标
+    pub terms: Vec<usize>,
+    /// 测量前追加的基变换
+    pub rotations: Vec<Operation>,
+    /// 需要测量的 qubit
+    pub qubits: Vec<LogicalQubitId>,
+    /// 基变换后每项化为 ±Z 串：(符号, 支撑)
+    pub diagonal: Vec<(f64, Vec<LogicalQubitId>)>,
+}
+
+// ============================================================================
+// Symplectic Conjugation
+// ============================================================================
+
+/// 带符号的辛 Pauli 表示，下标对应组内 qubit 列表
+#[derive(Debug, Clone)]
+struct Tableau {
+    x: Vec<bool>,
+    z: Vec<bool>,
+    negative: bool,
+}
+
+impl Tableau {
+    fn from_string(string: &PauliString, qubits: &[LogicalQubitId]) -> Self {
+        let (x, z) = qubits.iter().map(|&q| string.get(q).bits()).unzip();
+        Self { x, z, negative: false }
+    }
+
+    fn h(&mut self, a: usize) {
+        self.negative ^= self.x[a] && self.z[a];
+        std::mem::swap(&mut self.x[a], &mut self.z[a]);
+    }
+
+    /// S P S†：X→Y，Y→−X
+    fn s(&mut self, a: usize) {
+        self.negative ^= self.x[a] && self.z[a];
+        self.z[a] ^= self.x[a];
+    }
+
+    fn cnot(&mut self, c: usize, t: usize) {
+        self.negative ^= self.x[c] && self.z[t] && (self.x[t] == self.z[c]);
+        self.x[t] ^= self.x[c];
+        self.z[c] ^= self.z[t];
+    }
+
+    fn cz(&mut self, a: usize, b: usize) {
+        self.h(b);
+        self.cnot(a, b);
+        self.h(b);
+    }
+
+    /// 按门列表共轭（U P U†）
+    fn conjugate(&mut self, ops: &[Operation], index: impl Fn(LogicalQubitId) -> usize) {
+        for op in ops {
+            match op {
+                Operation::Gate1 { gate: SingleQubitGate::H, target } => self.h(index(*target)),
+                Operation::Gate1 { gate: SingleQubitGate::S, target } => self.s(index(*target)),
+                Operation::Gate1 { gate: SingleQubitGate::Sdg, target } => {
+                    (0..3).for_each(|_| self.s(index(*target)))
+                }
+                Operation::Gate2 { gate, control, target } => match gate.name() {
+                    "CNOT" => self.cnot(index(*control), index(*target)),
+                    "CZ" => self.cz(index(*control), index(*target)),
+                    _ => unreachable!("basis change only uses CNOT and CZ"),
+                },
+                _ => unreachable!("basis change only uses Clifford gates"),
+            }
+        }
+    }
+}
+
+/// 逐比特对易组：每个 qubit 上单独旋转到 Z 基
+fn qubitwise_rotations(strings: &[&PauliString], qubits: &[LogicalQubitId]) -> Vec<Operation> {
+    let mut ops = Vec::new();
+    for &q in qubits {
+        match strings.iter().map(|s| s.get(q)).find(|&p| p != Pauli::I) {
+            Some(Pauli::X) => ops.push(h(q)),
+            Some(Pauli::Y) => {
+                ops.push(Operation::gate1(SingleQubitGate::Sdg, q));
+                ops.push(h(q));
+            }
+            _ => {}
+        }
+    }
+    ops
+}
+
+/// 对易组的公共对角化 Clifford 电路
+///
+/// 依次处理各项：在尚未占用的 qubit 中选一个带 X 分量的 j，
+/// 用 CNOT 消去其余未占用位的 X 分量，S 消去 j 上的 Z 分量，CZ 消去其他位的 Z 分量，
+/// 此时该项化为 X_j，再作 H 得到 Z_j 并占用 j。其余项与 Z_j 对易，故在 j 上不再有 X 分量。
+fn commuting_rotations(strings: &[&PauliString], qubits: &[LogicalQubitId]) -> Vec<Operation> {
+    let n = qubits.len();
+    let mut rows: Vec<Tableau> = strings.iter().map(|s| Tableau::from_string(s, qubits)).collect();
+    let mut used = vec![false; n];
+    let mut ops = Vec::new();
+    let apply = |op: Operation, rows: &mut [Tableau], ops: &mut Vec<Operation>| {
+        for row in rows.iter_mut() {
+            row.conjugate(std::slice::from_ref(&op), |q| qubits.binary_search(&q).expect("qubit in group"));
+        }
+        ops.push(op);
+    };
+
+    for i in 0..rows.len() {
+        let Some(j) = (0..n).find(|&j| !used[j] && rows[i].x[j]) else {
+            continue;
+        };
+        for k in 0..n {
+            if k != j && !used[k] && rows[i].x[k] {
+                apply(cnot(qubits[j], qubits[k]), &mut rows, &mut ops);
+            }
+        }
+        if rows[i].z[j] {
+            apply(s(qubits[j]), &mut rows, &mut ops);
+        }
+        for k in 0..n {
+            if k != j && rows[i].z[k] {
+                apply(cz(qubits[j], qubits[k]), &mut rows, &mut ops);
+            }
+        }
+        apply(h(qubits[j]), &mut rows, &mut ops);
+        used[j] = true;
+    }
+    ops
+}
+
+// ============================================================================
+// Estimate
+// ============================================================================
+
+/// 期望值估计结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub value: f64,
+    /// 标准误差（精确计算时为 0）
+    pub std_error: f64,
+    /// 执行的测量电路数
+    pub num_circuits: usize,
+    /// 是否由态矢量精确给出
+    pub exact: bool,
+}
+
+// ============================================================================
+// Estimator
+// ============================================================================
+
+/// Pauli 可观测量期望值估计器
+#[derive(Debug, Clone)]
+pub struct Estimator {
+    /// 每条测量电路的 shots
+    pub shots: u32,
+    pub grouping: Grouping,
+    /// 后端支持时使用精确期望
+    pub use_exact: bool,
+}
+
+impl Default for Estimator {
+    fn default() -> Self {
+        Self {
+            shots: 1024,
+            grouping: Grouping::default(),
+            use_exact: true,
+        }
+    }
+}
+
+impl Estimator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_shots(mut self, shots: u32) -> Self {
+        self.shots = shots;
+        self
+    }
+
+    pub fn with_grouping(mut self, grouping: Grouping) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    pub fn with_exact(mut self, use_exact: bool) -> Self {
+        self.use_exact = use_exact;
+        self
+    }
+
+    /// 贪心分组（按权重从大到小放入第一个相容的组），恒等项不参与测量
+    pub fn groups(&self, observable: &PauliSum) -> Vec<MeasurementGroup> {
+        let terms = observable.terms();
+        let mut order: Vec<usize> = (0..terms.len()).filter(|&i| !terms[i].1.is_identity()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(terms[i].1.weight()));
+
+        let mut members: Vec<Vec<usize>> = Vec::new();
+        for i in order {
+            let string = &terms[i].1;
+            match members
+                .iter_mut()
+                .find(|group| group.iter().all(|&j| self.grouping.compatible(string, &terms[j].1)))
+            {
+                Some(group) => group.push(i),
+                None => members.push(vec![i]),
+            }
+        }
+
+        members
+            .into_iter()
+            .map(|mut indices| {
+                indices.sort();
+                let strings: Vec<&PauliString> = indices.iter().map(|&i| &terms[i].1).collect();
+                let mut qubits: Vec<LogicalQubitId> = strings.iter().flat_map(|s| s.qubits()).collect();
+                qubits.sort();
+                qubits.dedup();
+
+                let rotations = match self.grouping {
+                    Grouping::Commuting => commuting_rotations(&strings, &qubits),
+                    _ => qubitwise_rotations(&strings, &qubits),
+                };
+                let index = |q| qubits.binary_search(&q).expect("qubit in group");
+                let diagonal = strings
+                    .iter()
+                    .map(|string| {
+                        let mut t = Tableau::from_string(string, &qubits);
+                        t.conjugate(&rotations, index);
+                        debug_assert!(t.x.iter().all(|&x| !x), "basis change must diagonalize the group");
+                        let support = qubits.iter().zip(&t.z).filter(|(_, &z)| z).map(|(&q, _)| q).collect();
+                        (if t.negative { -1.0 } else { 1.0 }, support)
+                    })
+                    .collect();
+                MeasurementGroup { terms: indices, rotations, qubits, diagonal }
+            })
+            .collect()
+    }
+
+    /// 各组的测量电路（顺序同 `groups`）
+    pub fn measurement_circuits(&self, circuit: &CircuitDag, groups: &[MeasurementGroup]) -> Result<Vec<CircuitDag>> {
+        groups
+            .iter()
+            .map(|g| append_measurements(circuit, g.rotations.clone(), &g.qubits))
+            .collect()
+    }
+
+    /// 由各组测量结果计算期望值与标准误差
+    ///
+    /// 同组各项来自同一批 shots，按逐次的组内加权和计算样本方差；组间独立，方差相加。
+    pub fn estimate_from(
+        &self,
+        observable: &PauliSum,
+        groups: &[MeasurementGroup],
+        results: &[JobResult],
+    ) -> Result<Estimate> {
+        if results.len() != groups.len() {
+            return Err(IrError::InvalidOperation(format!(
+                "Expected {} measurement results, got {}", groups.len(), results.len()
+            )));
+        }
+        let mut value = observable.constant();
+        let mut variance = 0.0;
+        for (group, result) in groups.iter().zip(results) {
+            let mut per_shot: Vec<f64> = Vec::new();
+            for (&term, (sign, support)) in group.terms.iter().zip(&group.diagonal) {
+                let coefficient = observable.terms()[term].0 * sign;
+                let parities = shot_parities(result, support)?;
+                if per_shot.is_empty() {
+                    per_shot = vec![0.0; parities.len()];
+                }
+                if parities.len() != per_shot.len() {
+                    return Err(IrError::InvalidOperation("Counts have different shot numbers".to_string()));
+                }
+                for (acc, odd) in per_shot.iter_mut().zip(parities) {
+                    *acc += if odd { -coefficient } else { coefficient };
+                }
+            }
+            let shots = per_shot.len() as f64;
+            if shots == 0.0 {
+                return Err(IrError::InvalidOperation("No shots to estimate from".to_string()));
+            }
+            let mean = per_shot.iter().sum::<f64>() / shots;
+            value += mean;
+            if shots > 1.0 {
+                let sample_var = per_shot.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (shots - 1.0);
+                variance += sample_var / shots;
+            }
+        }
+        Ok(Estimate {
+            value,
+            std_error: variance.sqrt(),
+            num_circuits: groups.len(),
+            exact: false,
+        })
+    }
+
+    /// 在后端上估计；后端支持且 `use_exact` 时直接给出精确期望
+    pub fn estimate(&self, backend: &dyn BackendAdapter, circuit: &CircuitDag, observable: &PauliSum) -> Result<Estimate> {
+        let mut estimates = self.estimate_with(&[(circuit, observable)], backend, backend.id(), |job| backend.execute(&job))?;
+        Ok(estimates.remove(0))
+    }
+
+    /// 经运行时在指定后端上执行测量电路并估计
+    pub fn run(
+        &self,
+        runtime: &mut QuantumRuntime,
+        backend_id: &str,
+        circuit: &CircuitDag,
+        observable: &PauliSum,
+    ) -> Result<Estimate> {
+        let mut estimates = self.run_many(runtime, backend_id, circuit, std::slice::from_ref(observable))?;
+        Ok(estimates.remove(0))
+    }
+
+    /// 同一电路上的多个可观测量：所有测量电路合为一个批量作业
+    pub fn run_many(
+        &self,
+        runtime: &mut QuantumRuntime,
+        backend_id: &str,
+        circuit: &CircuitDag,
+        observables: &[PauliSum],
+    ) -> Result<Vec<Estimate>> {
+        let items: Vec<_> = observables.iter().map(|observable| (circuit, observable)).collect();
+        self.run_items(runtime, backend_id, &items)
+    }
+
+    /// 同一可观测量在多条电路上的期望值（如参数移位电路），合为一个批量作业
+    pub fn run_circuits(
+        &self,
+        runtime: &mut QuantumRuntime,
+        backend_id: &str,
+        circuits: &[CircuitDag],
+        observable: &PauliSum,
+    ) -> Result<Vec<Estimate>> {
+        let items: Vec<_> = circuits.iter().map(|circuit| (circuit, observable)).collect();
+        self.run_items(runtime, backend_id, &items)
+    }
+
+    fn run_items(
+        &self,
+        runtime: &mut QuantumRuntime,
+        backend_id: &str,
+        items: &[(&CircuitDag, &PauliSum)],
+    ) -> Result<Vec<Estimate>> {
+        let backend = runtime
+            .get_backend(backend_id)
+            .ok_or_else(|| IrError::BackendUnavailable(format!("Backend {} is not registered", backend_id)))?;
+        self.estimate_with(items, backend.as_ref(), backend_id, |job| runtime.run(job))
+    }
+
+    fn estimate_with(
+        &self,
+        items: &[(&CircuitDag, &PauliSum)],
+        backend: &dyn BackendAdapter,
+        backend_id: &str,
+        execute: impl FnOnce(Job) -> Result<JobResult>,
+    ) -> Result<Vec<Estimate>> {
+        let mut estimates: Vec<Option<Estimate>> = vec![None; items.len()];
+        if self.use_exact {
+            for (estimate, (circuit, observable)) in estimates.iter_mut().zip(items) {
+                if let Some(value) = backend.exact_expectation(circuit, *observable) {
+                    *estimate = Some(Estimate { value: value?, std_error: 0.0, num_circuits: 0, exact: true });
+                }
+            }
+        }
+
+        // 需要采样的项：(下标, 分组, 在批量作业中的起始点)
+        let mut pending = Vec::new();
+        let mut circuits = Vec::new();
+        for (i, (circuit, observable)) in items.iter().enumerate().filter(|(i, _)| estimates[*i].is_none()) {
+            let groups = self.groups(observable);
+            let offset = circuits.len();
+            circuits.extend(self.measurement_circuits(circuit, &groups)?);
+            pending.push((i, groups, offset));
+        }
+
+        let mut points = Vec::new();
+        let expected = circuits.len();
+        let mut circuits = circuits.into_iter();
+        if let Some(first) = circuits.next() {
+            let job = Job::new(first, self.shots, backend_id).with_circuits(circuits);
+            let job_id = job.id;
+            let result = execute(job)?;
+            if result.status != JobStatus::Completed {
+                return Err(IrError::JobExecutionFailed(
+                    result.error.unwrap_or_else(|| format!("Estimator job {} did not complete", job_id)),
+                ));
+            }
+            // 只有一条电路时批量作业不含执行点
+            points = if result.points.is_empty() { vec![result] } else { result.points };
+            if points.len() != expected {
+                return Err(IrError::JobExecutionFailed(format!(
+                    "Estimator job {} returned {} results for {} circuits", job_id, points.len(), expected
+                )));
+            }
+        }
+        for (i, groups, offset) in pending {
+            let results = &points[offset..offset + groups.len()];
+            estimates[i] = Some(self.estimate_from(items[i].1, &groups, results)?);
+        }
+        Ok(estimates.into_iter().map(|e| e.expect("every item is estimated")).collect())
+    }
+}
+
+// ============================================================================
+// Observable Implementations
+// ============================================================================
+
+impl Observable for PauliSum {
+    /// 按逐比特对易分组生成测量电路
+    fn measurement_circuits(&self, circuit: &CircuitDag) -> Result<Vec<CircuitDag>> {
+        let estimator = Estimator::new();
+        estimator.measurement_circuits(circuit, &estimator.groups(self))
+    }
+
+    fn expectation_from(&self, results: &[JobResult]) -> Result<f64> {
+        let estimator = Estimator::new();
+        Ok(estimator.estimate_from(self, &estimator.groups(self), results)?.value)
+    }
+
+    fn apply(&self, state: &StateVector, qubits: &[LogicalQubitId]) -> Result<StateVector> {
+        PauliSum::apply(self, state, qubits)
+    }
+}
+
+impl Observable for PauliString {
+    fn measurement_circuits(&self, circuit: &CircuitDag) -> Result<Vec<CircuitDag>> {
+        PauliSum::from(self.clone()).measurement_circuits(circuit)
+    }
+
+    fn expectation_from(&self, results: &[JobResult]) -> Result<f64> {
+        PauliSum::from(self.clone()).expectation_from(results)
+    }
+
+    fn apply(&self, state: &StateVector, qubits: &[LogicalQubitId]) -> Result<StateVector> {
+        PauliString::apply(self, state, qubits)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{IdealSimulatorBackend, MockBackendAdapter};
+    use crate::gradient::exact_expectation;
+    use crate::operation::{rx, ry};
+    use crate::params::ParamBinding;
+    use std::sync::Arc;
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    fn pauli(text: &str) -> PauliString {
+        PauliString::parse(text).unwrap()
+    }
+
+    fn hamiltonian() -> PauliSum {
+        PauliSum::new()
+            .with_term(0.5, pauli("Z0 Z1"))
+            .with_term(-0.3, pauli("X0 X1"))
+            .with_term(0.2, pauli("Y0 Y1"))
+            .with_term(0.7, pauli("Z0"))
+            .with_term(0.1, pauli("X1 Z2"))
+            .with_term(-1.5, PauliString::identity())
+    }
+
+    fn state_circuit() -> CircuitDag {
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(ry(q(0), 0.8));
+        circuit.add_node(cnot(q(0), q(1)));
+        circuit.add_node(rx(q(1), -0.4));
+        circuit.add_node(h(q(2)));
+        circuit.add_node(cz(q(1), q(2)));
+        circuit.add_node(Operation::gate1(SingleQubitGate::S, q(0)));
+        circuit
+    }
+
+    /// 以精确概率代替采样，逐组验证基变换与符号
+    fn exact_group_value(circuit: &CircuitDag, observable: &PauliSum, group: &MeasurementGroup) -> f64 {
+        let mut ops = circuit.operations_in_order();
+        ops.extend(group.rotations.iter().cloned());
+        let rotated = circuit.rebuild_with(ops);
+        group
+            .terms
+            .iter()
+            .zip(&group.diagonal)
+            .map(|(&t, (sign, support))| {
+                let z = PauliString::new(support.iter().map(|&q| (q, Pauli::Z)));
+                observable.terms()[t].0 * sign * exact_expectation(&rotated, &z, &ParamBinding::new()).unwrap()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_grouping_counts() {
+        let h = hamiltonian();
+        let none = Estimator::new().with_grouping(Grouping::None).groups(&h);
+        let qwc = Estimator::new().with_grouping(Grouping::QubitWise).groups(&h);
+        let general = Estimator::new().with_grouping(Grouping::Commuting).groups(&h);
+        assert_eq!(none.len(), 5);
+        assert!(qwc.len() < none.len());
+        assert!(general.len() <= qwc.len());
+        // ZZ、XX、YY 两两对易但不逐比特对易
+        assert!(general.iter().any(|g| g.terms.len() >= 3));
+    }
+
+    #[test]
+    fn test_basis_changes_diagonalize() {
+        let h = hamiltonian();
+        let circuit = state_circuit();
+        let expected = exact_expectation(&circuit, &h, &ParamBinding::new()).unwrap();
+        for grouping in [Grouping::None, Grouping::QubitWise, Grouping::Commuting] {
+            let groups = Estimator::new().with_grouping(grouping).groups(&h);
+            let value: f64 = h.constant() + groups.iter().map(|g| exact_group_value(&circuit, &h, g)).sum::<f64>();
+            assert!((value - expected).abs() < 1e-10, "{:?}: {} vs {}", grouping, value, expected);
+        }
+    }
+
+    #[test]
+    fn test_sampled_estimate() {
+        let h = hamiltonian();
+        let circuit = state_circuit();
+        let expected = exact_expectation(&circuit, &h, &ParamBinding::new()).unwrap();
+        let backend = IdealSimulatorBackend::new();
+        for grouping in [Grouping::QubitWise, Grouping::Commuting] {
+            let estimate = Estimator::new()
+                .with_shots(4000)
+                .with_grouping(grouping)
+                .with_exact(false)
+                .estimate(&backend, &circuit, &h)
+                .unwrap();
+            assert!(!estimate.exact);
+            assert!(estimate.std_error > 0.0 && estimate.std_error < 0.05);
+            assert!((estimate.value - expected).abs() < 5.0 * estimate.std_error);
+        }
+    }
+
+    #[test]
+    fn test_exact_shortcut_via_runtime() {
+        let h = hamiltonian();
+        let circuit = state_circuit();
+        let mut runtime = QuantumRuntime::default();
+        runtime.register_backend("ideal", Arc::new(IdealSimulatorBackend::new()));
+        runtime.register_backend("mock", Arc::new(MockBackendAdapter::new()));
+
+        let exact = Estimator::new().run(&mut runtime, "ideal", &circuit, &h).unwrap();
+        assert!(exact.exact);
+        assert_eq!(exact.std_error, 0.0);
+        let expected = exact_expectation(&circuit, &h, &ParamBinding::new()).unwrap();
+        assert!((exact.value - expected).abs() < 1e-12);
+
+        // Mock 后端没有精确捷径，走采样批量作业
+        let sampled = Estimator::new().run(&mut runtime, "mock", &circuit, &h).unwrap();
+        assert!(!sampled.exact);
+        assert_eq!(sampled.num_circuits, Estimator::new().groups(&h).len());
+        assert_eq!(runtime.stats().total_jobs_submitted, 1);
+        assert!(Estimator::new().run(&mut runtime, "missing", &circuit, &h).is_err());
+    }
+
+    #[test]
+    fn test_missing_batch_points() {
+        let h = hamiltonian();
+        let circuit = state_circuit();
+        let backend = IdealSimulatorBackend::new();
+        let estimator = Estimator::new().with_exact(false);
+        // 后端少返回一个执行点
+        let outcome = estimator.estimate_with(&[(&circuit, &h)], &backend, backend.id(), |job| {
+            let mut result = backend.execute(&job)?;
+            result.points.pop();
+            Ok(result)
+        });
+        assert!(matches!(outcome, Err(IrError::JobExecutionFailed(_))));
+    }
+
+    #[test]
+    fn test_identity_only() {
+        let h = PauliSum::new().with_term(2.0, PauliString::identity());
+        let estimate = Estimator::new()
+            .with_exact(false)
+            .estimate(&IdealSimulatorBackend::new(), &state_circuit(), &h)
+            .unwrap();
+        assert_eq!(estimate.value, 2.0);
+        assert_eq!(estimate.num_circuits, 0);
+    }
+}
@@ -0,0 +1,761 @@
+//! 异步执行模块
+//!
+//! 基于 `std::future::Future` 的作业完成通知，不依赖任何异步运行时：
+//! - `completion()` 创建一对 `Completer` / `CompletionFuture`，完成方在任意线程写入结果并唤醒等待者；
+//! - `JobFuture` 是作业结果的 future，由后端或 `AsyncRuntimeHandle` 在作业结束时完成；
+//! - `sleep` / `timeout` 由一个共享计时线程唤醒，不为每个等待创建线程；
+//! - `poll_until` 按退避间隔反复检查条件，供只能查询状态的后端等待作业结束；
+//! - `spawn_detached` 让 future 在唤醒方线程上推进，等待期间不占用任何线程；
+//! - `block_on` 与 `LocalExecutor` 是最小的内置执行器，供没有异步运行时的调用方使用。
+//!
+//! 这些 future 只依赖 `Waker`，可以直接在 tokio 等运行时中 `.await`。
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Once, OnceLock};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+use crate::job::JobResult;
+use crate::{Result, IrError};
+
+// ============================================================================
+// Completion
+// ============================================================================
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    value: Option<Result<T>>,
+    waker: Option<Waker>,
+    /// 完成方已丢弃
+    closed: bool,
+}
+
+/// 创建一对完成方与 future
+pub fn completion<T>() -> (Completer<T>, CompletionFuture<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State { value: None, waker: None, closed: false }),
+    });
+    (Completer { shared: Arc::clone(&shared) }, CompletionFuture { shared })
+}
+
+/// 完成方：写入结果并唤醒等待的任务
+///
+/// 未写入结果就被丢弃时，future 以错误结束。
+pub struct Completer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Completer<T> {
+    /// 写入结果
+    pub fn complete(self, value: Result<T>) {
+        self.resolve(Some(value));
+    }
+
+    fn resolve(&self, value: Option<Result<T>>) {
+        let waker = {
+            let mut state = self.shared.state.lock().unwrap();
+            if state.value.is_none() && !state.closed {
+                state.value = value;
+            }
+            state.closed = true;
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for Completer<T> {
+    fn drop(&mut self) {
+        self.resolve(None);
+    }
+}
+
+/// 在完成方写入结果后就绪的 future
+pub struct CompletionFuture<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> CompletionFuture<T> {
+    /// 已就绪的 future
+    pub fn ready(value: Result<T>) -> Self {
+        let (completer, future) = completion();
+        completer.complete(value);
+        future
+    }
+
+    /// 结果是否已可取得
+    pub fn is_ready(&self) -> bool {
+        self.shared.state.lock().unwrap().closed
+    }
+}
+
+impl<T> Future for CompletionFuture<T> {
+    type Output = Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.state.lock().unwrap();
+        if let Some(value) = state.value.take() {
+            return Poll::Ready(value);
+        }
+        if state.closed {
+            return Poll::Ready(Err(IrError::JobExecutionFailed(
+                "Completion dropped without a result".to_string(),
+            )));
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T> std::fmt::Debug for CompletionFuture<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompletionFuture").field("ready", &self.is_ready()).finish()
+    }
+}
+
+/// 作业结果的完成方
+pub type JobCompleter = Completer<JobResult>;
+
+/// 作业结果的 future
+///
+/// 由 `JobCompleter` 完成（`completion()` 的 future 经 `into()` 转换），
+/// 或包装其他 future（如轮询后端状态的 `poll_until`）。
+pub struct JobFuture {
+    inner: JobFutureInner,
+}
+
+enum JobFutureInner {
+    Completion(CompletionFuture<JobResult>),
+    Future(Pin<Box<dyn Future<Output = Result<JobResult>> + Send>>),
+}
+
+impl JobFuture {
+    /// 已就绪的 future
+    pub fn ready(value: Result<JobResult>) -> Self {
+        CompletionFuture::ready(value).into()
+    }
+
+    /// 包装任意 future
+    pub fn from_future(future: impl Future<Output = Result<JobResult>> + Send + 'static) -> Self {
+        Self { inner: JobFutureInner::Future(Box::pin(future)) }
+    }
+
+    /// 结果是否已可取得；包装的 future 要轮询后才知道，总是返回 false
+    pub fn is_ready(&self) -> bool {
+        match &self.inner {
+            JobFutureInner::Completion(future) => future.is_ready(),
+            JobFutureInner::Future(_) => false,
+        }
+    }
+}
+
+impl From<CompletionFuture<JobResult>> for JobFuture {
+    fn from(future: CompletionFuture<JobResult>) -> Self {
+        Self { inner: JobFutureInner::Completion(future) }
+    }
+}
+
+impl Future for JobFuture {
+    type Output = Result<JobResult>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut self.inner {
+            JobFutureInner::Completion(future) => Pin::new(future).poll(cx),
+            JobFutureInner::Future(future) => future.as_mut().poll(cx),
+        }
+    }
+}
+
+impl std::fmt::Debug for JobFuture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobFuture").field("ready", &self.is_ready()).finish()
+    }
+}
+
+// ============================================================================
+// Timer
+// ============================================================================
+
+/// 共享计时线程：到期时唤醒登记的 waker
+struct Timer {
+    state: Mutex<TimerState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct TimerState {
+    /// (到期时刻, 登记号)，最早到期者在堆顶；已取消的登记在出堆时跳过
+    deadlines: BinaryHeap<Reverse<(Instant, u64)>>,
+    wakers: HashMap<u64, Waker>,
+    next_key: u64,
+}
+
+impl Timer {
+    /// 全局计时器，首次使用时启动计时线程
+    fn global() -> &'static Timer {
+        static TIMER: OnceLock<Timer> = OnceLock::new();
+        static START: Once = Once::new();
+        let timer = TIMER.get_or_init(|| Timer { state: Mutex::default(), changed: Condvar::new() });
+        START.call_once(|| {
+            thread::Builder::new()
+                .name("quantum-ir-timer".to_string())
+                .spawn(move || timer.run())
+                .expect("spawn timer thread");
+        });
+        timer
+    }
+
+    /// 登记到期时要唤醒的 waker；key 仍有效时只更新其 waker，返回登记号
+    fn register(&self, key: Option<u64>, deadline: Instant, waker: &Waker) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        if let Some(existing) = key.and_then(|key| state.wakers.get_mut(&key)) {
+            if !existing.will_wake(waker) {
+                *existing = waker.clone();
+            }
+            return key.expect("registered key");
+        }
+        let key = state.next_key;
+        state.next_key += 1;
+        let earliest = state.deadlines.peek().is_none_or(|Reverse((at, _))| deadline < *at);
+        state.deadlines.push(Reverse((deadline, key)));
+        state.wakers.insert(key, waker.clone());
+        drop(state);
+        if earliest {
+            self.changed.notify_one();
+        }
+        key
+    }
+
+    fn cancel(&self, key: u64) {
+        self.state.lock().unwrap().wakers.remove(&key);
+    }
+
+    fn run(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            let mut due = Vec::new();
+            while let Some(&Reverse((at, key))) = state.deadlines.peek() {
+                if at > now {
+                    break;
+                }
+                state.deadlines.pop();
+                due.extend(state.wakers.remove(&key));
+            }
+            if !due.is_empty() {
+                drop(state);
+                due.into_iter().for_each(Waker::wake);
+                state = self.state.lock().unwrap();
+                continue;
+            }
+            state = match state.deadlines.peek() {
+                Some(&Reverse((at, _))) => self.changed.wait_timeout(state, at - now).unwrap().0,
+                None => self.changed.wait(state).unwrap(),
+            };
+        }
+    }
+}
+
+/// 等待 duration 后就绪
+pub fn sleep(duration: Duration) -> Sleep {
+    sleep_until(Instant::now() + duration)
+}
+
+/// 到 deadline 时就绪
+pub fn sleep_until(deadline: Instant) -> Sleep {
+    Sleep { deadline, key: None }
+}
+
+/// `sleep` 返回的 future，由共享计时线程唤醒
+#[derive(Debug)]
+pub struct Sleep {
+    deadline: Instant,
+    key: Option<u64>,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            if let Some(key) = self.key.take() {
+                Timer::global().cancel(key);
+            }
+            return Poll::Ready(());
+        }
+        self.key = Some(Timer::global().register(self.key, self.deadline, cx.waker()));
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            Timer::global().cancel(key);
+        }
+    }
+}
+
+/// 最多等待 duration；超时返回 `None`，future 随之丢弃
+pub fn timeout<F: Future>(future: F, duration: Duration) -> Timeout<F> {
+    Timeout { future: Box::pin(future), sleep: sleep(duration) }
+}
+
+/// `timeout` 返回的 future
+pub struct Timeout<F: Future> {
+    future: Pin<Box<F>>,
+    sleep: Sleep,
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(value) = self.future.as_mut().poll(cx) {
+            return Poll::Ready(Some(value));
+        }
+        Pin::new(&mut self.sleep).poll(cx).map(|()| None)
+    }
+}
+
+// ============================================================================
+// Polling
+// ============================================================================
+
+/// 反复调用 check 直到其返回结果；两次检查之间的间隔从 initial 开始加倍，不超过 max
+///
+/// 等待期间由共享计时线程唤醒任务，不占用执行器。
+pub fn poll_until<T, F>(check: F, initial: Duration, max: Duration) -> PollUntil<F>
+where
+    F: FnMut() -> Option<Result<T>> + Unpin,
+{
+    PollUntil { check, interval: initial, max_interval: max, delay: None }
+}
+
+/// `poll_until` 返回的 future
+pub struct PollUntil<F> {
+    check: F,
+    interval: Duration,
+    max_interval: Duration,
+    /// 到下一次检查前的等待
+    delay: Option<Sleep>,
+}
+
+impl<T, F> Future for PollUntil<F>
+where
+    F: FnMut() -> Option<Result<T>> + Unpin,
+{
+    type Output = Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            if let Some(delay) = this.delay.as_mut() {
+                if Pin::new(delay).poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+                this.delay = None;
+            }
+            if let Some(value) = (this.check)() {
+                return Poll::Ready(value);
+            }
+            this.delay = Some(sleep(this.interval));
+            this.interval = (this.interval * 2).min(this.max_interval);
+        }
+    }
+}
+
+// ============================================================================
+// Detached Tasks
+// ============================================================================
+
+/// 由唤醒推进的任务
+struct DetachedTask {
+    future: Mutex<Option<Pin<Box<dyn Future<Output = ()> + Send>>>>,
+    /// 有尚未处理的唤醒
+    notified: AtomicBool,
+}
+
+impl DetachedTask {
+    /// 在当前线程上轮询；其他线程正在轮询时只留下唤醒标记，由它再轮询一次
+    fn run(self: &Arc<Self>) {
+        self.notified.store(true, Ordering::SeqCst);
+        loop {
+            let Ok(mut slot) = self.future.try_lock() else {
+                return;
+            };
+            if !self.notified.swap(false, Ordering::SeqCst) {
+                return;
+            }
+            if let Some(future) = slot.as_mut() {
+                let waker = Waker::from(Arc::clone(self));
+                if future.as_mut().poll(&mut Context::from_waker(&waker)).is_ready() {
+                    *slot = None;
+                }
+            }
+            drop(slot);
+            if !self.notified.load(Ordering::SeqCst) {
+                return;
+            }
+        }
+    }
+}
+
+impl Wake for DetachedTask {
+    fn wake(self: Arc<Self>) {
+        self.run();
+    }
+}
+
+/// 在当前线程上开始推进 future，之后每次被唤醒时由唤醒方线程继续推进，直到完成
+///
+/// 等待期间不占用执行器或线程，适合等待由后端或计时器完成的 future；future 本身不应阻塞。
+pub fn spawn_detached(future: impl Future<Output = ()> + Send + 'static) {
+    let task = Arc::new(DetachedTask {
+        future: Mutex::new(Some(Box::pin(future))),
+        notified: AtomicBool::new(false),
+    });
+    task.run();
+}
+
+// ============================================================================
+// Join
+// ============================================================================
+
+/// 等待一组 future 全部就绪，结果按输入顺序返回
+pub fn join_all<F: Future>(futures: impl IntoIterator<Item = F>) -> JoinAll<F> {
+    let futures: Vec<_> = futures.into_iter().map(|f| Some(Box::pin(f))).collect();
+    let outputs = futures.iter().map(|_| None).collect();
+    JoinAll { futures, outputs }
+}
+
+/// `join_all` 返回的 future
+pub struct JoinAll<F: Future> {
+    futures: Vec<Option<Pin<Box<F>>>>,
+    outputs: Vec<Option<F::Output>>,
+}
+
+impl<F: Future> Unpin for JoinAll<F> {}
+
+impl<F: Future> Future for JoinAll<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        for (slot, output) in this.futures.iter_mut().zip(this.outputs.iter_mut()) {
+            if let Some(future) = slot {
+                if let Poll::Ready(value) = future.as_mut().poll(cx) {
+                    *output = Some(value);
+                    *slot = None;
+                }
+            }
+        }
+        if this.futures.iter().any(Option::is_some) {
+            return Poll::Pending;
+        }
+        Poll::Ready(this.outputs.iter_mut().map(|o| o.take().expect("output is set")).collect())
+    }
+}
+
+// ============================================================================
+// Executors
+// ============================================================================
+
+/// 唤醒时恢复被挂起的线程
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// 在当前线程上驱动 future 直到完成；等待期间线程挂起而不是轮询
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+            return value;
+        }
+        thread::park();
+    }
+}
+
+/// 与 `block_on` 相同，但最多等待 timeout；超时返回 `None`，future 随之丢弃
+pub fn block_on_timeout<F: Future>(future: F, timeout: Duration) -> Option<F::Output> {
+    let deadline = Instant::now() + timeout;
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+            return Some(value);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return None;
+        }
+        thread::park_timeout(deadline - now);
+    }
+}
+
+/// 就绪任务队列
+struct ReadyQueue {
+    ids: Mutex<VecDeque<usize>>,
+    thread: Mutex<Option<Thread>>,
+}
+
+impl ReadyQueue {
+    fn push(&self, id: usize) {
+        self.ids.lock().unwrap().push_back(id);
+        if let Some(thread) = self.thread.lock().unwrap().as_ref() {
+            thread.unpark();
+        }
+    }
+
+    fn pop(&self) -> Option<usize> {
+        self.ids.lock().unwrap().pop_front()
+    }
+}
+
+struct TaskWaker {
+    id: usize,
+    ready: Arc<ReadyQueue>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.ready.push(self.id);
+    }
+}
+
+/// 单线程任务执行器
+///
+/// `spawn` 的任务只在被唤醒时重新轮询；`run` 在所有任务完成后返回。
+pub struct LocalExecutor {
+    tasks: HashMap<usize, Pin<Box<dyn Future<Output = ()>>>>,
+    next_id: usize,
+    ready: Arc<ReadyQueue>,
+}
+
+impl LocalExecutor {
+    pub fn new() -> Self {
+        Self {
+            tasks: HashMap::new(),
+            next_id: 0,
+            ready: Arc::new(ReadyQueue {
+                ids: Mutex::new(VecDeque::new()),
+                thread: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// 加入任务，返回其结果的 future
+    pub fn spawn<F>(&mut self, future: F) -> CompletionFuture<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        let (completer, handle) = completion();
+        let id = self.next_id;
+        self.next_id += 1;
+        self.tasks.insert(id, Box::pin(async move { completer.complete(Ok(future.await)) }));
+        self.ready.push(id);
+        handle
+    }
+
+    /// 未完成的任务数
+    pub fn pending(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// 运行直到所有任务完成
+    pub fn run(&mut self) {
+        *self.ready.thread.lock().unwrap() = Some(thread::current());
+        while !self.tasks.is_empty() {
+            let Some(id) = self.ready.pop() else {
+                thread::park();
+                continue;
+            };
+            let Some(task) = self.tasks.get_mut(&id) else {
+                continue;
+            };
+            let waker = Waker::from(Arc::new(TaskWaker { id, ready: Arc::clone(&self.ready) }));
+            if task.as_mut().poll(&mut Context::from_waker(&waker)).is_ready() {
+                self.tasks.remove(&id);
+            }
+        }
+        *self.ready.thread.lock().unwrap() = None;
+    }
+}
+
+impl Default for LocalExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_completion_wakes_waiter() {
+        let (completer, future) = completion::<u32>();
+        assert!(!future.is_ready());
+        let worker = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            completer.complete(Ok(7));
+        });
+        assert_eq!(block_on(future).unwrap(), 7);
+        worker.join().unwrap();
+
+        assert_eq!(block_on(CompletionFuture::ready(Ok(3))).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_block_on_timeout() {
+        let (completer, future) = completion::<u32>();
+        let start = Instant::now();
+        assert!(block_on_timeout(future, Duration::from_millis(30)).is_none());
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        drop(completer);
+
+        let (completer, future) = completion::<u32>();
+        completer.complete(Ok(5));
+        assert_eq!(block_on_timeout(future, Duration::ZERO).unwrap().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_poll_until_backoff() {
+        let mut checks = Vec::new();
+        let start = Instant::now();
+        let value = block_on(poll_until(
+            || {
+                checks.push(start.elapsed());
+                (checks.len() == 4).then_some(Ok(checks.len()))
+            },
+            Duration::from_millis(5),
+            Duration::from_millis(10),
+        ));
+        assert_eq!(value.unwrap(), 4);
+        // 间隔依次为 5、10、10 毫秒
+        assert!(checks[1] >= Duration::from_millis(5));
+        assert!(checks[3] >= Duration::from_millis(25));
+    }
+
+    #[test]
+    fn test_sleep_and_timeout() {
+        let start = Instant::now();
+        block_on(sleep(Duration::from_millis(20)));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        let (completer, future) = completion::<u32>();
+        assert!(block_on(timeout(future, Duration::from_millis(10))).is_none());
+        drop(completer);
+
+        let (completer, future) = completion::<u32>();
+        completer.complete(Ok(9));
+        assert_eq!(block_on(timeout(future, Duration::from_secs(60))).unwrap().unwrap(), 9);
+    }
+
+    #[test]
+    fn test_spawn_detached_runs_on_waking_thread() {
+        let (completer, future) = completion::<u32>();
+        let (tx, rx) = std::sync::mpsc::channel();
+        spawn_detached(async move {
+            let value = future.await.unwrap();
+            tx.send((value, thread::current().id())).unwrap();
+        });
+        assert!(rx.try_recv().is_err());
+        let worker = thread::spawn(move || {
+            completer.complete(Ok(4));
+            thread::current().id()
+        });
+        // 完成方返回时任务已在其线程上结束
+        let worker_id = worker.join().unwrap();
+        assert_eq!(rx.try_recv().unwrap(), (4, worker_id));
+
+        // 计时器唤醒的任务在共享计时线程上继续
+        let (tx, rx) = std::sync::mpsc::channel();
+        spawn_detached(async move {
+            sleep(Duration::from_millis(5)).await;
+            tx.send(thread::current().name().map(str::to_string)).unwrap();
+        });
+        assert_eq!(rx.recv().unwrap().as_deref(), Some("quantum-ir-timer"));
+    }
+
+    #[test]
+    fn test_dropped_completer() {
+        let (completer, future) = completion::<u32>();
+        drop(completer);
+        assert!(future.is_ready());
+        assert!(matches!(block_on(future), Err(IrError::JobExecutionFailed(_))));
+    }
+
+    #[test]
+    fn test_join_all_preserves_order() {
+        let pairs: Vec<_> = (0..4).map(|_| completion::<usize>()).collect();
+        let (completers, futures): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+        let worker = thread::spawn(move || {
+            for (i, completer) in completers.into_iter().enumerate().rev() {
+                completer.complete(Ok(i * 10));
+            }
+        });
+        let values: Vec<usize> = block_on(join_all(futures)).into_iter().map(|v| v.unwrap()).collect();
+        assert_eq!(values, vec![0, 10, 20, 30]);
+        worker.join().unwrap();
+    }
+
+    #[test]
+    fn test_local_executor() {
+        let mut executor = LocalExecutor::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (completer, gate) = completion::<&str>();
+
+        let waiting = {
+            let log = Rc::clone(&log);
+            executor.spawn(async move {
+                let value = gate.await.unwrap();
+                log.borrow_mut().push(value);
+                value.len()
+            })
+        };
+        let immediate = {
+            let log = Rc::clone(&log);
+            executor.spawn(async move { log.borrow_mut().push("first") })
+        };
+        assert_eq!(executor.pending(), 2);
+
+        let worker = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            completer.complete(Ok("second"));
+        });
+        executor.run();
+        worker.join().unwrap();
+
+        assert_eq!(executor.pending(), 0);
+        assert_eq!(*log.borrow(), vec!["first", "second"]);
+        assert_eq!(block_on(waiting).unwrap(), 6);
+        assert!(block_on(immediate).is_ok());
+    }
+}
@@ -0,0 +1,514 @@
+//! 梯度模块
+//!
+//! 期望值 E(θ) = ⟨ψ(θ)|O|ψ(θ)⟩ 对符号参数的解析梯度：
+//! - 参数移位：每个依赖参数的门参数生成 ±s 两条移位电路，作为一个批量作业提交到任意后端；
+//! - 伴随微分：在态矢量上一次正向、一次反向扫描得到精确梯度。
+//!
+//! 参数经表达式进入门时按链式法则累加：∂E/∂θ = Σ (∂E/∂a) · (∂a/∂θ)。
+
+use std::f64::consts::PI;
+
+use crate::backend::BackendAdapter;
+use crate::circuit::CircuitDag;
+use crate::job::{Job, JobStatus};
+use crate::linalg::{Complex, Matrix};
+use crate::observable::Observable;
+use crate::operation::{Operation, SingleQubitGate, TwoQubitGate};
+use crate::params::{Param, ParamBinding};
+use crate::statevector::{unitary_ops, StateVector, MAX_SIMULATION_QUBITS};
+use crate::{Result, IrError};
+
+// ============================================================================
+// Shift Rules
+// ============================================================================
+
+/// 参数移位规则：∂E/∂a = c · [E(a + s) − E(a − s)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShiftRule {
+    pub shift: f64,
+    pub coefficient: f64,
+}
+
+impl ShiftRule {
+    /// 期望值关于 a 只含频率 ω 的三角项时的规则：s = π/(2ω)，c = ω/2
+    pub fn for_frequency(omega: f64) -> Self {
+        Self {
+            shift: PI / (2.0 * omega),
+            coefficient: omega / 2.0,
+        }
+    }
+
+    /// 门第 slot 个参数的移位规则
+    ///
+    /// 频率由生成元本征值之差决定：
+    /// - Rx/Ry/Rz、U 的 θ：生成元 σ/2，本征值 ±½；
+    /// - P、U 的 φ/λ：生成元 |1⟩⟨1|，本征值 {0, 1}；
+    /// - CP(φ)：生成元 |11⟩⟨11|，本征值 {0, 1}；
+    /// - MS(θ) = exp(-iθ/2·X⊗X)：生成元 X⊗X/2，本征值 ±½。
+    ///
+    /// 以上差值均为 1，故 ω = 1。
+    pub fn of(op: &Operation, slot: usize) -> Option<ShiftRule> {
+        let omega = match op {
+            Operation::Gate1 { gate, .. } => match (gate, slot) {
+                (SingleQubitGate::Rx(_) | SingleQubitGate::Ry(_) | SingleQubitGate::Rz(_) | SingleQubitGate::P(_), 0) => 1.0,
+                (SingleQubitGate::U(..), 0..=2) => 1.0,
+                _ => return None,
+            },
+            Operation::Gate2 { gate, .. } => match (gate, slot) {
+                (TwoQubitGate::CP(_), 0) => 1.0,
+                (TwoQubitGate::MS(_), 0) => 1.0,
+                _ => return None,
+            },
+            Operation::Symbolic { op, .. } => return ShiftRule::of(op, slot),
+            _ => return None,
+        };
+        Some(ShiftRule::for_frequency(omega))
+    }
+}
+
+/// 具体门对第 slot 个参数的导数矩阵 ∂U/∂a
+fn derivative_matrix(op: &Operation, slot: usize) -> Option<Matrix> {
+    let i1 = Matrix::diagonal(&[Complex::ZERO, Complex::I]);
+    let shifted = |values: &mut Vec<f64>| {
+        values[slot] += PI;
+        op.with_parameters(values).ok()?.matrix().map(|m| m.scale(Complex::real(0.5)))
+    };
+    let mut values = op.parameters();
+    match op {
+        // exp(-iaG/2) 型（G² = I）：∂U/∂a = ½ U(a + π)
+        Operation::Gate1 { gate: SingleQubitGate::Rx(_) | SingleQubitGate::Ry(_) | SingleQubitGate::Rz(_), .. }
+        | Operation::Gate2 { gate: TwoQubitGate::MS(_), .. } => shifted(&mut values),
+        Operation::Gate1 { gate: SingleQubitGate::U(..), .. } => match slot {
+            // U 的各元素是 θ/2 的正余弦，同样满足 ½ U(θ + π)
+            0 => shifted(&mut values),
+            // φ 只出现在第二行，λ 只出现在第二列
+            1 => Some(i1.matmul(&op.matrix()?)),
+            2 => Some(op.matrix()?.matmul(&i1)),
+            _ => None,
+        },
+        Operation::Gate1 { gate: SingleQubitGate::P(_), .. } => Some(i1.matmul(&op.matrix()?)),
+        Operation::Gate2 { gate: TwoQubitGate::CP(_), .. } => {
+            let projector = Matrix::diagonal(&[Complex::ZERO, Complex::ZERO, Complex::ZERO, Complex::I]);
+            Some(projector.matmul(&op.matrix()?))
+        }
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Gradient Result
+// ============================================================================
+
+/// 梯度结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradientResult {
+    /// 参数（按名称排序）
+    pub parameters: Vec<Param>,
+    /// 与 parameters 一一对应的偏导数
+    pub gradient: Vec<f64>,
+    /// 当前参数下的期望值（伴随微分时给出）
+    pub expectation: Option<f64>,
+    /// 执行的电路数
+    pub num_circuits: usize,
+}
+
+impl GradientResult {
+    /// 对 param 的偏导数
+    pub fn get(&self, param: &Param) -> Option<f64> {
+        self.parameters.iter().position(|p| p == param).map(|i| self.gradient[i])
+    }
+}
+
+/// 一个门参数的导数对 parameters 的贡献
+struct ParamSlot {
+    /// 在 ops 中的位置
+    index: usize,
+    slot: usize,
+    /// (参数下标, ∂a/∂θ)
+    chain: Vec<(usize, f64)>,
+}
+
+/// 绑定后的具体操作序列，以及每个依赖参数的门参数
+fn bind_ops(
+    circuit: &CircuitDag,
+    values: &ParamBinding,
+) -> Result<(Vec<Param>, Vec<Operation>, Vec<ParamSlot>)> {
+    let parameters = circuit.parameters();
+    let mut ops = Vec::new();
+    let mut slots = Vec::new();
+    for op in unitary_ops(circuit)? {
+        if let Operation::Symbolic { params, .. } = &op {
+            for (slot, expr) in params.iter().enumerate() {
+                let chain = parameters
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| Ok((i, expr.derivative(p).evaluate(values)?)))
+                    .filter(|d| !matches!(d, Ok((_, v)) if *v == 0.0))
+                    .collect::<Result<Vec<_>>>()?;
+                if !chain.is_empty() {
+                    slots.push(ParamSlot { index: ops.len(), slot, chain });
+                }
+            }
+        }
+        let bound = op.bind(values)?;
+        if bound.is_symbolic() {
+            return Err(IrError::InvalidOperation(format!(
+                "Unbound parameters: {:?}",
+                bound.free_parameters().iter().map(Param::name).collect::<Vec<_>>()
+            )));
+        }
+        ops.push(bound);
+    }
+    Ok((parameters, ops, slots))
+}
+
+// ============================================================================
+// Parameter Shift
+// ============================================================================
+
+/// 一对移位电路对梯度的贡献
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShiftTerm {
+    /// 参数在 `ShiftPlan::parameters` 中的下标
+    pub parameter: usize,
+    /// +s 电路在 `ShiftPlan::circuits` 中的下标
+    pub plus: usize,
+    /// −s 电路下标
+    pub minus: usize,
+    /// 移位系数与链式导数之积
+    pub coefficient: f64,
+}
+
+/// 参数移位电路集合
+#[derive(Debug, Clone)]
+pub struct ShiftPlan {
+    pub parameters: Vec<Param>,
+    pub circuits: Vec<CircuitDag>,
+    pub terms: Vec<ShiftTerm>,
+}
+
+impl ShiftPlan {
+    /// 由各移位电路的期望值组合出梯度
+    pub fn combine(&self, expectations: &[f64]) -> Vec<f64> {
+        let mut gradient = vec![0.0; self.parameters.len()];
+        for term in &self.terms {
+            gradient[term.parameter] += term.coefficient * (expectations[term.plus] - expectations[term.minus]);
+        }
+        gradient
+    }
+}
+
+/// 参数移位梯度
+#[derive(Debug, Clone)]
+pub struct ParameterShift {
+    /// 每条电路的测量次数
+    pub shots: u32,
+}
+
+impl Default for ParameterShift {
+    fn default() -> Self {
+        Self { shots: 1024 }
+    }
+}
+
+impl ParameterShift {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_shots(mut self, shots: u32) -> Self {
+        self.shots = shots;
+        self
+    }
+
+    /// 生成移位电路：每个依赖参数的门参数两条（+s / −s），其余门按 values 绑定
+    pub fn plan(&self, circuit: &CircuitDag, values: &ParamBinding) -> Result<ShiftPlan> {
+        let (parameters, ops, slots) = bind_ops(circuit, values)?;
+        let mut circuits = Vec::with_capacity(2 * slots.len());
+        let mut terms = Vec::new();
+        for ParamSlot { index, slot, chain } in slots {
+            let op = &ops[index];
+            let rule = ShiftRule::of(op, slot).ok_or_else(|| IrError::UnsupportedOperation(format!(
+                "No shift rule for parameter {} of {}", slot, op.name()
+            )))?;
+            for sign in [1.0, -1.0] {
+                let mut shifted = op.parameters();
+                shifted[slot] += sign * rule.shift;
+                let mut ops = ops.clone();
+                ops[index] = op.with_parameters(&shifted)?;
+                circuits.push(circuit.rebuild_with(ops));
+            }
+            let (plus, minus) = (circuits.len() - 2, circuits.len() - 1);
+            terms.extend(chain.into_iter().map(|(parameter, d)| ShiftTerm {
+                parameter,
+                plus,
+                minus,
+                coefficient: rule.coefficient * d,
+            }));
+        }
+        Ok(ShiftPlan { parameters, circuits, terms })
+    }
+
+    /// 在后端上以一个批量作业执行全部移位电路并组合梯度
+    pub fn gradient(
+        &self,
+        backend: &dyn BackendAdapter,
+        circuit: &CircuitDag,
+        observable: &dyn Observable,
+        values: &ParamBinding,
+    ) -> Result<GradientResult> {
+        let plan = self.plan(circuit, values)?;
+        let mut batch = Vec::new();
+        let mut per_circuit = None;
+        for shifted in &plan.circuits {
+            let measured = observable.measurement_circuits(shifted)?;
+            if *per_circuit.get_or_insert(measured.len()) != measured.len() {
+                return Err(IrError::InvalidOperation(
+                    "Observable produced a varying number of measurement circuits".to_string(),
+                ));
+            }
+            batch.extend(measured);
+        }
+        let num_circuits = batch.len();
+        if batch.is_empty() {
+            return Ok(GradientResult {
+                gradient: vec![0.0; plan.parameters.len()],
+                parameters: plan.parameters,
+                expectation: None,
+                num_circuits,
+            });
+        }
+
+        let mut batch = batch.into_iter();
+        let first = batch.next().expect("batch is not empty");
+        let job = Job::new(first, self.shots, backend.id()).with_circuits(batch);
+        let result = backend.execute(&job)?;
+        if result.status != JobStatus::Completed {
+            return Err(IrError::JobExecutionFailed(
+                result.error.unwrap_or_else(|| format!("Gradient job {} did not complete", job.id)),
+            ));
+        }
+
+        let per_circuit = per_circuit.unwrap_or(1).max(1);
+        let expectations = result
+            .points
+            .chunks(per_circuit)
+            .map(|results| observable.expectation_from(results))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(GradientResult {
+            gradient: plan.combine(&expectations),
+            parameters: plan.parameters,
+            expectation: None,
+            num_circuits,
+        })
+    }
+}
+
+// ============================================================================
+// Adjoint Differentiation
+// ============================================================================
+
+/// 伴随微分（态矢量精确梯度）
+///
+/// 正向模拟得到 |ψ⟩ 与 |λ⟩ = O|ψ⟩，再从末端逐门反推：
+/// ∂E/∂a = 2·Re⟨λ|(∂U/∂a)|φ⟩，其中 |φ⟩ 为该门之前的态。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AdjointGradient;
+
+impl AdjointGradient {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn gradient(
+        &self,
+        circuit: &CircuitDag,
+        observable: &dyn Observable,
+        values: &ParamBinding,
+    ) -> Result<GradientResult> {
+        let (parameters, ops, slots) = bind_ops(circuit, values)?;
+        let mut qubits = circuit.all_qubits();
+        qubits.sort();
+        if qubits.len() > MAX_SIMULATION_QUBITS {
+            return Err(IrError::UnsupportedOperation(format!(
+                "Simulation of {} qubits exceeds the limit of {}", qubits.len(), MAX_SIMULATION_QUBITS
+            )));
+        }
+        let index_of = |q| qubits.binary_search(&q).ok();
+        let targets = |op: &Operation| -> Vec<usize> {
+            op.qubits().into_iter().filter_map(index_of).collect()
+        };
+
+        let mut psi = StateVector::new(qubits.len());
+        for op in &ops {
+            psi.apply_operation(op, index_of)?;
+        }
+        let mut lambda = observable.apply(&psi, &qubits)?;
+        let expectation = psi.inner(&lambda).re;
+
+        let mut gradient = vec![0.0; parameters.len()];
+        let mut pending = slots.iter().rev().peekable();
+        for (index, op) in ops.iter().enumerate().rev() {
+            let Some(matrix) = op.matrix() else { continue };
+            let inverse = matrix.dagger();
+            psi.apply_matrix(&targets(op), &inverse);
+            while let Some(slot) = pending.next_if(|s| s.index == index) {
+                let derivative = derivative_matrix(op, slot.slot).ok_or_else(|| {
+                    IrError::UnsupportedOperation(format!("No derivative for parameter {} of {}", slot.slot, op.name()))
+                })?;
+                let mut mu = psi.clone();
+                mu.apply_matrix(&targets(op), &derivative);
+                let da = 2.0 * lambda.inner(&mu).re;
+                for &(i, d) in &slot.chain {
+                    gradient[i] += da * d;
+                }
+            }
+            lambda.apply_matrix(&targets(op), &inverse);
+        }
+
+        Ok(GradientResult {
+            parameters,
+            gradient,
+            expectation: Some(expectation),
+            num_circuits: 0,
+        })
+    }
+}
+
+/// 精确期望值（态矢量模拟）
+pub fn exact_expectation(circuit: &CircuitDag, observable: &dyn Observable, values: &ParamBinding) -> Result<f64> {
+    let (_, ops, _) = bind_ops(circuit, values)?;
+    let mut qubits = circuit.all_qubits();
+    qubits.sort();
+    let mut psi = StateVector::new(qubits.len());
+    for op in &ops {
+        psi.apply_operation(op, |q| qubits.binary_search(&q).ok())?;
+    }
+    observable.exact_expectation(&psi, &qubits)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::IdealSimulatorBackend;
+    use crate::observable::ZParity;
+    use crate::operation::{cnot, h};
+    use crate::params::{cp_param, ms_param, rx_param, ry_param};
+    use crate::qubit::LogicalQubitId;
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    fn values(pairs: &[(&str, f64)]) -> ParamBinding {
+        pairs.iter().map(|&(name, v)| (Param::new(name), v)).collect()
+    }
+
+    fn ansatz() -> CircuitDag {
+        let (a, b, c) = (Param::new("a"), Param::new("b"), Param::new("c"));
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(ry_param(q(0), a.expr()));
+        circuit.add_node(h(q(1)));
+        circuit.add_node(cp_param(q(0), q(1), b.expr() * 2.0));
+        circuit.add_node(h(q(1)));
+        circuit.add_node(ms_param(q(1), q(2), c.expr().sin()));
+        circuit.add_node(cnot(q(0), q(2)));
+        circuit.add_node(rx_param(q(2), a.expr() - c.expr()));
+        circuit.add_node(Operation::symbolic(
+            Operation::gate1(SingleQubitGate::U(0.0, 0.0, 0.0), q(1)),
+            vec![b.expr(), c.expr(), a.expr() * b.expr()],
+        ).unwrap());
+        circuit
+    }
+
+    fn observable() -> ZParity {
+        ZParity::new(vec![q(1), q(2)])
+    }
+
+    /// 中心差分
+    fn finite_difference(circuit: &CircuitDag, at: &ParamBinding) -> Vec<f64> {
+        let h = 1e-6;
+        circuit
+            .parameters()
+            .iter()
+            .map(|p| {
+                let mut plus = at.clone();
+                let mut minus = at.clone();
+                *plus.get_mut(p).unwrap() += h;
+                *minus.get_mut(p).unwrap() -= h;
+                let e = |v: &ParamBinding| exact_expectation(circuit, &observable(), v).unwrap();
+                (e(&plus) - e(&minus)) / (2.0 * h)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_shift_rules() {
+        let rule = ShiftRule::of(&Operation::gate2(TwoQubitGate::CP(0.3), q(0), q(1)), 0).unwrap();
+        assert_eq!(rule, ShiftRule::for_frequency(1.0));
+        assert!((rule.shift - PI / 2.0).abs() < 1e-15);
+        assert!(ShiftRule::of(&h(q(0)), 0).is_none());
+        assert!(ShiftRule::of(&Operation::gate1(SingleQubitGate::U(0.0, 0.0, 0.0), q(0)), 3).is_none());
+    }
+
+    #[test]
+    fn test_parameter_shift_plan_is_exact() {
+        let circuit = ansatz();
+        let at = values(&[("a", 0.4), ("b", -0.7), ("c", 1.1)]);
+        let plan = ParameterShift::new().plan(&circuit, &at).unwrap();
+        // 7 个依赖参数的门参数，各两条电路
+        assert_eq!(plan.circuits.len(), 14);
+
+        let expectations: Vec<f64> = plan
+            .circuits
+            .iter()
+            .map(|c| exact_expectation(c, &observable(), &ParamBinding::new()).unwrap())
+            .collect();
+        let gradient = plan.combine(&expectations);
+        for (g, fd) in gradient.iter().zip(finite_difference(&circuit, &at)) {
+            assert!((g - fd).abs() < 1e-6, "{} vs {}", g, fd);
+        }
+    }
+
+    #[test]
+    fn test_adjoint_matches_finite_difference() {
+        let circuit = ansatz();
+        let at = values(&[("a", 0.4), ("b", -0.7), ("c", 1.1)]);
+        let result = AdjointGradient::new().gradient(&circuit, &observable(), &at).unwrap();
+        assert_eq!(result.parameters, circuit.parameters());
+        let expected = exact_expectation(&circuit, &observable(), &at).unwrap();
+        assert!((result.expectation.unwrap() - expected).abs() < 1e-12);
+        for (g, fd) in result.gradient.iter().zip(finite_difference(&circuit, &at)) {
+            assert!((g - fd).abs() < 1e-6, "{} vs {}", g, fd);
+        }
+    }
+
+    #[test]
+    fn test_parameter_shift_on_backend() {
+        let theta = Param::new("theta");
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(ry_param(q(0), theta.expr()));
+        let at = values(&[("theta", 0.9)]);
+        let backend = IdealSimulatorBackend::new();
+
+        let result = ParameterShift::new()
+            .with_shots(4000)
+            .gradient(&backend, &circuit, &ZParity::new(vec![q(0)]), &at)
+            .unwrap();
+        assert_eq!(result.num_circuits, 2);
+        // ⟨Z⟩ = cos θ
+        assert!((result.get(&theta).unwrap() + 0.9f64.sin()).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_unbound_parameters_rejected() {
+        let circuit = ansatz();
+        let partial = values(&[("a", 0.4)]);
+        assert!(ParameterShift::new().plan(&circuit, &partial).is_err());
+        assert!(AdjointGradient::new().gradient(&circuit, &observable(), &partial).is_err());
+    }
+}
@@ -3,8 +3,15 @@
 //! 包含 Job 抽象、优先级队列和调度器
 
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use crate::qubit::LogicalQubitId;
 use crate::circuit::CircuitDag;
+use crate::params::ParamBinding;
+use crate::policy::{SchedulingPolicy, StrictPriority};
+use crate::quota::QuotaManager;
+use crate::retry::RetryPolicy;
+use crate::{Result, IrError, ErrorKind};
 
 // ============================================================================
 // Type Definitions
@@ -16,6 +23,9 @@ pub type JobId = u64;
 /// 调度器句柄
 pub type SchedulerHandle = u64;
 
+/// 下一个作业 ID
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
 // ============================================================================
 // Priority
 // ============================================================================
@@ -67,7 +77,7 @@ pub enum JobStatus {
     Failed,
     /// 已取消
     Cancelled,
-    /// 等待依赖
+    /// 等待依赖或配额
     Waiting,
 }
 
@@ -83,6 +93,28 @@ impl JobStatus {
     pub fn is_queued(&self) -> bool {
         matches!(self, JobStatus::Queued | JobStatus::Ready | JobStatus::Waiting)
     }
+    
+    /// 状态机是否允许转换到 next
+    /// 
+    /// 正常路径为 Pending → Queued → Ready → Running → Completed/Failed；
+    /// 排队期间可在 Queued 与 Waiting（等待依赖）之间往返，依赖失败时直接 Failed；
+    /// 需要重试的运行中作业回到 Queued；任何非终止状态都可以取消。
+    pub fn can_transition_to(&self, next: JobStatus) -> bool {
+        use JobStatus::*;
+        matches!(
+            (*self, next),
+            (Pending, Queued)
+                | (Queued, Waiting)
+                | (Waiting, Queued)
+                | (Queued, Ready)
+                | (Ready, Running)
+                | (Running, Queued)
+                | (Running, Completed)
+                | (Running, Failed)
+                | (Queued | Waiting, Failed)
+                | (Pending | Queued | Waiting | Ready | Running, Cancelled)
+        )
+    }
 }
 
 // ============================================================================
@@ -96,6 +128,8 @@ pub struct JobMetadata {
     pub project: Option<String>,
     pub experiment_name: Option<String>,
     pub custom: std::collections::HashMap<String, String>,
+    /// 执行超时；未设置时使用 `RuntimeConfig::job_timeout_secs`
+    pub timeout: Option<Duration>,
 }
 
 impl JobMetadata {
@@ -122,6 +156,30 @@ impl JobMetadata {
         self.custom.insert(key.into(), value.into());
         self
     }
+    
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+// ============================================================================
+// Job Attempt
+// ============================================================================
+
+/// 一次执行尝试的记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobAttempt {
+    /// 尝试序号，从 1 开始
+    pub attempt: u32,
+    /// 执行该次尝试的后端
+    pub backend: String,
+    pub status: JobStatus,
+    pub error: Option<String>,
+    /// 后端返回错误时的错误类别
+    pub error_kind: Option<ErrorKind>,
+    /// 该次尝试耗时（毫秒）
+    pub duration_ms: u64,
 }
 
 // ============================================================================
@@ -143,6 +201,12 @@ pub struct JobResult {
     pub error: Option<String>,
     /// 后端返回的额外数据
     pub backend_data: Option<String>,
+    /// 批量作业中每个扫描点的结果（按 `Job::point_circuits` 顺序）
+    pub points: Vec<JobResult>,
+    /// 因超时而失败
+    pub timed_out: bool,
+    /// 每次执行尝试（含重试与故障转移）
+    pub attempts: Vec<JobAttempt>,
 }
 
 impl JobResult {
@@ -155,6 +219,9 @@ impl JobResult {
             execution_time_ms: None,
             error: None,
             backend_data: None,
+            points: Vec::new(),
+            timed_out: false,
+            attempts: Vec::new(),
         }
     }
     
@@ -167,9 +234,57 @@ impl JobResult {
             execution_time_ms: None,
             error: Some(error.into()),
             backend_data: None,
+            points: Vec::new(),
+            timed_out: false,
+            attempts: Vec::new(),
         }
     }
     
+    /// 超过执行时限的失败结果
+    pub fn timed_out(job_id: JobId, timeout: Duration) -> Self {
+        let error = IrError::Timeout(format!("Job {} did not finish within {:?}", job_id, timeout));
+        let mut result = Self::failure(job_id, error.to_string());
+        result.timed_out = true;
+        result
+    }
+    
+    /// 汇总批量作业结果：任一扫描点失败则整体失败
+    pub fn batch(job_id: JobId, points: Vec<JobResult>) -> Self {
+        let failed: Vec<usize> = points
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.status != JobStatus::Completed)
+            .map(|(i, _)| i)
+            .collect();
+        let mut result = if failed.is_empty() {
+            Self::success(job_id)
+        } else {
+            Self::failure(job_id, format!("{} of {} sweep points failed: {:?}", failed.len(), points.len(), failed))
+        };
+        result.execution_time_ms = points.iter().map(|p| p.execution_time_ms).sum();
+        result.points = points;
+        result
+    }
+    
+    /// 重试次数（不含第一次尝试）
+    pub fn retries(&self) -> usize {
+        self.attempts.len().saturating_sub(1)
+    }
+    
+    /// 最后一次尝试所用的后端
+    pub fn final_backend(&self) -> Option<&str> {
+        self.attempts.last().map(|a| a.backend.as_str())
+    }
+    
+    pub fn is_batch(&self) -> bool {
+        !self.points.is_empty()
+    }
+    
+    /// 第 index 个扫描点的结果
+    pub fn point(&self, index: usize) -> Option<&JobResult> {
+        self.points.get(index)
+    }
+    
     pub fn add_counts(&mut self, qubit: LogicalQubitId, counts: Vec<u8>) {
         let ones = counts.iter().filter(|&&x| x == 1).count() as f64;
         let total = counts.len() as f64;
@@ -210,6 +325,18 @@ pub struct Job {
     pub depends_on: Vec<JobId>,
     /// 占用的 qubit 资源
     pub allocated_qubits: Vec<LogicalQubitId>,
+    /// 符号参数的取值（执行前绑定到电路）
+    pub parameters: Option<ParamBinding>,
+    /// 参数扫描点：每个取值绑定到 `circuit` 得到一个执行点
+    pub sweep: Vec<ParamBinding>,
+    /// 同批执行的附加电路
+    pub batch_circuits: Vec<CircuitDag>,
+    /// 截止时刻：晚于此时结束的作业没有价值
+    pub deadline: Option<Instant>,
+    /// 估计运行时间，用于截止时刻的准入检查
+    pub estimated_runtime: Option<Duration>,
+    /// 重试策略；未设置时使用 `RuntimeConfig::retry`
+    pub retry: Option<RetryPolicy>,
 }
 
 impl Job {
@@ -229,6 +356,12 @@ impl Job {
             completed_at: None,
             depends_on: Vec::new(),
             allocated_qubits,
+            parameters: None,
+            sweep: Vec::new(),
+            batch_circuits: Vec::new(),
+            deadline: None,
+            estimated_runtime: None,
+            retry: None,
         }
     }
     
@@ -242,12 +375,119 @@ impl Job {
         self
     }
     
+    /// 覆盖运行时配置的执行超时
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.metadata.timeout = Some(timeout);
+        self
+    }
+    
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+    
+    /// 以当前时刻起的时长设置截止时刻
+    pub fn with_deadline_in(self, within: Duration) -> Self {
+        self.with_deadline(Instant::now() + within)
+    }
+    
+    pub fn with_estimated_runtime(mut self, runtime: Duration) -> Self {
+        self.estimated_runtime = Some(runtime);
+        self
+    }
+    
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+    
     pub fn with_dependency(mut self, job_id: JobId) -> Self {
         self.depends_on.push(job_id);
         self
     }
     
-    pub fn set_status(&mut self, status: JobStatus) {
+    pub fn with_parameters(mut self, parameters: ParamBinding) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+    
+    /// 添加参数扫描点
+    pub fn with_sweep(mut self, points: impl IntoIterator<Item = ParamBinding>) -> Self {
+        self.sweep.extend(points);
+        self
+    }
+    
+    /// 添加同批执行的电路
+    pub fn with_circuits(mut self, circuits: impl IntoIterator<Item = CircuitDag>) -> Self {
+        for circuit in circuits {
+            for qubit in circuit.all_qubits() {
+                if !self.allocated_qubits.contains(&qubit) {
+                    self.allocated_qubits.push(qubit);
+                }
+            }
+            self.batch_circuits.push(circuit);
+        }
+        self
+    }
+    
+    /// 是否为批量作业
+    pub fn is_batch(&self) -> bool {
+        !self.sweep.is_empty() || !self.batch_circuits.is_empty()
+    }
+    
+    /// 批量作业的执行点数（普通作业为 1）
+    pub fn num_points(&self) -> usize {
+        self.sweep.len().max(1) + self.batch_circuits.len()
+    }
+    
+    /// 绑定参数后的可执行电路；仍有未绑定参数时返回错误
+    pub fn resolved_circuit(&self) -> Result<CircuitDag> {
+        self.resolve(&self.circuit, None)
+    }
+    
+    /// 各执行点的电路
+    /// 
+    /// 顺序为：`circuit` 依次绑定每个扫描点（无扫描点时执行一次），随后是附加电路。
+    /// 扫描点的取值覆盖 `parameters` 中的同名参数。
+    pub fn point_circuits(&self) -> Result<Vec<CircuitDag>> {
+        let mut circuits = Vec::with_capacity(self.num_points());
+        if self.sweep.is_empty() {
+            circuits.push(self.resolved_circuit()?);
+        } else {
+            for point in &self.sweep {
+                circuits.push(self.resolve(&self.circuit, Some(point))?);
+            }
+        }
+        for circuit in &self.batch_circuits {
+            circuits.push(self.resolve(circuit, None)?);
+        }
+        Ok(circuits)
+    }
+    
+    fn resolve(&self, circuit: &CircuitDag, point: Option<&ParamBinding>) -> Result<CircuitDag> {
+        let mut binding = self.parameters.clone().unwrap_or_default();
+        if let Some(point) = point {
+            binding.extend(point.iter().map(|(p, &v)| (p.clone(), v)));
+        }
+        let circuit = if circuit.is_parameterized() { circuit.bind(&binding)? } else { circuit.clone() };
+        let unbound = circuit.parameters();
+        if !unbound.is_empty() {
+            return Err(IrError::InvalidOperation(format!(
+                "Job {} has unbound parameters: {:?}",
+                self.id,
+                unbound.iter().map(|p| p.name()).collect::<Vec<_>>()
+            )));
+        }
+        Ok(circuit)
+    }
+    
+    /// 转换作业状态，非法转换返回 `InvalidStateTransition`
+    pub fn set_status(&mut self, status: JobStatus) -> Result<()> {
+        if !self.status.can_transition_to(status) {
+            return Err(IrError::InvalidStateTransition(format!(
+                "Job {} cannot move from {:?} to {:?}", self.id, self.status, status
+            )));
+        }
         let now = current_timestamp();
         self.status = status;
         
@@ -259,14 +499,11 @@ impl Job {
             }
             _ => {}
         }
+        Ok(())
     }
     
     pub fn cancel(&mut self) -> bool {
-        if self.status.is_terminal() {
-            return false;
-        }
-        self.set_status(JobStatus::Cancelled);
-        true
+        self.set_status(JobStatus::Cancelled).is_ok()
     }
     
     pub fn execution_duration(&self) -> Option<u64> {
@@ -277,8 +514,12 @@ impl Job {
     }
     
     fn generate_id() -> JobId {
-        static COUNTER: AtomicU64 = AtomicU64::new(1);
-        COUNTER.fetch_add(1, Ordering::SeqCst)
+        NEXT_JOB_ID.fetch_add(1, Ordering::SeqCst)
+    }
+    
+    /// 保证之后生成的 ID 大于 job_id（从存储恢复作业后调用）
+    pub(crate) fn reserve_ids_through(job_id: JobId) {
+        NEXT_JOB_ID.fetch_max(job_id + 1, Ordering::SeqCst);
     }
 }
 
@@ -346,25 +587,86 @@ impl JobQueue {
     }
 }
 
+// ============================================================================
+// Clock
+// ============================================================================
+
+/// 调度器读取当前时刻的时钟
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    fn now(&self) -> Instant;
+}
+
+/// 系统时钟（默认）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// 手动推进的时钟，克隆之间共享同一时刻
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    /// 从当前系统时刻开始
+    pub fn new() -> Self {
+        Self { now: Arc::new(Mutex::new(Instant::now())) }
+    }
+
+    /// 时钟前进 by
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+}
+
 // ============================================================================
 // Job Scheduler
 // ============================================================================
 
 /// 作业调度器
 /// 
-/// 负责管理作业队列、资源分配和调度决策
+/// 负责管理作业队列、资源分配和调度决策；在可调度作业之间的选择由 `SchedulingPolicy` 决定
 #[derive(Debug)]
 pub struct JobScheduler {
     /// 等待队列
     queue: JobQueue,
     /// 运行中的作业
     running: std::collections::HashMap<JobId, Job>,
+    /// 运行中作业的开始时刻
+    started: std::collections::HashMap<JobId, Instant>,
+    /// 等待重试的作业及其最早可再次调度的时刻
+    retry_at: std::collections::HashMap<JobId, Instant>,
     /// 已完成的作业
     completed: std::collections::HashMap<JobId, JobResult>,
+    /// 自上次 `take_finished` 以来结束的作业
+    finished: Vec<JobId>,
     /// 可用的 qubit 资源
     available_qubits: std::collections::HashSet<LogicalQubitId>,
     /// 最大并发作业数
     max_concurrent_jobs: usize,
+    /// 调度策略
+    policy: Box<dyn SchedulingPolicy>,
+    /// 用户与项目配额
+    quotas: QuotaManager,
+    /// 截止时刻、执行时长与配额窗口使用的时钟
+    clock: Box<dyn Clock>,
     /// 调度统计
     stats: SchedulerStats,
 }
@@ -376,6 +678,20 @@ pub struct SchedulerStats {
     pub total_failed: u64,
     pub total_cancelled: u64,
     pub current_queue_depth: u64,
+    /// 在截止时刻前完成的作业数
+    pub deadlines_met: u64,
+    /// 错过截止时刻的作业数（含排队期间过期与执行失败）
+    pub deadlines_missed: u64,
+    /// 因无法满足截止时刻而被拒绝提交的作业数
+    pub admission_rejections: u64,
+}
+
+impl SchedulerStats {
+    /// 带截止时刻的已结束作业中错过截止时刻的比例
+    pub fn deadline_miss_rate(&self) -> Option<f64> {
+        let total = self.deadlines_met + self.deadlines_missed;
+        (total > 0).then(|| self.deadlines_missed as f64 / total as f64)
+    }
 }
 
 impl JobScheduler {
@@ -383,55 +699,200 @@ impl JobScheduler {
         Self {
             queue: JobQueue::new(),
             running: std::collections::HashMap::new(),
+            started: std::collections::HashMap::new(),
+            retry_at: std::collections::HashMap::new(),
             completed: std::collections::HashMap::new(),
+            finished: Vec::new(),
             available_qubits: std::collections::HashSet::new(),
             max_concurrent_jobs,
+            policy: Box::new(StrictPriority::new()),
+            quotas: QuotaManager::new(),
+            clock: Box::new(SystemClock),
             stats: SchedulerStats::default(),
         }
     }
     
+    /// 设置时钟（默认系统时钟）
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+    
+    /// 调度器时钟的当前时刻
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+    
+    /// 设置调度策略（默认严格优先级）
+    pub fn with_policy(mut self, policy: impl SchedulingPolicy + 'static) -> Self {
+        self.policy = Box::new(policy);
+        self
+    }
+    
+    /// 当前的调度策略
+    pub fn policy(&self) -> &dyn SchedulingPolicy {
+        self.policy.as_ref()
+    }
+    
+    /// 设置用户与项目配额
+    pub fn with_quotas(mut self, quotas: QuotaManager) -> Self {
+        self.quotas = quotas;
+        self
+    }
+    
+    /// 配额与使用情况
+    pub fn quotas(&self) -> &QuotaManager {
+        &self.quotas
+    }
+    
     /// 初始化可用 qubit
     pub fn with_qubits(mut self, qubits: Vec<LogicalQubitId>) -> Self {
         self.available_qubits = qubits.into_iter().collect();
         self
     }
     
-    /// 提交作业
-    pub fn submit(&mut self, mut job: Job) -> JobId {
+    /// 提交作业（必须处于 Pending）
+    /// 
+    /// 带截止时刻的作业先做准入检查：最早结束时刻已晚于截止时刻时以 `SchedulingConflict` 拒绝；
+    /// 随后检查配额，超出时按 `OverQuota` 拒绝（`QuotaExceeded`）或接受后保持 Waiting。
+    pub fn submit(&mut self, mut job: Job) -> Result<JobId> {
         let job_id = job.id;
-        job.set_status(JobStatus::Queued);
+        if self.contains(job_id) {
+            return Err(IrError::InvalidStateTransition(format!("Job {} is already submitted", job_id)));
+        }
+        let now = self.clock.now();
+        if let Some(deadline) = job.deadline {
+            let finish = self.earliest_finish(&job, now, &mut Vec::new());
+            if finish > deadline {
+                self.stats.admission_rejections += 1;
+                return Err(IrError::SchedulingConflict(format!(
+                    "Job {} cannot meet its deadline: earliest finish is {:?} after it",
+                    job_id, finish - deadline
+                )));
+            }
+        }
+        self.quotas.admit(&job, now)?;
+        job.set_status(JobStatus::Queued)?;
         self.stats.total_submitted += 1;
         self.queue.push(job);
+        self.refresh_dependencies();
+        self.stats.current_queue_depth = self.queue.len() as u64;
+        Ok(job_id)
+    }
+    
+    /// 作业最早可能结束的时刻（下界）
+    /// 
+    /// 作业须等待依赖链结束，并等待它的每个 qubit 依次被运行中的作业和队列中排在它前面、
+    /// 已可调度的作业用完，再运行自身的估计时间；
+    /// 未给出估计运行时间的作业按 0 计，因此只拒绝确定无法按时完成的作业。
+    fn earliest_finish(&self, job: &Job, now: Instant, visiting: &mut Vec<JobId>) -> Instant {
+        visiting.push(job.id);
+        let mut start = now;
+        for dep in &job.depends_on {
+            if visiting.contains(dep) {
+                continue;
+            }
+            if let Some(running) = self.running.get(dep) {
+                start = start.max(self.running_finish(running, now));
+            } else if let Some(queued) = self.queue.find(*dep) {
+                start = start.max(self.earliest_finish(queued, now, visiting));
+            }
+        }
+        let ahead = self.queued_ahead(job);
+        for q in &job.allocated_qubits {
+            let mut free = now;
+            for running in self.running.values().filter(|r| r.allocated_qubits.contains(q)) {
+                free = free.max(self.running_finish(running, now));
+            }
+            for queued in ahead.iter().filter(|a| a.allocated_qubits.contains(q)) {
+                free += queued.estimated_runtime.unwrap_or_default();
+            }
+            start = start.max(free);
+        }
+        visiting.pop();
+        start + job.estimated_runtime.unwrap_or_default()
+    }
+    
+    /// 队列中排在作业之前且已可调度的作业
+    /// 
+    /// 队列按优先级排序、同优先级先到先得；尚未入队的作业排在所有不低于其优先级的作业之后。
+    fn queued_ahead(&self, job: &Job) -> Vec<&Job> {
+        let ahead: Vec<&Job> = match self.queue.iter().position(|j| j.id == job.id) {
+            Some(pos) => self.queue.iter().take(pos).collect(),
+            None => self.queue.iter().take_while(|j| j.priority >= job.priority).collect(),
+        };
+        ahead.into_iter().filter(|j| j.status == JobStatus::Queued).collect()
+    }
+    
+    /// 运行中作业的预计结束时刻
+    fn running_finish(&self, job: &Job, now: Instant) -> Instant {
+        let started = self.started.get(&job.id).copied().unwrap_or(now);
+        (started + job.estimated_runtime.unwrap_or_default()).max(now)
+    }
+    
+    /// 排队期间已过截止时刻的作业直接失败，不再占用资源
+    fn expire_overdue(&mut self) {
+        let now = self.clock.now();
+        let overdue: Vec<JobId> = self
+            .queue
+            .iter()
+            .filter(|job| job.deadline.is_some_and(|deadline| deadline < now))
+            .map(|job| job.id)
+            .collect();
+        for job_id in overdue {
+            let mut job = self.queue.remove(job_id).expect("overdue job is queued");
+            job.set_status(JobStatus::Failed).expect("queued job can fail");
+            self.policy.on_removed(job_id);
+            self.retry_at.remove(&job_id);
+            self.stats.total_failed += 1;
+            self.stats.deadlines_missed += 1;
+            self.completed.insert(job_id, JobResult::failure(job_id, "Deadline passed before the job could start"));
+            self.finished.push(job_id);
+        }
         self.stats.current_queue_depth = self.queue.len() as u64;
-        job_id
+    }
+    
+    /// 调度器是否持有该作业（任意状态）
+    fn contains(&self, job_id: JobId) -> bool {
+        self.queue.find(job_id).is_some() || self.running.contains_key(&job_id) || self.completed.contains_key(&job_id)
     }
     
     /// 调度下一个可执行的作业
+    /// 
+    /// 作业转为 Ready、占用资源并进入运行集合；返回其副本供执行方使用。
     pub fn schedule_next(&mut self) -> Option<Job> {
+        self.schedule_next_where(|_| true)
+    }
+    
+    /// 只在满足 filter 的作业中调度下一个，其余作业留在队列中
+    pub fn schedule_next_where(&mut self, filter: impl Fn(&Job) -> bool) -> Option<Job> {
         if self.running.len() >= self.max_concurrent_jobs {
             return None;
         }
+        self.expire_overdue();
+        self.refresh_dependencies();
         
-        // 查找第一个资源可用的作业
-        let mut candidates: Vec<usize> = Vec::new();
-        for (i, job) in self.queue.iter().enumerate() {
-            if self.can_schedule(job) {
-                candidates.push(i);
-            }
-        }
-        
+        // 资源可用的作业交由策略选择
+        let (indices, candidates): (Vec<usize>, Vec<&Job>) = self
+            .queue
+            .iter()
+            .enumerate()
+            .filter(|(_, job)| filter(job) && self.can_schedule(job))
+            .unzip();
         if candidates.is_empty() {
             return None;
         }
-        
-        // 选择优先级最高的
-        let best_idx = candidates
-            .into_iter()
-            .max_by_key(|&i| self.queue.iter().nth(i).unwrap().priority)
-            .unwrap();
+        let chosen = self.policy.select(&candidates)?;
+        let best_idx = *indices.get(chosen)?;
         
         let mut job = self.queue.jobs.remove(best_idx).unwrap();
-        job.set_status(JobStatus::Ready);
+        job.set_status(JobStatus::Ready).expect("queued job can become ready");
+        self.policy.on_scheduled(&job);
+        if self.retry_at.remove(&job.id).is_some() {
+            self.quotas.on_resume(&job);
+        } else {
+            self.quotas.on_start(&job, self.clock.now());
+        }
         
         // 分配资源
         for &q in &job.allocated_qubits {
@@ -439,78 +900,208 @@ impl JobScheduler {
         }
         
         self.stats.current_queue_depth = self.queue.len() as u64;
+        self.running.insert(job.id, job.clone());
         Some(job)
     }
     
     /// 检查作业是否可以调度
     fn can_schedule(&self, job: &Job) -> bool {
-        // 检查依赖
-        for &dep_id in &job.depends_on {
-            if !self.completed.contains_key(&dep_id) {
-                return false;
-            }
+        if job.status != JobStatus::Queued {
+            return false;
+        }
+        if self.retry_at.get(&job.id).is_some_and(|&at| at > self.clock.now()) {
+            return false;
         }
         
         // 检查资源
         job.allocated_qubits.iter().all(|q| self.available_qubits.contains(q))
     }
     
-    /// 开始执行作业
-    pub fn start_execution(&mut self, job_id: JobId) -> Option<&mut Job> {
-        if let Some(job) = self.queue.find_mut(job_id) {
-            job.set_status(JobStatus::Running);
-            return None;  // 作业还在队列中
+    /// 按依赖的完成情况更新排队作业
+    /// 
+    /// 依赖全部成功且配额允许的作业为 Queued，尚有依赖未结束或超出配额的为 Waiting；
+    /// 任一依赖失败或取消时作业直接失败（并可能继续连锁到它的下游），
+    /// 配额永远无法满足的作业同样直接失败。
+    fn refresh_dependencies(&mut self) {
+        let now = self.clock.now();
+        loop {
+            let mut failed = Vec::new();
+            for job in self.queue.iter_mut() {
+                let mut blocked = false;
+                let mut reason = None;
+                for dep_id in &job.depends_on {
+                    match self.completed.get(dep_id).map(|r| r.status) {
+                        Some(JobStatus::Completed) => {}
+                        Some(status) => {
+                            reason = Some(format!("Dependency {} ended as {:?}", dep_id, status));
+                            break;
+                        }
+                        None => blocked = true,
+                    }
+                }
+                if let Some(reason) = reason.or_else(|| self.quotas.never_admissible(job)) {
+                    failed.push((job.id, reason));
+                    continue;
+                }
+                let held = !blocked && self.quotas.check(job, now).is_err();
+                let next = if blocked || held { JobStatus::Waiting } else { JobStatus::Queued };
+                if job.status != next {
+                    job.set_status(next).expect("queued and waiting are interchangeable");
+                }
+            }
+            if failed.is_empty() {
+                return;
+            }
+            for (job_id, reason) in failed {
+                let mut job = self.queue.remove(job_id).expect("failed job is queued");
+                self.policy.on_removed(job_id);
+                self.retry_at.remove(&job_id);
+                job.set_status(JobStatus::Failed).expect("queued job can fail");
+                self.stats.total_failed += 1;
+                self.completed.insert(job_id, JobResult::failure(job_id, reason));
+                self.finished.push(job_id);
+            }
+            self.stats.current_queue_depth = self.queue.len() as u64;
+        }
+    }
+    
+    /// 不经队列立即执行的作业开始：检查配额并记账，返回开始时刻
+    /// 
+    /// 作业无法等待，超出配额时总是以 `QuotaExceeded` 拒绝。
+    pub(crate) fn start_immediate(&mut self, job: &Job) -> Result<Instant> {
+        let now = self.clock.now();
+        self.quotas.admit_now(job, now)?;
+        self.quotas.on_start(job, now);
+        Ok(now)
+    }
+    
+    /// 不经队列立即执行的作业结束：按执行时间计入配额
+    pub(crate) fn finish_immediate(&mut self, job: &Job, started: Instant) {
+        let elapsed = self.clock.now().saturating_duration_since(started);
+        self.quotas.on_finish(job, elapsed);
+    }
+    
+    /// 开始执行已调度（Ready）的作业
+    pub fn start_execution(&mut self, job_id: JobId) -> Result<&mut Job> {
+        if !self.running.contains_key(&job_id) {
+            return Err(self.not_running(job_id, JobStatus::Running));
+        }
+        let job = self.running.get_mut(&job_id).expect("job is running");
+        job.set_status(JobStatus::Running)?;
+        self.started.insert(job_id, self.clock.now());
+        Ok(job)
+    }
+    
+    /// 标记运行中的作业结束，释放资源并记录结果
+    pub fn complete(&mut self, job_id: JobId, result: JobResult) -> Result<()> {
+        if !result.status.is_terminal() {
+            return Err(IrError::InvalidStateTransition(format!(
+                "Job {} cannot complete with non-terminal status {:?}", job_id, result.status
+            )));
+        }
+        let Some(job) = self.running.get_mut(&job_id) else {
+            return Err(self.not_running(job_id, result.status));
+        };
+        job.set_status(result.status)?;
+        let job = self.running.remove(&job_id).expect("job is running");
+        self.release(&job);
+        if let Some(deadline) = job.deadline {
+            if result.status == JobStatus::Completed && self.clock.now() <= deadline {
+                self.stats.deadlines_met += 1;
+            } else if result.status != JobStatus::Cancelled {
+                self.stats.deadlines_missed += 1;
+            }
         }
         
-        if let Some(job) = self.running.get_mut(&job_id) {
-            job.set_status(JobStatus::Running);
-            return Some(job);
+        match result.status {
+            JobStatus::Completed => self.stats.total_completed += 1,
+            JobStatus::Cancelled => self.stats.total_cancelled += 1,
+            _ => self.stats.total_failed += 1,
         }
         
-        None
+        self.completed.insert(job_id, result);
+        self.finished.push(job_id);
+        self.refresh_dependencies();
+        Ok(())
     }
     
-    /// 标记作业完成
-    pub fn complete(&mut self, job_id: JobId, result: JobResult) {
-        if let Some(job) = self.running.remove(&job_id) {
-            // 释放资源
-            for &q in &job.allocated_qubits {
-                self.available_qubits.insert(q);
-            }
-            
-            if result.status == JobStatus::Completed {
-                self.stats.total_completed += 1;
-            } else {
-                self.stats.total_failed += 1;
-            }
-            
-            self.completed.insert(job_id, result);
+    /// 运行中的作业需要重试：释放资源并回到队列，到 not_before 之前不会再被调度
+    /// 
+    /// 重试期间作业保持 Queued，依赖它的作业继续等待；再次调度时不重复计入配额的作业数与 shots。
+    pub fn requeue(&mut self, job_id: JobId, not_before: Instant) -> Result<()> {
+        let Some(job) = self.running.get_mut(&job_id) else {
+            return Err(self.not_running(job_id, JobStatus::Queued));
+        };
+        let submitted_at = job.submitted_at;
+        job.set_status(JobStatus::Queued)?;
+        job.submitted_at = submitted_at;
+        let job = self.running.remove(&job_id).expect("job is running");
+        self.release(&job);
+        self.retry_at.insert(job_id, not_before);
+        self.queue.push(job);
+        self.refresh_dependencies();
+        self.stats.current_queue_depth = self.queue.len() as u64;
+        Ok(())
+    }
+    
+    /// 满足 filter 的作业中最早到期的重试时刻（只含尚未到期的）
+    pub fn next_retry_where(&self, filter: impl Fn(&Job) -> bool) -> Option<Instant> {
+        let now = self.clock.now();
+        self.queue
+            .iter()
+            .filter(|job| filter(job))
+            .filter_map(|job| self.retry_at.get(&job.id).copied())
+            .filter(|&at| at > now)
+            .min()
+    }
+    
+    /// 运行中的作业离开：释放 qubit 并按执行时间记入配额
+    fn release(&mut self, job: &Job) {
+        let now = self.clock.now();
+        let elapsed = self.started.remove(&job.id).map(|t| now.saturating_duration_since(t)).unwrap_or_default();
+        self.quotas.on_finish(job, elapsed);
+        for &q in &job.allocated_qubits {
+            self.available_qubits.insert(q);
         }
     }
     
-    /// 取消作业
-    pub fn cancel(&mut self, job_id: JobId) -> bool {
-        // 从队列中取消
-        if let Some(job) = self.queue.remove(job_id) {
-            let mut job = job;
-            job.cancel();
-            self.stats.total_cancelled += 1;
-            return true;
+    /// 作业不在运行集合中时的错误
+    fn not_running(&self, job_id: JobId, to: JobStatus) -> IrError {
+        match self.get_status(job_id) {
+            Some(from) => IrError::InvalidStateTransition(format!(
+                "Job {} cannot move from {:?} to {:?}", job_id, from, to
+            )),
+            None => IrError::InvalidStateTransition(format!("Job {} is not known to the scheduler", job_id)),
         }
-        
-        // 从运行中取消
-        if let Some(mut job) = self.running.remove(&job_id) {
-            job.cancel();
-            self.stats.total_cancelled += 1;
-            
-            // 释放资源
-            for &q in &job.allocated_qubits {
-                self.available_qubits.insert(q);
+    }
+    
+    /// 取消作业（排队、等待或运行中均可），返回是否取消成功
+    pub fn cancel(&mut self, job_id: JobId) -> bool {
+        let job = match self.queue.remove(job_id) {
+            Some(job) => {
+                self.policy.on_removed(job_id);
+                self.retry_at.remove(&job_id);
+                self.stats.current_queue_depth = self.queue.len() as u64;
+                job
             }
-            return true;
-        }
+            None => match self.running.remove(&job_id) {
+                Some(job) => {
+                    self.release(&job);
+                    job
+                }
+                None => return false,
+            },
+        };
         
-        false
+        let mut job = job;
+        job.cancel();
+        self.stats.total_cancelled += 1;
+        let mut result = JobResult::failure(job_id, "Cancelled");
+        result.status = JobStatus::Cancelled;
+        self.completed.insert(job_id, result);
+        self.finished.push(job_id);
+        self.refresh_dependencies();
+        true
     }
     
     /// 获取作业状态
@@ -532,6 +1123,27 @@ impl JobScheduler {
         self.completed.get(&job_id)
     }
     
+    /// 取出自上次调用以来结束的作业（完成、失败、取消或过期），按结束顺序
+    pub fn take_finished(&mut self) -> Vec<JobId> {
+        std::mem::take(&mut self.finished)
+    }
+    
+    /// 登记重启前已结束作业的结果
+    /// 
+    /// 结果只供查询和依赖判断使用，不计入统计，也不出现在 `take_finished` 中。
+    pub fn restore_result(&mut self, job_id: JobId, result: JobResult) -> Result<()> {
+        if !result.status.is_terminal() {
+            return Err(IrError::InvalidStateTransition(format!(
+                "Job {} cannot be restored with non-terminal status {:?}", job_id, result.status
+            )));
+        }
+        if self.contains(job_id) {
+            return Err(IrError::InvalidStateTransition(format!("Job {} is already known", job_id)));
+        }
+        self.completed.insert(job_id, result);
+        Ok(())
+    }
+    
     /// 获取运行中的作业
     pub fn running_jobs(&self) -> Vec<&Job> {
         self.running.values().collect()
@@ -619,7 +1231,7 @@ mod tests {
         let mut scheduler = JobScheduler::new(4);
         let circuit = bell_state_dag();
         
-        let job_id = scheduler.submit(Job::new(circuit, 100, "simulator"));
+        let job_id = scheduler.submit(Job::new(circuit, 100, "simulator")).unwrap();
         
         assert!(job_id > 0);
         assert_eq!(scheduler.queue_length(), 1);
@@ -635,13 +1247,134 @@ mod tests {
             ]);
         
         let circuit = bell_state_dag();
-        let job_id = scheduler.submit(Job::new(circuit, 100, "simulator"));
+        let job_id = scheduler.submit(Job::new(circuit, 100, "simulator")).unwrap();
         
         let scheduled = scheduler.schedule_next();
         assert!(scheduled.is_some());
         assert_eq!(scheduled.unwrap().id, job_id);
     }
 
+    #[test]
+    fn test_status_transitions() {
+        let mut job = Job::new(bell_state_dag(), 100, "simulator");
+        assert!(job.set_status(JobStatus::Running).is_err());
+        assert_eq!(job.status, JobStatus::Pending);
+        for status in [JobStatus::Queued, JobStatus::Ready, JobStatus::Running, JobStatus::Completed] {
+            job.set_status(status).unwrap();
+        }
+        assert!(job.completed_at.is_some());
+        // 终止状态不能再转换
+        assert!(job.set_status(JobStatus::Queued).is_err());
+        assert!(!job.cancel());
+    }
+
+    #[test]
+    fn test_scheduler_lifecycle() {
+        let q0 = LogicalQubitId::new(0);
+        let q1 = LogicalQubitId::new(1);
+        let mut scheduler = JobScheduler::new(4).with_qubits(vec![q0, q1]);
+        let first = scheduler.submit(Job::new(bell_state_dag(), 100, "simulator")).unwrap();
+        let second = scheduler
+            .submit(Job::new(bell_state_dag(), 100, "simulator").with_dependency(first))
+            .unwrap();
+        assert_eq!(scheduler.get_status(first), Some(JobStatus::Queued));
+        assert_eq!(scheduler.get_status(second), Some(JobStatus::Waiting));
+
+        // 调度后作业进入运行集合并占用 qubit
+        assert_eq!(scheduler.schedule_next().unwrap().id, first);
+        assert_eq!(scheduler.get_status(first), Some(JobStatus::Ready));
+        assert_eq!(scheduler.running_jobs().len(), 1);
+        assert!(scheduler.schedule_next().is_none());
+        assert!(scheduler.complete(first, JobResult::success(first)).is_err());
+
+        scheduler.start_execution(first).unwrap();
+        assert_eq!(scheduler.get_status(first), Some(JobStatus::Running));
+        assert!(scheduler.start_execution(first).is_err());
+        scheduler.complete(first, JobResult::success(first)).unwrap();
+        assert_eq!(scheduler.get_status(first), Some(JobStatus::Completed));
+        assert!(scheduler.get_result(first).is_some());
+        assert_eq!(scheduler.stats().total_completed, 1);
+
+        // 依赖完成后释放下游作业，qubit 也已归还
+        assert_eq!(scheduler.get_status(second), Some(JobStatus::Queued));
+        assert_eq!(scheduler.schedule_next().unwrap().id, second);
+        assert!(scheduler.complete(second, JobResult::success(second)).is_err());
+        assert!(scheduler.submit(Job::new(bell_state_dag(), 1, "simulator")).is_ok());
+    }
+
+    #[test]
+    fn test_scheduler_failure_propagation() {
+        let mut scheduler = JobScheduler::new(4).with_qubits(vec![LogicalQubitId::new(0), LogicalQubitId::new(1)]);
+        let root = scheduler.submit(Job::new(bell_state_dag(), 100, "simulator")).unwrap();
+        let child = scheduler
+            .submit(Job::new(bell_state_dag(), 100, "simulator").with_dependency(root))
+            .unwrap();
+        let grandchild = scheduler
+            .submit(Job::new(bell_state_dag(), 100, "simulator").with_dependency(child))
+            .unwrap();
+
+        scheduler.schedule_next().unwrap();
+        scheduler.start_execution(root).unwrap();
+        scheduler.complete(root, JobResult::failure(root, "boom")).unwrap();
+        assert_eq!(scheduler.get_status(child), Some(JobStatus::Failed));
+        assert_eq!(scheduler.get_status(grandchild), Some(JobStatus::Failed));
+        assert_eq!(scheduler.queue_length(), 0);
+        assert_eq!(scheduler.stats().total_failed, 3);
+
+        // 取消运行中的作业会归还资源，下游同样失败
+        let running = scheduler.submit(Job::new(bell_state_dag(), 100, "simulator")).unwrap();
+        let dependent = scheduler
+            .submit(Job::new(bell_state_dag(), 100, "simulator").with_dependency(running))
+            .unwrap();
+        scheduler.schedule_next().unwrap();
+        assert!(scheduler.cancel(running));
+        assert!(!scheduler.cancel(running));
+        assert_eq!(scheduler.get_status(running), Some(JobStatus::Cancelled));
+        assert_eq!(scheduler.get_status(dependent), Some(JobStatus::Failed));
+        assert!(scheduler.submit(Job::new(bell_state_dag(), 100, "simulator")).is_ok());
+        assert!(scheduler.schedule_next().is_some());
+    }
+
+    #[test]
+    fn test_job_parameters() {
+        use crate::params::{ry_param, Param};
+
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(ry_param(LogicalQubitId::new(0), Param::new("theta")));
+        let job = Job::new(circuit, 100, "simulator");
+        assert!(job.resolved_circuit().is_err());
+
+        let job = job.with_parameters([(Param::new("theta"), 0.5)].into_iter().collect());
+        let resolved = job.resolved_circuit().unwrap();
+        assert!(!resolved.is_parameterized());
+        assert_eq!(resolved.get_node(0).unwrap().op.parameters(), vec![0.5]);
+    }
+
+    #[test]
+    fn test_job_sweep() {
+        use crate::params::{rz_param, Param};
+
+        let theta = Param::new("theta");
+        let phi = Param::new("phi");
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(rz_param(LogicalQubitId::new(0), theta.expr() + phi.expr()));
+        let job = Job::new(circuit, 10, "simulator")
+            .with_parameters([(phi.clone(), 1.0)].into_iter().collect())
+            .with_sweep([0.0, 0.5].map(|v| [(theta.clone(), v)].into_iter().collect()))
+            .with_circuits(vec![bell_state_dag()]);
+        assert!(job.is_batch());
+
+        let circuits = job.point_circuits().unwrap();
+        assert_eq!(circuits.len(), 3);
+        assert_eq!(circuits[0].get_node(0).unwrap().op.parameters(), vec![1.0]);
+        assert_eq!(circuits[1].get_node(0).unwrap().op.parameters(), vec![1.5]);
+
+        let points = vec![JobResult::success(job.id), JobResult::failure(job.id, "boom")];
+        let result = JobResult::batch(job.id, points);
+        assert_eq!(result.status, JobStatus::Failed);
+        assert!(result.is_batch());
+    }
+
     #[test]
     fn test_job_result() {
         let mut result = JobResult::success(1);
@@ -660,13 +1393,147 @@ mod tests {
         // 提交一个占用 qubit 0 和 1 的作业（bell 态需要 2 个 qubit）
         let circuit = bell_state_dag();
         let job = Job::new(circuit, 100, "simulator");
-        let job_id = scheduler.submit(job);
+        let job_id = scheduler.submit(job).unwrap();
 
-        // 调度（返回 Job 但不自动加入 running）
-        let scheduled = scheduler.schedule_next();
-        assert!(scheduled.is_some());
+        // 调度后作业进入运行集合
+        let scheduled = scheduler.schedule_next().unwrap();
+        assert_eq!(scheduled.id, job_id);
+        let running: Vec<JobId> = scheduler.running_jobs().iter().map(|job| job.id).collect();
+        assert_eq!(running, vec![job_id]);
         
-        // 验证作业已从队列移除
+        // 验证作业已从队列移除，qubit 被占用
         assert_eq!(scheduler.queue_length(), 0);
+        scheduler.submit(Job::new(bell_state_dag(), 100, "simulator")).unwrap();
+        assert!(scheduler.schedule_next().is_none());
+    }
+
+    #[test]
+    fn test_deadline_admission() {
+        let q = |i| LogicalQubitId::new(i);
+        let mut scheduler = JobScheduler::new(4).with_qubits(vec![q(0), q(1), q(2)]);
+        let secs = Duration::from_secs;
+
+        // 自身运行时间就超过截止时刻
+        let too_long = Job::new(bell_state_dag(), 10, "sim").with_estimated_runtime(secs(20)).with_deadline_in(secs(10));
+        assert!(matches!(scheduler.submit(too_long), Err(IrError::SchedulingConflict(_))));
+
+        // 占用同一 qubit 的运行中作业与依赖链都计入最早结束时刻
+        let running = scheduler.submit(Job::new(bell_state_dag(), 10, "sim").with_estimated_runtime(secs(30))).unwrap();
+        scheduler.schedule_next().unwrap();
+        scheduler.start_execution(running).unwrap();
+        let blocked = Job::new(bell_state_dag(), 10, "sim").with_estimated_runtime(secs(1)).with_deadline_in(secs(10));
+        assert!(scheduler.submit(blocked).is_err());
+
+        let mut other = CircuitDag::new();
+        other.add_node(crate::operation::h(q(2)));
+        let upstream = scheduler.submit(Job::new(other.clone(), 10, "sim").with_dependency(running).with_estimated_runtime(secs(5))).unwrap();
+        let chained = Job::new(other.clone(), 10, "sim").with_dependency(upstream).with_deadline_in(secs(20));
+        assert!(scheduler.submit(chained).is_err());
+
+        // 运行时间未知或时间充足的作业照常接受
+        assert!(scheduler.submit(Job::new(other.clone(), 10, "sim").with_deadline_in(secs(10))).is_ok());
+        assert!(scheduler.submit(Job::new(other, 10, "sim").with_dependency(upstream).with_deadline_in(secs(60))).is_ok());
+        assert_eq!(scheduler.stats().admission_rejections, 3);
+    }
+
+    #[test]
+    fn test_deadline_admission_counts_queued_jobs() {
+        let q = LogicalQubitId::new(0);
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(crate::operation::h(q));
+        let clock = ManualClock::new();
+        let mut scheduler = JobScheduler::new(1).with_qubits(vec![q]).with_clock(clock.clone());
+        let secs = Duration::from_secs;
+        let job = |runtime: u64| Job::new(circuit.clone(), 10, "sim").with_estimated_runtime(secs(runtime));
+
+        // 两个排在前面的作业共占用 qubit 8 秒：截止时刻在 5 秒内的作业无法完成
+        scheduler.submit(job(4)).unwrap();
+        scheduler.submit(job(4)).unwrap();
+        let rejected = job(1).with_deadline(clock.now() + secs(5));
+        assert!(matches!(scheduler.submit(rejected), Err(IrError::SchedulingConflict(_))));
+        assert!(scheduler.submit(job(1).with_deadline(clock.now() + secs(9))).is_ok());
+
+        // 更高优先级的作业排在它们前面
+        let urgent = job(1).with_priority(Priority::High).with_deadline(clock.now() + secs(5));
+        assert!(scheduler.submit(urgent).is_ok());
+
+        // 运行中作业剩余的时间同样计入
+        let first = scheduler.schedule_next().unwrap().id;
+        scheduler.start_execution(first).unwrap();
+        clock.advance(secs(1));
+        let after_all = job(1).with_deadline(clock.now() + secs(9));
+        assert!(scheduler.submit(after_all).is_err());
+        assert_eq!(scheduler.stats().admission_rejections, 2);
+    }
+
+    #[test]
+    fn test_deadline_expiry_and_stats() {
+        let q = LogicalQubitId::new(0);
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(crate::operation::h(q));
+        let clock = ManualClock::new();
+        let mut scheduler = JobScheduler::new(1).with_qubits(vec![q]).with_clock(clock.clone());
+        let within = |ms| clock.now() + Duration::from_millis(ms);
+        assert_eq!(scheduler.stats().deadline_miss_rate(), None);
+
+        let on_time = scheduler.submit(Job::new(circuit.clone(), 10, "sim").with_deadline(within(60_000))).unwrap();
+        let expiring = scheduler.submit(Job::new(circuit.clone(), 10, "sim").with_deadline(within(20))).unwrap();
+        let downstream = scheduler.submit(Job::new(circuit.clone(), 10, "sim").with_dependency(expiring)).unwrap();
+
+        // 第一个作业运行期间第二个作业过期
+        assert_eq!(scheduler.schedule_next().unwrap().id, on_time);
+        scheduler.start_execution(on_time).unwrap();
+        clock.advance(Duration::from_millis(30));
+        scheduler.complete(on_time, JobResult::success(on_time)).unwrap();
+
+        assert!(scheduler.schedule_next().is_none());
+        assert_eq!(scheduler.get_status(expiring), Some(JobStatus::Failed));
+        assert_eq!(scheduler.get_status(downstream), Some(JobStatus::Failed));
+        assert_eq!(scheduler.stats().deadlines_met, 1);
+        assert_eq!(scheduler.stats().deadlines_missed, 1);
+        assert_eq!(scheduler.stats().deadline_miss_rate(), Some(0.5));
+
+        // 完成晚于截止时刻同样计为错过
+        let late = scheduler.submit(Job::new(circuit, 10, "sim").with_deadline(within(20))).unwrap();
+        scheduler.schedule_next().unwrap();
+        scheduler.start_execution(late).unwrap();
+        clock.advance(Duration::from_millis(30));
+        scheduler.complete(late, JobResult::success(late)).unwrap();
+        assert_eq!(scheduler.stats().deadlines_missed, 2);
+    }
+
+    #[test]
+    fn test_requeue_waits_until_retry_time() {
+        let q = LogicalQubitId::new(0);
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(crate::operation::h(q));
+        let clock = ManualClock::new();
+        let mut scheduler = JobScheduler::new(1).with_qubits(vec![q]).with_clock(clock.clone());
+        let retried = scheduler.submit(Job::new(circuit.clone(), 10, "sim").with_priority(Priority::High)).unwrap();
+        let other = scheduler.submit(Job::new(circuit.clone(), 10, "sim")).unwrap();
+        let downstream = scheduler.submit(Job::new(circuit, 10, "sim").with_dependency(retried)).unwrap();
+        assert!(scheduler.requeue(retried, clock.now()).is_err());
+
+        scheduler.schedule_next().unwrap();
+        scheduler.start_execution(retried).unwrap();
+        scheduler.requeue(retried, clock.now() + Duration::from_millis(100)).unwrap();
+        assert_eq!(scheduler.get_status(retried), Some(JobStatus::Queued));
+        assert_eq!(scheduler.get_status(downstream), Some(JobStatus::Waiting));
+        assert_eq!(scheduler.next_retry_where(|_| true), Some(clock.now() + Duration::from_millis(100)));
+        assert_eq!(scheduler.next_retry_where(|job| job.id == other), None);
+
+        // 退避期间 qubit 已释放，其他作业照常调度
+        assert_eq!(scheduler.schedule_next().unwrap().id, other);
+        scheduler.start_execution(other).unwrap();
+        scheduler.complete(other, JobResult::success(other)).unwrap();
+        assert!(scheduler.schedule_next().is_none());
+
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(scheduler.next_retry_where(|_| true), None);
+        assert_eq!(scheduler.schedule_next().unwrap().id, retried);
+        scheduler.start_execution(retried).unwrap();
+        scheduler.complete(retried, JobResult::success(retried)).unwrap();
+        assert_eq!(scheduler.get_status(downstream), Some(JobStatus::Queued));
+        assert_eq!(scheduler.stats().total_completed, 2);
     }
 }
@@ -9,8 +9,33 @@
 //! operation.rs - 操作抽象（门、测量、自定义）
 //! circuit.rs   - DAG 电路结构
 //! job.rs       - Job 和调度器
+//! policy.rs    - 可插拔调度策略（优先级老化、公平份额、最短作业优先）
+//! quota.rs     - 用户/项目配额与限流
+//! store.rs     - 作业持久化存储与崩溃恢复
+//! workflow.rs  - 作业工作流 DAG 与结果传递
+//! retry.rs     - 作业重试策略与后端故障转移
 //! runtime.rs   - QuantumRuntime 执行引擎
 //! backend.rs   - 异步 BackendAdapter trait
+//! future.rs    - 作业完成 future 与内置执行器
+//! synthesis.rs - Clifford+T 近似综合
+//! resource.rs  - 资源估算（T-count、深度、表面码开销）
+//! timing.rs    - ASAP/ALAP 时序调度
+//! decoupling.rs - 动态解耦插入
+//! cleanup.rs   - 无效门与未使用 qubit 清理
+//! linalg.rs    - 复数与稠密矩阵
+//! statevector.rs - 态矢量模拟
+//! clifford.rs  - Clifford 稳定子 tableau
+//! equivalence.rs - 电路等价性检查
+//! controlled.rs - 逆与多控制变换
+//! composite.rs - 复合门定义库与展开
+//! params.rs    - 符号参数与延迟绑定
+//! observable.rs - 可观测量接口
+//! gradient.rs  - 参数移位与伴随微分梯度
+//! pauli.rs     - Pauli 串与 Hamiltonian
+//! estimator.rs - 分组测量期望值估计
+//! primitives.rs - Sampler/Estimator 执行原语
+//! optimizer.rs - 经典优化器
+//! variational.rs - VQE 与 QAOA 工作流
 //! ```
 
 #![allow(dead_code)]
@@ -24,8 +49,34 @@ pub mod qubit;
 pub mod operation;
 pub mod circuit;
 pub mod job;
+pub mod policy;
+pub mod quota;
+pub mod store;
 pub mod runtime;
+pub mod workflow;
+pub mod retry;
 pub mod backend;
+pub mod future;
+pub mod synthesis;
+pub mod resource;
+pub mod timing;
+pub mod decoupling;
+pub mod cleanup;
+pub mod linalg;
+pub(crate) mod rng;
+pub mod statevector;
+pub mod clifford;
+pub mod equivalence;
+pub mod controlled;
+pub mod composite;
+pub mod params;
+pub mod observable;
+pub mod gradient;
+pub mod pauli;
+pub mod estimator;
+pub mod primitives;
+pub mod optimizer;
+pub mod variational;
 pub mod prelude;
 
 // ============================================================================
@@ -35,9 +86,33 @@ pub mod prelude;
 pub use qubit::{LogicalQubitId, PhysicalQubitId, QubitState};
 pub use operation::{Operation, SingleQubitGate, TwoQubitGate, CustomOp};
 pub use circuit::{CircuitDag, OperationNode};
-pub use job::{Job, JobId, Priority, JobStatus, JobResult, JobScheduler};
-pub use runtime::QuantumRuntime;
-pub use backend::{BackendAdapter, BackendCapabilities, BackendCircuit};
+pub use job::{Job, JobId, Priority, JobStatus, JobResult, JobAttempt, JobScheduler, Clock, SystemClock, ManualClock};
+pub use policy::{SchedulingPolicy, StrictPriority, PriorityAging, FairShare, ShareKey, ShortestJobFirst, EarliestDeadlineFirst};
+pub use quota::{QuotaManager, QuotaKey, QuotaLimits, QuotaUsage, OverQuota};
+pub use store::{JobStore, StoredJob, InMemoryJobStore, FileJobStore, RecoveryMode, RecoveryReport};
+pub use runtime::{QuantumRuntime, ExecutionMode, AsyncRuntimeHandle};
+pub use workflow::{Workflow, WorkflowStatus, WorkflowReport, WorkflowInputs, NodeId, NodeStatus, NodeOutput, NodeReport, FailurePolicy};
+pub use retry::RetryPolicy;
+pub use backend::{BackendAdapter, BackendCapabilities, BackendCircuit, GateDurations, SubmittedJob};
+pub use future::{JobFuture, JobCompleter, block_on, sleep, timeout, LocalExecutor};
+pub use synthesis::{CliffordTSynthesizer, GateSequence, SynthesisReport};
+pub use resource::{ResourceEstimate, SurfaceCodeModel, SurfaceCodeEstimate};
+pub use timing::{ScheduleMode, TimingSchedule, ScheduledOp, IdleWindow};
+pub use decoupling::{DdSequence, DynamicalDecoupling, DecouplingReport, simulate_idle_fidelity};
+pub use cleanup::{CircuitCleanup, CleanupReport};
+pub use linalg::{Complex, Matrix};
+pub use statevector::StateVector;
+pub use clifford::CliffordTableau;
+pub use equivalence::{EquivalenceChecker, EquivalenceVerdict, EquivalenceMethod, Counterexample};
+pub use composite::{GateDefinition, GateLibrary, CompositeInliner, InlineReport};
+pub use params::{Param, ParamExpr, ParamBinding};
+pub use observable::{Observable, ZParity};
+pub use gradient::{ParameterShift, AdjointGradient, GradientResult, ShiftRule, ShiftPlan};
+pub use pauli::{Pauli, PauliString, PauliSum};
+pub use estimator::{Estimator, Estimate, Grouping, MeasurementGroup};
+pub use primitives::QuasiDistribution;
+pub use optimizer::{Optimizer, Objective, OptimizationResult, IterationRecord, NelderMead, Cobyla, Spsa, GradientDescent};
+pub use variational::{Vqe, VqeResult, Qaoa, QaoaResult};
 
 // ============================================================================
 // Error Types
@@ -64,6 +139,12 @@ pub enum IrError {
     SchedulingConflict(String),
     /// 异步操作超时
     Timeout(String),
+    /// 非法的作业状态转换
+    InvalidStateTransition(String),
+    /// 超出用户或项目配额
+    QuotaExceeded(String),
+    /// 作业存储读写失败
+    Storage(String),
 }
 
 impl std::fmt::Display for IrError {
@@ -78,50 +159,50 @@ impl std::fmt::Display for IrError {
             IrError::CyclicDependency(msg) => write!(f, "Cyclic dependency detected: {}", msg),
             IrError::SchedulingConflict(msg) => write!(f, "Scheduling conflict: {}", msg),
             IrError::Timeout(msg) => write!(f, "Operation timeout: {}", msg),
+            IrError::InvalidStateTransition(msg) => write!(f, "Invalid state transition: {}", msg),
+            IrError::QuotaExceeded(msg) => write!(f, "Quota exceeded: {}", msg),
+            IrError::Storage(msg) => write!(f, "Storage error: {}", msg),
         }
     }
 }
 
 impl std::error::Error for IrError {}
 
-pub type Result<T> = std::result::Result<T, IrError>;
-
-// ============================================================================
-// Async Trait Support (without external crate)
-// ============================================================================
-
-/// 简化的 Future trait（用于 async trait 模拟）
-pub trait FutureLike<T> {
-    fn poll(&mut self) -> Option<T>;
-}
-
-/// 异步任务句柄
-#[derive(Debug, Clone)]
-pub struct AsyncTaskHandle<T> {
-    id: u64,
-    completed: bool,
-    result: Option<T>,
+/// `IrError` 的变体（不含消息），用于按错误类别配置重试等行为
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    QubitNotFound,
+    QubitAlreadyAllocated,
+    InvalidOperation,
+    UnsupportedOperation,
+    BackendUnavailable,
+    JobExecutionFailed,
+    CyclicDependency,
+    SchedulingConflict,
+    Timeout,
+    InvalidStateTransition,
+    QuotaExceeded,
+    Storage,
 }
 
-impl<T> AsyncTaskHandle<T> {
-    pub fn new(id: u64) -> Self {
-        Self {
-            id,
-            completed: false,
-            result: None,
+impl IrError {
+    /// 错误的类别
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            IrError::QubitNotFound(_) => ErrorKind::QubitNotFound,
+            IrError::QubitAlreadyAllocated(_) => ErrorKind::QubitAlreadyAllocated,
+            IrError::InvalidOperation(_) => ErrorKind::InvalidOperation,
+            IrError::UnsupportedOperation(_) => ErrorKind::UnsupportedOperation,
+            IrError::BackendUnavailable(_) => ErrorKind::BackendUnavailable,
+            IrError::JobExecutionFailed(_) => ErrorKind::JobExecutionFailed,
+            IrError::CyclicDependency(_) => ErrorKind::CyclicDependency,
+            IrError::SchedulingConflict(_) => ErrorKind::SchedulingConflict,
+            IrError::Timeout(_) => ErrorKind::Timeout,
+            IrError::InvalidStateTransition(_) => ErrorKind::InvalidStateTransition,
+            IrError::QuotaExceeded(_) => ErrorKind::QuotaExceeded,
+            IrError::Storage(_) => ErrorKind::Storage,
         }
     }
-    
-    pub fn is_completed(&self) -> bool {
-        self.completed
-    }
-    
-    pub fn set_result(&mut self, result: T) {
-        self.result = Some(result);
-        self.completed = true;
-    }
-    
-    pub fn get_result(&self) -> Option<&T> {
-        self.result.as_ref()
-    }
 }
+
+pub type Result<T> = std::result::Result<T, IrError>;
@@ -0,0 +1,372 @@
+//! 线性代数基础模块
+//!
+//! 复数与稠密复矩阵，保持无外部依赖。
+
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+
+// ============================================================================
+// Complex
+// ============================================================================
+
+/// 复数
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub const ZERO: Complex = Complex { re: 0.0, im: 0.0 };
+    pub const ONE: Complex = Complex { re: 1.0, im: 0.0 };
+    pub const I: Complex = Complex { re: 0.0, im: 1.0 };
+
+    pub const fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    pub const fn real(re: f64) -> Self {
+        Self { re, im: 0.0 }
+    }
+
+    /// e^{iθ}
+    pub fn cis(theta: f64) -> Self {
+        let (s, c) = theta.sin_cos();
+        Self::new(c, s)
+    }
+
+    pub fn from_polar(r: f64, theta: f64) -> Self {
+        Self::cis(theta) * r
+    }
+
+    pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    pub fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    pub fn abs(self) -> f64 {
+        self.norm_sqr().sqrt()
+    }
+
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn approx_eq(self, other: Complex, tol: f64) -> bool {
+        (self - other).abs() <= tol
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, o: Complex) -> Complex {
+        Complex::new(self.re + o.re, self.im + o.im)
+    }
+}
+
+impl AddAssign for Complex {
+    fn add_assign(&mut self, o: Complex) {
+        self.re += o.re;
+        self.im += o.im;
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, o: Complex) -> Complex {
+        Complex::new(self.re - o.re, self.im - o.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, o: Complex) -> Complex {
+        Complex::new(self.re * o.re - self.im * o.im, self.re * o.im + self.im * o.re)
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Complex;
+    fn mul(self, k: f64) -> Complex {
+        Complex::new(self.re * k, self.im * k)
+    }
+}
+
+impl Div<f64> for Complex {
+    type Output = Complex;
+    fn div(self, k: f64) -> Complex {
+        Complex::new(self.re / k, self.im / k)
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+    fn neg(self) -> Complex {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl std::fmt::Display for Complex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.im >= 0.0 {
+            write!(f, "{:.6}+{:.6}i", self.re, self.im)
+        } else {
+            write!(f, "{:.6}-{:.6}i", self.re, -self.im)
+        }
+    }
+}
+
+// ============================================================================
+// Matrix
+// ============================================================================
+
+/// 稠密复方阵（行优先）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    dim: usize,
+    data: Vec<Complex>,
+}
+
+impl Matrix {
+    pub fn zeros(dim: usize) -> Self {
+        Self {
+            dim,
+            data: vec![Complex::ZERO; dim * dim],
+        }
+    }
+
+    pub fn identity(dim: usize) -> Self {
+        let mut m = Self::zeros(dim);
+        for i in 0..dim {
+            m.set(i, i, Complex::ONE);
+        }
+        m
+    }
+
+    /// 由行构造，要求为方阵
+    pub fn from_rows(rows: Vec<Vec<Complex>>) -> Self {
+        let dim = rows.len();
+        assert!(rows.iter().all(|r| r.len() == dim), "matrix must be square");
+        Self {
+            dim,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// 由实数行构造
+    pub fn from_real_rows(rows: Vec<Vec<f64>>) -> Self {
+        Self::from_rows(
+            rows.into_iter()
+                .map(|r| r.into_iter().map(Complex::real).collect())
+                .collect(),
+        )
+    }
+
+    /// 对角矩阵
+    pub fn diagonal(entries: &[Complex]) -> Self {
+        let mut m = Self::zeros(entries.len());
+        for (i, &v) in entries.iter().enumerate() {
+            m.set(i, i, v);
+        }
+        m
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// 对应的 qubit 数（维度不是 2 的幂时返回 None）
+    pub fn num_qubits(&self) -> Option<usize> {
+        if self.dim.is_power_of_two() {
+            Some(self.dim.trailing_zeros() as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Complex {
+        self.data[row * self.dim + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: Complex) {
+        self.data[row * self.dim + col] = value;
+    }
+
+    /// 矩阵乘法 self · other
+    pub fn matmul(&self, other: &Matrix) -> Matrix {
+        assert_eq!(self.dim, other.dim, "dimension mismatch");
+        let n = self.dim;
+        let mut out = Matrix::zeros(n);
+        for i in 0..n {
+            for k in 0..n {
+                let a = self.get(i, k);
+                if a == Complex::ZERO {
+                    continue;
+                }
+                for j in 0..n {
+                    out.data[i * n + j] += a * other.get(k, j);
+                }
+            }
+        }
+        out
+    }
+
+    /// 共轭转置
+    pub fn dagger(&self) -> Matrix {
+        let n = self.dim;
+        let mut out = Matrix::zeros(n);
+        for i in 0..n {
+            for j in 0..n {
+                out.set(j, i, self.get(i, j).conj());
+            }
+        }
+        out
+    }
+
+    /// 张量积 self ⊗ other（self 为高位）
+    pub fn kron(&self, other: &Matrix) -> Matrix {
+        let (a, b) = (self.dim, other.dim);
+        let mut out = Matrix::zeros(a * b);
+        for i in 0..a {
+            for j in 0..a {
+                let x = self.get(i, j);
+                for k in 0..b {
+                    for l in 0..b {
+                        out.set(i * b + k, j * b + l, x * other.get(k, l));
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// 数乘
+    pub fn scale(&self, k: Complex) -> Matrix {
+        Matrix {
+            dim: self.dim,
+            data: self.data.iter().map(|&v| v * k).collect(),
+        }
+    }
+
+    /// 矩阵作用于向量
+    pub fn apply(&self, vector: &[Complex]) -> Vec<Complex> {
+        assert_eq!(self.dim, vector.len(), "dimension mismatch");
+        (0..self.dim)
+            .map(|i| {
+                let mut acc = Complex::ZERO;
+                for (j, &v) in vector.iter().enumerate() {
+                    acc += self.get(i, j) * v;
+                }
+                acc
+            })
+            .collect()
+    }
+
+    /// 第 j 列
+    pub fn column(&self, j: usize) -> Vec<Complex> {
+        (0..self.dim).map(|i| self.get(i, j)).collect()
+    }
+
+    pub fn trace(&self) -> Complex {
+        let mut acc = Complex::ZERO;
+        for i in 0..self.dim {
+            acc += self.get(i, i);
+        }
+        acc
+    }
+
+    /// 是否为酉矩阵
+    pub fn is_unitary(&self, tol: f64) -> bool {
+        self.dagger().matmul(self).approx_eq(&Matrix::identity(self.dim), tol)
+    }
+
+    /// 逐元素近似相等
+    pub fn approx_eq(&self, other: &Matrix, tol: f64) -> bool {
+        self.dim == other.dim
+            && self.data.iter().zip(&other.data).all(|(a, b)| a.approx_eq(*b, tol))
+    }
+
+    /// 忽略全局相位时相等，返回相位 φ 使 self ≈ e^{iφ}·other
+    pub fn global_phase_to(&self, other: &Matrix, tol: f64) -> Option<f64> {
+        if self.dim != other.dim {
+            return None;
+        }
+        // 以 other 中模最大的元素为参照
+        let (idx, reference) = other
+            .data
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.norm_sqr().partial_cmp(&b.1.norm_sqr()).unwrap_or(std::cmp::Ordering::Equal))?;
+        if reference.abs() <= tol {
+            return self.approx_eq(other, tol).then_some(0.0);
+        }
+        let ratio = self.data[idx] * reference.conj() / reference.norm_sqr();
+        if (ratio.abs() - 1.0).abs() > tol {
+            return None;
+        }
+        let phase = ratio.arg();
+        self.approx_eq(&other.scale(Complex::cis(phase)), tol).then_some(phase)
+    }
+
+    /// 忽略全局相位时是否相等
+    pub fn equal_up_to_phase(&self, other: &Matrix, tol: f64) -> bool {
+        self.global_phase_to(other, tol).is_some()
+    }
+}
+
+/// 向量内积 ⟨a|b⟩
+pub fn inner_product(a: &[Complex], b: &[Complex]) -> Complex {
+    let mut acc = Complex::ZERO;
+    for (x, y) in a.iter().zip(b) {
+        acc += x.conj() * *y;
+    }
+    acc
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complex_arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(3.0, -1.0);
+        assert_eq!(a * b, Complex::new(5.0, 5.0));
+        assert_eq!(a + b, Complex::new(4.0, 1.0));
+        assert!((Complex::cis(std::f64::consts::PI) + Complex::ONE).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_matrix_mul_and_dagger() {
+        let x = Matrix::from_real_rows(vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+        assert!(x.matmul(&x).approx_eq(&Matrix::identity(2), 1e-12));
+
+        let s = Matrix::diagonal(&[Complex::ONE, Complex::I]);
+        assert!(s.is_unitary(1e-12));
+        assert!(s.matmul(&s.dagger()).approx_eq(&Matrix::identity(2), 1e-12));
+    }
+
+    #[test]
+    fn test_kron() {
+        let x = Matrix::from_real_rows(vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+        let xi = x.kron(&Matrix::identity(2));
+        assert_eq!(xi.dim(), 4);
+        assert_eq!(xi.get(2, 0), Complex::ONE);
+        assert_eq!(xi.num_qubits(), Some(2));
+    }
+
+    #[test]
+    fn test_global_phase() {
+        let z = Matrix::diagonal(&[Complex::ONE, -Complex::ONE]);
+        let phased = z.scale(Complex::cis(0.7));
+        let phase = phased.global_phase_to(&z, 1e-12).unwrap();
+        assert!((phase - 0.7).abs() < 1e-12);
+        assert!(!z.equal_up_to_phase(&Matrix::identity(2), 1e-9));
+    }
+}
@@ -0,0 +1,194 @@
+//! 可观测量模块
+//!
+//! 可观测量给出两种求期望的途径：
+//! - 采样：在电路末尾追加基变换与测量，由计数结果估计期望；
+//! - 精确：在态矢量上作用 O，直接计算 ⟨ψ|O|ψ⟩（伴随微分也依赖此接口）。
+
+use std::collections::BTreeSet;
+
+use crate::circuit::CircuitDag;
+use crate::job::JobResult;
+use crate::operation::Operation;
+use crate::qubit::LogicalQubitId;
+use crate::statevector::StateVector;
+use crate::{Result, IrError};
+
+// ============================================================================
+// Observable Trait
+// ============================================================================
+
+/// 可观测量
+pub trait Observable: Send + Sync {
+    /// 估计期望所需的测量电路（在 circuit 末尾追加基变换与测量）
+    fn measurement_circuits(&self, circuit: &CircuitDag) -> Result<Vec<CircuitDag>>;
+
+    /// 由各测量电路的执行结果（顺序同 `measurement_circuits`）计算期望值
+    fn expectation_from(&self, results: &[JobResult]) -> Result<f64>;
+
+    /// O|ψ⟩（一般不归一），qubits 给出态矢量各位对应的逻辑 qubit
+    fn apply(&self, state: &StateVector, qubits: &[LogicalQubitId]) -> Result<StateVector>;
+
+    /// 精确期望 ⟨ψ|O|ψ⟩
+    fn exact_expectation(&self, state: &StateVector, qubits: &[LogicalQubitId]) -> Result<f64> {
+        Ok(state.inner(&self.apply(state, qubits)?).re)
+    }
+}
+
+/// 在电路末尾追加基变换与测量
+///
+/// 已被测量的 qubit 不再重复测量；若还需在其上作基变换则返回错误。
+pub(crate) fn append_measurements(
+    circuit: &CircuitDag,
+    rotations: Vec<Operation>,
+    qubits: &[LogicalQubitId],
+) -> Result<CircuitDag> {
+    let mut ops = circuit.operations_in_order();
+    let measured: BTreeSet<LogicalQubitId> = ops
+        .iter()
+        .filter_map(|op| match op {
+            Operation::Measure { qubit, .. } => Some(*qubit),
+            _ => None,
+        })
+        .collect();
+    if let Some(q) = rotations.iter().flat_map(|op| op.qubits()).find(|q| measured.contains(q)) {
+        return Err(IrError::InvalidOperation(format!(
+            "{} is already measured and cannot be rotated into the measurement basis", q
+        )));
+    }
+    ops.extend(rotations);
+    ops.extend(qubits.iter().filter(|q| !measured.contains(q)).map(|&q| Operation::measure(q)));
+    Ok(circuit.rebuild_with(ops))
+}
+
+/// 态矢量中 qubit 所在的位
+pub(crate) fn bit_of(qubits: &[LogicalQubitId], qubit: LogicalQubitId) -> Result<usize> {
+    qubits
+        .iter()
+        .position(|&q| q == qubit)
+        .ok_or_else(|| IrError::QubitNotFound(format!("{} is not in the state", qubit)))
+}
+
+/// 每次测量中给定 qubit 的奇偶性（true 表示奇数个 1）
+pub(crate) fn shot_parities(result: &JobResult, qubits: &[LogicalQubitId]) -> Result<Vec<bool>> {
+    let counts = qubits
+        .iter()
+        .map(|q| {
+            result
+                .get_counts(*q)
+                .ok_or_else(|| IrError::QubitNotFound(format!("No counts for {}", q)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let shots = counts.first().map_or(0, |c| c.len());
+    if counts.iter().any(|c| c.len() != shots) {
+        return Err(IrError::InvalidOperation("Counts have different shot numbers".to_string()));
+    }
+    Ok((0..shots).map(|i| counts.iter().fold(false, |p, c| p ^ (c[i] == 1))).collect())
+}
+
+// ============================================================================
+// Z Parity
+// ============================================================================
+
+/// Z⊗Z⊗…⊗Z 奇偶性可观测量
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZParity {
+    pub qubits: Vec<LogicalQubitId>,
+}
+
+impl ZParity {
+    pub fn new(qubits: Vec<LogicalQubitId>) -> Self {
+        Self { qubits }
+    }
+}
+
+impl Observable for ZParity {
+    fn measurement_circuits(&self, circuit: &CircuitDag) -> Result<Vec<CircuitDag>> {
+        Ok(vec![append_measurements(circuit, Vec::new(), &self.qubits)?])
+    }
+
+    fn expectation_from(&self, results: &[JobResult]) -> Result<f64> {
+        let result = results
+            .first()
+            .ok_or_else(|| IrError::InvalidOperation("No measurement results".to_string()))?;
+        let parities = shot_parities(result, &self.qubits)?;
+        if parities.is_empty() {
+            return Err(IrError::InvalidOperation("No shots to estimate from".to_string()));
+        }
+        let odd = parities.iter().filter(|&&p| p).count() as f64;
+        Ok(1.0 - 2.0 * odd / parities.len() as f64)
+    }
+
+    fn apply(&self, state: &StateVector, qubits: &[LogicalQubitId]) -> Result<StateVector> {
+        let mask = self
+            .qubits
+            .iter()
+            .map(|&q| bit_of(qubits, q).map(|b| 1usize << b))
+            .sum::<Result<usize>>()?;
+        let amplitudes = state
+            .amplitudes()
+            .iter()
+            .enumerate()
+            .map(|(k, &a)| if (k & mask).count_ones() % 2 == 1 { -a } else { a })
+            .collect();
+        Ok(StateVector::from_raw(amplitudes))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{BackendAdapter, IdealSimulatorBackend};
+    use crate::circuit::bell_state_dag;
+    use crate::job::Job;
+    use crate::operation::{cnot, h, x};
+    use crate::statevector::final_state;
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    #[test]
+    fn test_exact_parity() {
+        let (qubits, state) = final_state(&bell_state_dag()).unwrap();
+        let zz = ZParity::new(vec![q(0), q(1)]);
+        assert!((zz.exact_expectation(&state, &qubits).unwrap() - 1.0).abs() < 1e-12);
+        let z0 = ZParity::new(vec![q(0)]);
+        assert!(z0.exact_expectation(&state, &qubits).unwrap().abs() < 1e-12);
+        assert!(ZParity::new(vec![q(5)]).apply(&state, &qubits).is_err());
+    }
+
+    #[test]
+    fn test_measurement_circuits() {
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(h(q(0)));
+        circuit.add_node(cnot(q(0), q(1)));
+        let zz = ZParity::new(vec![q(0), q(1)]);
+        let measured = zz.measurement_circuits(&circuit).unwrap();
+        assert_eq!(measured.len(), 1);
+        assert_eq!(measured[0].operations_in_order().iter().filter(|op| op.is_measurement()).count(), 2);
+
+        // 已测量的 qubit 不重复测量
+        let again = zz.measurement_circuits(&measured[0]).unwrap();
+        assert_eq!(again[0].num_nodes(), measured[0].num_nodes());
+        assert!(append_measurements(&measured[0], vec![h(q(0))], &[q(0)]).is_err());
+    }
+
+    #[test]
+    fn test_sampled_parity() {
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(x(q(0)));
+        circuit.add_node(h(q(1)));
+        circuit.add_node(cnot(q(1), q(2)));
+        let backend = IdealSimulatorBackend::new();
+
+        let zz = ZParity::new(vec![q(1), q(2)]);
+        let measured = zz.measurement_circuits(&circuit).unwrap().remove(0);
+        let results = vec![backend.execute(&Job::new(measured, 100, backend.id())).unwrap()];
+        assert_eq!(zz.expectation_from(&results).unwrap(), 1.0);
+        assert_eq!(ZParity::new(vec![q(0)]).expectation_from(&results).unwrap(), -1.0);
+    }
+}
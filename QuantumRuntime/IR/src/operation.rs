@@ -2,7 +2,10 @@
 //! 
 //! 定义量子操作（门、测量、barrier、自定义操作）
 
+use crate::linalg::{Complex, Matrix};
+use crate::params::ParamExpr;
 use crate::qubit::LogicalQubitId;
+use crate::{Result, IrError};
 
 // ============================================================================
 // Single Qubit Gates
@@ -58,6 +61,14 @@ impl SingleQubitGate {
             SingleQubitGate::U(_, _, _))
     }
     
+    /// 是否是计算基下的对角门
+    pub fn is_diagonal(&self) -> bool {
+        matches!(self,
+            SingleQubitGate::Z | SingleQubitGate::S | SingleQubitGate::Sdg |
+            SingleQubitGate::T | SingleQubitGate::Tdg |
+            SingleQubitGate::Rz(_) | SingleQubitGate::P(_))
+    }
+    
     pub fn parameters(&self) -> Vec<f64> {
         match self {
             SingleQubitGate::Rx(theta) => vec![*theta],
@@ -68,6 +79,79 @@ impl SingleQubitGate {
             _ => vec![],
         }
     }
+    
+    /// 替换参数，参数个数不符时返回 None
+    pub fn with_parameters(&self, values: &[f64]) -> Option<SingleQubitGate> {
+        match (*self, values) {
+            (SingleQubitGate::Rx(_), &[theta]) => Some(SingleQubitGate::Rx(theta)),
+            (SingleQubitGate::Ry(_), &[theta]) => Some(SingleQubitGate::Ry(theta)),
+            (SingleQubitGate::Rz(_), &[theta]) => Some(SingleQubitGate::Rz(theta)),
+            (SingleQubitGate::P(_), &[phi]) => Some(SingleQubitGate::P(phi)),
+            (SingleQubitGate::U(..), &[theta, phi, lam]) => Some(SingleQubitGate::U(theta, phi, lam)),
+            (gate, []) if !gate.is_parametric() => Some(gate),
+            _ => None,
+        }
+    }
+    
+    /// 逆门 U†
+    pub fn inverse(&self) -> SingleQubitGate {
+        match *self {
+            SingleQubitGate::S => SingleQubitGate::Sdg,
+            SingleQubitGate::Sdg => SingleQubitGate::S,
+            SingleQubitGate::T => SingleQubitGate::Tdg,
+            SingleQubitGate::Tdg => SingleQubitGate::T,
+            SingleQubitGate::Rx(theta) => SingleQubitGate::Rx(-theta),
+            SingleQubitGate::Ry(theta) => SingleQubitGate::Ry(-theta),
+            SingleQubitGate::Rz(theta) => SingleQubitGate::Rz(-theta),
+            SingleQubitGate::P(phi) => SingleQubitGate::P(-phi),
+            SingleQubitGate::U(theta, phi, lam) => SingleQubitGate::U(-theta, -lam, -phi),
+            gate => gate,
+        }
+    }
+    
+    /// 精确的 2×2 酉矩阵
+    pub fn matrix(&self) -> Matrix {
+        use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_4};
+        let c = Complex::real;
+        match *self {
+            SingleQubitGate::X => Matrix::from_real_rows(vec![vec![0.0, 1.0], vec![1.0, 0.0]]),
+            SingleQubitGate::Y => Matrix::from_rows(vec![
+                vec![Complex::ZERO, -Complex::I],
+                vec![Complex::I, Complex::ZERO],
+            ]),
+            SingleQubitGate::Z => Matrix::from_real_rows(vec![vec![1.0, 0.0], vec![0.0, -1.0]]),
+            SingleQubitGate::H => Matrix::from_real_rows(vec![
+                vec![FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+                vec![FRAC_1_SQRT_2, -FRAC_1_SQRT_2],
+            ]),
+            SingleQubitGate::S => Matrix::diagonal(&[Complex::ONE, Complex::I]),
+            SingleQubitGate::Sdg => Matrix::diagonal(&[Complex::ONE, -Complex::I]),
+            SingleQubitGate::T => Matrix::diagonal(&[Complex::ONE, Complex::cis(FRAC_PI_4)]),
+            SingleQubitGate::Tdg => Matrix::diagonal(&[Complex::ONE, Complex::cis(-FRAC_PI_4)]),
+            SingleQubitGate::Rx(theta) => {
+                let (s, co) = (theta / 2.0).sin_cos();
+                Matrix::from_rows(vec![
+                    vec![c(co), Complex::new(0.0, -s)],
+                    vec![Complex::new(0.0, -s), c(co)],
+                ])
+            }
+            SingleQubitGate::Ry(theta) => {
+                let (s, co) = (theta / 2.0).sin_cos();
+                Matrix::from_real_rows(vec![vec![co, -s], vec![s, co]])
+            }
+            SingleQubitGate::Rz(theta) => {
+                Matrix::diagonal(&[Complex::cis(-theta / 2.0), Complex::cis(theta / 2.0)])
+            }
+            SingleQubitGate::P(phi) => Matrix::diagonal(&[Complex::ONE, Complex::cis(phi)]),
+            SingleQubitGate::U(theta, phi, lam) => {
+                let (s, co) = (theta / 2.0).sin_cos();
+                Matrix::from_rows(vec![
+                    vec![c(co), -Complex::cis(lam) * s],
+                    vec![Complex::cis(phi) * s, Complex::cis(phi + lam) * co],
+                ])
+            }
+        }
+    }
 }
 
 // ============================================================================
@@ -110,6 +194,11 @@ impl TwoQubitGate {
         matches!(self, TwoQubitGate::CP(_) | TwoQubitGate::MS(_))
     }
     
+    /// 是否是计算基下的对角门
+    pub fn is_diagonal(&self) -> bool {
+        matches!(self, TwoQubitGate::CZ | TwoQubitGate::CP(_))
+    }
+    
     pub fn parameters(&self) -> Vec<f64> {
         match self {
             TwoQubitGate::CP(phi) => vec![*phi],
@@ -117,6 +206,83 @@ impl TwoQubitGate {
             _ => vec![],
         }
     }
+    
+    /// 替换参数，参数个数不符时返回 None
+    pub fn with_parameters(&self, values: &[f64]) -> Option<TwoQubitGate> {
+        match (*self, values) {
+            (TwoQubitGate::CP(_), &[phi]) => Some(TwoQubitGate::CP(phi)),
+            (TwoQubitGate::MS(_), &[theta]) => Some(TwoQubitGate::MS(theta)),
+            (gate, []) if !gate.is_parametric() => Some(gate),
+            _ => None,
+        }
+    }
+    
+    /// 逆门；iSWAP 与 √SWAP 的逆不在门集中，返回 None
+    pub fn inverse(&self) -> Option<TwoQubitGate> {
+        match *self {
+            TwoQubitGate::CP(phi) => Some(TwoQubitGate::CP(-phi)),
+            TwoQubitGate::MS(theta) => Some(TwoQubitGate::MS(-theta)),
+            TwoQubitGate::ISWAP | TwoQubitGate::SqrtSWAP => None,
+            gate => Some(gate),
+        }
+    }
+    
+    /// 由 `adjoint_of` 元数据中的门名找回不在门集中的逆门的原门
+    pub(crate) fn from_adjoint_name(name: &str) -> Option<TwoQubitGate> {
+        [TwoQubitGate::ISWAP, TwoQubitGate::SqrtSWAP]
+            .into_iter()
+            .find(|g| g.name() == name)
+    }
+    
+    /// 精确的 4×4 酉矩阵（control 为高位）
+    pub fn matrix(&self) -> Matrix {
+        let o = Complex::ZERO;
+        let l = Complex::ONE;
+        match *self {
+            TwoQubitGate::CNOT => Matrix::from_real_rows(vec![
+                vec![1.0, 0.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0, 0.0],
+                vec![0.0, 0.0, 0.0, 1.0],
+                vec![0.0, 0.0, 1.0, 0.0],
+            ]),
+            TwoQubitGate::CZ => Matrix::diagonal(&[l, l, l, -l]),
+            TwoQubitGate::SWAP => Matrix::from_real_rows(vec![
+                vec![1.0, 0.0, 0.0, 0.0],
+                vec![0.0, 0.0, 1.0, 0.0],
+                vec![0.0, 1.0, 0.0, 0.0],
+                vec![0.0, 0.0, 0.0, 1.0],
+            ]),
+            TwoQubitGate::CP(phi) => Matrix::diagonal(&[l, l, l, Complex::cis(phi)]),
+            TwoQubitGate::ISWAP => Matrix::from_rows(vec![
+                vec![l, o, o, o],
+                vec![o, o, Complex::I, o],
+                vec![o, Complex::I, o, o],
+                vec![o, o, o, l],
+            ]),
+            TwoQubitGate::SqrtSWAP => {
+                let a = Complex::new(0.5, 0.5);
+                let b = Complex::new(0.5, -0.5);
+                Matrix::from_rows(vec![
+                    vec![l, o, o, o],
+                    vec![o, a, b, o],
+                    vec![o, b, a, o],
+                    vec![o, o, o, l],
+                ])
+            }
+            // MS(θ) = exp(-iθ/2 · X⊗X)
+            TwoQubitGate::MS(theta) => {
+                let (s, co) = (theta / 2.0).sin_cos();
+                let c = Complex::real(co);
+                let m = Complex::new(0.0, -s);
+                Matrix::from_rows(vec![
+                    vec![c, o, o, m],
+                    vec![o, c, m, o],
+                    vec![o, m, c, o],
+                    vec![m, o, o, c],
+                ])
+            }
+        }
+    }
 }
 
 // ============================================================================
@@ -166,6 +332,8 @@ pub struct CustomOp {
     pub params: Vec<f64>,
     /// 元数据（可选）
     pub metadata: std::collections::HashMap<String, String>,
+    /// 用户提供的酉矩阵（可选，qubits[0] 为最高位）
+    pub matrix: Option<Matrix>,
 }
 
 impl CustomOp {
@@ -175,6 +343,7 @@ impl CustomOp {
             qubits: Vec::new(),
             params: Vec::new(),
             metadata: std::collections::HashMap::new(),
+            matrix: None,
         }
     }
     
@@ -192,6 +361,12 @@ impl CustomOp {
         self.metadata.insert(key.into(), value.into());
         self
     }
+    
+    /// 附加酉矩阵，维度须为 2^qubits
+    pub fn with_matrix(mut self, matrix: Matrix) -> Self {
+        self.matrix = Some(matrix);
+        self
+    }
 }
 
 // ============================================================================
@@ -234,8 +409,19 @@ pub enum Operation {
     Barrier {
         qubits: Vec<LogicalQubitId>,
     },
+    /// 显式空闲（延时）
+    Delay {
+        qubit: LogicalQubitId,
+        /// 空闲时长（纳秒）
+        duration_ns: f64,
+    },
     /// 自定义操作
     Custom(CustomOp),
+    /// 符号参数门：内部参数门的数值参数仅作占位，绑定后由表达式求值替换
+    Symbolic {
+        op: Box<Operation>,
+        params: Vec<ParamExpr>,
+    },
 }
 
 /// 三比特门
@@ -257,6 +443,27 @@ impl ThreeQubitGate {
             ThreeQubitGate::CCZ => "CCZ",
         }
     }
+    
+    /// 精确的 8×8 酉矩阵（controls[0] 为最高位）
+    pub fn matrix(&self) -> Matrix {
+        let mut m = Matrix::identity(8);
+        match self {
+            ThreeQubitGate::Toffoli => {
+                m.set(6, 6, Complex::ZERO);
+                m.set(7, 7, Complex::ZERO);
+                m.set(6, 7, Complex::ONE);
+                m.set(7, 6, Complex::ONE);
+            }
+            ThreeQubitGate::Fredkin => {
+                m.set(5, 5, Complex::ZERO);
+                m.set(6, 6, Complex::ZERO);
+                m.set(5, 6, Complex::ONE);
+                m.set(6, 5, Complex::ONE);
+            }
+            ThreeQubitGate::CCZ => m.set(7, 7, -Complex::ONE),
+        }
+        m
+    }
 }
 
 impl Operation {
@@ -299,6 +506,11 @@ impl Operation {
         Operation::Barrier { qubits }
     }
     
+    /// 创建延时操作
+    pub fn delay(qubit: LogicalQubitId, duration_ns: f64) -> Self {
+        Operation::Delay { qubit, duration_ns }
+    }
+    
     /// 获取操作涉及的 qubit 列表
     pub fn qubits(&self) -> Vec<LogicalQubitId> {
         match self {
@@ -310,7 +522,9 @@ impl Operation {
             Operation::Measure { qubit, .. } => vec![*qubit],
             Operation::Reset { qubit } => vec![*qubit],
             Operation::Barrier { qubits } => qubits.clone(),
+            Operation::Delay { qubit, .. } => vec![*qubit],
             Operation::Custom(op) => op.qubits.clone(),
+            Operation::Symbolic { op, .. } => op.qubits(),
         }
     }
     
@@ -323,7 +537,9 @@ impl Operation {
             Operation::Measure { .. } => "M",
             Operation::Reset { .. } => "Reset",
             Operation::Barrier { .. } => "Barrier",
+            Operation::Delay { .. } => "Delay",
             Operation::Custom(op) => &op.name,
+            Operation::Symbolic { op, .. } => op.name(),
         }
     }
     
@@ -336,7 +552,10 @@ impl Operation {
             Operation::Measure { .. } => vec![],
             Operation::Reset { .. } => vec![],
             Operation::Barrier { .. } => vec![],
+            Operation::Delay { .. } => vec![],
             Operation::Custom(op) => op.params.clone(),
+            // 未绑定的参数没有数值
+            Operation::Symbolic { .. } => vec![],
         }
     }
     
@@ -350,11 +569,160 @@ impl Operation {
         matches!(self, Operation::Barrier { .. })
     }
     
+    /// 检查是否是延时操作
+    pub fn is_delay(&self) -> bool {
+        matches!(self, Operation::Delay { .. })
+    }
+    
     /// 检查是否是重置操作
     pub fn is_reset(&self) -> bool {
         matches!(self, Operation::Reset { .. })
     }
     
+    /// 检查是否是计算基下的对角门
+    pub fn is_diagonal(&self) -> bool {
+        match self {
+            Operation::Gate1 { gate, .. } => gate.is_diagonal(),
+            Operation::Gate2 { gate, .. } => gate.is_diagonal(),
+            Operation::Gate3 { gate, .. } => matches!(gate, ThreeQubitGate::CCZ),
+            Operation::Symbolic { op, .. } => op.is_diagonal(),
+            _ => false,
+        }
+    }
+    
+    /// 操作的酉矩阵（第一个 qubit 为最高位）
+    /// 
+    /// 测量、重置、barrier、延时、符号参数门以及未附带矩阵的自定义操作返回 None
+    pub fn matrix(&self) -> Option<Matrix> {
+        match self {
+            Operation::Gate1 { gate, .. } => Some(gate.matrix()),
+            Operation::Gate2 { gate, .. } => Some(gate.matrix()),
+            Operation::Gate3 { gate, .. } => Some(gate.matrix()),
+            Operation::Custom(op) => op.matrix.clone(),
+            _ => None,
+        }
+    }
+    
+    /// 以数值替换参数门的参数；符号门按表达式顺序替换为具体门
+    pub fn with_parameters(&self, values: &[f64]) -> Result<Operation> {
+        let replaced = match self {
+            Operation::Gate1 { gate, target } => gate.with_parameters(values).map(|g| Operation::gate1(g, *target)),
+            Operation::Gate2 { gate, control, target } => {
+                gate.with_parameters(values).map(|g| Operation::gate2(g, *control, *target))
+            }
+            Operation::Symbolic { op, .. } => return op.with_parameters(values),
+            _ if values.is_empty() => Some(self.clone()),
+            _ => None,
+        };
+        replaced.ok_or_else(|| IrError::InvalidOperation(format!(
+            "{} does not take {} parameters", self.name(), values.len()
+        )))
+    }
+    
+    /// 逆操作 U†
+    /// 
+    /// iSWAP†、√SWAP† 以携带矩阵的自定义操作表示（元数据 `adjoint_of` 记录原门名）；
+    /// 测量、重置和未附带矩阵的自定义操作不可逆。
+    pub fn inverse(&self) -> Result<Operation> {
+        match self {
+            Operation::Gate1 { gate, target } => Ok(Operation::gate1(gate.inverse(), *target)),
+            Operation::Gate2 { gate, control, target } => match gate.inverse() {
+                Some(inv) => Ok(Operation::gate2(inv, *control, *target)),
+                None => Ok(Operation::Custom(
+                    CustomOp::new(format!("{}†", gate.name()))
+                        .with_qubits(vec![*control, *target])
+                        .with_metadata("adjoint_of", gate.name())
+                        .with_matrix(gate.matrix().dagger()),
+                )),
+            },
+            Operation::Gate3 { .. } | Operation::Barrier { .. } | Operation::Delay { .. } => Ok(self.clone()),
+            Operation::Custom(op) => {
+                let original = op.metadata.get("adjoint_of").map(String::as_str);
+                if let (Some(gate), [a, b]) = (original.and_then(TwoQubitGate::from_adjoint_name), op.qubits.as_slice()) {
+                    return Ok(Operation::gate2(gate, *a, *b));
+                }
+                let matrix = op.matrix.as_ref().ok_or_else(|| IrError::UnsupportedOperation(
+                    format!("Custom operation {} has no matrix to invert", op.name)
+                ))?;
+                let mut inv = op.clone();
+                inv.matrix = Some(matrix.dagger());
+                inv.name = match op.name.strip_suffix('†') {
+                    Some(base) => base.to_string(),
+                    None => format!("{}†", op.name),
+                };
+                if original.is_some() {
+                    inv.metadata.remove("adjoint_of");
+                } else {
+                    inv.metadata.insert("adjoint_of".to_string(), op.name.clone());
+                }
+                Ok(Operation::Custom(inv))
+            }
+            Operation::Symbolic { op, params } => {
+                // U(θ,φ,λ)† = U(-θ,-λ,-φ)，其余参数门取反
+                let params = match (op.as_ref(), params.as_slice()) {
+                    (Operation::Gate1 { gate: SingleQubitGate::U(..), .. }, [theta, phi, lam]) => {
+                        vec![-theta.clone(), -lam.clone(), -phi.clone()]
+                    }
+                    _ => params.iter().map(|e| -e.clone()).collect(),
+                };
+                Ok(Operation::Symbolic { op: Box::new(op.inverse()?), params })
+            }
+            Operation::Measure { .. } | Operation::Reset { .. } => Err(IrError::InvalidOperation(
+                format!("{} is not invertible", self.name())
+            )),
+        }
+    }
+    
+    /// 按映射函数替换操作涉及的 qubit
+    pub fn map_qubits<F>(&self, f: F) -> Operation
+    where
+        F: Fn(LogicalQubitId) -> LogicalQubitId,
+    {
+        match self {
+            Operation::Gate1 { gate, target } => Operation::Gate1 { gate: *gate, target: f(*target) },
+            Operation::Gate2 { gate, control, target } => Operation::Gate2 {
+                gate: *gate,
+                control: f(*control),
+                target: f(*target),
+            },
+            Operation::Gate3 { gate, controls, target } => Operation::Gate3 {
+                gate: *gate,
+                controls: [f(controls[0]), f(controls[1])],
+                target: f(*target),
+            },
+            Operation::Measure { qubit, classical_reg } => Operation::Measure {
+                qubit: f(*qubit),
+                classical_reg: *classical_reg,
+            },
+            Operation::Reset { qubit } => Operation::Reset { qubit: f(*qubit) },
+            Operation::Barrier { qubits } => Operation::Barrier {
+                qubits: qubits.iter().map(|&q| f(q)).collect(),
+            },
+            Operation::Delay { qubit, duration_ns } => Operation::Delay {
+                qubit: f(*qubit),
+                duration_ns: *duration_ns,
+            },
+            Operation::Custom(op) => {
+                let mut op = op.clone();
+                op.qubits = op.qubits.iter().map(|&q| f(q)).collect();
+                Operation::Custom(op)
+            }
+            // 内部只会是单/双比特参数门
+            Operation::Symbolic { op, params } => {
+                let op = match **op {
+                    Operation::Gate1 { gate, target } => Operation::Gate1 { gate, target: f(target) },
+                    Operation::Gate2 { gate, control, target } => Operation::Gate2 {
+                        gate,
+                        control: f(control),
+                        target: f(target),
+                    },
+                    ref other => other.clone(),
+                };
+                Operation::Symbolic { op: Box::new(op), params: params.clone() }
+            }
+        }
+    }
+    
     /// 检查是否是自定义操作
     pub fn is_custom(&self) -> bool {
         matches!(self, Operation::Custom(_))
@@ -421,6 +789,10 @@ pub fn measure(qubit: LogicalQubitId) -> Operation {
     Operation::measure(qubit)
 }
 
+pub fn delay(qubit: LogicalQubitId, duration_ns: f64) -> Operation {
+    Operation::delay(qubit, duration_ns)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -458,6 +830,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_custom_operation() {
         let custom = CustomOp::new("MyGate")
             .with_qubits(vec![LogicalQubitId::new(0), LogicalQubitId::new(1)])
@@ -470,6 +843,20 @@ mod tests {
         assert_eq!(op.parameters().len(), 2);
     }
 
+    #[test]
+    fn test_map_qubits_and_diagonal() {
+        let q0 = LogicalQubitId::new(0);
+        let q1 = LogicalQubitId::new(1);
+        
+        let op = cnot(q0, q1).map_qubits(|q| LogicalQubitId::new(q.value() + 5));
+        assert_eq!(op.qubits(), vec![LogicalQubitId::new(5), LogicalQubitId::new(6)]);
+        
+        assert!(cz(q0, q1).is_diagonal());
+        assert!(rz(q0, 0.3).is_diagonal());
+        assert!(!h(q0).is_diagonal());
+        assert!(!measure(q0).is_diagonal());
+    }
+
     #[test]
     fn test_operation_qubits() {
         let q0 = LogicalQubitId::new(0);
@@ -481,4 +868,62 @@ mod tests {
         assert!(qubits.contains(&q0));
         assert!(qubits.contains(&q1));
     }
+
+    #[test]
+    fn test_gate_matrices() {
+        let single = [
+            SingleQubitGate::X, SingleQubitGate::Y, SingleQubitGate::Z, SingleQubitGate::H,
+            SingleQubitGate::S, SingleQubitGate::T, SingleQubitGate::Sdg, SingleQubitGate::Tdg,
+            SingleQubitGate::Rx(0.4), SingleQubitGate::Ry(1.1), SingleQubitGate::Rz(-0.7),
+            SingleQubitGate::P(0.9), SingleQubitGate::U(0.3, 1.2, -0.5),
+        ];
+        for gate in single {
+            assert!(gate.matrix().is_unitary(1e-12), "{} is not unitary", gate.name());
+        }
+        let two = [
+            TwoQubitGate::CNOT, TwoQubitGate::CZ, TwoQubitGate::SWAP, TwoQubitGate::CP(0.6),
+            TwoQubitGate::ISWAP, TwoQubitGate::SqrtSWAP, TwoQubitGate::MS(0.8),
+        ];
+        for gate in two {
+            assert!(gate.matrix().is_unitary(1e-12), "{} is not unitary", gate.name());
+        }
+        for gate in [ThreeQubitGate::Toffoli, ThreeQubitGate::Fredkin, ThreeQubitGate::CCZ] {
+            assert!(gate.matrix().is_unitary(1e-12));
+        }
+
+        // S² = Z，√SWAP² = SWAP，T = P(π/4)
+        let s = SingleQubitGate::S.matrix();
+        assert!(s.matmul(&s).approx_eq(&SingleQubitGate::Z.matrix(), 1e-12));
+        let r = TwoQubitGate::SqrtSWAP.matrix();
+        assert!(r.matmul(&r).approx_eq(&TwoQubitGate::SWAP.matrix(), 1e-12));
+        assert!(SingleQubitGate::T.matrix().approx_eq(&SingleQubitGate::P(std::f64::consts::FRAC_PI_4).matrix(), 1e-12));
+    }
+
+    #[test]
+    fn test_operation_matrix() {
+        let q0 = LogicalQubitId::new(0);
+        assert!(measure(q0).matrix().is_none());
+        assert!(Operation::Custom(CustomOp::new("opaque")).matrix().is_none());
+
+        let custom = CustomOp::new("Z").with_qubits(vec![q0]).with_matrix(SingleQubitGate::Z.matrix());
+        assert_eq!(Operation::Custom(custom).matrix(), Some(z(q0).matrix().unwrap()));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let q0 = LogicalQubitId::new(0);
+        let q1 = LogicalQubitId::new(1);
+        let ops = vec![
+            s(q0), t(q0), rx(q0, 0.3), Operation::gate1(SingleQubitGate::U(0.4, 1.1, -0.6), q0),
+            Operation::gate2(TwoQubitGate::CP(0.7), q0, q1), Operation::gate2(TwoQubitGate::MS(0.2), q0, q1),
+            Operation::gate2(TwoQubitGate::ISWAP, q0, q1), Operation::gate2(TwoQubitGate::SqrtSWAP, q0, q1),
+        ];
+        for op in ops {
+            let inv = op.inverse().unwrap();
+            assert!(inv.matrix().unwrap().approx_eq(&op.matrix().unwrap().dagger(), 1e-12), "{}", op.name());
+            assert_eq!(inv.inverse().unwrap(), op);
+        }
+        assert_eq!(s(q0).inverse().unwrap().name(), "Sdg");
+        assert!(measure(q0).inverse().is_err());
+    }
 }
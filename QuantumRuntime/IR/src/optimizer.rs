@@ -0,0 +1,929 @@
+//! 经典优化器模块
+//!
+//! 变分算法外层循环使用的最小化方法：
+//! - `NelderMead`：单纯形法，只用函数值；
+//! - `Cobyla`：Powell 的 COBYLA，单纯形线性插值加信赖域，支持不等式约束；
+//! - `Spsa`：同时扰动随机逼近，每步两次函数值，适合有采样噪声的目标；
+//! - `GradientDescent`：梯度下降，梯度由目标函数给出（变分算法中为参数移位）。
+//!
+//! 每种优化器都逐迭代记录历史，便于绘制收敛曲线。
+
+use std::sync::Arc;
+
+use crate::rng::SplitMix64;
+use crate::{Result, IrError};
+
+// ============================================================================
+// Objective
+// ============================================================================
+
+/// 待最小化的目标函数
+pub trait Objective {
+    fn value(&mut self, x: &[f64]) -> Result<f64>;
+
+    /// 梯度；默认用中心差分（每个分量两次函数值）
+    fn gradient(&mut self, x: &[f64]) -> Result<Vec<f64>> {
+        const STEP: f64 = 1e-4;
+        let mut point = x.to_vec();
+        let mut gradient = Vec::with_capacity(x.len());
+        for i in 0..x.len() {
+            point[i] = x[i] + STEP;
+            let plus = self.value(&point)?;
+            point[i] = x[i] - STEP;
+            let minus = self.value(&point)?;
+            point[i] = x[i];
+            gradient.push((plus - minus) / (2.0 * STEP));
+        }
+        Ok(gradient)
+    }
+}
+
+impl<F: FnMut(&[f64]) -> Result<f64>> Objective for F {
+    fn value(&mut self, x: &[f64]) -> Result<f64> {
+        self(x)
+    }
+}
+
+/// 统计求值次数的包装
+struct Counted<'a> {
+    inner: &'a mut dyn Objective,
+    evaluations: usize,
+    gradient_evaluations: usize,
+}
+
+impl<'a> Counted<'a> {
+    fn new(inner: &'a mut dyn Objective) -> Self {
+        Self { inner, evaluations: 0, gradient_evaluations: 0 }
+    }
+
+    fn value(&mut self, x: &[f64]) -> Result<f64> {
+        self.evaluations += 1;
+        let value = self.inner.value(x)?;
+        if value.is_nan() {
+            return Err(IrError::InvalidOperation(format!("Objective returned NaN at {:?}", x)));
+        }
+        Ok(value)
+    }
+
+    fn gradient(&mut self, x: &[f64]) -> Result<Vec<f64>> {
+        self.gradient_evaluations += 1;
+        self.inner.gradient(x)
+    }
+
+    fn finish(self, parameters: Vec<f64>, value: f64, history: Vec<IterationRecord>, converged: bool) -> OptimizationResult {
+        OptimizationResult {
+            parameters,
+            value,
+            iterations: history.len(),
+            evaluations: self.evaluations,
+            gradient_evaluations: self.gradient_evaluations,
+            converged,
+            history,
+        }
+    }
+}
+
+// ============================================================================
+// Optimization Result
+// ============================================================================
+
+/// 单次迭代记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct IterationRecord {
+    pub iteration: usize,
+    /// 本次迭代后的目标值（SPSA 为两次扰动求值的平均）
+    pub value: f64,
+    pub parameters: Vec<f64>,
+    /// 截至本次迭代的函数求值次数
+    pub evaluations: usize,
+}
+
+/// 优化结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptimizationResult {
+    pub parameters: Vec<f64>,
+    pub value: f64,
+    pub iterations: usize,
+    pub evaluations: usize,
+    pub gradient_evaluations: usize,
+    /// 是否满足收敛判据（否则为达到迭代上限）
+    pub converged: bool,
+    pub history: Vec<IterationRecord>,
+}
+
+// ============================================================================
+// Optimizer Trait
+// ============================================================================
+
+/// 经典优化器
+pub trait Optimizer: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn minimize(&self, objective: &mut dyn Objective, initial: &[f64]) -> Result<OptimizationResult>;
+}
+
+fn check_initial(initial: &[f64]) -> Result<()> {
+    if initial.is_empty() {
+        return Err(IrError::InvalidOperation("Cannot optimize over zero parameters".to_string()));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Nelder-Mead
+// ============================================================================
+
+/// Nelder-Mead 单纯形法
+#[derive(Debug, Clone)]
+pub struct NelderMead {
+    pub max_iterations: usize,
+    /// 单纯形顶点函数值之差的收敛阈值
+    pub tolerance: f64,
+    /// 初始单纯形沿各坐标轴的边长
+    pub initial_step: f64,
+}
+
+impl Default for NelderMead {
+    fn default() -> Self {
+        Self { max_iterations: 500, tolerance: 1e-8, initial_step: 0.5 }
+    }
+}
+
+impl NelderMead {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_iterations(mut self, max: usize) -> Self {
+        self.max_iterations = max;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_initial_step(mut self, step: f64) -> Self {
+        self.initial_step = step;
+        self
+    }
+}
+
+impl Optimizer for NelderMead {
+    fn name(&self) -> &str {
+        "Nelder-Mead"
+    }
+
+    fn minimize(&self, objective: &mut dyn Objective, initial: &[f64]) -> Result<OptimizationResult> {
+        check_initial(initial)?;
+        let n = initial.len();
+        let mut f = Counted::new(objective);
+
+        let mut simplex: Vec<(Vec<f64>, f64)> = Vec::with_capacity(n + 1);
+        simplex.push((initial.to_vec(), f.value(initial)?));
+        for i in 0..n {
+            let mut x = initial.to_vec();
+            x[i] += self.initial_step;
+            let value = f.value(&x)?;
+            simplex.push((x, value));
+        }
+
+        let mut history = Vec::new();
+        let mut converged = false;
+        for iteration in 0..self.max_iterations {
+            simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+            if simplex[n].1 - simplex[0].1 <= self.tolerance {
+                converged = true;
+                break;
+            }
+
+            let centroid: Vec<f64> = (0..n)
+                .map(|j| simplex[..n].iter().map(|(x, _)| x[j]).sum::<f64>() / n as f64)
+                .collect();
+            let along = |t: f64| -> Vec<f64> {
+                centroid.iter().zip(&simplex[n].0).map(|(c, w)| c + t * (c - w)).collect()
+            };
+
+            let reflected = along(1.0);
+            let fr = f.value(&reflected)?;
+            if fr < simplex[0].1 {
+                let expanded = along(2.0);
+                let fe = f.value(&expanded)?;
+                simplex[n] = if fe < fr { (expanded, fe) } else { (reflected, fr) };
+            } else if fr < simplex[n - 1].1 {
+                simplex[n] = (reflected, fr);
+            } else {
+                // 反射点优于最差点时外收缩，否则内收缩
+                let outside = fr < simplex[n].1;
+                let contracted = along(if outside { 0.5 } else { -0.5 });
+                let fc = f.value(&contracted)?;
+                let threshold = if outside { fr } else { simplex[n].1 };
+                if fc < threshold {
+                    simplex[n] = (contracted, fc);
+                } else {
+                    // 向最优点收缩
+                    let best = simplex[0].0.clone();
+                    for vertex in simplex.iter_mut().skip(1) {
+                        let x: Vec<f64> = best.iter().zip(&vertex.0).map(|(b, v)| b + 0.5 * (v - b)).collect();
+                        let value = f.value(&x)?;
+                        *vertex = (x, value);
+                    }
+                }
+            }
+
+            let best = simplex.iter().min_by(|a, b| a.1.total_cmp(&b.1)).expect("simplex is not empty");
+            history.push(IterationRecord {
+                iteration,
+                value: best.1,
+                parameters: best.0.clone(),
+                evaluations: f.evaluations,
+            });
+        }
+
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (x, value) = simplex.swap_remove(0);
+        Ok(f.finish(x, value, history, converged))
+    }
+}
+
+// ============================================================================
+// COBYLA
+// ============================================================================
+
+/// 不等式约束 c(x) ≥ 0
+pub type Constraint = Arc<dyn Fn(&[f64]) -> f64 + Send + Sync>;
+
+/// Powell 的 COBYLA（Constrained Optimization BY Linear Approximations）
+///
+/// 维护 n+1 个顶点的单纯形，在其上线性插值目标与每个约束；每次迭代在信赖域内求解
+/// 线性化子问题：先使线性化约束的违反量最小，再在此前提下使线性化目标最小。
+/// 试探点按罚函数 Φ = f + μ·max(0, -min c) 接受，μ 在预测违反量下降时按需增大。
+/// 单纯形变形（顶点过远或过扁）时先做几何改进步，否则将信赖域半径 ρ 减半，直到 ρ 达到 `rho_end`。
+///
+/// 子问题的信赖域取边长 2ρ/√n 的立方体（内切于半径 ρ 的球），用单纯形法精确求解；
+/// 违反量以大权重的弹性变量计入目标，相当于 Powell 的两阶段求解。
+#[derive(Clone)]
+pub struct Cobyla {
+    /// 初始信赖域半径
+    pub rho_begin: f64,
+    /// 最终信赖域半径（收敛判据）
+    pub rho_end: f64,
+    pub max_evaluations: usize,
+    /// 不等式约束，每个要求 c(x) ≥ 0
+    pub constraints: Vec<Constraint>,
+}
+
+impl Default for Cobyla {
+    fn default() -> Self {
+        Self { rho_begin: 0.5, rho_end: 1e-6, max_evaluations: 2000, constraints: Vec::new() }
+    }
+}
+
+impl std::fmt::Debug for Cobyla {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cobyla")
+            .field("rho_begin", &self.rho_begin)
+            .field("rho_end", &self.rho_end)
+            .field("max_evaluations", &self.max_evaluations)
+            .field("constraints", &self.constraints.len())
+            .finish()
+    }
+}
+
+impl Cobyla {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rho(mut self, rho_begin: f64, rho_end: f64) -> Self {
+        self.rho_begin = rho_begin;
+        self.rho_end = rho_end;
+        self
+    }
+
+    pub fn with_max_evaluations(mut self, max: usize) -> Self {
+        self.max_evaluations = max;
+        self
+    }
+
+    /// 添加不等式约束 c(x) ≥ 0
+    pub fn with_constraint(mut self, constraint: impl Fn(&[f64]) -> f64 + Send + Sync + 'static) -> Self {
+        self.constraints.push(Arc::new(constraint));
+        self
+    }
+}
+
+/// 单纯形顶点：位置、目标值与约束值
+#[derive(Debug, Clone)]
+struct Vertex {
+    x: Vec<f64>,
+    f: f64,
+    c: Vec<f64>,
+}
+
+impl Vertex {
+    /// 约束违反量 max(0, -min c)
+    fn violation(&self) -> f64 {
+        self.c.iter().fold(0.0, |v: f64, &c| v.max(-c))
+    }
+
+    fn merit(&self, mu: f64) -> f64 {
+        self.f + mu * self.violation()
+    }
+}
+
+impl Cobyla {
+    fn evaluate(&self, f: &mut Counted, x: Vec<f64>) -> Result<Vertex> {
+        let value = f.value(&x)?;
+        let c = self.constraints.iter().map(|constraint| constraint(&x)).collect::<Vec<_>>();
+        if c.iter().any(|c| c.is_nan()) {
+            return Err(IrError::InvalidOperation(format!("Constraint returned NaN at {:?}", x)));
+        }
+        Ok(Vertex { x, f: value, c })
+    }
+
+    /// 以 base 为起点、沿各坐标轴偏移 rho 的单纯形
+    fn initial_simplex(&self, f: &mut Counted, base: Vertex, rho: f64) -> Result<Vec<Vertex>> {
+        let mut simplex = vec![base];
+        for i in 0..simplex[0].x.len() {
+            let mut x = simplex[0].x.clone();
+            x[i] += rho;
+            simplex.push(self.evaluate(f, x)?);
+        }
+        Ok(simplex)
+    }
+}
+
+/// 罚函数意义下的最优顶点（相同时取违反量较小者）
+fn best_vertex(simplex: &[Vertex], mu: f64) -> usize {
+    (0..simplex.len())
+        .min_by(|&a, &b| {
+            simplex[a].merit(mu).total_cmp(&simplex[b].merit(mu))
+                .then(simplex[a].violation().total_cmp(&simplex[b].violation()))
+        })
+        .expect("simplex is not empty")
+}
+
+impl Optimizer for Cobyla {
+    fn name(&self) -> &str {
+        "COBYLA"
+    }
+
+    fn minimize(&self, objective: &mut dyn Objective, initial: &[f64]) -> Result<OptimizationResult> {
+        check_initial(initial)?;
+        let n = initial.len();
+        let m = self.constraints.len();
+        let mut f = Counted::new(objective);
+        let start = self.evaluate(&mut f, initial.to_vec())?;
+        let mut simplex = self.initial_simplex(&mut f, start, self.rho_begin)?;
+        let mut rho = self.rho_begin;
+        let mut mu = 0.0;
+        let mut history = Vec::new();
+        let mut converged = false;
+        // 上一次试探步不理想：本次先改善几何或缩小 ρ
+        let mut improve = false;
+
+        while f.evaluations < self.max_evaluations {
+            let pivot = best_vertex(&simplex, mu);
+            simplex.swap(0, pivot);
+            let base = &simplex[0];
+            let offsets: Vec<Vec<f64>> = simplex[1..]
+                .iter()
+                .map(|v| v.x.iter().zip(&base.x).map(|(a, b)| a - b).collect())
+                .collect();
+            let Some(inverse) = invert(&offsets) else {
+                // 单纯形退化：以当前最优点重建
+                let base = simplex.swap_remove(0);
+                simplex = self.initial_simplex(&mut f, base, rho)?;
+                continue;
+            };
+
+            // 线性模型：offsets · g = Δf，offsets · a_i = Δc_i
+            let slope = |delta: Vec<f64>| -> Vec<f64> {
+                (0..n).map(|k| (0..n).map(|j| inverse[k][j] * delta[j]).sum()).collect()
+            };
+            let g = slope(simplex[1..].iter().map(|v| v.f - base.f).collect());
+            let linear_constraints: Vec<(f64, Vec<f64>)> = (0..m)
+                .map(|i| (base.c[i], slope(simplex[1..].iter().map(|v| v.c[i] - base.c[i]).collect())))
+                .collect();
+
+            // 信赖域试探步；步长过短或预测无改进时转入几何改进或缩小 ρ
+            let mut tried = false;
+            if !improve {
+                let step = trust_region_step(&g, &linear_constraints, rho / (n as f64).sqrt());
+                let step_norm = norm(&step);
+                let violation_after = linear_constraints
+                    .iter()
+                    .fold(0.0, |v: f64, (b, a)| v.max(-(b + dot(a, &step))));
+                let f_decrease = -dot(&g, &step);
+                let v_decrease = base.violation() - violation_after;
+                if v_decrease > 0.0 && f_decrease + 0.5 * mu * v_decrease < 0.0 {
+                    mu = -2.0 * f_decrease / v_decrease;
+                    // μ 增大后最优顶点可能改变
+                    if best_vertex(&simplex, mu) != 0 {
+                        continue;
+                    }
+                }
+                let predicted = f_decrease + mu * v_decrease;
+
+                if step_norm >= 0.1 * rho && predicted > 0.0 {
+                    let trial = self.evaluate(&mut f, base.x.iter().zip(&step).map(|(x, d)| x + d).collect())?;
+                    let actual = simplex[0].merit(mu) - trial.merit(mu);
+                    improve = actual < 0.1 * predicted;
+                    tried = true;
+                    replace_vertex(&mut simplex, &inverse, &offsets, &step, trial, actual > 0.0, rho);
+                }
+            }
+
+            if !tried && f.evaluations < self.max_evaluations {
+                improve = false;
+                if let Some((j, direction)) = geometry_step(&offsets, &inverse, rho) {
+                    // 沿预测罚函数较小的方向移动
+                    let linear_merit = |s: f64| {
+                        let d: Vec<f64> = direction.iter().map(|x| s * x).collect();
+                        dot(&g, &d) + mu * linear_constraints.iter().fold(0.0, |v: f64, (b, a)| v.max(-(b + dot(a, &d))))
+                    };
+                    let sign = if linear_merit(1.0) <= linear_merit(-1.0) { 1.0 } else { -1.0 };
+                    let x = simplex[0].x.iter().zip(&direction).map(|(x, d)| x + sign * d).collect();
+                    simplex[j + 1] = self.evaluate(&mut f, x)?;
+                } else if rho <= self.rho_end {
+                    converged = true;
+                    break;
+                } else {
+                    rho *= 0.5;
+                    if rho <= 1.5 * self.rho_end {
+                        rho = self.rho_end;
+                    }
+                }
+            }
+
+            let best = &simplex[best_vertex(&simplex, mu)];
+            history.push(IterationRecord {
+                iteration: history.len(),
+                value: best.f,
+                parameters: best.x.clone(),
+                evaluations: f.evaluations,
+            });
+        }
+
+        let best = simplex.swap_remove(best_vertex(&simplex, mu));
+        Ok(f.finish(best.x, best.f, history, converged))
+    }
+}
+
+/// 用试探点替换一个顶点
+///
+/// 试探点更优时替换使单纯形体积最大的顶点；否则只在能改善几何（体积更大或换掉过远顶点）时替换。
+fn replace_vertex(
+    simplex: &mut [Vertex],
+    inverse: &[Vec<f64>],
+    offsets: &[Vec<f64>],
+    step: &[f64],
+    trial: Vertex,
+    improved: bool,
+    rho: f64,
+) {
+    let n = offsets.len();
+    // 替换第 j 个顶点后体积变为原来的 |step · inverse 第 j 列| 倍
+    let score = |j: usize| {
+        let volume = (0..n).map(|k| step[k] * inverse[k][j]).sum::<f64>().abs();
+        if improved { volume } else { volume * (norm(&offsets[j]) / rho).max(1.0).powi(2) }
+    };
+    let j = (0..n).max_by(|&a, &b| score(a).total_cmp(&score(b))).expect("simplex has n >= 1 edges");
+    if improved || score(j) > 1.0 {
+        simplex[j + 1] = trial;
+    }
+}
+
+/// 单纯形变形时需要替换的顶点及新顶点相对最优点的位移
+///
+/// 顶点离最优点超过 2.1ρ，或到对面超平面的距离小于 0.25ρ 时替换；新位移沿该超平面的法向，长度 0.5ρ。
+fn geometry_step(offsets: &[Vec<f64>], inverse: &[Vec<f64>], rho: f64) -> Option<(usize, Vec<f64>)> {
+    let n = offsets.len();
+    let column = |j: usize| -> Vec<f64> { (0..n).map(|k| inverse[k][j]).collect() };
+    let far = (0..n).max_by(|&a, &b| norm(&offsets[a]).total_cmp(&norm(&offsets[b]))).expect("n >= 1");
+    let flat = (0..n).max_by(|&a, &b| norm(&column(a)).total_cmp(&norm(&column(b)))).expect("n >= 1");
+    let j = if norm(&offsets[far]) > 2.1 * rho {
+        far
+    } else if 1.0 / norm(&column(flat)) < 0.25 * rho {
+        flat
+    } else {
+        return None;
+    };
+    let normal = column(j);
+    let scale = 0.5 * rho / norm(&normal);
+    Some((j, normal.into_iter().map(|x| x * scale).collect()))
+}
+
+/// 信赖域子问题：|d_k| ≤ half_width 内使线性化约束 b_i + a_i·d ≥ 0 的违反量最小，再使 g·d 最小
+fn trust_region_step(g: &[f64], constraints: &[(f64, Vec<f64>)], half_width: f64) -> Vec<f64> {
+    let n = g.len();
+    let m = constraints.len();
+    // 违反量的权重远大于目标的变化范围
+    let scale = g.iter().map(|x| x.abs()).sum::<f64>() + 1.0;
+    let weight = 1e6 * scale;
+
+    // 变量 u = d + half_width ∈ [0, 2·half_width]，弹性 t ≥ 0，剩余 s ≥ 0，上界松弛 w ≥ 0
+    let (u0, t0, s0, w0) = (0, n, n + m, n + 2 * m);
+    let cols = 2 * n + 2 * m;
+    let mut cost = vec![0.0; cols];
+    cost[..n].copy_from_slice(g);
+    for c in &mut cost[t0..s0] {
+        *c = weight;
+    }
+
+    // b_i + a_i·(u - h) + t_i - s_i = 0
+    let mut rows = Vec::with_capacity(m + n);
+    let mut basis = Vec::with_capacity(m + n);
+    for (i, (b, a)) in constraints.iter().enumerate() {
+        let mut row = vec![0.0; cols + 1];
+        row[u0..u0 + n].copy_from_slice(a);
+        row[t0 + i] = 1.0;
+        row[s0 + i] = -1.0;
+        row[cols] = half_width * a.iter().sum::<f64>() - b;
+        if row[cols] >= 0.0 {
+            basis.push(t0 + i);
+        } else {
+            row.iter_mut().for_each(|x| *x = -*x);
+            basis.push(s0 + i);
+        }
+        rows.push(row);
+    }
+    for k in 0..n {
+        let mut row = vec![0.0; cols + 1];
+        row[u0 + k] = 1.0;
+        row[w0 + k] = 1.0;
+        row[cols] = 2.0 * half_width;
+        rows.push(row);
+        basis.push(w0 + k);
+    }
+
+    simplex_method(&mut rows, &mut basis, &cost);
+    let mut u = vec![0.0; n];
+    for (row, &var) in rows.iter().zip(&basis) {
+        if var < n {
+            u[var] = row[cols];
+        }
+    }
+    u.into_iter().map(|x| x - half_width).collect()
+}
+
+/// 单纯形法求解 min cost·x，rows 为已按 basis 规范化的表（最后一列为右端项）
+///
+/// 用 Bland 规则避免循环。
+fn simplex_method(rows: &mut [Vec<f64>], basis: &mut [usize], cost: &[f64]) {
+    const EPS: f64 = 1e-12;
+    let cols = cost.len();
+    for _ in 0..50 * (rows.len() + cols) {
+        let reduced = |j: usize| cost[j] - rows.iter().zip(basis.iter()).map(|(row, &b)| cost[b] * row[j]).sum::<f64>();
+        let Some(entering) = (0..cols).find(|&j| !basis.contains(&j) && reduced(j) < -EPS * (1.0 + cost[j].abs())) else {
+            return;
+        };
+        let leaving = (0..rows.len())
+            .filter(|&r| rows[r][entering] > EPS)
+            .min_by(|&a, &b| {
+                let ratio = |r: usize| rows[r][cols] / rows[r][entering];
+                ratio(a).total_cmp(&ratio(b)).then(basis[a].cmp(&basis[b]))
+            });
+        let Some(leaving) = leaving else {
+            return;
+        };
+        let pivot = rows[leaving][entering];
+        rows[leaving].iter_mut().for_each(|x| *x /= pivot);
+        let pivot_row = rows[leaving].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[entering];
+            if r != leaving && factor != 0.0 {
+                row.iter_mut().zip(&pivot_row).for_each(|(x, p)| *x -= factor * p);
+            }
+        }
+        basis[leaving] = entering;
+    }
+}
+
+/// 按行给出的方阵求逆（部分主元 Gauss-Jordan），奇异时返回 None
+fn invert(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let scale = matrix.iter().flatten().fold(0.0, |s: f64, x| s.max(x.abs()));
+    let mut a: Vec<Vec<f64>> = matrix.to_vec();
+    let mut inv: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&x, &y| a[x][col].abs().total_cmp(&a[y][col].abs()))?;
+        if a[pivot][col].abs() <= 1e-12 * scale {
+            return None;
+        }
+        a.swap(col, pivot);
+        inv.swap(col, pivot);
+        let p = a[col][col];
+        a[col].iter_mut().for_each(|x| *x /= p);
+        inv[col].iter_mut().for_each(|x| *x /= p);
+        for row in 0..n {
+            let factor = a[row][col];
+            if row != col && factor != 0.0 {
+                for k in 0..n {
+                    a[row][k] -= factor * a[col][k];
+                    inv[row][k] -= factor * inv[col][k];
+                }
+            }
+        }
+    }
+    Some(inv)
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn norm(a: &[f64]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+// ============================================================================
+// SPSA
+// ============================================================================
+
+/// 同时扰动随机逼近
+///
+/// 步长 a_k = a / (k + 1 + A)^α，扰动 c_k = c / (k + 1)^γ，扰动方向各分量独立取 ±1。
+#[derive(Debug, Clone)]
+pub struct Spsa {
+    pub max_iterations: usize,
+    pub a: f64,
+    pub c: f64,
+    pub alpha: f64,
+    pub gamma: f64,
+    /// 步长稳定常数 A（默认为迭代上限的 10%）
+    pub stability: f64,
+    pub seed: u64,
+}
+
+impl Default for Spsa {
+    fn default() -> Self {
+        Self {
+            max_iterations: 200,
+            a: 0.2,
+            c: 0.1,
+            alpha: 0.602,
+            gamma: 0.101,
+            stability: 20.0,
+            seed: 0x5eed,
+        }
+    }
+}
+
+impl Spsa {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_iterations(mut self, max: usize) -> Self {
+        self.max_iterations = max;
+        self.stability = max as f64 * 0.1;
+        self
+    }
+
+    pub fn with_gains(mut self, a: f64, c: f64) -> Self {
+        self.a = a;
+        self.c = c;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl Optimizer for Spsa {
+    fn name(&self) -> &str {
+        "SPSA"
+    }
+
+    fn minimize(&self, objective: &mut dyn Objective, initial: &[f64]) -> Result<OptimizationResult> {
+        check_initial(initial)?;
+        let mut f = Counted::new(objective);
+        let mut rng = SplitMix64::new(self.seed);
+        let mut x = initial.to_vec();
+        let mut history = Vec::new();
+
+        for k in 0..self.max_iterations {
+            let ak = self.a / (k as f64 + 1.0 + self.stability).powf(self.alpha);
+            let ck = self.c / (k as f64 + 1.0).powf(self.gamma);
+            let delta: Vec<f64> = x.iter().map(|_| if rng.next_u64() & 1 == 1 { 1.0 } else { -1.0 }).collect();
+
+            let plus: Vec<f64> = x.iter().zip(&delta).map(|(xi, d)| xi + ck * d).collect();
+            let minus: Vec<f64> = x.iter().zip(&delta).map(|(xi, d)| xi - ck * d).collect();
+            let (fp, fm) = (f.value(&plus)?, f.value(&minus)?);
+            let slope = (fp - fm) / (2.0 * ck);
+            for (xi, d) in x.iter_mut().zip(&delta) {
+                // Δ_i = ±1，故 1/Δ_i = Δ_i
+                *xi -= ak * slope * d;
+            }
+
+            history.push(IterationRecord {
+                iteration: k,
+                value: 0.5 * (fp + fm),
+                parameters: x.clone(),
+                evaluations: f.evaluations,
+            });
+        }
+
+        let value = f.value(&x)?;
+        Ok(f.finish(x, value, history, false))
+    }
+}
+
+// ============================================================================
+// Gradient Descent
+// ============================================================================
+
+/// 梯度下降（可选动量）
+#[derive(Debug, Clone)]
+pub struct GradientDescent {
+    pub learning_rate: f64,
+    pub momentum: f64,
+    pub max_iterations: usize,
+    /// 梯度范数的收敛阈值
+    pub tolerance: f64,
+}
+
+impl Default for GradientDescent {
+    fn default() -> Self {
+        Self { learning_rate: 0.1, momentum: 0.0, max_iterations: 200, tolerance: 1e-6 }
+    }
+}
+
+impl GradientDescent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_learning_rate(mut self, rate: f64) -> Self {
+        self.learning_rate = rate;
+        self
+    }
+
+    pub fn with_momentum(mut self, momentum: f64) -> Self {
+        self.momentum = momentum;
+        self
+    }
+
+    pub fn with_max_iterations(mut self, max: usize) -> Self {
+        self.max_iterations = max;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl Optimizer for GradientDescent {
+    fn name(&self) -> &str {
+        "GradientDescent"
+    }
+
+    fn minimize(&self, objective: &mut dyn Objective, initial: &[f64]) -> Result<OptimizationResult> {
+        check_initial(initial)?;
+        let mut f = Counted::new(objective);
+        let mut x = initial.to_vec();
+        let mut velocity = vec![0.0; x.len()];
+        let mut history = Vec::new();
+        let mut converged = false;
+
+        for iteration in 0..self.max_iterations {
+            let gradient = f.gradient(&x)?;
+            if gradient.len() != x.len() {
+                return Err(IrError::InvalidOperation(format!(
+                    "Gradient has {} components, expected {}", gradient.len(), x.len()
+                )));
+            }
+            if gradient.iter().map(|g| g * g).sum::<f64>().sqrt() <= self.tolerance {
+                converged = true;
+                break;
+            }
+            for ((xi, vi), g) in x.iter_mut().zip(&mut velocity).zip(&gradient) {
+                *vi = self.momentum * *vi - self.learning_rate * g;
+                *xi += *vi;
+            }
+            history.push(IterationRecord {
+                iteration,
+                value: f.value(&x)?,
+                parameters: x.clone(),
+                evaluations: f.evaluations,
+            });
+        }
+
+        let value = match history.last() {
+            Some(record) => record.value,
+            None => f.value(&x)?,
+        };
+        Ok(f.finish(x, value, history, converged))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rosenbrock 函数，最小值 0 位于 (1, 1)
+    fn rosenbrock(x: &[f64]) -> Result<f64> {
+        Ok((1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2))
+    }
+
+    fn bowl(x: &[f64]) -> Result<f64> {
+        Ok((x[0] - 1.0).powi(2) + 2.0 * (x[1] + 0.5).powi(2) + 3.0)
+    }
+
+    #[test]
+    fn test_nelder_mead() {
+        let result = NelderMead::new().with_tolerance(1e-12).minimize(&mut rosenbrock, &[-1.2, 1.0]).unwrap();
+        assert!(result.converged);
+        assert!(result.value < 1e-8);
+        assert!((result.parameters[0] - 1.0).abs() < 1e-3);
+        // 历史中的最优值单调不增
+        assert!(result.history.windows(2).all(|w| w[1].value <= w[0].value));
+        assert_eq!(result.iterations, result.history.len());
+    }
+
+    #[test]
+    fn test_cobyla() {
+        let result = Cobyla::new().minimize(&mut bowl, &[0.0, 0.0]).unwrap();
+        assert!(result.converged);
+        assert!((result.value - 3.0).abs() < 1e-8);
+        assert!((result.parameters[1] + 0.5).abs() < 1e-3);
+        assert!(result.evaluations <= Cobyla::new().max_evaluations);
+    }
+
+    /// Powell (1994) 与 SciPy 的 COBYLA 测试问题，对照其已知最优解
+    #[test]
+    fn test_cobyla_constrained() {
+        // 单位圆盘上最小化 x·y：最优值 -1/2，位于 (1/√2, -1/√2)
+        let mut product = |x: &[f64]| -> Result<f64> { Ok(x[0] * x[1]) };
+        let disk = Cobyla::new().with_constraint(|x| 1.0 - x[0] * x[0] - x[1] * x[1]);
+        let result = disk.minimize(&mut product, &[1.0, 1.0]).unwrap();
+        assert!(result.converged);
+        assert!((result.value + 0.5).abs() < 1e-6, "{:?}", result.parameters);
+        assert!((result.parameters[0].abs() - 0.5f64.sqrt()).abs() < 1e-3);
+        assert!(result.parameters[0] * result.parameters[1] < 0.0);
+
+        // SciPy test_cobyla：x² + |y|³，约束 x² + y² = 25 写成两个不等式
+        let mut cubic = |x: &[f64]| -> Result<f64> { Ok(x[0] * x[0] + x[1].abs().powi(3)) };
+        let circle = Cobyla::new()
+            .with_rho(1.0, 1e-7)
+            .with_constraint(|x| x[0] * x[0] + x[1] * x[1] - 25.0)
+            .with_constraint(|x| 25.0 - x[0] * x[0] - x[1] * x[1]);
+        let result = circle.minimize(&mut cubic, &[4.95, 0.66]).unwrap();
+        let expected = [(25.0f64 - 4.0 / 9.0).sqrt(), 2.0 / 3.0];
+        assert!((result.parameters[0] - expected[0]).abs() < 1e-4, "{:?}", result.parameters);
+        assert!((result.parameters[1] - expected[1]).abs() < 1e-4, "{:?}", result.parameters);
+
+        // Rosen-Suzuki：最优解 (0, 1, 2, -1)，最优值 -44
+        let mut rosen_suzuki = |x: &[f64]| -> Result<f64> {
+            Ok(x[0] * x[0] + x[1] * x[1] + 2.0 * x[2] * x[2] + x[3] * x[3]
+                - 5.0 * x[0] - 5.0 * x[1] - 21.0 * x[2] + 7.0 * x[3])
+        };
+        let optimizer = Cobyla::new()
+            .with_rho(1.0, 1e-6)
+            .with_constraint(|x| 8.0 - x[0] * x[0] - x[1] * x[1] - x[2] * x[2] - x[3] * x[3] - x[0] + x[1] - x[2] + x[3])
+            .with_constraint(|x| 10.0 - x[0] * x[0] - 2.0 * x[1] * x[1] - x[2] * x[2] - 2.0 * x[3] * x[3] + x[0] + x[3])
+            .with_constraint(|x| 5.0 - 2.0 * x[0] * x[0] - x[1] * x[1] - x[2] * x[2] - 2.0 * x[0] + x[1] + x[3]);
+        let result = optimizer.minimize(&mut rosen_suzuki, &[0.0; 4]).unwrap();
+        assert!((result.value + 44.0).abs() < 1e-3, "{} at {:?}", result.value, result.parameters);
+        for (x, expected) in result.parameters.iter().zip([0.0, 1.0, 2.0, -1.0]) {
+            assert!((x - expected).abs() < 1e-2, "{:?}", result.parameters);
+        }
+        assert!(optimizer.constraints.iter().all(|c| c(&result.parameters) > -1e-6));
+    }
+
+    #[test]
+    fn test_spsa() {
+        let result = Spsa::new().with_max_iterations(300).minimize(&mut bowl, &[3.0, 2.0]).unwrap();
+        assert_eq!(result.history.len(), 300);
+        assert_eq!(result.evaluations, 601);
+        assert!(result.value < 3.05);
+        // 相同种子结果可复现
+        let again = Spsa::new().with_max_iterations(300).minimize(&mut bowl, &[3.0, 2.0]).unwrap();
+        assert_eq!(result.parameters, again.parameters);
+    }
+
+    #[test]
+    fn test_gradient_descent() {
+        let result = GradientDescent::new()
+            .with_learning_rate(0.2)
+            .with_momentum(0.5)
+            .with_max_iterations(500)
+            .minimize(&mut bowl, &[3.0, 2.0])
+            .unwrap();
+        assert!(result.converged);
+        assert!((result.value - 3.0).abs() < 1e-9);
+        assert!(result.gradient_evaluations > 0);
+        assert!(GradientDescent::new().minimize(&mut bowl, &[]).is_err());
+    }
+}
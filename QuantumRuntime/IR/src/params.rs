@@ -0,0 +1,513 @@
+//! 符号参数模块
+//!
+//! 参数门的角度可以是符号表达式（具名参数、四则运算、常数、sin/cos），
+//! 电路编译一次后通过 `bind` 代入不同取值，无需重建 DAG。
+//!
+//! 符号门以 `Operation::Symbolic` 表示：内部门的数值参数只是占位，
+//! 绑定后替换为表达式的求值结果。
+
+use std::collections::{BTreeSet, HashMap};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::circuit::CircuitDag;
+use crate::operation::{Operation, SingleQubitGate, TwoQubitGate};
+use crate::qubit::LogicalQubitId;
+use crate::{Result, IrError};
+
+/// 参数取值表
+pub type ParamBinding = HashMap<Param, f64>;
+
+// ============================================================================
+// Param
+// ============================================================================
+
+/// 具名参数
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Param(String);
+
+impl Param {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    /// 对应的表达式
+    pub fn expr(&self) -> ParamExpr {
+        ParamExpr::Param(self.clone())
+    }
+}
+
+impl std::fmt::Display for Param {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// ============================================================================
+// ParamExpr
+// ============================================================================
+
+/// 参数表达式
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamExpr {
+    Const(f64),
+    Param(Param),
+    Add(Box<ParamExpr>, Box<ParamExpr>),
+    Sub(Box<ParamExpr>, Box<ParamExpr>),
+    Mul(Box<ParamExpr>, Box<ParamExpr>),
+    Div(Box<ParamExpr>, Box<ParamExpr>),
+    Neg(Box<ParamExpr>),
+    Sin(Box<ParamExpr>),
+    Cos(Box<ParamExpr>),
+}
+
+impl ParamExpr {
+    pub fn constant(value: f64) -> Self {
+        ParamExpr::Const(value)
+    }
+
+    pub fn param(name: impl Into<String>) -> Self {
+        ParamExpr::Param(Param::new(name))
+    }
+
+    pub fn sin(self) -> Self {
+        ParamExpr::Sin(Box::new(self))
+    }
+
+    pub fn cos(self) -> Self {
+        ParamExpr::Cos(Box::new(self))
+    }
+
+    /// 常数表达式的值
+    pub fn as_constant(&self) -> Option<f64> {
+        match self {
+            ParamExpr::Const(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.as_constant().is_some()
+    }
+
+    /// 表达式中出现的参数
+    pub fn parameters(&self) -> BTreeSet<Param> {
+        let mut out = BTreeSet::new();
+        self.collect_parameters(&mut out);
+        out
+    }
+
+    fn collect_parameters(&self, out: &mut BTreeSet<Param>) {
+        match self {
+            ParamExpr::Const(_) => {}
+            ParamExpr::Param(p) => {
+                out.insert(p.clone());
+            }
+            ParamExpr::Add(a, b) | ParamExpr::Sub(a, b) | ParamExpr::Mul(a, b) | ParamExpr::Div(a, b) => {
+                a.collect_parameters(out);
+                b.collect_parameters(out);
+            }
+            ParamExpr::Neg(a) | ParamExpr::Sin(a) | ParamExpr::Cos(a) => a.collect_parameters(out),
+        }
+    }
+
+    /// 代入已知参数并折叠常数（未给出的参数保持符号）
+    /// 
+    /// 同时消去加减 0、乘除 1 与乘 0 的子式。
+    pub fn substitute(&self, values: &ParamBinding) -> ParamExpr {
+        let binary = |a: &ParamExpr, b: &ParamExpr, f: fn(f64, f64) -> f64, make: fn(Box<ParamExpr>, Box<ParamExpr>) -> ParamExpr| {
+            let (a, b) = (a.substitute(values), b.substitute(values));
+            match (a.as_constant(), b.as_constant()) {
+                (Some(x), Some(y)) => ParamExpr::Const(f(x, y)),
+                _ => make(Box::new(a), Box::new(b)).simplified(),
+            }
+        };
+        let unary = |a: &ParamExpr, f: fn(f64) -> f64, make: fn(Box<ParamExpr>) -> ParamExpr| {
+            let a = a.substitute(values);
+            match a.as_constant() {
+                Some(x) => ParamExpr::Const(f(x)),
+                None => make(Box::new(a)),
+            }
+        };
+        match self {
+            ParamExpr::Const(v) => ParamExpr::Const(*v),
+            ParamExpr::Param(p) => match values.get(p) {
+                Some(&v) => ParamExpr::Const(v),
+                None => self.clone(),
+            },
+            ParamExpr::Add(a, b) => binary(a, b, |x, y| x + y, ParamExpr::Add),
+            ParamExpr::Sub(a, b) => binary(a, b, |x, y| x - y, ParamExpr::Sub),
+            ParamExpr::Mul(a, b) => binary(a, b, |x, y| x * y, ParamExpr::Mul),
+            ParamExpr::Div(a, b) => binary(a, b, |x, y| x / y, ParamExpr::Div),
+            ParamExpr::Neg(a) => unary(a, |x| -x, ParamExpr::Neg),
+            ParamExpr::Sin(a) => unary(a, f64::sin, ParamExpr::Sin),
+            ParamExpr::Cos(a) => unary(a, f64::cos, ParamExpr::Cos),
+        }
+    }
+
+    /// 一层代数化简（操作数中至多一个为常数）
+    fn simplified(self) -> ParamExpr {
+        match self {
+            ParamExpr::Add(a, b) => match (a.as_constant(), b.as_constant()) {
+                (Some(0.0), _) => *b,
+                (_, Some(0.0)) => *a,
+                _ => ParamExpr::Add(a, b),
+            },
+            ParamExpr::Sub(a, b) => match (a.as_constant(), b.as_constant()) {
+                (_, Some(0.0)) => *a,
+                (Some(0.0), _) => -*b,
+                _ => ParamExpr::Sub(a, b),
+            },
+            ParamExpr::Mul(a, b) => match (a.as_constant(), b.as_constant()) {
+                (Some(0.0), _) | (_, Some(0.0)) => ParamExpr::Const(0.0),
+                (Some(1.0), _) => *b,
+                (_, Some(1.0)) => *a,
+                _ => ParamExpr::Mul(a, b),
+            },
+            ParamExpr::Div(a, b) => match (a.as_constant(), b.as_constant()) {
+                (Some(0.0), _) => ParamExpr::Const(0.0),
+                (_, Some(1.0)) => *a,
+                _ => ParamExpr::Div(a, b),
+            },
+            other => other,
+        }
+    }
+
+    /// 对 param 的符号导数（已折叠常数）
+    pub fn derivative(&self, param: &Param) -> ParamExpr {
+        let d = match self {
+            ParamExpr::Const(_) => ParamExpr::Const(0.0),
+            ParamExpr::Param(p) => ParamExpr::Const(if p == param { 1.0 } else { 0.0 }),
+            ParamExpr::Add(a, b) => a.derivative(param) + b.derivative(param),
+            ParamExpr::Sub(a, b) => a.derivative(param) - b.derivative(param),
+            ParamExpr::Mul(a, b) => {
+                a.derivative(param) * (**b).clone() + (**a).clone() * b.derivative(param)
+            }
+            ParamExpr::Div(a, b) => {
+                (a.derivative(param) * (**b).clone() - (**a).clone() * b.derivative(param))
+                    / ((**b).clone() * (**b).clone())
+            }
+            ParamExpr::Neg(a) => -a.derivative(param),
+            ParamExpr::Sin(a) => (**a).clone().cos() * a.derivative(param),
+            ParamExpr::Cos(a) => -((**a).clone().sin()) * a.derivative(param),
+        };
+        d.substitute(&ParamBinding::new())
+    }
+
+    /// 求值，所有参数都必须给出
+    pub fn evaluate(&self, values: &ParamBinding) -> Result<f64> {
+        match self.substitute(values) {
+            ParamExpr::Const(v) => Ok(v),
+            unbound => Err(IrError::InvalidOperation(format!(
+                "Unbound parameters in {}: {:?}",
+                unbound,
+                unbound.parameters().iter().map(Param::name).collect::<Vec<_>>()
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for ParamExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamExpr::Const(v) => write!(f, "{}", v),
+            ParamExpr::Param(p) => write!(f, "{}", p),
+            ParamExpr::Add(a, b) => write!(f, "({} + {})", a, b),
+            ParamExpr::Sub(a, b) => write!(f, "({} - {})", a, b),
+            ParamExpr::Mul(a, b) => write!(f, "{} * {}", a, b),
+            ParamExpr::Div(a, b) => write!(f, "{} / {}", a, b),
+            ParamExpr::Neg(a) => write!(f, "-{}", a),
+            ParamExpr::Sin(a) => write!(f, "sin({})", a),
+            ParamExpr::Cos(a) => write!(f, "cos({})", a),
+        }
+    }
+}
+
+impl From<f64> for ParamExpr {
+    fn from(value: f64) -> Self {
+        ParamExpr::Const(value)
+    }
+}
+
+impl From<Param> for ParamExpr {
+    fn from(param: Param) -> Self {
+        ParamExpr::Param(param)
+    }
+}
+
+impl From<&Param> for ParamExpr {
+    fn from(param: &Param) -> Self {
+        ParamExpr::Param(param.clone())
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $variant:ident) => {
+        impl<T: Into<ParamExpr>> $trait<T> for ParamExpr {
+            type Output = ParamExpr;
+            fn $method(self, rhs: T) -> ParamExpr {
+                ParamExpr::$variant(Box::new(self), Box::new(rhs.into()))
+            }
+        }
+
+        impl $trait<ParamExpr> for f64 {
+            type Output = ParamExpr;
+            fn $method(self, rhs: ParamExpr) -> ParamExpr {
+                ParamExpr::$variant(Box::new(ParamExpr::Const(self)), Box::new(rhs))
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, Add);
+impl_binary_op!(Sub, sub, Sub);
+impl_binary_op!(Mul, mul, Mul);
+impl_binary_op!(Div, div, Div);
+
+impl Neg for ParamExpr {
+    type Output = ParamExpr;
+    fn neg(self) -> ParamExpr {
+        match self {
+            ParamExpr::Const(v) => ParamExpr::Const(-v),
+            ParamExpr::Neg(inner) => *inner,
+            other => ParamExpr::Neg(Box::new(other)),
+        }
+    }
+}
+
+// ============================================================================
+// Symbolic Operations
+// ============================================================================
+
+impl Operation {
+    /// 构造符号参数门
+    ///
+    /// op 须为单/双比特参数门，表达式个数与门参数个数一致；
+    /// 表达式全为常数时直接返回具体门。
+    pub fn symbolic(op: Operation, params: Vec<ParamExpr>) -> Result<Operation> {
+        let arity = match &op {
+            Operation::Gate1 { gate, .. } if gate.is_parametric() => gate.parameters().len(),
+            Operation::Gate2 { gate, .. } if gate.is_parametric() => gate.parameters().len(),
+            _ => {
+                return Err(IrError::InvalidOperation(format!(
+                    "{} is not a parametric gate", op.name()
+                )))
+            }
+        };
+        if params.len() != arity {
+            return Err(IrError::InvalidOperation(format!(
+                "{} takes {} parameters, got {}", op.name(), arity, params.len()
+            )));
+        }
+        match params.iter().map(ParamExpr::as_constant).collect::<Option<Vec<_>>>() {
+            Some(values) => op.with_parameters(&values),
+            None => Ok(Operation::Symbolic { op: Box::new(op), params }),
+        }
+    }
+
+    /// 是否含未绑定参数
+    pub fn is_symbolic(&self) -> bool {
+        matches!(self, Operation::Symbolic { .. })
+    }
+
+    /// 操作中未绑定的参数
+    pub fn free_parameters(&self) -> BTreeSet<Param> {
+        match self {
+            Operation::Symbolic { params, .. } => params.iter().flat_map(ParamExpr::parameters).collect(),
+            _ => BTreeSet::new(),
+        }
+    }
+
+    /// 代入参数值；全部绑定时得到具体门，否则保留剩余符号
+    pub fn bind(&self, values: &ParamBinding) -> Result<Operation> {
+        match self {
+            Operation::Symbolic { op, params } => {
+                Operation::symbolic((**op).clone(), params.iter().map(|e| e.substitute(values)).collect())
+            }
+            _ => Ok(self.clone()),
+        }
+    }
+}
+
+// ============================================================================
+// Symbolic Gate Constructors
+// ============================================================================
+
+fn symbolic_gate(op: Operation, params: Vec<ParamExpr>) -> Operation {
+    Operation::symbolic(op, params).expect("parameter count matches the gate")
+}
+
+pub fn rx_param(qubit: LogicalQubitId, theta: impl Into<ParamExpr>) -> Operation {
+    symbolic_gate(Operation::gate1(SingleQubitGate::Rx(0.0), qubit), vec![theta.into()])
+}
+
+pub fn ry_param(qubit: LogicalQubitId, theta: impl Into<ParamExpr>) -> Operation {
+    symbolic_gate(Operation::gate1(SingleQubitGate::Ry(0.0), qubit), vec![theta.into()])
+}
+
+pub fn rz_param(qubit: LogicalQubitId, theta: impl Into<ParamExpr>) -> Operation {
+    symbolic_gate(Operation::gate1(SingleQubitGate::Rz(0.0), qubit), vec![theta.into()])
+}
+
+pub fn p_param(qubit: LogicalQubitId, phi: impl Into<ParamExpr>) -> Operation {
+    symbolic_gate(Operation::gate1(SingleQubitGate::P(0.0), qubit), vec![phi.into()])
+}
+
+pub fn cp_param(control: LogicalQubitId, target: LogicalQubitId, phi: impl Into<ParamExpr>) -> Operation {
+    symbolic_gate(Operation::gate2(TwoQubitGate::CP(0.0), control, target), vec![phi.into()])
+}
+
+pub fn ms_param(q1: LogicalQubitId, q2: LogicalQubitId, theta: impl Into<ParamExpr>) -> Operation {
+    symbolic_gate(Operation::gate2(TwoQubitGate::MS(0.0), q1, q2), vec![theta.into()])
+}
+
+// ============================================================================
+// Circuit Binding
+// ============================================================================
+
+impl CircuitDag {
+    /// 电路中出现的全部参数（按名称排序）
+    pub fn parameters(&self) -> Vec<Param> {
+        let mut params = BTreeSet::new();
+        for node in self.nodes() {
+            params.extend(node.op.free_parameters());
+        }
+        params.into_iter().collect()
+    }
+
+    /// 是否仍含未绑定参数
+    pub fn is_parameterized(&self) -> bool {
+        self.nodes().iter().any(|n| n.op.is_symbolic())
+    }
+
+    /// 代入参数值，返回绑定后的副本
+    ///
+    /// 只替换节点上的操作，DAG 结构（节点 ID、边）保持不变；未给出的参数保持符号。
+    pub fn bind(&self, values: &ParamBinding) -> Result<CircuitDag> {
+        let mut bound = self.clone();
+        for id in 0..bound.num_nodes() {
+            let node = bound.get_node_mut(id).expect("node id in range");
+            if node.op.is_symbolic() {
+                node.op = node.op.bind(values)?;
+            }
+        }
+        Ok(bound)
+    }
+
+    /// 按 `parameters()` 的顺序代入参数向量
+    pub fn bind_values(&self, values: &[f64]) -> Result<CircuitDag> {
+        let params = self.parameters();
+        if params.len() != values.len() {
+            return Err(IrError::InvalidOperation(format!(
+                "Circuit has {} parameters, got {} values", params.len(), values.len()
+            )));
+        }
+        self.bind(&params.into_iter().zip(values.iter().copied()).collect())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation::{cnot, h};
+    use std::f64::consts::PI;
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    fn binding(pairs: &[(&str, f64)]) -> ParamBinding {
+        pairs.iter().map(|&(name, v)| (Param::new(name), v)).collect()
+    }
+
+    #[test]
+    fn test_expression_evaluation() {
+        let theta = Param::new("theta");
+        let phi = Param::new("phi");
+        let expr = 2.0 * theta.expr() + phi.expr().sin() / 2.0 - 1.0;
+        assert_eq!(expr.parameters().len(), 2);
+
+        let v = expr.evaluate(&binding(&[("theta", 0.5), ("phi", PI / 2.0)])).unwrap();
+        assert!((v - 0.5).abs() < 1e-12);
+        assert!(expr.evaluate(&binding(&[("theta", 0.5)])).is_err());
+
+        let partial = expr.substitute(&binding(&[("phi", 0.0)]));
+        assert_eq!(partial.parameters().into_iter().collect::<Vec<_>>(), vec![theta]);
+        assert_eq!((-(-phi.expr())), phi.expr());
+    }
+
+    #[test]
+    fn test_derivative() {
+        let x = Param::new("x");
+        let y = Param::new("y");
+        let expr = x.expr() * y.expr() + x.expr().sin() / 2.0;
+        let values = binding(&[("x", 0.3), ("y", 2.0)]);
+        let dx = expr.derivative(&x).evaluate(&values).unwrap();
+        assert!((dx - (2.0 + 0.3f64.cos() / 2.0)).abs() < 1e-12);
+        assert_eq!(expr.derivative(&y).evaluate(&values).unwrap(), 0.3);
+        assert_eq!(y.expr().cos().derivative(&x), ParamExpr::Const(0.0));
+    }
+
+    #[test]
+    fn test_symbolic_operation() {
+        let op = rx_param(q(0), Param::new("a"));
+        assert!(op.is_symbolic());
+        assert_eq!(op.name(), "Rx");
+        assert_eq!(op.qubits(), vec![q(0)]);
+        assert!(op.matrix().is_none());
+
+        let bound = op.bind(&binding(&[("a", 0.3)])).unwrap();
+        assert_eq!(bound, Operation::gate1(SingleQubitGate::Rx(0.3), q(0)));
+
+        // 全常数表达式直接生成具体门
+        let concrete = Operation::symbolic(Operation::gate1(SingleQubitGate::P(0.0), q(1)), vec![1.5.into()]).unwrap();
+        assert_eq!(concrete, Operation::gate1(SingleQubitGate::P(1.5), q(1)));
+
+        assert!(Operation::symbolic(h(q(0)), vec![1.0.into()]).is_err());
+        assert!(Operation::symbolic(Operation::gate1(SingleQubitGate::Rx(0.0), q(0)), vec![]).is_err());
+    }
+
+    #[test]
+    fn test_symbolic_inverse() {
+        let op = Operation::symbolic(
+            Operation::gate1(SingleQubitGate::U(0.0, 0.0, 0.0), q(0)),
+            vec![ParamExpr::param("t"), ParamExpr::param("p"), ParamExpr::param("l")],
+        )
+        .unwrap();
+        let values = binding(&[("t", 0.4), ("p", 1.1), ("l", -0.6)]);
+        let inverse = op.inverse().unwrap().bind(&values).unwrap();
+        let expected = op.bind(&values).unwrap().inverse().unwrap();
+        assert_eq!(inverse, expected);
+    }
+
+    #[test]
+    fn test_circuit_bind() {
+        let mut dag = CircuitDag::new();
+        dag.add_node(h(q(0)));
+        dag.add_node(ry_param(q(0), ParamExpr::param("b") * 2.0));
+        dag.add_node(cnot(q(0), q(1)));
+        dag.add_node(cp_param(q(0), q(1), Param::new("a")));
+        assert_eq!(dag.parameters(), vec![Param::new("a"), Param::new("b")]);
+        assert!(dag.is_parameterized());
+
+        let bound = dag.bind_values(&[0.25, 0.5]).unwrap();
+        assert!(!bound.is_parameterized());
+        assert_eq!(bound.num_nodes(), dag.num_nodes());
+        assert_eq!(bound.edges(), dag.edges());
+        assert_eq!(bound.get_node(1).unwrap().op, Operation::gate1(SingleQubitGate::Ry(1.0), q(0)));
+        assert_eq!(bound.get_node(3).unwrap().op, Operation::gate2(TwoQubitGate::CP(0.25), q(0), q(1)));
+        assert!(bound.unitary().is_ok());
+
+        let partial = dag.bind(&binding(&[("a", 0.1)])).unwrap();
+        assert_eq!(partial.parameters(), vec![Param::new("b")]);
+        assert!(partial.unitary().is_err());
+        assert!(dag.bind_values(&[1.0]).is_err());
+    }
+}
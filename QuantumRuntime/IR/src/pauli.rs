@@ -0,0 +1,343 @@
+//! Pauli 可观测量模块
+//!
+//! `PauliString` 为作用在逻辑 qubit 上的 Pauli 张量积（省略恒等），
+//! `PauliSum` 为实系数 Pauli 串之和（Hamiltonian）。
+
+use std::collections::BTreeMap;
+
+use crate::linalg::Complex;
+use crate::observable::bit_of;
+use crate::qubit::LogicalQubitId;
+use crate::statevector::StateVector;
+use crate::{Result, IrError};
+
+// ============================================================================
+// Pauli
+// ============================================================================
+
+/// 单比特 Pauli 算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Pauli {
+    I,
+    X,
+    Y,
+    Z,
+}
+
+impl Pauli {
+    pub fn from_char(c: char) -> Option<Pauli> {
+        match c.to_ascii_uppercase() {
+            'I' => Some(Pauli::I),
+            'X' => Some(Pauli::X),
+            'Y' => Some(Pauli::Y),
+            'Z' => Some(Pauli::Z),
+            _ => None,
+        }
+    }
+
+    pub fn as_char(self) -> char {
+        match self {
+            Pauli::I => 'I',
+            Pauli::X => 'X',
+            Pauli::Y => 'Y',
+            Pauli::Z => 'Z',
+        }
+    }
+
+    /// 辛表示 (x, z)
+    pub fn bits(self) -> (bool, bool) {
+        match self {
+            Pauli::I => (false, false),
+            Pauli::X => (true, false),
+            Pauli::Y => (true, true),
+            Pauli::Z => (false, true),
+        }
+    }
+
+    pub fn from_bits(x: bool, z: bool) -> Pauli {
+        match (x, z) {
+            (false, false) => Pauli::I,
+            (true, false) => Pauli::X,
+            (true, true) => Pauli::Y,
+            (false, true) => Pauli::Z,
+        }
+    }
+
+    pub fn commutes_with(self, other: Pauli) -> bool {
+        self == Pauli::I || other == Pauli::I || self == other
+    }
+}
+
+// ============================================================================
+// Pauli String
+// ============================================================================
+
+/// Pauli 串（恒等位省略）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PauliString {
+    ops: BTreeMap<LogicalQubitId, Pauli>,
+}
+
+impl PauliString {
+    pub fn identity() -> Self {
+        Self::default()
+    }
+
+    pub fn new(ops: impl IntoIterator<Item = (LogicalQubitId, Pauli)>) -> Self {
+        Self {
+            ops: ops.into_iter().filter(|&(_, p)| p != Pauli::I).collect(),
+        }
+    }
+
+    pub fn single(qubit: LogicalQubitId, pauli: Pauli) -> Self {
+        Self::new([(qubit, pauli)])
+    }
+
+    /// 解析 "X0 Y2 Z5" 形式（数字为逻辑 qubit ID，"I" 或空串为恒等）
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut ops = BTreeMap::new();
+        for token in text.split_whitespace() {
+            let mut chars = token.chars();
+            let pauli = chars.next().and_then(Pauli::from_char).ok_or_else(|| {
+                IrError::InvalidOperation(format!("Invalid Pauli term '{}'", token))
+            })?;
+            let rest = chars.as_str();
+            if rest.is_empty() && pauli == Pauli::I {
+                continue;
+            }
+            let id: u64 = rest.parse().map_err(|_| {
+                IrError::InvalidOperation(format!("Invalid qubit index in '{}'", token))
+            })?;
+            if ops.insert(LogicalQubitId::new(id), pauli).is_some() {
+                return Err(IrError::InvalidOperation(format!("Qubit {} appears twice in '{}'", id, text)));
+            }
+        }
+        Ok(Self::new(ops))
+    }
+
+    pub fn get(&self, qubit: LogicalQubitId) -> Pauli {
+        self.ops.get(&qubit).copied().unwrap_or(Pauli::I)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (LogicalQubitId, Pauli)> + '_ {
+        self.ops.iter().map(|(&q, &p)| (q, p))
+    }
+
+    /// 非恒等作用的 qubit（升序）
+    pub fn qubits(&self) -> Vec<LogicalQubitId> {
+        self.ops.keys().copied().collect()
+    }
+
+    pub fn weight(&self) -> usize {
+        self.ops.len()
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// 是否只含 Z（计算基下对角）
+    pub fn is_diagonal(&self) -> bool {
+        self.ops.values().all(|&p| p == Pauli::Z)
+    }
+
+    /// 一般对易：反对易的位置数为偶数
+    pub fn commutes_with(&self, other: &PauliString) -> bool {
+        self.ops
+            .iter()
+            .filter(|&(q, &p)| !p.commutes_with(other.get(*q)))
+            .count()
+            % 2
+            == 0
+    }
+
+    /// 逐比特对易：每个位置都对易
+    pub fn qubitwise_commutes_with(&self, other: &PauliString) -> bool {
+        self.ops.iter().all(|(q, &p)| p.commutes_with(other.get(*q)))
+    }
+
+    /// P|ψ⟩，qubits 给出态矢量各位对应的逻辑 qubit
+    pub fn apply(&self, state: &StateVector, qubits: &[LogicalQubitId]) -> Result<StateVector> {
+        let mut out = vec![Complex::ZERO; state.amplitudes().len()];
+        self.accumulate(Complex::ONE, state, qubits, &mut out)?;
+        Ok(StateVector::from_raw(out))
+    }
+
+    /// out += c·P|ψ⟩；P = i^{#Y} X^x Z^z
+    fn accumulate(&self, c: Complex, state: &StateVector, qubits: &[LogicalQubitId], out: &mut [Complex]) -> Result<()> {
+        let (mut x_mask, mut z_mask, mut num_y) = (0usize, 0usize, 0);
+        for (q, p) in self.iter() {
+            let bit = 1usize << bit_of(qubits, q)?;
+            let (x, z) = p.bits();
+            if x {
+                x_mask |= bit;
+            }
+            if z {
+                z_mask |= bit;
+            }
+            num_y += usize::from(x && z);
+        }
+        let phase = [Complex::ONE, Complex::I, -Complex::ONE, -Complex::I][num_y % 4] * c;
+        for (k, &a) in state.amplitudes().iter().enumerate() {
+            let amp = if (k & z_mask).count_ones() % 2 == 1 { -a } else { a };
+            out[k ^ x_mask] += phase * amp;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for PauliString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ops.is_empty() {
+            return write!(f, "I");
+        }
+        let terms: Vec<String> = self.iter().map(|(q, p)| format!("{}{}", p.as_char(), q.value())).collect();
+        write!(f, "{}", terms.join(" "))
+    }
+}
+
+// ============================================================================
+// Pauli Sum
+// ============================================================================
+
+/// 实系数 Pauli 串之和
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PauliSum {
+    terms: Vec<(f64, PauliString)>,
+}
+
+impl PauliSum {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_term(mut self, coefficient: f64, string: PauliString) -> Self {
+        self.add_term(coefficient, string);
+        self
+    }
+
+    /// 添加一项，同一 Pauli 串的系数合并
+    pub fn add_term(&mut self, coefficient: f64, string: PauliString) {
+        match self.terms.iter_mut().find(|(_, s)| *s == string) {
+            Some((c, _)) => *c += coefficient,
+            None => self.terms.push((coefficient, string)),
+        }
+    }
+
+    pub fn terms(&self) -> &[(f64, PauliString)] {
+        &self.terms
+    }
+
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// 恒等项系数之和
+    pub fn constant(&self) -> f64 {
+        self.terms.iter().filter(|(_, s)| s.is_identity()).map(|(c, _)| c).sum()
+    }
+
+    /// 涉及的 qubit（升序）
+    pub fn qubits(&self) -> Vec<LogicalQubitId> {
+        let mut qubits: Vec<_> = self.terms.iter().flat_map(|(_, s)| s.qubits()).collect();
+        qubits.sort();
+        qubits.dedup();
+        qubits
+    }
+
+    /// H|ψ⟩
+    pub fn apply(&self, state: &StateVector, qubits: &[LogicalQubitId]) -> Result<StateVector> {
+        let mut out = vec![Complex::ZERO; state.amplitudes().len()];
+        for (c, string) in &self.terms {
+            string.accumulate(Complex::real(*c), state, qubits, &mut out)?;
+        }
+        Ok(StateVector::from_raw(out))
+    }
+}
+
+impl From<PauliString> for PauliSum {
+    fn from(string: PauliString) -> Self {
+        PauliSum::new().with_term(1.0, string)
+    }
+}
+
+impl std::fmt::Display for PauliSum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+        let terms: Vec<String> = self.terms.iter().map(|(c, s)| format!("{} * {}", c, s)).collect();
+        write!(f, "{}", terms.join(" + "))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::bell_state_dag;
+    use crate::statevector::final_state;
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let p = PauliString::parse("X0 I1 Z3").unwrap();
+        assert_eq!(p.weight(), 2);
+        assert_eq!(p.get(q(3)), Pauli::Z);
+        assert_eq!(p.get(q(1)), Pauli::I);
+        assert_eq!(p.to_string(), "X0 Z3");
+        assert!(PauliString::parse("I").unwrap().is_identity());
+        assert!(PauliString::parse("X0 Z0").is_err());
+        assert!(PauliString::parse("Q1").is_err());
+    }
+
+    #[test]
+    fn test_commutation() {
+        let xx = PauliString::parse("X0 X1").unwrap();
+        let zz = PauliString::parse("Z0 Z1").unwrap();
+        let zi = PauliString::parse("Z0").unwrap();
+        assert!(xx.commutes_with(&zz));
+        assert!(!xx.qubitwise_commutes_with(&zz));
+        assert!(!xx.commutes_with(&zi));
+        assert!(zz.qubitwise_commutes_with(&zi));
+    }
+
+    #[test]
+    fn test_bell_expectations() {
+        let (qubits, state) = final_state(&bell_state_dag()).unwrap();
+        let expect = |text: &str| {
+            let p = PauliString::parse(text).unwrap();
+            state.inner(&p.apply(&state, &qubits).unwrap()).re
+        };
+        assert!((expect("X0 X1") - 1.0).abs() < 1e-12);
+        assert!((expect("Y0 Y1") + 1.0).abs() < 1e-12);
+        assert!((expect("Z0 Z1") - 1.0).abs() < 1e-12);
+        assert!(expect("Z0").abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pauli_sum() {
+        let mut h = PauliSum::new()
+            .with_term(0.5, PauliString::parse("Z0 Z1").unwrap())
+            .with_term(-1.0, PauliString::identity());
+        h.add_term(0.25, PauliString::parse("Z1 Z0").unwrap());
+        assert_eq!(h.len(), 2);
+        assert_eq!(h.terms()[0].0, 0.75);
+        assert_eq!(h.constant(), -1.0);
+        assert_eq!(h.qubits(), vec![q(0), q(1)]);
+
+        let (qubits, state) = final_state(&bell_state_dag()).unwrap();
+        let value = state.inner(&h.apply(&state, &qubits).unwrap()).re;
+        assert!((value + 0.25).abs() < 1e-12);
+    }
+}
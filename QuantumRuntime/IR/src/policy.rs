@@ -0,0 +1,529 @@
+//! 调度策略模块
+//!
+//! `JobScheduler` 在每次调度时把资源与依赖都已满足的作业交给 `SchedulingPolicy`，
+//! 由策略决定下一个执行的作业：
+//! - `StrictPriority`：严格优先级，同优先级先来先服务（默认）；
+//! - `PriorityAging`：等待越久优先级越高，低优先级作业不会被持续饿死；
+//! - `FairShare`：按用户或项目的加权已用份额选择，份额最少者优先；
+//! - `ShortestJobFirst`：按估计代价选最短作业，等待超过上限的作业优先；
+//! - `EarliestDeadlineFirst`：同优先级内截止时刻最早者优先。
+//!
+//! 等待时间以调度轮次计：作业每次作为候选却未被选中，等待轮次加一。
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use crate::job::{Job, JobId};
+
+// ============================================================================
+// Policy Trait
+// ============================================================================
+
+/// 调度策略
+pub trait SchedulingPolicy: Send + Debug {
+    /// 策略名称
+    fn name(&self) -> &str;
+
+    /// 从候选作业中选出下一个，返回其下标
+    ///
+    /// 候选按队列顺序给出且非空；返回 `None` 表示本轮不调度。
+    fn select(&mut self, candidates: &[&Job]) -> Option<usize>;
+
+    /// 作业被选中并开始占用资源
+    fn on_scheduled(&mut self, job: &Job) {}
+
+    /// 作业未经调度就离开队列（取消或依赖失败）
+    fn on_removed(&mut self, job_id: JobId) {}
+}
+
+/// 作业的估计代价：shots × 所有执行点的操作数之和
+///
+/// 绑定参数不改变操作数，因此直接按模板电路计数，不必逐点绑定电路。
+pub fn estimated_cost(job: &Job) -> f64 {
+    let operations = job.circuit.num_operations() * job.sweep.len().max(1)
+        + job.batch_circuits.iter().map(|c| c.num_operations()).sum::<usize>();
+    f64::from(job.shots.max(1)) * operations.max(1) as f64
+}
+
+/// 记录候选作业已等待的调度轮次
+#[derive(Debug, Default, Clone)]
+struct WaitTracker {
+    round: u64,
+    first_seen: HashMap<JobId, u64>,
+}
+
+impl WaitTracker {
+    /// 开始新一轮调度
+    fn observe(&mut self, candidates: &[&Job]) {
+        self.round += 1;
+        for job in candidates {
+            self.first_seen.entry(job.id).or_insert(self.round);
+        }
+    }
+
+    /// 作业已等待的轮次（本轮首次出现为 0）
+    fn waited(&self, job_id: JobId) -> u64 {
+        self.first_seen.get(&job_id).map_or(0, |&seen| self.round - seen)
+    }
+
+    /// 首次成为候选的轮次，用于先来先服务
+    fn arrival(&self, job_id: JobId) -> (u64, JobId) {
+        (self.first_seen.get(&job_id).copied().unwrap_or(self.round), job_id)
+    }
+
+    fn forget(&mut self, job_id: JobId) {
+        self.first_seen.remove(&job_id);
+    }
+}
+
+// ============================================================================
+// Strict Priority
+// ============================================================================
+
+/// 严格优先级：总是选优先级最高的作业，同优先级先来先服务
+///
+/// 持续到达的高优先级作业会使低优先级作业无限等待。
+#[derive(Debug, Default, Clone)]
+pub struct StrictPriority {
+    waits: WaitTracker,
+}
+
+impl StrictPriority {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SchedulingPolicy for StrictPriority {
+    fn name(&self) -> &str {
+        "strict-priority"
+    }
+
+    fn select(&mut self, candidates: &[&Job]) -> Option<usize> {
+        self.waits.observe(candidates);
+        let waits = &self.waits;
+        (0..candidates.len()).min_by_key(|&i| {
+            let job = candidates[i];
+            (std::cmp::Reverse(job.priority), waits.arrival(job.id))
+        })
+    }
+
+    fn on_scheduled(&mut self, job: &Job) {
+        self.waits.forget(job.id);
+    }
+
+    fn on_removed(&mut self, job_id: JobId) {
+        self.waits.forget(job_id);
+    }
+}
+
+// ============================================================================
+// Priority Aging
+// ============================================================================
+
+/// 带老化的优先级：每等待 `aging_interval` 轮，有效优先级提升一级
+///
+/// 低优先级作业最多等待约 `aging_interval × 3` 轮即可与新到的 Urgent 作业竞争。
+#[derive(Debug, Clone)]
+pub struct PriorityAging {
+    aging_interval: u64,
+    waits: WaitTracker,
+}
+
+impl PriorityAging {
+    pub fn new(aging_interval: u64) -> Self {
+        Self {
+            aging_interval: aging_interval.max(1),
+            waits: WaitTracker::default(),
+        }
+    }
+
+    /// 作业当前的有效优先级
+    pub fn effective_priority(&self, job: &Job) -> u64 {
+        u64::from(job.priority.to_u8()) + self.waits.waited(job.id) / self.aging_interval
+    }
+}
+
+impl Default for PriorityAging {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl SchedulingPolicy for PriorityAging {
+    fn name(&self) -> &str {
+        "priority-aging"
+    }
+
+    fn select(&mut self, candidates: &[&Job]) -> Option<usize> {
+        self.waits.observe(candidates);
+        (0..candidates.len()).min_by_key(|&i| {
+            let job = candidates[i];
+            (std::cmp::Reverse(self.effective_priority(job)), self.waits.arrival(job.id))
+        })
+    }
+
+    fn on_scheduled(&mut self, job: &Job) {
+        self.waits.forget(job.id);
+    }
+
+    fn on_removed(&mut self, job_id: JobId) {
+        self.waits.forget(job_id);
+    }
+}
+
+// ============================================================================
+// Fair Share
+// ============================================================================
+
+/// 公平份额的分组依据
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShareKey {
+    /// 按 `JobMetadata::user_id`
+    #[default]
+    User,
+    /// 按 `JobMetadata::project`
+    Project,
+}
+
+/// 加权公平份额：选择（已用代价 / 权重）最小的分组，组内按优先级与到达顺序
+///
+/// 已用代价在作业被调度时按 `estimated_cost` 累加；未设置用户或项目的作业归入同一匿名组。
+/// 新加入的分组从当前最小的已用份额起算，不会因为历史空闲而长期独占资源。
+#[derive(Debug, Clone, Default)]
+pub struct FairShare {
+    key: ShareKey,
+    weights: HashMap<String, f64>,
+    usage: HashMap<String, f64>,
+    waits: WaitTracker,
+}
+
+impl FairShare {
+    pub fn new(key: ShareKey) -> Self {
+        Self { key, ..Self::default() }
+    }
+
+    /// 设置分组权重（默认 1）
+    pub fn with_weight(mut self, group: impl Into<String>, weight: f64) -> Self {
+        self.weights.insert(group.into(), weight.max(f64::EPSILON));
+        self
+    }
+
+    /// 作业所属的分组
+    pub fn group_of(&self, job: &Job) -> String {
+        let group = match self.key {
+            ShareKey::User => job.metadata.user_id.as_ref(),
+            ShareKey::Project => job.metadata.project.as_ref(),
+        };
+        group.cloned().unwrap_or_default()
+    }
+
+    /// 分组的加权已用份额
+    pub fn share(&self, group: &str) -> f64 {
+        let usage = self.usage.get(group).copied().unwrap_or_else(|| self.baseline());
+        usage / self.weights.get(group).copied().unwrap_or(1.0)
+    }
+
+    /// 新分组的起始份额：已有分组中最小的加权份额
+    fn baseline(&self) -> f64 {
+        self.usage
+            .iter()
+            .map(|(group, usage)| usage / self.weights.get(group).copied().unwrap_or(1.0))
+            .fold(None, |min: Option<f64>, share| Some(min.map_or(share, |m| m.min(share))))
+            .unwrap_or(0.0)
+    }
+}
+
+impl SchedulingPolicy for FairShare {
+    fn name(&self) -> &str {
+        "fair-share"
+    }
+
+    fn select(&mut self, candidates: &[&Job]) -> Option<usize> {
+        self.waits.observe(candidates);
+        // 新分组在首次出现时确定起始用量
+        for job in candidates {
+            let group = self.group_of(job);
+            if !self.usage.contains_key(&group) {
+                let weight = self.weights.get(&group).copied().unwrap_or(1.0);
+                let start = self.baseline() * weight;
+                self.usage.insert(group, start);
+            }
+        }
+        let shares: Vec<f64> = candidates.iter().map(|job| self.share(&self.group_of(job))).collect();
+        (0..candidates.len()).min_by(|&a, &b| {
+            let (ja, jb) = (candidates[a], candidates[b]);
+            shares[a]
+                .total_cmp(&shares[b])
+                .then(jb.priority.cmp(&ja.priority))
+                .then(self.waits.arrival(ja.id).cmp(&self.waits.arrival(jb.id)))
+        })
+    }
+
+    fn on_scheduled(&mut self, job: &Job) {
+        self.waits.forget(job.id);
+        let group = self.group_of(job);
+        let weight = self.weights.get(&group).copied().unwrap_or(1.0);
+        let start = self.share(&group) * weight;
+        self.usage.insert(group, start + estimated_cost(job));
+    }
+
+    fn on_removed(&mut self, job_id: JobId) {
+        self.waits.forget(job_id);
+    }
+}
+
+// ============================================================================
+// Shortest Job First
+// ============================================================================
+
+/// 最短作业优先：按 `estimated_cost` 选择代价最小的作业
+///
+/// 等待达到 `max_wait` 轮的作业优先于所有未达上限的作业（其间先来先服务），
+/// 因此持续到达的短作业不会使长作业无限等待。
+#[derive(Debug, Clone)]
+pub struct ShortestJobFirst {
+    max_wait: u64,
+    waits: WaitTracker,
+}
+
+impl ShortestJobFirst {
+    pub fn new(max_wait: u64) -> Self {
+        Self {
+            max_wait: max_wait.max(1),
+            waits: WaitTracker::default(),
+        }
+    }
+}
+
+impl Default for ShortestJobFirst {
+    fn default() -> Self {
+        Self::new(16)
+    }
+}
+
+impl SchedulingPolicy for ShortestJobFirst {
+    fn name(&self) -> &str {
+        "shortest-job-first"
+    }
+
+    fn select(&mut self, candidates: &[&Job]) -> Option<usize> {
+        self.waits.observe(candidates);
+        let overdue = |job: &Job| self.waits.waited(job.id) >= self.max_wait;
+        if let Some(i) = (0..candidates.len())
+            .filter(|&i| overdue(candidates[i]))
+            .min_by_key(|&i| self.waits.arrival(candidates[i].id))
+        {
+            return Some(i);
+        }
+        let costs: Vec<f64> = candidates.iter().map(|job| estimated_cost(job)).collect();
+        (0..candidates.len()).min_by(|&a, &b| {
+            costs[a]
+                .total_cmp(&costs[b])
+                .then(self.waits.arrival(candidates[a].id).cmp(&self.waits.arrival(candidates[b].id)))
+        })
+    }
+
+    fn on_scheduled(&mut self, job: &Job) {
+        self.waits.forget(job.id);
+    }
+
+    fn on_removed(&mut self, job_id: JobId) {
+        self.waits.forget(job_id);
+    }
+}
+
+// ============================================================================
+// Earliest Deadline First
+// ============================================================================
+
+/// 最早截止时刻优先：先按优先级，同优先级内截止时刻早者优先，无截止时刻的作业排在最后
+///
+/// 已过截止时刻的作业由调度器在排队期间移除，不会占用资源。
+#[derive(Debug, Default, Clone)]
+pub struct EarliestDeadlineFirst {
+    waits: WaitTracker,
+}
+
+impl EarliestDeadlineFirst {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SchedulingPolicy for EarliestDeadlineFirst {
+    fn name(&self) -> &str {
+        "earliest-deadline-first"
+    }
+
+    fn select(&mut self, candidates: &[&Job]) -> Option<usize> {
+        self.waits.observe(candidates);
+        let waits = &self.waits;
+        (0..candidates.len()).min_by_key(|&i| {
+            let job = candidates[i];
+            (std::cmp::Reverse(job.priority), job.deadline.is_none(), job.deadline, waits.arrival(job.id))
+        })
+    }
+
+    fn on_scheduled(&mut self, job: &Job) {
+        self.waits.forget(job.id);
+    }
+
+    fn on_removed(&mut self, job_id: JobId) {
+        self.waits.forget(job_id);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::CircuitDag;
+    use crate::job::{JobMetadata, JobResult, JobScheduler, Priority};
+    use crate::operation::{h, measure};
+    use crate::qubit::LogicalQubitId;
+    use std::time::Duration;
+
+    fn job(gates: usize, priority: Priority) -> Job {
+        let q = LogicalQubitId::new(0);
+        let mut circuit = CircuitDag::new();
+        for _ in 0..gates {
+            circuit.add_node(h(q));
+        }
+        circuit.add_node(measure(q));
+        Job::new(circuit, 100, "sim").with_priority(priority)
+    }
+
+    fn user_job(user: &str) -> Job {
+        job(1, Priority::Normal).with_metadata(JobMetadata::new().with_user(user))
+    }
+
+    fn scheduler(policy: impl SchedulingPolicy + 'static) -> JobScheduler {
+        JobScheduler::new(1)
+            .with_qubits(vec![LogicalQubitId::new(0)])
+            .with_policy(policy)
+    }
+
+    /// 调度并立即完成下一个作业
+    fn run_next(scheduler: &mut JobScheduler) -> Job {
+        let job = scheduler.schedule_next().expect("a job is schedulable");
+        scheduler.start_execution(job.id).unwrap();
+        scheduler.complete(job.id, JobResult::success(job.id)).unwrap();
+        job
+    }
+
+    /// 每轮到达一个新作业的情况下，目标作业在多少轮内被调度
+    fn rounds_until(scheduler: &mut JobScheduler, target: JobId, mut arrival: impl FnMut() -> Job, limit: usize) -> Option<usize> {
+        for round in 0..limit {
+            scheduler.submit(arrival()).unwrap();
+            if run_next(scheduler).id == target {
+                return Some(round);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_strict_priority_starves() {
+        let mut scheduler = scheduler(StrictPriority::new());
+        assert_eq!(scheduler.policy().name(), "strict-priority");
+        let first = scheduler.submit(job(1, Priority::High)).unwrap();
+        let second = scheduler.submit(job(1, Priority::High)).unwrap();
+        let low = scheduler.submit(job(1, Priority::Low)).unwrap();
+        // 同优先级先来先服务
+        assert_eq!(run_next(&mut scheduler).id, first);
+        assert_eq!(run_next(&mut scheduler).id, second);
+
+        scheduler.submit(job(1, Priority::Urgent)).unwrap();
+        assert_eq!(rounds_until(&mut scheduler, low, || job(1, Priority::Urgent), 50), None);
+    }
+
+    #[test]
+    fn test_priority_aging_prevents_starvation() {
+        let mut scheduler = scheduler(PriorityAging::new(2));
+        let low = scheduler.submit(job(1, Priority::Low)).unwrap();
+        scheduler.submit(job(1, Priority::Urgent)).unwrap();
+        let rounds = rounds_until(&mut scheduler, low, || job(1, Priority::Urgent), 50).unwrap();
+        // Low → Urgent 需要提升三级，每两轮一级
+        assert!(rounds <= 8, "low job waited {} rounds", rounds);
+    }
+
+    #[test]
+    fn test_fair_share_between_users() {
+        let mut scheduler = scheduler(FairShare::new(ShareKey::User));
+        for _ in 0..10 {
+            scheduler.submit(user_job("alice")).unwrap();
+        }
+        run_next(&mut scheduler);
+        run_next(&mut scheduler);
+        // 后到的用户从 alice 当前的份额起算，不必等 alice 的积压全部完成
+        let bob = scheduler.submit(user_job("bob")).unwrap();
+        let next_two = [run_next(&mut scheduler).id, run_next(&mut scheduler).id];
+        assert!(next_two.contains(&bob));
+
+        // alice 持续提交时 bob 的作业仍交替得到执行
+        let bob_again = scheduler.submit(user_job("bob")).unwrap();
+        assert!(rounds_until(&mut scheduler, bob_again, || user_job("alice"), 10).unwrap() <= 1);
+    }
+
+    #[test]
+    fn test_weighted_fair_share() {
+        let mut scheduler = scheduler(FairShare::new(ShareKey::Project).with_weight("big", 2.0));
+        let project = |name: &str| job(1, Priority::Normal).with_metadata(JobMetadata::new().with_project(name));
+        for _ in 0..30 {
+            scheduler.submit(project("big")).unwrap();
+            scheduler.submit(project("small")).unwrap();
+        }
+        let served: Vec<String> = (0..30)
+            .map(|_| run_next(&mut scheduler).metadata.project.unwrap())
+            .collect();
+        let big = served.iter().filter(|p| *p == "big").count();
+        assert_eq!(big, 20);
+        // 低权重项目也持续得到调度
+        assert!(served.windows(4).all(|w| w.iter().any(|p| p == "small")));
+    }
+
+    #[test]
+    fn test_earliest_deadline_first() {
+        let mut scheduler = scheduler(EarliestDeadlineFirst::new());
+        let none = scheduler.submit(job(1, Priority::Normal)).unwrap();
+        let late = scheduler.submit(job(1, Priority::Normal).with_deadline_in(Duration::from_secs(60))).unwrap();
+        let soon = scheduler.submit(job(1, Priority::Normal).with_deadline_in(Duration::from_secs(10))).unwrap();
+        let urgent = scheduler.submit(job(1, Priority::Urgent)).unwrap();
+        let order: Vec<JobId> = (0..4).map(|_| run_next(&mut scheduler).id).collect();
+        // 优先级优先于截止时刻，无截止时刻的作业最后
+        assert_eq!(order, vec![urgent, soon, late, none]);
+
+        // 截止时刻较晚的作业在持续到达的新作业中也会轮到：新作业的截止时刻都更晚
+        let waiting = scheduler.submit(job(1, Priority::Normal).with_deadline_in(Duration::from_secs(30))).unwrap();
+        scheduler.submit(job(1, Priority::Normal).with_deadline_in(Duration::from_secs(20))).unwrap();
+        let mut offset = 40;
+        let rounds = rounds_until(&mut scheduler, waiting, || {
+            offset += 10;
+            job(1, Priority::Normal).with_deadline_in(Duration::from_secs(offset))
+        }, 10);
+        assert_eq!(rounds, Some(1));
+    }
+
+    #[test]
+    fn test_shortest_job_first() {
+        let mut scheduler = scheduler(ShortestJobFirst::new(5));
+        let long = scheduler.submit(job(20, Priority::Normal)).unwrap();
+        let short = scheduler.submit(job(1, Priority::Normal)).unwrap();
+        assert!(estimated_cost(&job(20, Priority::Normal)) > estimated_cost(&job(1, Priority::Normal)));
+        // 扫描点与附加电路按执行点累加
+        let single = estimated_cost(&job(1, Priority::Normal));
+        let sweep = job(1, Priority::Normal).with_sweep(vec![Default::default(); 3]);
+        assert_eq!(estimated_cost(&sweep), 3.0 * single);
+        let batch = sweep.with_circuits([job(1, Priority::Normal).circuit]);
+        assert_eq!(estimated_cost(&batch), 4.0 * single);
+        assert_eq!(run_next(&mut scheduler).id, short);
+
+        // 持续到达的短作业不会让长作业超过等待上限
+        let rounds = rounds_until(&mut scheduler, long, || job(1, Priority::Normal), 50).unwrap();
+        assert!(rounds <= 5, "long job waited {} rounds", rounds);
+    }
+}
@@ -5,13 +5,38 @@
 pub use crate::qubit::{LogicalQubitId, PhysicalQubitId, QubitState, LogicalQubit, LogicalQubitManager, QubitMapping};
 pub use crate::operation::{
     Operation, SingleQubitGate, TwoQubitGate, ThreeQubitGate, CustomOp, MeasurementResult,
-    x, y, z, h, s, t, rx, ry, rz, cnot, cz, swap, measure, toffoli,
+    x, y, z, h, s, t, rx, ry, rz, cnot, cz, swap, measure, toffoli, delay,
 };
 pub use crate::circuit::{CircuitDag, CircuitDagBuilder, OperationNode, CircuitMetadata, bell_state_dag, ghz_dag};
-pub use crate::job::{Job, JobId, Priority, JobStatus, JobResult, JobMetadata, JobScheduler, SchedulerStats};
+pub use crate::job::{Job, JobId, Priority, JobStatus, JobResult, JobAttempt, JobMetadata, JobScheduler, SchedulerStats, Clock, SystemClock, ManualClock};
+pub use crate::quota::{QuotaManager, QuotaKey, QuotaLimits, QuotaUsage, OverQuota};
+pub use crate::store::{JobStore, StoredJob, InMemoryJobStore, FileJobStore, RecoveryMode, RecoveryReport};
+pub use crate::policy::{SchedulingPolicy, StrictPriority, PriorityAging, FairShare, ShareKey, ShortestJobFirst, EarliestDeadlineFirst, estimated_cost};
 pub use crate::backend::{
     BackendAdapter, BackendCapabilities, BackendType, BackendCircuit, CouplingMap, ErrorModel,
-    MockBackendAdapter, IdealSimulatorBackend,
+    MockBackendAdapter, IdealSimulatorBackend, GateDurations, SubmittedJob,
 };
-pub use crate::runtime::{QuantumRuntime, RuntimeConfig, RuntimeStats, BackendRegistry};
-pub use crate::{Result, IrError};
+pub use crate::future::{JobFuture, JobCompleter, Completer, CompletionFuture, completion, block_on, block_on_timeout, join_all, sleep, timeout, LocalExecutor};
+pub use crate::synthesis::{CliffordTSynthesizer, GateSequence, SynthesisReport};
+pub use crate::resource::{ResourceEstimate, SurfaceCodeModel, SurfaceCodeEstimate};
+pub use crate::timing::{ScheduleMode, TimingSchedule, ScheduledOp, IdleWindow};
+pub use crate::decoupling::{DdSequence, DynamicalDecoupling, DecouplingReport, simulate_idle_fidelity};
+pub use crate::cleanup::{CircuitCleanup, CleanupReport};
+pub use crate::linalg::{Complex, Matrix};
+pub use crate::statevector::{StateVector, circuit_unitary, final_state};
+pub use crate::clifford::{CliffordTableau, is_clifford};
+pub use crate::controlled::{multi_controlled_unitary, euler_zyz};
+pub use crate::equivalence::{EquivalenceChecker, EquivalenceVerdict, EquivalenceMethod, Counterexample};
+pub use crate::composite::{GateDefinition, GateLibrary, CompositeInliner, InlineReport, formal, qft_definition};
+pub use crate::params::{Param, ParamExpr, ParamBinding, rx_param, ry_param, rz_param, p_param, cp_param, ms_param};
+pub use crate::observable::{Observable, ZParity};
+pub use crate::gradient::{ParameterShift, AdjointGradient, GradientResult, ShiftRule, ShiftPlan, ShiftTerm, exact_expectation};
+pub use crate::pauli::{Pauli, PauliString, PauliSum};
+pub use crate::estimator::{Estimator, Estimate, Grouping, MeasurementGroup};
+pub use crate::primitives::QuasiDistribution;
+pub use crate::optimizer::{Optimizer, Objective, OptimizationResult, IterationRecord, NelderMead, Cobyla, Spsa, GradientDescent};
+pub use crate::variational::{Vqe, VqeResult, Qaoa, QaoaResult, maxcut_hamiltonian};
+pub use crate::runtime::{QuantumRuntime, RuntimeConfig, RuntimeStats, BackendRegistry, ExecutionMode, AsyncRuntimeHandle};
+pub use crate::workflow::{Workflow, WorkflowStatus, WorkflowReport, WorkflowInputs, NodeId, NodeStatus, NodeOutput, NodeReport, FailurePolicy};
+pub use crate::retry::RetryPolicy;
+pub use crate::{Result, IrError, ErrorKind};
@@ -0,0 +1,298 @@
+//! 执行原语模块
+//!
+//! 在 `QuantumRuntime` 之上提供两个高层接口，屏蔽后端适配、批量提交与结果解码：
+//! - `sample`：执行电路并返回测量结果的准概率分布；
+//! - `estimate`：绑定参数后估计一组 Pauli 可观测量的期望值与标准误差。
+//!
+//! 所有电路先经 `transpile` 校验并按后端能力清理（不重新编号 qubit），
+//! 再作为一个批量作业同步执行。
+
+use std::collections::BTreeMap;
+
+use crate::circuit::CircuitDag;
+use crate::cleanup::CircuitCleanup;
+use crate::estimator::{Estimate, Estimator};
+use crate::job::{Job, JobResult, JobStatus};
+use crate::observable::append_measurements;
+use crate::params::ParamBinding;
+use crate::pauli::PauliSum;
+use crate::qubit::LogicalQubitId;
+use crate::runtime::QuantumRuntime;
+use crate::{Result, IrError};
+
+// ============================================================================
+// Quasi Distribution
+// ============================================================================
+
+/// 测量结果的（准）概率分布
+///
+/// 结果以整数编码：第 k 位对应 `qubits()[k]`（升序的被测 qubit）。
+/// 误差缓解得到的分布可能含负值，故称准概率。
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuasiDistribution {
+    qubits: Vec<LogicalQubitId>,
+    probabilities: BTreeMap<u64, f64>,
+    shots: usize,
+}
+
+impl QuasiDistribution {
+    /// 由逐次测量结果统计分布
+    pub fn from_result(result: &JobResult) -> Result<Self> {
+        let mut qubits: Vec<LogicalQubitId> = result.counts.keys().copied().collect();
+        qubits.sort();
+        if qubits.len() > 64 {
+            return Err(IrError::UnsupportedOperation(format!(
+                "Cannot encode outcomes of {} measured qubits", qubits.len()
+            )));
+        }
+        let counts: Vec<&Vec<u8>> = qubits.iter().map(|q| &result.counts[q]).collect();
+        let shots = counts.first().map_or(0, |c| c.len());
+        if counts.iter().any(|c| c.len() != shots) {
+            return Err(IrError::InvalidOperation("Counts have different shot numbers".to_string()));
+        }
+
+        let mut tally: BTreeMap<u64, usize> = BTreeMap::new();
+        for shot in 0..shots {
+            let outcome = counts
+                .iter()
+                .enumerate()
+                .fold(0u64, |acc, (k, c)| acc | (u64::from(c[shot] == 1) << k));
+            *tally.entry(outcome).or_insert(0) += 1;
+        }
+        let probabilities = tally
+            .into_iter()
+            .map(|(outcome, n)| (outcome, n as f64 / shots as f64))
+            .collect();
+        Ok(Self { qubits, probabilities, shots })
+    }
+
+    /// 被测 qubit（升序，对应结果编码的各位）
+    pub fn qubits(&self) -> &[LogicalQubitId] {
+        &self.qubits
+    }
+
+    pub fn shots(&self) -> usize {
+        self.shots
+    }
+
+    /// 结果出现的概率（未出现为 0）
+    pub fn get(&self, outcome: u64) -> f64 {
+        self.probabilities.get(&outcome).copied().unwrap_or(0.0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u64, f64)> + '_ {
+        self.probabilities.iter().map(|(&k, &p)| (k, p))
+    }
+
+    /// 出现过的结果数
+    pub fn len(&self) -> usize {
+        self.probabilities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.probabilities.is_empty()
+    }
+
+    /// 概率最大的结果
+    pub fn most_likely(&self) -> Option<(u64, f64)> {
+        self.iter().max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// 结果的比特串，最高位（最大 qubit）在左
+    pub fn bitstring(&self, outcome: u64) -> String {
+        (0..self.qubits.len())
+            .rev()
+            .map(|k| if outcome >> k & 1 == 1 { '1' } else { '0' })
+            .collect()
+    }
+
+    /// 以比特串为键的分布
+    pub fn to_bitstrings(&self) -> BTreeMap<String, f64> {
+        self.iter().map(|(k, p)| (self.bitstring(k), p)).collect()
+    }
+}
+
+// ============================================================================
+// Runtime Primitives
+// ============================================================================
+
+impl QuantumRuntime {
+    /// 将电路适配到目标后端：检查参数已全部绑定、校验后端约束，并按后端能力清理
+    pub fn transpile(&self, backend_id: &str, circuit: &CircuitDag) -> Result<CircuitDag> {
+        let backend = self.get_backend(backend_id).ok_or_else(|| {
+            IrError::BackendUnavailable(format!("Backend {} is not registered", backend_id))
+        })?;
+        if circuit.is_parameterized() {
+            let names: Vec<String> = circuit.parameters().iter().map(|p| p.to_string()).collect();
+            return Err(IrError::InvalidOperation(format!(
+                "Circuit has unbound parameters: {}", names.join(", ")
+            )));
+        }
+        backend.validate_circuit(circuit)?;
+        let cleanup = CircuitCleanup::for_backend(&backend.capabilities()).with_compact_qubits(false);
+        Ok(cleanup.run(circuit).0)
+    }
+
+    /// 在默认后端上采样
+    pub fn sample(&mut self, circuits: &[CircuitDag], shots: u32) -> Result<Vec<QuasiDistribution>> {
+        let backend_id = self.config().default_backend.clone();
+        self.sample_on(&backend_id, circuits, shots)
+    }
+
+    /// 在指定后端上采样；没有测量的电路在末尾测量全部 qubit
+    pub fn sample_on(&mut self, backend_id: &str, circuits: &[CircuitDag], shots: u32) -> Result<Vec<QuasiDistribution>> {
+        let mut prepared = circuits
+            .iter()
+            .map(|circuit| {
+                let measured = if circuit.operations_in_order().iter().any(|op| op.is_measurement()) {
+                    circuit.clone()
+                } else {
+                    append_measurements(circuit, Vec::new(), &circuit.all_qubits())?
+                };
+                self.transpile(backend_id, &measured)
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter();
+        let Some(first) = prepared.next() else {
+            return Ok(Vec::new());
+        };
+
+        let job = Job::new(first, shots, backend_id).with_circuits(prepared);
+        let job_id = job.id;
+        let result = self.run(job)?;
+        if result.status != JobStatus::Completed {
+            return Err(IrError::JobExecutionFailed(
+                result.error.unwrap_or_else(|| format!("Sampler job {} did not complete", job_id)),
+            ));
+        }
+        if result.points.is_empty() {
+            Ok(vec![QuasiDistribution::from_result(&result)?])
+        } else {
+            result.points.iter().map(QuasiDistribution::from_result).collect()
+        }
+    }
+
+    /// 在默认后端上用默认估计器估计期望值
+    pub fn estimate(
+        &mut self,
+        circuit: &CircuitDag,
+        observables: &[PauliSum],
+        params: &ParamBinding,
+    ) -> Result<Vec<Estimate>> {
+        let backend_id = self.config().default_backend.clone();
+        self.estimate_on(&backend_id, &Estimator::new(), circuit, observables, params)
+    }
+
+    /// 绑定参数后在指定后端上估计各可观测量的期望值
+    pub fn estimate_on(
+        &mut self,
+        backend_id: &str,
+        estimator: &Estimator,
+        circuit: &CircuitDag,
+        observables: &[PauliSum],
+        params: &ParamBinding,
+    ) -> Result<Vec<Estimate>> {
+        let bound = self.transpile(backend_id, &circuit.bind(params)?)?;
+        estimator.run_many(self, backend_id, &bound, observables)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{IdealSimulatorBackend, MockBackendAdapter};
+    use crate::circuit::bell_state_dag;
+    use crate::operation::{cnot, h, x};
+    use crate::params::{ry_param, Param};
+    use crate::pauli::PauliString;
+    use crate::runtime::RuntimeConfig;
+    use std::sync::Arc;
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    fn runtime() -> QuantumRuntime {
+        let mut runtime = QuantumRuntime::new(RuntimeConfig::new().with_default_backend("ideal"));
+        runtime.register_backend("ideal", Arc::new(IdealSimulatorBackend::new()));
+        runtime.register_backend("mock", Arc::new(MockBackendAdapter::new()));
+        runtime
+    }
+
+    #[test]
+    fn test_quasi_distribution() {
+        let mut result = JobResult::success(1);
+        result.add_counts(q(0), vec![1, 0, 1, 1]);
+        result.add_counts(q(3), vec![0, 0, 1, 1]);
+        let dist = QuasiDistribution::from_result(&result).unwrap();
+        assert_eq!(dist.qubits(), &[q(0), q(3)]);
+        assert_eq!(dist.shots(), 4);
+        assert_eq!(dist.get(0b11), 0.5);
+        assert_eq!(dist.get(0b10), 0.0);
+        assert_eq!(dist.most_likely(), Some((0b11, 0.5)));
+        assert_eq!(dist.to_bitstrings()["01"], 0.25);
+    }
+
+    #[test]
+    fn test_sample_batch() {
+        let mut runtime = runtime();
+        let mut flipped = CircuitDag::new();
+        flipped.add_node(x(q(1)));
+        flipped.add_node(h(q(0)));
+        flipped.add_node(h(q(0)));
+
+        let dists = runtime.sample(&[bell_state_dag(), flipped], 200).unwrap();
+        assert_eq!(dists.len(), 2);
+        assert!(dists[0].iter().all(|(k, _)| k == 0b00 || k == 0b11));
+        // 未测量的电路测量全部 qubit
+        assert_eq!(dists[1].qubits(), &[q(0), q(1)]);
+        assert_eq!(dists[1].get(0b10), 1.0);
+        assert_eq!(runtime.stats().total_jobs_submitted, 1);
+        assert!(runtime.sample(&[], 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sample_rejects_unbound() {
+        let mut runtime = runtime();
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(ry_param(q(0), Param::new("theta")));
+        assert!(runtime.sample(&[circuit], 10).is_err());
+        assert!(runtime.sample_on("missing", &[bell_state_dag()], 10).is_err());
+    }
+
+    #[test]
+    fn test_estimate_with_params() {
+        let mut runtime = runtime();
+        let theta = Param::new("theta");
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(ry_param(q(0), &theta));
+        circuit.add_node(cnot(q(0), q(1)));
+        let observables = [
+            PauliSum::from(PauliString::parse("Z0 Z1").unwrap()),
+            PauliSum::from(PauliString::parse("Z0").unwrap()),
+            PauliSum::from(PauliString::parse("X0 X1").unwrap()),
+        ];
+        let params = ParamBinding::from([(theta, 0.6)]);
+
+        let exact = runtime.estimate(&circuit, &observables, &params).unwrap();
+        assert!(exact.iter().all(|e| e.exact));
+        assert!((exact[0].value - 1.0).abs() < 1e-12);
+        assert!((exact[1].value - 0.6f64.cos()).abs() < 1e-12);
+        assert!((exact[2].value - 0.6f64.sin()).abs() < 1e-12);
+
+        // 采样路径：三个可观测量的测量电路合为一个作业
+        let sampled = runtime
+            .estimate_on("ideal", &Estimator::new().with_exact(false).with_shots(4000), &circuit, &observables, &params)
+            .unwrap();
+        for (s, e) in sampled.iter().zip(&exact) {
+            assert!(!s.exact);
+            assert!((s.value - e.value).abs() <= 5.0 * s.std_error + 1e-9);
+        }
+        assert_eq!(runtime.stats().total_jobs_submitted, 1);
+        assert!(runtime.estimate(&circuit, &observables, &ParamBinding::new()).is_err());
+    }
+}
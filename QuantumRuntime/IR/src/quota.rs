@@ -0,0 +1,519 @@
+//! 配额模块
+//!
+//! 按 `JobMetadata::user_id` 与 `project` 记账并限制共享 Runtime 的使用：
+//! - 最大并发作业数；
+//! - 滑动时间窗口内的最大 shots；
+//! - 累计 qubit·秒（占用 qubit 数 × 执行时间）上限。
+//!
+//! 作业同时受其用户与项目两个账户约束。超出配额的作业按 `OverQuota` 处理：
+//! 提交时直接以 `QuotaExceeded` 拒绝，或在调度器中保持 `Waiting` 直到配额释放。
+//! 永远无法满足的作业（shots 超过窗口上限、qubit·秒预算已不够）总是被拒绝，已在等待的则直接失败。
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::job::Job;
+use crate::{Result, IrError};
+
+// ============================================================================
+// Quota Keys & Limits
+// ============================================================================
+
+/// 配额账户
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum QuotaKey {
+    User(String),
+    Project(String),
+}
+
+impl QuotaKey {
+    pub fn user(id: impl Into<String>) -> Self {
+        QuotaKey::User(id.into())
+    }
+
+    pub fn project(name: impl Into<String>) -> Self {
+        QuotaKey::Project(name.into())
+    }
+
+    /// 作业所属的账户（未设置用户或项目时不受对应配额约束）
+    pub fn for_job(job: &Job) -> Vec<QuotaKey> {
+        let user = job.metadata.user_id.clone().map(QuotaKey::User);
+        let project = job.metadata.project.clone().map(QuotaKey::Project);
+        user.into_iter().chain(project).collect()
+    }
+}
+
+impl std::fmt::Display for QuotaKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuotaKey::User(id) => write!(f, "user {}", id),
+            QuotaKey::Project(name) => write!(f, "project {}", name),
+        }
+    }
+}
+
+/// 单个账户的配额上限（`None` 表示不限）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuotaLimits {
+    pub max_concurrent_jobs: Option<usize>,
+    /// 每个时间窗口内的最大 shots
+    pub max_shots: Option<(u64, Duration)>,
+    pub max_qubit_seconds: Option<f64>,
+}
+
+impl QuotaLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_concurrent_jobs(mut self, jobs: usize) -> Self {
+        self.max_concurrent_jobs = Some(jobs);
+        self
+    }
+
+    pub fn with_shot_rate(mut self, shots: u64, window: Duration) -> Self {
+        self.max_shots = Some((shots, window));
+        self
+    }
+
+    pub fn with_max_qubit_seconds(mut self, qubit_seconds: f64) -> Self {
+        self.max_qubit_seconds = Some(qubit_seconds);
+        self
+    }
+}
+
+/// 超出配额时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverQuota {
+    /// 提交时拒绝
+    Reject,
+    /// 接受提交，作业保持 Waiting 直到配额允许
+    #[default]
+    Hold,
+}
+
+// ============================================================================
+// Usage Accounting
+// ============================================================================
+
+/// 账户的使用情况报告
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaUsage {
+    pub key: QuotaKey,
+    pub limits: Option<QuotaLimits>,
+    pub running_jobs: usize,
+    /// 当前窗口内已使用的 shots
+    pub shots_in_window: u64,
+    pub total_shots: u64,
+    pub total_jobs: u64,
+    pub qubit_seconds: f64,
+    /// 被拒绝的提交数
+    pub rejected: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Account {
+    running: usize,
+    /// 窗口内的 (开始时刻, shots)，只在设置了 shots 窗口上限时记录
+    window: VecDeque<(Instant, u64)>,
+    total_shots: u64,
+    total_jobs: u64,
+    qubit_seconds: f64,
+    /// 运行中作业预留的 qubit·秒
+    reserved_qubit_seconds: f64,
+    rejected: u64,
+}
+
+impl Account {
+    fn shots_since(&self, since: Option<Instant>) -> u64 {
+        self.window
+            .iter()
+            .filter(|(t, _)| since.is_none_or(|since| *t > since))
+            .map(|(_, shots)| shots)
+            .sum()
+    }
+}
+
+/// 作业开始时预留的 qubit·秒：占用 qubit 数 × 估计运行时间（未给出时按超时，都没有时为 0）
+fn reservation(job: &Job) -> f64 {
+    let runtime = job.estimated_runtime.or(job.metadata.timeout).unwrap_or_default();
+    job.allocated_qubits.len() as f64 * runtime.as_secs_f64()
+}
+
+/// 配额管理器
+///
+/// 作业开始时计入并发数与 shots 并预留 qubit·秒，结束时释放预留、按实际执行时间计入 qubit·秒。
+#[derive(Debug, Clone, Default)]
+pub struct QuotaManager {
+    limits: HashMap<QuotaKey, QuotaLimits>,
+    default_user: Option<QuotaLimits>,
+    default_project: Option<QuotaLimits>,
+    accounts: HashMap<QuotaKey, Account>,
+    action: OverQuota,
+}
+
+impl QuotaManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 为指定账户设置上限
+    pub fn with_limits(mut self, key: QuotaKey, limits: QuotaLimits) -> Self {
+        self.limits.insert(key, limits);
+        self
+    }
+
+    /// 未单独设置上限的用户使用的默认上限
+    pub fn with_default_user_limits(mut self, limits: QuotaLimits) -> Self {
+        self.default_user = Some(limits);
+        self
+    }
+
+    /// 未单独设置上限的项目使用的默认上限
+    pub fn with_default_project_limits(mut self, limits: QuotaLimits) -> Self {
+        self.default_project = Some(limits);
+        self
+    }
+
+    pub fn with_action(mut self, action: OverQuota) -> Self {
+        self.action = action;
+        self
+    }
+
+    pub fn action(&self) -> OverQuota {
+        self.action
+    }
+
+    /// 账户生效的上限
+    pub fn limits(&self, key: &QuotaKey) -> Option<&QuotaLimits> {
+        self.limits.get(key).or(match key {
+            QuotaKey::User(_) => self.default_user.as_ref(),
+            QuotaKey::Project(_) => self.default_project.as_ref(),
+        })
+    }
+
+    /// 作业此刻能否开始；不能时返回第一个超出的配额
+    pub fn check(&self, job: &Job, now: Instant) -> std::result::Result<(), String> {
+        for key in QuotaKey::for_job(job) {
+            let Some(limits) = self.limits(&key) else {
+                continue;
+            };
+            let account = self.accounts.get(&key);
+            if let Some(max) = limits.max_concurrent_jobs {
+                let running = account.map_or(0, |a| a.running);
+                if running >= max {
+                    return Err(format!("{} has {} of {} concurrent jobs running", key, running, max));
+                }
+            }
+            if let Some((max, window)) = limits.max_shots {
+                let used = account.map_or(0, |a| a.shots_since(now.checked_sub(window)));
+                if used + u64::from(job.shots) > max {
+                    return Err(format!(
+                        "{} used {} of {} shots per {:?}; job needs {}", key, used, max, window, job.shots
+                    ));
+                }
+            }
+            if let Some(max) = limits.max_qubit_seconds {
+                let used = account.map_or(0.0, |a| a.qubit_seconds + a.reserved_qubit_seconds);
+                let needed = reservation(job);
+                if used >= max || used + needed > max {
+                    return Err(format!(
+                        "{} used or reserved {:.3} of {} qubit-seconds; job needs {:.3}", key, used, max, needed
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 作业是否永远无法开始：返回原因
+    ///
+    /// shots 超过窗口上限、并发上限为 0，或已用的 qubit·秒加上作业所需超过预算时，
+    /// 等待其他作业结束也无济于事（已用的 qubit·秒不会减少）。
+    pub fn never_admissible(&self, job: &Job) -> Option<String> {
+        QuotaKey::for_job(job).into_iter().find_map(|key| {
+            let limits = self.limits(&key)?;
+            if let Some((max, window)) = limits.max_shots {
+                if u64::from(job.shots) > max {
+                    return Some(format!("Job {} needs {} shots but {} allows {} per {:?}", job.id, job.shots, key, max, window));
+                }
+            }
+            if limits.max_concurrent_jobs == Some(0) {
+                return Some(format!("Job {} cannot run: {} allows no concurrent jobs", job.id, key));
+            }
+            let max = limits.max_qubit_seconds?;
+            let used = self.accounts.get(&key).map_or(0.0, |a| a.qubit_seconds);
+            let needed = reservation(job);
+            (used >= max || used + needed > max).then(|| {
+                format!("Job {} needs {:.3} qubit-seconds but {} has {:.3} of {} left", job.id, needed, key, (max - used).max(0.0), max)
+            })
+        })
+    }
+
+    /// 提交时的检查
+    ///
+    /// 永远无法开始的作业（见 `never_admissible`）总是拒绝；
+    /// `OverQuota::Reject` 下当前超出配额的作业也被拒绝。
+    pub fn admit(&mut self, job: &Job, now: Instant) -> Result<()> {
+        self.admit_with(job, now, self.action == OverQuota::Reject)
+    }
+
+    /// 立即执行的作业（`QuantumRuntime::run`）无法等待，超出配额时总是拒绝
+    pub(crate) fn admit_now(&mut self, job: &Job, now: Instant) -> Result<()> {
+        self.admit_with(job, now, true)
+    }
+
+    fn admit_with(&mut self, job: &Job, now: Instant, reject_over: bool) -> Result<()> {
+        let reason = match self.never_admissible(job) {
+            Some(reason) => reason,
+            None if reject_over => match self.check(job, now) {
+                Ok(()) => return Ok(()),
+                Err(reason) => format!("Job {} rejected: {}", job.id, reason),
+            },
+            None => return Ok(()),
+        };
+        for key in QuotaKey::for_job(job) {
+            self.accounts.entry(key).or_default().rejected += 1;
+        }
+        Err(IrError::QuotaExceeded(reason))
+    }
+
+    /// 作业开始执行
+    pub(crate) fn on_start(&mut self, job: &Job, now: Instant) {
+        let reserved = reservation(job);
+        for key in QuotaKey::for_job(job) {
+            let window = self.limits(&key).and_then(|l| l.max_shots).map(|(_, w)| w);
+            let account = self.accounts.entry(key).or_default();
+            if let Some(window) = window {
+                if let Some(since) = now.checked_sub(window) {
+                    while account.window.front().is_some_and(|(t, _)| *t <= since) {
+                        account.window.pop_front();
+                    }
+                }
+                account.window.push_back((now, u64::from(job.shots)));
+            }
+            account.running += 1;
+            account.total_jobs += 1;
+            account.total_shots += u64::from(job.shots);
+            account.reserved_qubit_seconds += reserved;
+        }
+    }
+
+    /// 重试的作业再次开始：只恢复运行计数与 qubit·秒预留，作业数、shots 与窗口在首次开始时已计入
+    pub(crate) fn on_resume(&mut self, job: &Job) {
+        let reserved = reservation(job);
+        for key in QuotaKey::for_job(job) {
+            let account = self.accounts.entry(key).or_default();
+            account.running += 1;
+            account.reserved_qubit_seconds += reserved;
+        }
+    }
+
+    /// 作业结束（含运行中取消）：释放预留，按执行时间计入 qubit·秒
+    pub(crate) fn on_finish(&mut self, job: &Job, elapsed: Duration) {
+        let reserved = reservation(job);
+        let qubit_seconds = job.allocated_qubits.len() as f64 * elapsed.as_secs_f64();
+        for key in QuotaKey::for_job(job) {
+            let account = self.accounts.entry(key).or_default();
+            account.running = account.running.saturating_sub(1);
+            account.reserved_qubit_seconds = (account.reserved_qubit_seconds - reserved).max(0.0);
+            account.qubit_seconds += qubit_seconds;
+        }
+    }
+
+    /// 账户此刻的使用情况
+    pub fn usage(&self, key: &QuotaKey) -> QuotaUsage {
+        self.usage_at(key, Instant::now())
+    }
+
+    /// 账户在 now 时刻的使用情况
+    pub fn usage_at(&self, key: &QuotaKey, now: Instant) -> QuotaUsage {
+        let limits = self.limits(key).cloned();
+        let window = limits.as_ref().and_then(|l| l.max_shots).map(|(_, w)| w);
+        let since = window.and_then(|w| now.checked_sub(w));
+        let account = self.accounts.get(key).cloned().unwrap_or_default();
+        QuotaUsage {
+            key: key.clone(),
+            limits,
+            running_jobs: account.running,
+            shots_in_window: if window.is_some() { account.shots_since(since) } else { account.total_shots },
+            total_shots: account.total_shots,
+            total_jobs: account.total_jobs,
+            qubit_seconds: account.qubit_seconds,
+            rejected: account.rejected,
+        }
+    }
+
+    /// 所有已记账账户的使用情况（按账户排序）
+    pub fn report(&self) -> Vec<QuotaUsage> {
+        let mut keys: Vec<&QuotaKey> = self.accounts.keys().collect();
+        keys.sort();
+        keys.into_iter().map(|key| self.usage(key)).collect()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::CircuitDag;
+    use crate::job::{Clock, JobMetadata, JobResult, JobScheduler, JobStatus, ManualClock};
+    use crate::operation::h;
+    use crate::qubit::LogicalQubitId;
+
+    fn job(qubit: u64, shots: u32, user: &str, project: &str) -> Job {
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(h(LogicalQubitId::new(qubit)));
+        Job::new(circuit, shots, "sim").with_metadata(JobMetadata::new().with_user(user).with_project(project))
+    }
+
+    fn scheduler(quotas: QuotaManager) -> JobScheduler {
+        JobScheduler::new(8)
+            .with_qubits((0..8).map(LogicalQubitId::new).collect())
+            .with_quotas(quotas)
+    }
+
+    fn clocked(quotas: QuotaManager) -> (JobScheduler, ManualClock) {
+        let clock = ManualClock::new();
+        (scheduler(quotas).with_clock(clock.clone()), clock)
+    }
+
+    fn start(scheduler: &mut JobScheduler) -> Option<Job> {
+        let job = scheduler.schedule_next()?;
+        scheduler.start_execution(job.id).unwrap();
+        Some(job)
+    }
+
+    #[test]
+    fn test_concurrency_hold() {
+        let quotas = QuotaManager::new()
+            .with_limits(QuotaKey::user("alice"), QuotaLimits::new().with_max_concurrent_jobs(1));
+        let mut scheduler = scheduler(quotas);
+        let first = scheduler.submit(job(0, 10, "alice", "p")).unwrap();
+        let second = scheduler.submit(job(1, 10, "alice", "p")).unwrap();
+        let bob = scheduler.submit(job(2, 10, "bob", "p")).unwrap();
+
+        assert_eq!(start(&mut scheduler).unwrap().id, first);
+        // alice 的第二个作业保持 Waiting，bob 不受影响
+        assert_eq!(start(&mut scheduler).unwrap().id, bob);
+        assert!(start(&mut scheduler).is_none());
+        assert_eq!(scheduler.get_status(second), Some(JobStatus::Waiting));
+
+        scheduler.complete(first, JobResult::success(first)).unwrap();
+        assert_eq!(start(&mut scheduler).unwrap().id, second);
+    }
+
+    #[test]
+    fn test_reject_over_quota() {
+        let quotas = QuotaManager::new()
+            .with_default_project_limits(QuotaLimits::new().with_max_concurrent_jobs(1))
+            .with_action(OverQuota::Reject);
+        let mut scheduler = scheduler(quotas);
+        let first = scheduler.submit(job(0, 10, "alice", "calib")).unwrap();
+        start(&mut scheduler).unwrap();
+        let err = scheduler.submit(job(1, 10, "bob", "calib")).unwrap_err();
+        assert!(matches!(err, IrError::QuotaExceeded(_)));
+        assert!(err.to_string().contains("project calib"));
+        // 其他项目不受影响
+        assert!(scheduler.submit(job(2, 10, "bob", "other")).is_ok());
+
+        let usage = scheduler.quotas().usage(&QuotaKey::project("calib"));
+        assert_eq!((usage.running_jobs, usage.rejected), (1, 1));
+        assert_eq!(scheduler.quotas().usage(&QuotaKey::user("bob")).rejected, 1);
+        scheduler.complete(first, JobResult::success(first)).unwrap();
+        assert!(scheduler.submit(job(1, 10, "bob", "calib")).is_ok());
+    }
+
+    #[test]
+    fn test_shot_rate_window() {
+        let window = Duration::from_millis(60);
+        let quotas = QuotaManager::new()
+            .with_limits(QuotaKey::user("alice"), QuotaLimits::new().with_shot_rate(100, window));
+        let (mut scheduler, clock) = clocked(quotas);
+        assert!(matches!(scheduler.submit(job(0, 500, "alice", "p")), Err(IrError::QuotaExceeded(_))));
+
+        let a = scheduler.submit(job(0, 60, "alice", "p")).unwrap();
+        let b = scheduler.submit(job(1, 60, "alice", "p")).unwrap();
+        assert_eq!(start(&mut scheduler).unwrap().id, a);
+        scheduler.complete(a, JobResult::success(a)).unwrap();
+        // 窗口内已用 60 shots，第二个作业须等窗口滑过
+        assert!(start(&mut scheduler).is_none());
+        let alice = QuotaKey::user("alice");
+        assert_eq!(scheduler.quotas().usage_at(&alice, clock.now()).shots_in_window, 60);
+        clock.advance(window + Duration::from_millis(10));
+        assert_eq!(scheduler.quotas().usage_at(&alice, clock.now()).shots_in_window, 0);
+        assert_eq!(start(&mut scheduler).unwrap().id, b);
+    }
+
+    #[test]
+    fn test_window_recorded_only_with_limit() {
+        let quotas = QuotaManager::new()
+            .with_limits(QuotaKey::project("p"), QuotaLimits::new().with_max_concurrent_jobs(8));
+        let mut scheduler = scheduler(quotas);
+        for i in 0..5 {
+            let id = scheduler.submit(job(i, 10, "alice", "p")).unwrap();
+            start(&mut scheduler).unwrap();
+            scheduler.complete(id, JobResult::success(id)).unwrap();
+        }
+        let quotas = scheduler.quotas();
+        assert!(quotas.accounts.values().all(|account| account.window.is_empty()));
+        let usage = quotas.usage(&QuotaKey::user("alice"));
+        assert_eq!((usage.total_jobs, usage.shots_in_window), (5, 50));
+    }
+
+    #[test]
+    fn test_qubit_seconds_budget() {
+        let quotas = QuotaManager::new()
+            .with_limits(QuotaKey::project("p"), QuotaLimits::new().with_max_qubit_seconds(0.02));
+        let (mut scheduler, clock) = clocked(quotas);
+        let a = scheduler.submit(job(0, 10, "alice", "p")).unwrap();
+        let b = scheduler.submit(job(1, 10, "alice", "p")).unwrap();
+        assert_eq!(start(&mut scheduler).unwrap().id, a);
+        assert_eq!(start(&mut scheduler).unwrap().id, b);
+        let held = scheduler.submit(job(2, 10, "alice", "p")).unwrap();
+        clock.advance(Duration::from_millis(30));
+        scheduler.complete(a, JobResult::success(a)).unwrap();
+
+        // 预算已用完：等待中的作业直接失败，新提交的作业被拒绝
+        assert!(start(&mut scheduler).is_none());
+        assert_eq!(scheduler.get_status(held), Some(JobStatus::Failed));
+        assert!(scheduler.get_result(held).unwrap().error.as_ref().unwrap().contains("qubit-seconds"));
+        assert!(matches!(scheduler.submit(job(3, 10, "alice", "p")), Err(IrError::QuotaExceeded(_))));
+        scheduler.complete(b, JobResult::success(b)).unwrap();
+
+        let report = scheduler.quotas().report();
+        assert_eq!(report.len(), 2);
+        let project = report.iter().find(|u| u.key == QuotaKey::project("p")).unwrap();
+        assert!((project.qubit_seconds - 0.06).abs() < 1e-9);
+        assert_eq!((project.total_jobs, project.total_shots, project.running_jobs, project.rejected), (2, 20, 0, 1));
+    }
+
+    #[test]
+    fn test_qubit_seconds_reserved_at_start() {
+        let quotas = QuotaManager::new()
+            .with_limits(QuotaKey::project("p"), QuotaLimits::new().with_max_qubit_seconds(10.0));
+        let (mut scheduler, clock) = clocked(quotas);
+        let estimated = |qubit| job(qubit, 10, "alice", "p").with_estimated_runtime(Duration::from_secs(4));
+
+        // 两个作业各预留 4 qubit·秒，第三个须等预留释放
+        let a = scheduler.submit(estimated(0)).unwrap();
+        scheduler.submit(estimated(1)).unwrap();
+        let c = scheduler.submit(estimated(2)).unwrap();
+        assert_eq!(start(&mut scheduler).unwrap().id, a);
+        assert!(start(&mut scheduler).is_some());
+        assert!(start(&mut scheduler).is_none());
+        assert_eq!(scheduler.get_status(c), Some(JobStatus::Waiting));
+
+        // 实际只用了 1 秒：释放预留后按实际用量记账，第三个作业可以开始
+        clock.advance(Duration::from_secs(1));
+        scheduler.complete(a, JobResult::success(a)).unwrap();
+        assert_eq!(start(&mut scheduler).unwrap().id, c);
+
+        // 估计运行时间超过剩余预算的作业永远无法开始
+        let err = scheduler.submit(job(3, 10, "alice", "p").with_estimated_runtime(Duration::from_secs(20))).unwrap_err();
+        assert!(matches!(err, IrError::QuotaExceeded(_)));
+    }
+}
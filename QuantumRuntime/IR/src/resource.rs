@@ -0,0 +1,445 @@
+//! 资源估算模块
+//!
+//! 对应 Q# `CircuitIR.qs` 中的 `ResourceCost`（门数、T-count、深度、qubit 数），
+//! 并在此基础上给出表面码容错实现下的物理 qubit 与运行时间开销。
+
+use std::collections::BTreeMap;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use crate::backend::GateDurations;
+use crate::circuit::CircuitDag;
+use crate::operation::{Operation, SingleQubitGate, ThreeQubitGate, TwoQubitGate};
+
+// ============================================================================
+// Non-Clifford Cost
+// ============================================================================
+
+/// 单个操作的非 Clifford 开销
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct NonCliffordCost {
+    /// 精确分解所需的 T 门数量
+    t_count: usize,
+    /// 精确分解后的 T 深度
+    t_depth: usize,
+    /// 需要近似综合的任意角度旋转数量
+    rotations: usize,
+}
+
+impl NonCliffordCost {
+    fn add(self, other: NonCliffordCost) -> NonCliffordCost {
+        NonCliffordCost {
+            t_count: self.t_count + other.t_count,
+            t_depth: self.t_depth + other.t_depth,
+            rotations: self.rotations + other.rotations,
+        }
+    }
+}
+
+/// 角度是否为 unit 的整数倍
+fn is_multiple_of(angle: f64, unit: f64) -> bool {
+    let k = angle / unit;
+    (k - k.round()).abs() < 1e-9
+}
+
+/// 绕 Pauli 轴旋转 angle 的开销（π/2 倍数为 Clifford，π/4 倍数需一个 T）
+fn rotation_cost(angle: f64) -> NonCliffordCost {
+    if is_multiple_of(angle, FRAC_PI_2) {
+        NonCliffordCost::default()
+    } else if is_multiple_of(angle, FRAC_PI_4) {
+        NonCliffordCost { t_count: 1, t_depth: 1, rotations: 0 }
+    } else {
+        NonCliffordCost { t_count: 0, t_depth: 0, rotations: 1 }
+    }
+}
+
+fn single_qubit_cost(gate: &SingleQubitGate) -> NonCliffordCost {
+    match *gate {
+        SingleQubitGate::T | SingleQubitGate::Tdg => rotation_cost(FRAC_PI_4),
+        SingleQubitGate::Rx(a)
+        | SingleQubitGate::Ry(a)
+        | SingleQubitGate::Rz(a)
+        | SingleQubitGate::P(a) => rotation_cost(a),
+        // U(θ,φ,λ) = Rz(φ) Ry(θ) Rz(λ)
+        SingleQubitGate::U(theta, phi, lam) => rotation_cost(phi)
+            .add(rotation_cost(theta))
+            .add(rotation_cost(lam)),
+        _ => NonCliffordCost::default(),
+    }
+}
+
+fn operation_cost(op: &Operation) -> NonCliffordCost {
+    match op {
+        Operation::Gate1 { gate, .. } => single_qubit_cost(gate),
+        Operation::Gate2 { gate, .. } => match *gate {
+            // CP(φ) = Rz(φ/2)⊗Rz(φ/2) · CNOT · Rz(-φ/2) · CNOT，两个 qubit 上的旋转可并行
+            TwoQubitGate::CP(phi) => {
+                let half = rotation_cost(phi / 2.0);
+                NonCliffordCost {
+                    t_count: half.t_count * 3,
+                    t_depth: half.t_depth * 2,
+                    rotations: half.rotations * 3,
+                }
+            }
+            // MS(θ) 与 Rz(θ) 相差 Clifford 共轭
+            TwoQubitGate::MS(theta) => rotation_cost(theta),
+            // √SWAP 含两个 √iSWAP 等价的非 Clifford 成分，保守计为任意旋转
+            TwoQubitGate::SqrtSWAP => NonCliffordCost { t_count: 0, t_depth: 0, rotations: 1 },
+            _ => NonCliffordCost::default(),
+        },
+        // Toffoli / CCZ / Fredkin 的标准分解：7 个 T，T 深度 3
+        Operation::Gate3 { gate, .. } => match gate {
+            ThreeQubitGate::Toffoli | ThreeQubitGate::CCZ | ThreeQubitGate::Fredkin => {
+                NonCliffordCost { t_count: 7, t_depth: 3, rotations: 0 }
+            }
+        },
+        // 未绑定的角度按任意旋转计
+        Operation::Symbolic { params, .. } => NonCliffordCost { t_count: 0, t_depth: 0, rotations: params.len() },
+        _ => NonCliffordCost::default(),
+    }
+}
+
+fn is_two_qubit(op: &Operation) -> bool {
+    match op {
+        Operation::Gate2 { .. } => true,
+        Operation::Symbolic { op, .. } => is_two_qubit(op),
+        Operation::Custom(custom) => custom.qubits.len() == 2,
+        _ => false,
+    }
+}
+
+// ============================================================================
+// Resource Estimate
+// ============================================================================
+
+/// 电路资源估算结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceEstimate {
+    /// 逻辑 qubit 数量
+    pub num_qubits: usize,
+    /// 各类型操作数量（按操作名称）
+    pub gate_counts: BTreeMap<String, usize>,
+    /// 操作总数
+    pub total_operations: usize,
+    /// 电路深度
+    pub depth: usize,
+    /// T-count（含 Toffoli 等门精确分解所需的 T）
+    pub t_count: usize,
+    /// T 深度
+    pub t_depth: usize,
+    /// 需要近似综合的任意角度旋转数量
+    pub rotation_count: usize,
+    /// 双比特门数量
+    pub two_qubit_count: usize,
+    /// 双比特门深度
+    pub two_qubit_depth: usize,
+    /// 测量数量
+    pub measurement_count: usize,
+    /// 关键路径时延（纳秒）
+    pub critical_path_ns: f64,
+}
+
+impl ResourceEstimate {
+    /// 分析电路资源，关键路径时延按 durations 计算
+    pub fn analyze(circuit: &CircuitDag, durations: &GateDurations) -> Self {
+        let mut gate_counts = BTreeMap::new();
+        let mut t_count = 0;
+        let mut rotation_count = 0;
+        let mut two_qubit_count = 0;
+        let mut measurement_count = 0;
+
+        for node in circuit.nodes() {
+            *gate_counts.entry(node.op.name().to_string()).or_insert(0) += 1;
+            let cost = operation_cost(&node.op);
+            t_count += cost.t_count;
+            rotation_count += cost.rotations;
+            if is_two_qubit(&node.op) {
+                two_qubit_count += 1;
+            }
+            if node.op.is_measurement() {
+                measurement_count += 1;
+            }
+        }
+
+        let t_depth = circuit.longest_path(|op| operation_cost(op).t_depth as f64) as usize;
+        let two_qubit_depth = circuit.longest_path(|op| {
+            if is_two_qubit(op) { 1.0 } else { 0.0 }
+        }) as usize;
+        let depth = circuit.longest_path(|_| 1.0) as usize;
+        let critical_path_ns = circuit.longest_path(|op| durations.duration(op));
+
+        Self {
+            num_qubits: circuit.num_qubits(),
+            gate_counts,
+            total_operations: circuit.num_operations(),
+            depth,
+            t_count,
+            t_depth,
+            rotation_count,
+            two_qubit_count,
+            two_qubit_depth,
+            measurement_count,
+            critical_path_ns,
+        }
+    }
+
+    /// 按名称查询操作数量
+    pub fn count(&self, gate_name: &str) -> usize {
+        self.gate_counts.get(gate_name).copied().unwrap_or(0)
+    }
+
+    /// 在表面码模型下估算物理开销
+    pub fn surface_code(&self, model: &SurfaceCodeModel) -> SurfaceCodeEstimate {
+        let d = model.code_distance;
+        let qubits_per_patch = 2 * d * d;
+
+        let logical_qubits =
+            (self.num_qubits as f64 * model.routing_overhead).ceil() as usize;
+        let data_qubits = logical_qubits * qubits_per_patch;
+        let factory_qubits = model.num_factories * model.factory_qubits;
+
+        let t_states = self.rotation_count
+            .saturating_mul(model.t_per_rotation())
+            .saturating_add(self.t_count);
+
+        // 每个逻辑层需要 d 个综合征测量周期；T 态供给受工厂吞吐限制
+        let layer_cycles = self.depth.max(self.t_depth).saturating_mul(d);
+        let factory_cycles = if model.num_factories == 0 {
+            0
+        } else {
+            t_states.saturating_mul(model.factory_cycles_per_t).div_ceil(model.num_factories)
+        };
+        let code_cycles = layer_cycles.max(factory_cycles);
+
+        SurfaceCodeEstimate {
+            code_distance: d,
+            logical_qubits,
+            physical_data_qubits: data_qubits,
+            physical_factory_qubits: factory_qubits,
+            total_physical_qubits: data_qubits + factory_qubits,
+            t_states,
+            code_cycles,
+            runtime_ns: code_cycles as f64 * model.cycle_time_ns,
+        }
+    }
+}
+
+impl CircuitDag {
+    /// 使用默认门时长估算资源
+    pub fn resource_estimate(&self) -> ResourceEstimate {
+        ResourceEstimate::analyze(self, &GateDurations::default())
+    }
+}
+
+// ============================================================================
+// Surface Code Model
+// ============================================================================
+
+/// 表面码容错开销模型
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceCodeModel {
+    /// 码距 d
+    pub code_distance: usize,
+    /// 综合征测量周期（纳秒）
+    pub cycle_time_ns: f64,
+    /// 单个魔法态工厂占用的物理 qubit
+    pub factory_qubits: usize,
+    /// 工厂产出一个 T 态所需的周期数
+    pub factory_cycles_per_t: usize,
+    /// 并行工厂数量
+    pub num_factories: usize,
+    /// 逻辑 qubit 的布线开销系数（路由通道等）
+    pub routing_overhead: f64,
+    /// 任意角度旋转的综合精度
+    pub rotation_epsilon: f64,
+}
+
+impl SurfaceCodeModel {
+    pub fn new(code_distance: usize) -> Self {
+        Self {
+            code_distance,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_cycle_time(mut self, cycle_time_ns: f64) -> Self {
+        self.cycle_time_ns = cycle_time_ns;
+        self
+    }
+
+    pub fn with_factories(mut self, num_factories: usize, factory_qubits: usize, cycles_per_t: usize) -> Self {
+        self.num_factories = num_factories;
+        self.factory_qubits = factory_qubits;
+        self.factory_cycles_per_t = cycles_per_t;
+        self
+    }
+
+    pub fn with_routing_overhead(mut self, overhead: f64) -> Self {
+        self.routing_overhead = overhead;
+        self
+    }
+
+    pub fn with_rotation_epsilon(mut self, epsilon: f64) -> Self {
+        self.rotation_epsilon = epsilon;
+        self
+    }
+
+    /// 单个任意旋转综合所需的 T 数量（gridsynth 经验公式 3·log2(1/ε)）
+    /// 
+    /// ε 限制在 (0, 1) 内：不大于 0 或 NaN 时按最小正浮点数计，不小于 1 时无需 T 门。
+    pub fn t_per_rotation(&self) -> usize {
+        let epsilon = self.rotation_epsilon;
+        if epsilon >= 1.0 {
+            return 0;
+        }
+        let epsilon = if epsilon > 0.0 { epsilon } else { f64::MIN_POSITIVE };
+        (3.0 * (1.0 / epsilon).log2()).ceil() as usize
+    }
+}
+
+impl Default for SurfaceCodeModel {
+    fn default() -> Self {
+        Self {
+            code_distance: 15,
+            cycle_time_ns: 1_000.0,
+            // 15-to-1 蒸馏工厂
+            factory_qubits: 4_620,
+            factory_cycles_per_t: 43,
+            num_factories: 1,
+            routing_overhead: 2.0,
+            rotation_epsilon: 1e-10,
+        }
+    }
+}
+
+/// 表面码开销估算结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceCodeEstimate {
+    pub code_distance: usize,
+    /// 含布线开销的逻辑 patch 数
+    pub logical_qubits: usize,
+    /// 数据 patch 的物理 qubit
+    pub physical_data_qubits: usize,
+    /// 魔法态工厂的物理 qubit
+    pub physical_factory_qubits: usize,
+    pub total_physical_qubits: usize,
+    /// 消耗的 T 态总数（含旋转综合）
+    pub t_states: usize,
+    /// 总综合征测量周期数
+    pub code_cycles: usize,
+    /// 估算运行时间（纳秒）
+    pub runtime_ns: f64,
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::{bell_state_dag, CircuitDagBuilder};
+    use crate::operation::{cnot, h, measure, rz, t, toffoli};
+    use crate::qubit::LogicalQubitId;
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    #[test]
+    fn test_bell_state_estimate() {
+        let est = ResourceEstimate::analyze(&bell_state_dag(), &GateDurations::unit());
+        assert_eq!(est.num_qubits, 2);
+        assert_eq!(est.count("H"), 1);
+        assert_eq!(est.count("CNOT"), 1);
+        assert_eq!(est.measurement_count, 2);
+        assert_eq!(est.two_qubit_count, 1);
+        assert_eq!(est.two_qubit_depth, 1);
+        assert_eq!(est.t_count, 0);
+        assert_eq!(est.depth, 3);
+        assert_eq!(est.critical_path_ns, 3.0);
+    }
+
+    #[test]
+    fn test_t_count_and_depth() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(t(q(0)));
+        builder.add_op(t(q(1)));
+        builder.add_op(t(q(0)));
+        builder.add_op(rz(q(1), FRAC_PI_4));
+        builder.add_op(rz(q(2), 0.123));
+        builder.add_op(toffoli(q(0), q(1), q(2)));
+        let est = builder.build().resource_estimate();
+
+        // 3 个 T + Rz(π/4) + Toffoli 的 7 个
+        assert_eq!(est.t_count, 11);
+        // q0: T, T 然后 Toffoli(3)
+        assert_eq!(est.t_depth, 5);
+        assert_eq!(est.rotation_count, 1);
+    }
+
+    #[test]
+    fn test_clifford_rotations_are_free() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(rz(q(0), FRAC_PI_2));
+        builder.add_op(rz(q(0), std::f64::consts::PI));
+        let est = builder.build().resource_estimate();
+        assert_eq!(est.t_count, 0);
+        assert_eq!(est.rotation_count, 0);
+    }
+
+    #[test]
+    fn test_critical_path_uses_durations() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(0)));
+        builder.add_op(h(q(1)));
+        builder.add_op(cnot(q(0), q(1)));
+        builder.add_op(measure(q(0)));
+        let durations = GateDurations::new(10.0, 100.0, 300.0, 500.0, 500.0);
+        let est = ResourceEstimate::analyze(&builder.build(), &durations);
+        assert_eq!(est.critical_path_ns, 610.0);
+    }
+
+    #[test]
+    fn test_surface_code_overhead() {
+        let mut builder = CircuitDagBuilder::new();
+        for _ in 0..100 {
+            builder.add_op(t(q(0)));
+        }
+        let est = builder.build().resource_estimate();
+
+        let model = SurfaceCodeModel::new(11)
+            .with_factories(2, 1_000, 10)
+            .with_routing_overhead(1.0);
+        let sc = est.surface_code(&model);
+
+        assert_eq!(sc.logical_qubits, 1);
+        assert_eq!(sc.physical_data_qubits, 2 * 11 * 11);
+        assert_eq!(sc.physical_factory_qubits, 2_000);
+        assert_eq!(sc.t_states, 100);
+        // 层周期 100*11 = 1100 > 工厂周期 100*10/2 = 500
+        assert_eq!(sc.code_cycles, 1_100);
+        assert_eq!(sc.runtime_ns, 1_100.0 * 1_000.0);
+
+        // 单工厂且慢速时受 T 态供给限制
+        let slow = SurfaceCodeModel::new(11).with_factories(1, 1_000, 50);
+        assert_eq!(est.surface_code(&slow).code_cycles, 5_000);
+    }
+
+    #[test]
+    fn test_rotation_epsilon_is_clamped() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(rz(q(0), 0.3));
+        builder.add_op(t(q(0)));
+        let est = builder.build().resource_estimate();
+
+        let finest = SurfaceCodeModel::new(5).with_rotation_epsilon(f64::MIN_POSITIVE).t_per_rotation();
+        for epsilon in [0.0, -1e-3, f64::NAN] {
+            let model = SurfaceCodeModel::new(5).with_rotation_epsilon(epsilon);
+            assert_eq!(model.t_per_rotation(), finest);
+            assert_eq!(est.surface_code(&model).t_states, finest + 1);
+        }
+        let coarse = SurfaceCodeModel::new(5).with_rotation_epsilon(2.0);
+        assert_eq!(coarse.t_per_rotation(), 0);
+        assert_eq!(est.surface_code(&coarse).t_states, 1);
+    }
+}
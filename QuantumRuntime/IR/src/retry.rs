@@ -0,0 +1,143 @@
+//! 重试与故障转移模块
+//!
+//! 后端执行返回错误时，`RetryPolicy` 决定是否重试、重试前等待多久以及是否换到其他后端：
+//! - 只有 `retry_on` 中列出的错误类别会重试，后端返回的失败结果（而非错误）不重试；
+//! - 退避时间按指数增长并受上限约束；
+//! - 启用故障转移时，重试依次换到其他已注册且能力可运行该电路的后端。
+//!
+//! 每次尝试都记入 `JobResult::attempts`。
+
+use std::time::Duration;
+
+use crate::{ErrorKind, IrError};
+
+// ============================================================================
+// Retry Policy
+// ============================================================================
+
+/// 重试策略
+///
+/// 可在 `RuntimeConfig::with_retry` 中设置默认值，或用 `Job::with_retry` 覆盖单个作业。
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// 最多尝试次数（含第一次），至少为 1
+    pub max_attempts: u32,
+    /// 第一次重试前的等待时间
+    pub initial_backoff: Duration,
+    /// 每次重试后等待时间的倍数
+    pub backoff_multiplier: f64,
+    /// 等待时间上限
+    pub max_backoff: Duration,
+    /// 可重试的错误类别
+    pub retry_on: Vec<ErrorKind>,
+    /// 重试时换到其他能运行该电路的后端
+    pub failover: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff: Duration::from_millis(100),
+            backoff_multiplier: 2.0,
+            max_backoff: Duration::from_secs(10),
+            retry_on: vec![ErrorKind::BackendUnavailable, ErrorKind::JobExecutionFailed, ErrorKind::Timeout],
+            failover: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 最多尝试 max_attempts 次，其余取默认值
+    pub fn new(max_attempts: u32) -> Self {
+        Self { max_attempts: max_attempts.max(1), ..Self::default() }
+    }
+
+    /// 不重试（默认）
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn with_backoff(mut self, initial: Duration, multiplier: f64) -> Self {
+        self.initial_backoff = initial;
+        self.backoff_multiplier = multiplier.max(1.0);
+        self
+    }
+
+    pub fn with_max_backoff(mut self, max: Duration) -> Self {
+        self.max_backoff = max;
+        self
+    }
+
+    /// 替换可重试的错误类别
+    pub fn with_retry_on(mut self, kinds: impl IntoIterator<Item = ErrorKind>) -> Self {
+        self.retry_on = kinds.into_iter().collect();
+        self
+    }
+
+    pub fn with_failover(mut self, failover: bool) -> Self {
+        self.failover = failover;
+        self
+    }
+
+    /// 错误是否属于可重试的类别
+    pub fn is_retryable(&self, error: &IrError) -> bool {
+        self.retry_on.contains(&error.kind())
+    }
+
+    /// 第 retry 次重试（从 1 开始）前的等待时间
+    /// 
+    /// 计算结果溢出或不是有限值（如倍数被设为 NaN）时取上限，负值按 0 计。
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.backoff_multiplier.powi(retry.saturating_sub(1) as i32);
+        let secs = self.initial_backoff.as_secs_f64() * factor;
+        if !secs.is_finite() || secs >= self.max_backoff.as_secs_f64() {
+            self.max_backoff
+        } else {
+            Duration::from_secs_f64(secs.max(0.0))
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_schedule() {
+        let policy = RetryPolicy::new(5)
+            .with_backoff(Duration::from_millis(50), 2.0)
+            .with_max_backoff(Duration::from_millis(300));
+        let delays: Vec<u128> = (1..=5).map(|retry| policy.backoff(retry).as_millis()).collect();
+        assert_eq!(delays, vec![50, 100, 200, 300, 300]);
+
+        let fixed = RetryPolicy::new(3).with_backoff(Duration::from_millis(20), 1.0);
+        assert_eq!(fixed.backoff(3), Duration::from_millis(20));
+
+        // 倍数溢出、NaN 或负值时不 panic
+        let zero = RetryPolicy::new(3).with_backoff(Duration::ZERO, 10.0).with_max_backoff(Duration::from_secs(1));
+        assert_eq!(zero.backoff(1000), Duration::from_secs(1));
+        let mut broken = RetryPolicy::new(3).with_max_backoff(Duration::from_secs(1));
+        broken.backoff_multiplier = f64::NAN;
+        assert_eq!(broken.backoff(2), Duration::from_secs(1));
+        broken.backoff_multiplier = -2.0;
+        assert_eq!(broken.backoff(2), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_retryable_kinds() {
+        let policy = RetryPolicy::new(3);
+        assert!(policy.is_retryable(&IrError::BackendUnavailable("offline".into())));
+        assert!(policy.is_retryable(&IrError::Timeout("slow".into())));
+        assert!(!policy.is_retryable(&IrError::UnsupportedOperation("gate".into())));
+
+        let policy = policy.with_retry_on([ErrorKind::UnsupportedOperation]);
+        assert!(policy.is_retryable(&IrError::UnsupportedOperation("gate".into())));
+        assert!(!policy.is_retryable(&IrError::Timeout("slow".into())));
+        assert_eq!(RetryPolicy::new(0).max_attempts, 1);
+    }
+}
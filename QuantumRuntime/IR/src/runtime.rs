@@ -2,26 +2,51 @@
 //! 
 //! QuantumRuntime 负责协调调度器、后端和执行流程
 
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{mpsc, Arc, Mutex};
+use std::task::Poll;
+use std::time::{Duration, Instant};
 use crate::circuit::CircuitDag;
-use crate::job::{Job, JobId, JobResult, JobScheduler, JobStatus, Priority, JobMetadata};
-use crate::backend::{BackendAdapter, BackendCapabilities};
+use crate::future::{block_on, block_on_timeout, completion, sleep, spawn_detached, JobCompleter, JobFuture};
+use crate::job::{Job, JobAttempt, JobId, JobResult, JobScheduler, JobStatus, Priority, JobMetadata};
+use crate::backend::{BackendAdapter, BackendCapabilities, SubmittedJob};
+use crate::quota::{QuotaKey, QuotaManager, QuotaUsage};
+use crate::retry::RetryPolicy;
+use crate::store::{JobStore, RecoveryMode, RecoveryReport};
+use crate::{Result, IrError};
 
 // ============================================================================
 // Runtime Configuration
 // ============================================================================
 
+/// 作业执行方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExecutionMode {
+    /// 在调用线程上逐个执行
+    #[default]
+    Serial,
+    /// 由 `max_concurrent_jobs` 个工作线程并行执行
+    WorkerPool,
+}
+
 /// Runtime 配置
 #[derive(Debug, Clone)]
 pub struct RuntimeConfig {
-    /// 最大并发作业数
+    /// 最大并发作业数（工作线程数）
     pub max_concurrent_jobs: usize,
     /// 默认后端
     pub default_backend: String,
-    /// 作业超时时间（秒）
+    /// 作业执行超时（秒），0 表示不限；可被 `JobMetadata::timeout` 覆盖
     pub job_timeout_secs: u64,
     /// 启用详细日志
     pub verbose: bool,
+    /// `execute_all` 的执行方式
+    pub execution_mode: ExecutionMode,
+    /// 默认重试策略；可被 `Job::with_retry` 覆盖
+    pub retry: RetryPolicy,
 }
 
 impl Default for RuntimeConfig {
@@ -31,6 +56,8 @@ impl Default for RuntimeConfig {
             default_backend: "default".to_string(),
             job_timeout_secs: 300,
             verbose: false,
+            execution_mode: ExecutionMode::default(),
+            retry: RetryPolicy::default(),
         }
     }
 }
@@ -55,10 +82,25 @@ impl RuntimeConfig {
         self
     }
     
+    /// 默认的作业执行超时
+    pub fn job_timeout(&self) -> Option<Duration> {
+        (self.job_timeout_secs > 0).then(|| Duration::from_secs(self.job_timeout_secs))
+    }
+    
     pub fn with_verbose(mut self, verbose: bool) -> Self {
         self.verbose = verbose;
         self
     }
+    
+    pub fn with_execution_mode(mut self, mode: ExecutionMode) -> Self {
+        self.execution_mode = mode;
+        self
+    }
+    
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
 }
 
 // ============================================================================
@@ -103,6 +145,14 @@ pub struct RuntimeStats {
     pub total_jobs_submitted: u64,
     pub total_jobs_completed: u64,
     pub total_jobs_failed: u64,
+    /// 因超时失败的作业数（计入 `total_jobs_failed`）
+    pub total_jobs_timed_out: u64,
+    /// 写入作业存储失败的次数
+    pub total_store_errors: u64,
+    /// 重试次数（不含每个作业的第一次尝试）
+    pub total_retries: u64,
+    /// 重试时换用其他后端的次数
+    pub total_failovers: u64,
     pub total_execution_time_ms: u64,
     pub current_running_jobs: u64,
 }
@@ -133,6 +183,10 @@ pub struct QuantumRuntime {
     scheduler: JobScheduler,
     stats: RuntimeStats,
     running: bool,
+    /// 作业存储（写穿）
+    store: Option<Box<dyn JobStore>>,
+    /// 等待重试的作业已做过的尝试与下一次使用的候选后端
+    retries: HashMap<JobId, RetryState>,
 }
 
 impl QuantumRuntime {
@@ -145,6 +199,8 @@ impl QuantumRuntime {
             scheduler: JobScheduler::new(max_jobs),
             stats: RuntimeStats::default(),
             running: false,
+            store: None,
+            retries: HashMap::new(),
         }
     }
     
@@ -169,11 +225,24 @@ impl QuantumRuntime {
     }
     
     /// 提交作业
-    pub fn submit_job(&mut self, job: Job) -> JobId {
-        let job_id = job.id;
-        self.scheduler.submit(job);
+    /// 
+    /// 设置了作业存储时，作业被接受后立即写入；写入失败时作业被取消并返回错误。
+    pub fn submit_job(&mut self, job: Job) -> Result<JobId> {
+        let record = self.store.is_some().then(|| job.clone());
+        let job_id = self.scheduler.submit(job)?;
         self.stats.total_jobs_submitted += 1;
-        job_id
+        if let (Some(store), Some(mut record)) = (self.store.as_mut(), record) {
+            record.status = self.scheduler.get_status(job_id).unwrap_or(JobStatus::Queued);
+            if let Err(e) = store.record_submitted(&record) {
+                self.stats.total_store_errors += 1;
+                self.scheduler.cancel(job_id);
+                let finished = self.scheduler.take_finished().into_iter().filter(|&id| id != job_id).collect();
+                self.persist_results(finished);
+                return Err(e);
+            }
+        }
+        self.persist_finished();
+        Ok(job_id)
     }
     
     /// 创建并提交新作业
@@ -183,7 +252,7 @@ impl QuantumRuntime {
         shots: u32,
         priority: Priority,
         metadata: JobMetadata,
-    ) -> JobId {
+    ) -> Result<JobId> {
         let backend = if circuit.metadata().name.is_some() {
             circuit.metadata().name.clone().unwrap_or(self.config.default_backend.clone())
         } else {
@@ -197,41 +266,309 @@ impl QuantumRuntime {
         self.submit_job(job)
     }
     
-    /// 调度并执行下一个作业（同步）
+    /// 调度并执行下一个作业（同步），直到它结束
+    /// 
+    /// 作业需要重试时，调用线程等到重试时刻再执行它的下一次尝试。
     pub fn schedule_and_execute(&mut self) -> Option<JobResult> {
-        // 调度下一个可执行的作业
-        let job = self.scheduler.schedule_next()?;
-        let job_id = job.id;
-        
-        // 获取后端
-        let backend = self.registry.get(&job.target_backend)?;
+        let mut dispatch = self.start_next()?;
+        loop {
+            let task = match dispatch {
+                Dispatch::Run(task) => task,
+                Dispatch::Finished(result) => return Some(result),
+            };
+            let job_id = task.job.id;
+            let outcome = task.run();
+            if let Some(result) = self.complete_attempt(outcome) {
+                return Some(result);
+            }
+            if let Some(at) = self.scheduler.next_retry_where(|job| job.id == job_id) {
+                std::thread::sleep(at.saturating_duration_since(self.scheduler.now()));
+            }
+            dispatch = self.start_next_where(|job| job.id == job_id)?;
+        }
+    }
+    
+    /// 不经调度队列直接在目标后端上同步执行作业
+    /// 
+    /// 用于估计器、梯度等需要立即取得结果的上层原语；后端执行出错时按重试策略重试，仍失败时返回失败结果。
+    /// 作业不经调度器，重试的退避在调用线程上等待。
+    /// 作业同样受配额约束并计入使用量；无法等待配额释放，超出时以 `QuotaExceeded` 拒绝。
+    pub fn run(&mut self, mut job: Job) -> Result<JobResult> {
+        let plan = self.plan(&job).ok_or_else(|| {
+            IrError::BackendUnavailable(format!("Backend {} is not registered", job.target_backend))
+        })?;
+        self.apply_timeout(&mut job);
+        let started = self.scheduler.start_immediate(&job)?;
+        self.stats.total_jobs_submitted += 1;
+        let result = execute_job(plan, job.clone());
+        self.scheduler.finish_immediate(&job, started);
+        self.record_result(&result);
+        Ok(result)
+    }
+    
+    /// 调度下一个作业并转为 Running；后端未注册时作业直接以失败结束
+    fn start_next(&mut self) -> Option<Dispatch> {
+        self.start_next_where(|_| true)
+    }
+    
+    /// 只在满足 filter 的作业中调度下一个
+    fn start_next_where(&mut self, filter: impl Fn(&Job) -> bool) -> Option<Dispatch> {
+        let next = self.scheduler.schedule_next_where(filter);
+        self.persist_finished();
+        let mut job = next?;
+        self.apply_timeout(&mut job);
+        self.scheduler
+            .start_execution(job.id)
+            .expect("scheduled job can start");
+        self.stats.current_running_jobs += 1;
+        self.persist_status(job.id, JobStatus::Running);
         
-        // 执行
-        match backend.execute(&job) {
-            Ok(result) => {
-                self.scheduler.complete(job_id, result.clone());
-                self.stats.total_jobs_completed += 1;
-                if let Some(exec_time) = result.execution_time_ms {
-                    self.stats.total_execution_time_ms += exec_time;
-                }
-                Some(result)
+        let retry = self.retries.remove(&job.id).unwrap_or_default();
+        match self.plan(&job) {
+            Some(plan) => {
+                let candidate = retry.candidate % plan.backends.len();
+                let attempt = retry.attempts.len() as u32 + 1;
+                self.retries.insert(job.id, retry);
+                Some(Dispatch::Run(AttemptTask { plan, job: Box::new(job), attempt, candidate }))
+            }
+            None => {
+                let mut result = JobResult::failure(job.id, format!("Backend {} is not registered", job.target_backend));
+                result.attempts = retry.attempts;
+                self.finish(job.id, result.clone());
+                Some(Dispatch::Finished(result))
             }
-            Err(e) => {
-                let result = JobResult::failure(job_id, e.to_string());
-                self.scheduler.complete(job_id, result.clone());
-                self.stats.total_jobs_failed += 1;
-                Some(result)
+        }
+    }
+    
+    /// 一次尝试结束：需要重试时把作业放回队列等待退避，否则以该结果结束作业
+    /// 
+    /// 返回作业的最终结果；作业回到队列时返回 None。
+    fn complete_attempt(&mut self, outcome: AttemptOutcome) -> Option<JobResult> {
+        let AttemptOutcome { job_id, mut result, record, retry } = outcome;
+        let mut state = self.retries.remove(&job_id).unwrap_or_default();
+        state.attempts.push(record);
+        if let Some((candidate, delay)) = retry {
+            let not_before = self.scheduler.now() + delay;
+            if self.scheduler.requeue(job_id, not_before).is_ok() {
+                self.stats.current_running_jobs -= 1;
+                self.persist_status(job_id, JobStatus::Queued);
+                self.retries.insert(job_id, RetryState { attempts: state.attempts, candidate });
+                return None;
             }
         }
+        result.attempts = state.attempts;
+        self.finish(job_id, result.clone());
+        Some(result)
+    }
+    
+    /// 运行中的作业结束：更新统计并交还调度器
+    /// 
+    /// 执行期间已被取消的作业保留取消结果，后端返回的结果被丢弃。
+    fn finish(&mut self, job_id: JobId, result: JobResult) {
+        self.stats.current_running_jobs -= 1;
+        if self.scheduler.complete(job_id, result.clone()).is_ok() {
+            self.record_result(&result);
+        }
+        self.persist_finished();
+    }
+    
+    /// 作业的执行计划：目标后端在前；启用故障转移时其后依次是能运行该作业的其他已注册后端
+    /// 
+    /// 目标后端未注册且没有可替代的后端时返回 None。
+    fn plan(&self, job: &Job) -> Option<ExecutionPlan> {
+        let retry = job.retry.clone().unwrap_or_else(|| self.config.retry.clone());
+        let mut backends: Vec<_> = self.registry.get(&job.target_backend)
+            .map(|backend| (job.target_backend.clone(), backend))
+            .into_iter()
+            .collect();
+        if retry.failover {
+            let mut others: Vec<_> = self.registry.backends
+                .iter()
+                .filter(|(id, backend)| **id != job.target_backend && backend.capabilities().check_job(job).is_ok())
+                .map(|(id, backend)| (id.clone(), Arc::clone(backend)))
+                .collect();
+            others.sort_by(|a, b| a.0.cmp(&b.0));
+            backends.extend(others);
+        }
+        (!backends.is_empty()).then_some(ExecutionPlan { backends, retry })
     }
     
-    /// 执行所有排队的作业
+    /// 作业未指定超时时使用配置的默认值，后端据此限制等待时间
+    fn apply_timeout(&self, job: &mut Job) {
+        if job.metadata.timeout.is_none() {
+            job.metadata.timeout = self.config.job_timeout();
+        }
+    }
+    
+    fn record_result(&mut self, result: &JobResult) {
+        self.stats.total_retries += result.retries() as u64;
+        self.stats.total_failovers += result.attempts.windows(2).filter(|w| w[0].backend != w[1].backend).count() as u64;
+        if result.timed_out {
+            self.stats.total_jobs_timed_out += 1;
+        }
+        if result.status == JobStatus::Completed {
+            self.stats.total_jobs_completed += 1;
+            if let Some(exec_time) = result.execution_time_ms {
+                self.stats.total_execution_time_ms += exec_time;
+            }
+        } else {
+            self.stats.total_jobs_failed += 1;
+        }
+    }
+    
+    /// 执行所有排队的作业（按配置的执行方式），结果按完成顺序返回
     pub fn execute_all(&mut self) -> Vec<JobResult> {
+        self.drive(None)
+    }
+    
+    /// 只执行给定的作业（按配置的执行方式），调度器中的其他作业留在队列中
+    /// 
+    /// 每个作业结束（完成、失败、取消或过期）后立即以其 ID 调用 on_finish；
+    /// 回调可以提交新作业并返回其 ID，这些作业随即加入执行范围。
+    /// 返回实际执行的作业结果，按完成顺序。
+    pub fn execute_jobs_with<F>(&mut self, jobs: impl IntoIterator<Item = JobId>, mut on_finish: F) -> Vec<JobResult>
+    where
+        F: FnMut(&mut QuantumRuntime, JobId) -> Vec<JobId>,
+    {
+        self.drive(Some(Selection {
+            jobs: jobs.into_iter().collect(),
+            reported: HashSet::new(),
+            on_finish: &mut on_finish,
+        }))
+    }
+    
+    fn drive(&mut self, selection: Option<Selection<'_>>) -> Vec<JobResult> {
+        match self.config.execution_mode {
+            ExecutionMode::Serial => self.drive_serial(selection),
+            ExecutionMode::WorkerPool => self.drive_pooled(selection),
+        }
+    }
+    
+    /// 在调用线程上逐个执行
+    /// 
+    /// 需要重试的作业回到队列等待退避，期间其他作业照常执行。
+    fn drive_serial(&mut self, mut selection: Option<Selection<'_>>) -> Vec<JobResult> {
         let mut results = Vec::new();
-        
-        while let Some(result) = self.schedule_and_execute() {
-            results.push(result);
+        self.notify_finished(&mut selection);
+        loop {
+            match self.start_next_where(|job| Selection::selects(&selection, job)) {
+                Some(Dispatch::Run(task)) => {
+                    let outcome = task.run();
+                    results.extend(self.complete_attempt(outcome));
+                }
+                Some(Dispatch::Finished(result)) => results.push(result),
+                None if self.notify_finished(&mut selection) => continue,
+                // 只剩等待退避的作业时等到最早的重试时刻
+                None => match self.scheduler.next_retry_where(|job| Selection::selects(&selection, job)) {
+                    Some(at) => std::thread::sleep(at.saturating_duration_since(self.scheduler.now())),
+                    None => break,
+                },
+            }
+            self.notify_finished(&mut selection);
+        }
+        results
+    }
+    
+    /// 执行范围内新结束的作业逐个交给回调，返回回调是否加入了新作业
+    fn notify_finished(&mut self, selection: &mut Option<Selection<'_>>) -> bool {
+        let Some(selection) = selection else {
+            return false;
+        };
+        let mut added = false;
+        loop {
+            let mut finished: Vec<JobId> = selection
+                .jobs
+                .iter()
+                .filter(|id| !selection.reported.contains(id))
+                .filter(|&&id| self.scheduler.get_status(id).is_some_and(|s| s.is_terminal()))
+                .copied()
+                .collect();
+            if finished.is_empty() {
+                return added;
+            }
+            finished.sort_unstable();
+            for job_id in finished {
+                selection.reported.insert(job_id);
+                let submitted = (selection.on_finish)(self, job_id);
+                added |= !submitted.is_empty();
+                selection.jobs.extend(submitted);
+            }
         }
+    }
+    
+    /// 工作线程池执行
+    /// 
+    /// 调度、统计与结果登记只在调用线程上进行；工作线程从共享通道领取作业，
+    /// 在各自的后端上执行后把结果发回。每收到一个结果就释放其资源并尝试调度新的作业，
+    /// 因此依赖于它的作业可以立即补位。工作线程每次只执行一次尝试，
+    /// 需要重试的作业回到队列等待退避，不占用工作线程。
+    fn drive_pooled(&mut self, mut selection: Option<Selection<'_>>) -> Vec<JobResult> {
+        let workers = self.config.max_concurrent_jobs.max(1);
+        let (job_tx, job_rx) = mpsc::channel::<AttemptTask>();
+        let job_rx = Mutex::new(job_rx);
+        let (result_tx, result_rx) = mpsc::channel::<AttemptOutcome>();
+        let mut results = Vec::new();
+        
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                let job_rx = &job_rx;
+                let result_tx = result_tx.clone();
+                scope.spawn(move || loop {
+                    let next = job_rx.lock().expect("job channel lock").recv();
+                    let Ok(task) = next else {
+                        break;
+                    };
+                    if result_tx.send(task.run()).is_err() {
+                        break;
+                    }
+                });
+            }
+            
+            // 只有工作线程持有结果发送端：工作线程全部退出时接收立即返回错误而不是挂起
+            drop(result_tx);
+            self.notify_finished(&mut selection);
+            let mut in_flight = 0;
+            loop {
+                while in_flight < workers {
+                    match self.start_next_where(|job| Selection::selects(&selection, job)) {
+                        Some(Dispatch::Run(task)) => {
+                            job_tx.send(task).expect("workers are alive");
+                            in_flight += 1;
+                        }
+                        Some(Dispatch::Finished(result)) => results.push(result),
+                        None => break,
+                    }
+                }
+                if self.notify_finished(&mut selection) {
+                    continue;
+                }
+                // 等待结果，或等到最早的重试时刻再调度
+                let retry_in = self.scheduler
+                    .next_retry_where(|job| Selection::selects(&selection, job))
+                    .map(|at| at.saturating_duration_since(self.scheduler.now()));
+                let outcome = match (in_flight, retry_in) {
+                    (0, None) => break,
+                    (0, Some(wait)) => {
+                        std::thread::sleep(wait);
+                        continue;
+                    }
+                    (_, None) => match result_rx.recv() {
+                        Ok(outcome) => outcome,
+                        Err(_) => break,
+                    },
+                    (_, Some(wait)) => match result_rx.recv_timeout(wait) {
+                        Ok(outcome) => outcome,
+                        Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                        Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                    },
+                };
+                in_flight -= 1;
+                results.extend(self.complete_attempt(outcome));
+                self.notify_finished(&mut selection);
+            }
+            // 关闭通道，工作线程随之退出
+            drop(job_tx);
+        });
         
         results
     }
@@ -248,7 +585,137 @@ impl QuantumRuntime {
     
     /// 取消作业
     pub fn cancel_job(&mut self, job_id: JobId) -> bool {
-        self.scheduler.cancel(job_id)
+        let cancelled = self.scheduler.cancel(job_id);
+        self.persist_finished();
+        cancelled
+    }
+    
+    /// 获取配置
+    pub fn config(&self) -> &RuntimeConfig {
+        &self.config
+    }
+    
+    /// 设置用户与项目配额
+    pub fn set_quotas(&mut self, quotas: QuotaManager) {
+        let scheduler = std::mem::take(&mut self.scheduler);
+        self.scheduler = scheduler.with_quotas(quotas);
+    }
+    
+    /// 账户的配额使用情况
+    pub fn quota_usage(&self, key: &QuotaKey) -> QuotaUsage {
+        self.scheduler.quotas().usage_at(key, self.scheduler.now())
+    }
+    
+    /// 所有账户的配额使用情况
+    pub fn quota_report(&self) -> Vec<QuotaUsage> {
+        self.scheduler.quotas().report()
+    }
+    
+    /// 设置作业存储，之后的提交、开始执行与结果都写入其中
+    /// 
+    /// 启动时先设置存储再调用 `recover` 取回重启前的作业。
+    pub fn set_store(&mut self, store: impl JobStore + 'static) {
+        self.store = Some(Box::new(store));
+    }
+    
+    /// 当前的作业存储
+    pub fn store(&self) -> Option<&dyn JobStore> {
+        self.store.as_deref()
+    }
+    
+    /// 从作业存储恢复重启前的作业
+    /// 
+    /// 已结束的作业恢复结果；排队或等待中的作业重新提交；运行中的作业按 `mode` 重新提交或以失败结束。
+    /// 无法重新提交的作业（如已无法满足截止时刻）同样以失败结束。之后生成的作业 ID 不会与恢复的作业冲突。
+    pub fn recover(&mut self, mode: RecoveryMode) -> Result<RecoveryReport> {
+        let store = self
+            .store
+            .as_ref()
+            .ok_or_else(|| IrError::Storage("No job store is configured".to_string()))?;
+        let mut report = RecoveryReport::default();
+        let mut unfinished = Vec::new();
+        for stored in store.load()? {
+            let job_id = stored.job.id;
+            Job::reserve_ids_through(job_id);
+            match stored.result {
+                Some(result) => {
+                    self.scheduler.restore_result(job_id, result)?;
+                    report.restored.push(job_id);
+                }
+                None => unfinished.push(stored.job),
+            }
+        }
+        
+        for mut job in unfinished {
+            let job_id = job.id;
+            let was_running = matches!(job.status, JobStatus::Ready | JobStatus::Running);
+            if was_running && mode == RecoveryMode::MarkFailed {
+                self.fail_recovered(job_id, JobResult::failure(job_id, "Interrupted by a runtime restart"))?;
+                report.failed.push(job_id);
+                continue;
+            }
+            job.status = JobStatus::Pending;
+            job.started_at = None;
+            match self.scheduler.submit(job) {
+                Ok(_) => {
+                    self.stats.total_jobs_submitted += 1;
+                    let status = self.scheduler.get_status(job_id).unwrap_or(JobStatus::Queued);
+                    if let Some(store) = self.store.as_mut() {
+                        store.record_status(job_id, status)?;
+                    }
+                    if was_running {
+                        report.resubmitted.push(job_id);
+                    } else {
+                        report.requeued.push(job_id);
+                    }
+                }
+                Err(e) => {
+                    self.fail_recovered(job_id, JobResult::failure(job_id, e.to_string()))?;
+                    report.failed.push(job_id);
+                }
+            }
+        }
+        self.persist_finished();
+        Ok(report)
+    }
+    
+    /// 恢复期间以失败结束的作业：登记结果并写回存储
+    fn fail_recovered(&mut self, job_id: JobId, result: JobResult) -> Result<()> {
+        if let Some(store) = self.store.as_mut() {
+            store.record_result(job_id, &result)?;
+        }
+        self.scheduler.restore_result(job_id, result)
+    }
+    
+    /// 记录状态变化；存储失败只计数，不影响内存中的调度
+    fn persist_status(&mut self, job_id: JobId, status: JobStatus) {
+        if let Some(store) = self.store.as_mut() {
+            if store.record_status(job_id, status).is_err() {
+                self.stats.total_store_errors += 1;
+            }
+        }
+    }
+    
+    /// 把调度器中新结束的作业结果写入存储
+    fn persist_finished(&mut self) {
+        let finished = self.scheduler.take_finished();
+        for job_id in &finished {
+            self.retries.remove(job_id);
+        }
+        self.persist_results(finished);
+    }
+    
+    fn persist_results(&mut self, job_ids: Vec<JobId>) {
+        let Some(store) = self.store.as_mut() else {
+            return;
+        };
+        for job_id in job_ids {
+            if let Some(result) = self.scheduler.get_result(job_id) {
+                if store.record_result(job_id, result).is_err() {
+                    self.stats.total_store_errors += 1;
+                }
+            }
+        }
     }
     
     /// 获取统计信息
@@ -287,6 +754,224 @@ impl QuantumRuntime {
     }
 }
 
+/// `execute_jobs_with` 的执行范围
+struct Selection<'a> {
+    jobs: HashSet<JobId>,
+    /// 已交给回调的作业
+    reported: HashSet<JobId>,
+    on_finish: &'a mut dyn FnMut(&mut QuantumRuntime, JobId) -> Vec<JobId>,
+}
+
+impl Selection<'_> {
+    /// 作业是否在执行范围内（未限定范围时为全部作业）
+    fn selects(selection: &Option<Selection<'_>>, job: &Job) -> bool {
+        selection.as_ref().is_none_or(|s| s.jobs.contains(&job.id))
+    }
+}
+
+/// 已调度作业的去向
+enum Dispatch {
+    /// 交给后端执行一次尝试
+    Run(AttemptTask),
+    /// 未能执行，已以失败结束
+    Finished(JobResult),
+}
+
+/// 作业可用的后端（按尝试顺序）与重试策略
+struct ExecutionPlan {
+    backends: Vec<(String, Arc<dyn BackendAdapter>)>,
+    retry: RetryPolicy,
+}
+
+/// 等待重试的作业的执行进度
+#[derive(Default)]
+struct RetryState {
+    attempts: Vec<JobAttempt>,
+    /// 下一次尝试使用的候选后端
+    candidate: usize,
+}
+
+/// 一次执行尝试
+struct AttemptTask {
+    plan: ExecutionPlan,
+    job: Box<Job>,
+    /// 尝试序号（从 1 开始）
+    attempt: u32,
+    /// 使用的候选后端
+    candidate: usize,
+}
+
+/// 一次尝试的结果
+struct AttemptOutcome {
+    job_id: JobId,
+    result: JobResult,
+    record: JobAttempt,
+    /// 需要重试时：下一次使用的候选后端与退避时间
+    retry: Option<(usize, Duration)>,
+}
+
+/// 等待一次尝试的后端作业的结果
+enum AttemptWait {
+    /// 后端给出了结果
+    Finished(Box<Result<JobResult>>),
+    /// 超过作业超时仍未结束
+    TimedOut,
+    /// 后端 panic
+    Panicked,
+}
+
+impl AttemptTask {
+    /// 在调用线程上执行一次尝试，等待期间挂起当前线程
+    fn run(&self) -> AttemptOutcome {
+        let start = Instant::now();
+        let (backend_job_id, wait) = match submit_attempt(self.backend(), &self.job) {
+            Some(submitted) => (submitted.backend_job_id, wait_blocking(submitted.future, self.remaining(start))),
+            None => (None, AttemptWait::Panicked),
+        };
+        self.complete(start, backend_job_id, wait)
+    }
+    
+    /// 提交一次尝试后立即返回，等待期间不占用线程
+    /// 
+    /// 后端的 future 就绪或超时时，在唤醒它的线程（后端或共享计时线程）上以结果调用 on_finish。
+    fn spawn(self, on_finish: impl FnOnce(AttemptOutcome) + Send + 'static) {
+        let start = Instant::now();
+        let submitted = submit_attempt(self.backend(), &self.job);
+        spawn_detached(async move {
+            let (backend_job_id, wait) = match submitted {
+                Some(submitted) => (submitted.backend_job_id, wait_async(submitted.future, self.remaining(start)).await),
+                None => (None, AttemptWait::Panicked),
+            };
+            on_finish(self.complete(start, backend_job_id, wait));
+        });
+    }
+    
+    fn backend(&self) -> &Arc<dyn BackendAdapter> {
+        &self.plan.backends[self.candidate].1
+    }
+    
+    /// 距作业超时还剩的时间
+    fn remaining(&self, start: Instant) -> Option<Duration> {
+        self.job.metadata.timeout.map(|timeout| timeout.saturating_sub(start.elapsed()))
+    }
+    
+    /// 记录一次尝试的结果，并按重试策略决定是否重试
+    /// 
+    /// 只有后端返回可重试的错误（含超时）时才重试；后端返回的失败结果与 panic 不重试。
+    /// 启用故障转移时依次换用下一个候选后端，所有候选后端都试过一轮后才退避等待。
+    fn complete(&self, start: Instant, backend_job_id: Option<JobId>, wait: AttemptWait) -> AttemptOutcome {
+        let policy = &self.plan.retry;
+        let (id, backend) = &self.plan.backends[self.candidate];
+        let (result, error) = attempt_result(backend.as_ref(), &self.job, start, backend_job_id, wait);
+        let record = JobAttempt {
+            attempt: self.attempt,
+            backend: id.clone(),
+            status: result.status,
+            error: result.error.clone(),
+            error_kind: error.as_ref().map(IrError::kind),
+            duration_ms: start.elapsed().as_millis() as u64,
+        };
+        let retryable = error.is_some_and(|e| policy.is_retryable(&e));
+        let retry = (retryable && self.attempt < policy.max_attempts).then(|| {
+            let next = if policy.failover { (self.candidate + 1) % self.plan.backends.len() } else { 0 };
+            let delay = if next == 0 { policy.backoff(self.attempt) } else { Duration::ZERO };
+            (next, delay)
+        });
+        AttemptOutcome { job_id: self.job.id, result, record, retry }
+    }
+}
+
+/// 在调用线程上执行作业直到结束，每次尝试记入 `JobResult::attempts`，重试前在调用线程上退避
+fn execute_job(plan: ExecutionPlan, job: Job) -> JobResult {
+    let mut task = AttemptTask { plan, job: Box::new(job), attempt: 1, candidate: 0 };
+    let mut attempts = Vec::new();
+    loop {
+        let AttemptOutcome { mut result, record, retry, .. } = task.run();
+        attempts.push(record);
+        let Some((candidate, delay)) = retry else {
+            result.attempts = attempts;
+            return result;
+        };
+        std::thread::sleep(delay);
+        task.attempt += 1;
+        task.candidate = candidate;
+    }
+}
+
+/// 向后端提交一次尝试；后端 panic 时返回 `None`
+fn submit_attempt(backend: &Arc<dyn BackendAdapter>, job: &Job) -> Option<SubmittedJob> {
+    catch_unwind(AssertUnwindSafe(|| Arc::clone(backend).execute_async(job))).ok()
+}
+
+/// 挂起当前线程等待后端结果，最多等待 timeout
+fn wait_blocking(future: JobFuture, timeout: Option<Duration>) -> AttemptWait {
+    let waited = catch_unwind(AssertUnwindSafe(|| match timeout {
+        Some(timeout) => block_on_timeout(future, timeout),
+        None => Some(block_on(future)),
+    }));
+    match waited {
+        Ok(Some(result)) => AttemptWait::Finished(Box::new(result)),
+        Ok(None) => AttemptWait::TimedOut,
+        Err(_) => AttemptWait::Panicked,
+    }
+}
+
+/// 异步等待后端结果，最多等待 timeout；超时由共享计时线程唤醒
+async fn wait_async(mut future: JobFuture, timeout: Option<Duration>) -> AttemptWait {
+    let guarded = std::future::poll_fn(move |cx| {
+        match catch_unwind(AssertUnwindSafe(|| Pin::new(&mut future).poll(cx))) {
+            Ok(Poll::Ready(result)) => Poll::Ready(AttemptWait::Finished(Box::new(result))),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(_) => Poll::Ready(AttemptWait::Panicked),
+        }
+    });
+    match timeout {
+        Some(timeout) => crate::future::timeout(guarded, timeout).await.unwrap_or(AttemptWait::TimedOut),
+        None => guarded.await,
+    }
+}
+
+/// 把一次尝试的等待结果转为作业结果：出错、返回非终止状态、超时或 panic 都转为失败结果
+/// 
+/// 后端返回错误、超时或非终止状态时同时返回对应的错误，供重试判断。
+/// 超过 `job.metadata.timeout` 或后端报告超时时以后端返回的作业 ID 取消后端作业；
+/// 阻塞执行的后端无法中途打断，其超时返回的结果被丢弃。
+fn attempt_result(
+    backend: &dyn BackendAdapter,
+    job: &Job,
+    start: Instant,
+    backend_job_id: Option<JobId>,
+    wait: AttemptWait,
+) -> (JobResult, Option<IrError>) {
+    let timeout = job.metadata.timeout;
+    let timed_out = || {
+        let result = JobResult::timed_out(job.id, timeout.unwrap_or_default());
+        let error = IrError::Timeout(result.error.clone().unwrap_or_default());
+        (result, Some(error))
+    };
+    let failed = |error: IrError| (JobResult::failure(job.id, error.to_string()), Some(error));
+    let cancel = || {
+        if let Some(backend_job_id) = backend_job_id {
+            let _ = backend.cancel_job(backend_job_id);
+        }
+    };
+    let outcome = match wait {
+        AttemptWait::Finished(result) => Some(*result),
+        AttemptWait::TimedOut => None,
+        AttemptWait::Panicked => return (JobResult::failure(job.id, format!("Backend {} panicked", backend.id())), None),
+    };
+    match outcome {
+        None | Some(Err(IrError::Timeout(_))) => {
+            cancel();
+            timed_out()
+        }
+        Some(_) if timeout.is_some_and(|t| start.elapsed() > t) => timed_out(),
+        Some(Ok(result)) if result.status.is_terminal() => (result, None),
+        Some(Ok(result)) => failed(IrError::JobExecutionFailed(format!("Backend returned non-terminal status {:?}", result.status))),
+        Some(Err(e)) => failed(e),
+    }
+}
+
 impl Default for QuantumRuntime {
     fn default() -> Self {
         Self::new(RuntimeConfig::default())
@@ -294,44 +979,178 @@ impl Default for QuantumRuntime {
 }
 
 // ============================================================================
-// Async Runtime (Skeleton)
+// Async Runtime
 // ============================================================================
 
+/// 向后端提交作业的位置
+/// 
+/// 只有提交（`execute_async`）在这里执行：阻塞执行的后端会占用它直到返回，
+/// 原生异步的后端提交后线程立即结束，之后由后端的唤醒推进作业。
+#[derive(Clone)]
+enum Spawner {
+    /// 每次提交一个短期线程，同时运行的作业数受 `max_concurrent_jobs` 限制
+    Threads,
+    /// tokio 的阻塞线程池
+    #[cfg(feature = "tokio")]
+    Tokio(tokio::runtime::Handle),
+}
+
+impl Spawner {
+    fn spawn(&self, task: impl FnOnce() + Send + 'static) {
+        match self {
+            Spawner::Threads => {
+                std::thread::spawn(task);
+            }
+            #[cfg(feature = "tokio")]
+            Spawner::Tokio(handle) => {
+                handle.spawn_blocking(task);
+            }
+        }
+    }
+}
+
+struct AsyncState {
+    runtime: QuantumRuntime,
+    /// 等待作业结束的 future
+    waiters: HashMap<JobId, Vec<JobCompleter>>,
+}
+
 /// 异步运行时句柄
+/// 
+/// 可克隆并跨线程共享。提交的作业仍由调度器按依赖、优先级与资源分派，
+/// 在后台线程上提交给后端；后端的 `JobFuture` 被唤醒时在唤醒方线程上结束这次尝试，
+/// 超时与重试退避由共享计时线程唤醒，等待中的作业不占用线程。
+/// 作业结束（含取消与依赖失败）时唤醒等待它的 `JobFuture`，调用方的异步任务不会被阻塞。
+#[derive(Clone)]
 pub struct AsyncRuntimeHandle {
-    runtime_id: u64,
+    state: Arc<Mutex<AsyncState>>,
+    spawner: Spawner,
 }
 
 impl AsyncRuntimeHandle {
-    pub fn new(runtime_id: u64) -> Self {
-        Self { runtime_id }
+    /// 接管 Runtime，作业在短期线程上提交
+    pub fn new(runtime: QuantumRuntime) -> Self {
+        Self::with_spawner(runtime, Spawner::Threads)
     }
     
-    pub fn runtime_id(&self) -> u64 {
-        self.runtime_id
+    /// 接管 Runtime，作业在 tokio 的阻塞线程池上提交
+    #[cfg(feature = "tokio")]
+    pub fn with_tokio(runtime: QuantumRuntime, handle: tokio::runtime::Handle) -> Self {
+        Self::with_spawner(runtime, Spawner::Tokio(handle))
     }
-}
-
-/// 异步执行上下文
-pub struct AsyncExecutionContext {
-    job_id: JobId,
-    backend_id: String,
-}
-
-impl AsyncExecutionContext {
-    pub fn new(job_id: JobId, backend_id: impl Into<String>) -> Self {
+    
+    fn with_spawner(runtime: QuantumRuntime, spawner: Spawner) -> Self {
         Self {
-            job_id,
-            backend_id: backend_id.into(),
+            state: Arc::new(Mutex::new(AsyncState { runtime, waiters: HashMap::new() })),
+            spawner,
         }
     }
     
-    pub fn job_id(&self) -> JobId {
-        self.job_id
+    /// 提交作业并立即尝试调度
+    pub fn submit(&self, job: Job) -> Result<JobId> {
+        let job_id = self.state.lock().unwrap().runtime.submit_job(job)?;
+        self.dispatch();
+        Ok(job_id)
+    }
+    
+    /// 作业结束时就绪的 future
+    pub fn wait(&self, job_id: JobId) -> JobFuture {
+        let mut state = self.state.lock().unwrap();
+        if let Some(result) = state.runtime.get_job_result(job_id) {
+            return JobFuture::ready(Ok(result));
+        }
+        if state.runtime.get_job_status(job_id).is_none() {
+            return JobFuture::ready(Err(IrError::InvalidOperation(format!(
+                "Job {} is not known to the runtime", job_id
+            ))));
+        }
+        let (completer, future) = completion();
+        state.waiters.entry(job_id).or_default().push(completer);
+        future.into()
+    }
+    
+    /// 提交作业并返回其结果的 future
+    pub fn execute(&self, job: Job) -> JobFuture {
+        match self.submit(job) {
+            Ok(job_id) => self.wait(job_id),
+            Err(e) => JobFuture::ready(Err(e)),
+        }
+    }
+    
+    /// 取消作业；运行中的作业在后端返回后丢弃其结果
+    pub fn cancel(&self, job_id: JobId) -> bool {
+        let cancelled = {
+            let mut state = self.state.lock().unwrap();
+            let cancelled = state.runtime.cancel_job(job_id);
+            state.resolve_finished();
+            cancelled
+        };
+        self.dispatch();
+        cancelled
     }
     
-    pub fn backend_id(&self) -> &str {
-        &self.backend_id
+    /// 在锁内访问 Runtime（注册后端、读取统计等），之后重新尝试调度
+    pub fn with_runtime<R>(&self, f: impl FnOnce(&mut QuantumRuntime) -> R) -> R {
+        let value = f(&mut self.state.lock().unwrap().runtime);
+        self.dispatch();
+        value
+    }
+    
+    /// 把所有可调度的作业交给后台执行
+    fn dispatch(&self) {
+        let mut state = self.state.lock().unwrap();
+        while let Some(dispatch) = state.runtime.start_next() {
+            if let Dispatch::Run(task) = dispatch {
+                let handle = self.clone();
+                self.spawner.spawn(move || task.spawn(move |outcome| handle.finish(outcome)));
+            }
+        }
+        state.resolve_finished();
+    }
+    
+    /// 一次尝试结束；作业回到队列等待退避时，由共享计时线程在重试时刻重新调度
+    fn finish(&self, outcome: AttemptOutcome) {
+        let delay = outcome.retry.map(|(_, delay)| delay);
+        let requeued = {
+            let mut state = self.state.lock().unwrap();
+            let requeued = state.runtime.complete_attempt(outcome).is_none();
+            state.resolve_finished();
+            requeued
+        };
+        if let Some(delay) = delay.filter(|_| requeued) {
+            let handle = self.clone();
+            spawn_detached(async move {
+                sleep(delay).await;
+                handle.dispatch();
+            });
+        }
+        self.dispatch();
+    }
+}
+
+impl AsyncState {
+    /// 唤醒已有结果的作业的等待者
+    fn resolve_finished(&mut self) {
+        let runtime = &self.runtime;
+        let finished: Vec<JobId> = self
+            .waiters
+            .keys()
+            .copied()
+            .filter(|&job_id| runtime.get_job_result(job_id).is_some())
+            .collect();
+        for job_id in finished {
+            let result = self.runtime.get_job_result(job_id).expect("job has a result");
+            for completer in self.waiters.remove(&job_id).unwrap_or_default() {
+                completer.complete(Ok(result.clone()));
+            }
+        }
+    }
+}
+
+impl QuantumRuntime {
+    /// 转为可共享的异步句柄
+    pub fn into_async(self) -> AsyncRuntimeHandle {
+        AsyncRuntimeHandle::new(self)
     }
 }
 
@@ -343,7 +1162,278 @@ impl AsyncExecutionContext {
 mod tests {
     use super::*;
     use crate::circuit::bell_state_dag;
-    use crate::backend::MockBackendAdapter;
+    use crate::backend::{BackendCircuit, MockBackendAdapter};
+    use crate::ErrorKind;
+    use crate::operation::{measure, x};
+    use crate::qubit::LogicalQubitId;
+    use crate::backend::SubmittedJob;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    use std::sync::Condvar;
+    use std::time::{Duration, Instant};
+
+    /// 执行耗时固定的后端，记录并发峰值与执行顺序
+    ///
+    /// 设置 rendezvous 后，每个作业先等待同时执行的作业数达到该值（最多 5 秒）再继续。
+    struct SlowBackend {
+        inner: MockBackendAdapter,
+        delay: Duration,
+        active: AtomicUsize,
+        peak: AtomicUsize,
+        log: Mutex<Vec<(JobId, &'static str)>>,
+        rendezvous: usize,
+        gate: (Mutex<()>, Condvar),
+    }
+
+    impl SlowBackend {
+        fn new(delay_ms: u64) -> Self {
+            Self {
+                inner: MockBackendAdapter::new(),
+                delay: Duration::from_millis(delay_ms),
+                active: AtomicUsize::new(0),
+                peak: AtomicUsize::new(0),
+                log: Mutex::new(Vec::new()),
+                rendezvous: 0,
+                gate: (Mutex::new(()), Condvar::new()),
+            }
+        }
+
+        fn with_rendezvous(mut self, jobs: usize) -> Self {
+            self.rendezvous = jobs;
+            self
+        }
+    }
+
+    impl BackendAdapter for SlowBackend {
+        fn id(&self) -> &str { "slow" }
+        fn capabilities(&self) -> BackendCapabilities { self.inner.capabilities() }
+        fn translate_circuit(&self, circuit: &CircuitDag) -> Result<BackendCircuit> { self.inner.translate_circuit(circuit) }
+        fn submit_job(&self, job: &Job) -> Result<JobId> { self.inner.submit_job(job) }
+        fn get_job_status(&self, job_id: JobId) -> Result<JobStatus> { self.inner.get_job_status(job_id) }
+        fn get_job_result(&self, job_id: JobId) -> Result<JobResult> { self.inner.get_job_result(job_id) }
+        fn cancel_job(&self, job_id: JobId) -> Result<()> { self.inner.cancel_job(job_id) }
+
+        fn execute(&self, job: &Job) -> Result<JobResult> {
+            if job.shots == 0 {
+                panic!("zero shots");
+            }
+            self.log.lock().unwrap().push((job.id, "start"));
+            let (lock, arrived) = &self.gate;
+            let guard = lock.lock().unwrap();
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(active, Ordering::SeqCst);
+            arrived.notify_all();
+            let (guard, _) = arrived
+                .wait_timeout_while(guard, Duration::from_secs(5), |_| {
+                    self.active.load(Ordering::SeqCst) < self.rendezvous
+                })
+                .unwrap();
+            drop(guard);
+            std::thread::sleep(self.delay);
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            self.log.lock().unwrap().push((job.id, "end"));
+            self.inner.execute(job)
+        }
+    }
+
+    /// 原生异步后端：提交后立即返回，由后台线程在作业结束时完成 future
+    /// 
+    /// 后端作业 ID 从 5000 起自行分配，与运行时的作业 ID 无关。
+    struct DeferredBackend {
+        inner: Arc<MockBackendAdapter>,
+        delay: Duration,
+        next_id: AtomicU64,
+        jobs: Arc<Mutex<HashMap<JobId, DeferredJob>>>,
+        cancelled: Mutex<Vec<JobId>>,
+        /// 到时不给出结果，而是报告后端超时
+        reports_timeout: bool,
+    }
+
+    #[derive(Default)]
+    struct DeferredJob {
+        result: Option<JobResult>,
+        cancelled: bool,
+        waiters: Vec<JobCompleter>,
+    }
+
+    impl DeferredBackend {
+        fn new(delay_ms: u64) -> Self {
+            Self {
+                inner: Arc::new(MockBackendAdapter::new()),
+                delay: Duration::from_millis(delay_ms),
+                next_id: AtomicU64::new(5000),
+                jobs: Arc::new(Mutex::new(HashMap::new())),
+                cancelled: Mutex::new(Vec::new()),
+                reports_timeout: false,
+            }
+        }
+
+        fn reporting_timeout(mut self) -> Self {
+            self.reports_timeout = true;
+            self
+        }
+    }
+
+    impl BackendAdapter for DeferredBackend {
+        fn id(&self) -> &str { "deferred" }
+        fn capabilities(&self) -> BackendCapabilities { self.inner.capabilities() }
+        fn translate_circuit(&self, circuit: &CircuitDag) -> Result<BackendCircuit> { self.inner.translate_circuit(circuit) }
+
+        fn submit_job(&self, job: &Job) -> Result<JobId> {
+            let job_id = self.next_id.fetch_add(1, Ordering::SeqCst);
+            self.jobs.lock().unwrap().insert(job_id, DeferredJob::default());
+            let (inner, jobs, delay, job) = (Arc::clone(&self.inner), Arc::clone(&self.jobs), self.delay, job.clone());
+            let reports_timeout = self.reports_timeout;
+            std::thread::spawn(move || {
+                std::thread::sleep(delay);
+                let result = inner.execute(&job).unwrap_or_else(|e| JobResult::failure(job.id, e.to_string()));
+                // 在锁外唤醒等待者：被唤醒的任务可能回调本后端（如取消作业）
+                let waiters = {
+                    let mut jobs = jobs.lock().unwrap();
+                    let state = jobs.get_mut(&job_id).unwrap();
+                    if state.cancelled {
+                        return;
+                    }
+                    if !reports_timeout {
+                        state.result = Some(result.clone());
+                    }
+                    std::mem::take(&mut state.waiters)
+                };
+                for waiter in waiters {
+                    if reports_timeout {
+                        waiter.complete(Err(IrError::Timeout(format!("Job {} timed out on the backend", job_id))));
+                    } else {
+                        waiter.complete(Ok(result.clone()));
+                    }
+                }
+            });
+            Ok(job_id)
+        }
+
+        fn get_job_status(&self, job_id: JobId) -> Result<JobStatus> {
+            match self.jobs.lock().unwrap().get(&job_id) {
+                Some(state) if state.cancelled => Ok(JobStatus::Cancelled),
+                Some(state) => Ok(state.result.as_ref().map_or(JobStatus::Running, |r| r.status)),
+                None => Err(IrError::JobExecutionFailed(format!("Job {} not found", job_id))),
+            }
+        }
+
+        fn get_job_result(&self, job_id: JobId) -> Result<JobResult> {
+            self.jobs.lock().unwrap().get(&job_id)
+                .and_then(|state| state.result.clone())
+                .ok_or_else(|| IrError::JobExecutionFailed(format!("Job {} has no result", job_id)))
+        }
+
+        fn cancel_job(&self, job_id: JobId) -> Result<()> {
+            self.cancelled.lock().unwrap().push(job_id);
+            if let Some(state) = self.jobs.lock().unwrap().get_mut(&job_id) {
+                state.cancelled = true;
+                state.waiters.clear();
+            }
+            Ok(())
+        }
+
+        fn completion(self: Arc<Self>, job_id: JobId) -> JobFuture {
+            let mut jobs = self.jobs.lock().unwrap();
+            match jobs.get_mut(&job_id) {
+                Some(DeferredJob { result: Some(result), .. }) => JobFuture::ready(Ok(result.clone())),
+                Some(state) => {
+                    let (completer, future) = completion();
+                    state.waiters.push(completer);
+                    future.into()
+                }
+                None => JobFuture::ready(Err(IrError::JobExecutionFailed(format!("Job {} not found", job_id)))),
+            }
+        }
+
+        fn execute_async(self: Arc<Self>, job: &Job) -> SubmittedJob {
+            match self.submit_job(job) {
+                Ok(job_id) => SubmittedJob::submitted(job_id, self.completion(job_id)),
+                Err(e) => SubmittedJob::ready(Err(e)),
+            }
+        }
+    }
+
+    /// 原生异步后端：作业在测试代码完成其 completer 时结束
+    struct GatedBackend {
+        inner: MockBackendAdapter,
+        /// 运行时作业 ID -> (后端作业 ID, 完成方)
+        pending: Mutex<HashMap<JobId, (JobId, JobCompleter)>>,
+        cancelled: Mutex<Vec<JobId>>,
+    }
+
+    impl BackendAdapter for GatedBackend {
+        fn id(&self) -> &str { "gated" }
+        fn capabilities(&self) -> BackendCapabilities { self.inner.capabilities() }
+        fn translate_circuit(&self, circuit: &CircuitDag) -> Result<BackendCircuit> { self.inner.translate_circuit(circuit) }
+        fn submit_job(&self, job: &Job) -> Result<JobId> { self.inner.submit_job(job) }
+        fn get_job_status(&self, job_id: JobId) -> Result<JobStatus> { Ok(JobStatus::Running) }
+        fn get_job_result(&self, job_id: JobId) -> Result<JobResult> { self.inner.get_job_result(job_id) }
+
+        fn cancel_job(&self, job_id: JobId) -> Result<()> {
+            self.cancelled.lock().unwrap().push(job_id);
+            Ok(())
+        }
+
+        fn execute_async(self: Arc<Self>, job: &Job) -> SubmittedJob {
+            match self.submit_job(job) {
+                Ok(backend_job_id) => {
+                    let (completer, future) = completion();
+                    self.pending.lock().unwrap().insert(job.id, (backend_job_id, completer));
+                    SubmittedJob::submitted(backend_job_id, future.into())
+                }
+                Err(e) => SubmittedJob::ready(Err(e)),
+            }
+        }
+    }
+
+    /// 前 failures 次执行返回给定错误，之后正常执行
+    struct FlakyBackend {
+        inner: MockBackendAdapter,
+        failures: usize,
+        error: fn(String) -> IrError,
+        calls: AtomicUsize,
+    }
+
+    impl FlakyBackend {
+        fn new(failures: usize, error: fn(String) -> IrError) -> Self {
+            Self { inner: MockBackendAdapter::new(), failures, error, calls: AtomicUsize::new(0) }
+        }
+    }
+
+    impl BackendAdapter for FlakyBackend {
+        fn id(&self) -> &str { "flaky" }
+        fn capabilities(&self) -> BackendCapabilities { self.inner.capabilities() }
+        fn translate_circuit(&self, circuit: &CircuitDag) -> Result<BackendCircuit> { self.inner.translate_circuit(circuit) }
+        fn submit_job(&self, job: &Job) -> Result<JobId> { self.inner.submit_job(job) }
+        fn get_job_status(&self, job_id: JobId) -> Result<JobStatus> { self.inner.get_job_status(job_id) }
+        fn get_job_result(&self, job_id: JobId) -> Result<JobResult> { self.inner.get_job_result(job_id) }
+        fn cancel_job(&self, job_id: JobId) -> Result<()> { self.inner.cancel_job(job_id) }
+
+        fn execute(&self, job: &Job) -> Result<JobResult> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err((self.error)(format!("attempt on job {} failed", job.id)));
+            }
+            self.inner.execute(job)
+        }
+    }
+
+    fn single_qubit_job(qubit: u64, backend: &str) -> Job {
+        let q = LogicalQubitId::new(qubit);
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(x(q));
+        circuit.add_node(measure(q));
+        Job::new(circuit, 10, backend)
+    }
+
+    fn pooled_runtime(backend: Arc<SlowBackend>, workers: usize) -> QuantumRuntime {
+        let config = RuntimeConfig::new()
+            .with_max_jobs(workers)
+            .with_execution_mode(ExecutionMode::WorkerPool);
+        let mut runtime = QuantumRuntime::new(config);
+        runtime.register_backend("slow", backend);
+        *runtime.scheduler_mut() = JobScheduler::new(workers).with_qubits((0..8).map(LogicalQubitId::new).collect());
+        runtime
+    }
 
     #[test]
     fn test_runtime_creation() {
@@ -378,7 +1468,7 @@ mod tests {
             100,
             Priority::Normal,
             JobMetadata::new(),
-        );
+        ).unwrap();
         
         assert!(job_id > 0);
         assert_eq!(runtime.stats.total_jobs_submitted, 1);
@@ -396,6 +1486,469 @@ mod tests {
         assert_eq!(runtime.stats.average_execution_time(), Some(100.0));
     }
 
+    #[test]
+    fn test_runtime_execute_with_dependencies() {
+        let mut runtime = QuantumRuntime::default();
+        runtime.register_backend("mock", Arc::new(MockBackendAdapter::new()));
+        *runtime.scheduler_mut() = JobScheduler::new(4)
+            .with_qubits(vec![crate::qubit::LogicalQubitId::new(0), crate::qubit::LogicalQubitId::new(1)]);
+
+        let first = runtime.submit_job(Job::new(bell_state_dag(), 10, "mock")).unwrap();
+        let second = runtime
+            .submit_job(Job::new(bell_state_dag(), 10, "mock").with_dependency(first))
+            .unwrap();
+        let orphan = runtime.submit_job(Job::new(bell_state_dag(), 10, "missing")).unwrap();
+
+        let results = runtime.execute_all();
+        assert_eq!(results.len(), 3);
+        assert_eq!(runtime.get_job_status(first), Some(JobStatus::Completed));
+        assert_eq!(runtime.get_job_status(second), Some(JobStatus::Completed));
+        assert_eq!(runtime.get_job_status(orphan), Some(JobStatus::Failed));
+        assert!(runtime.get_job_result(second).is_some());
+        assert_eq!(runtime.stats().total_jobs_completed, 2);
+        assert_eq!(runtime.stats().total_jobs_failed, 1);
+        assert_eq!(runtime.scheduler().running_jobs().len(), 0);
+
+        // 同一作业不能重复提交
+        let job = Job::new(bell_state_dag(), 10, "mock");
+        runtime.submit_job(job.clone()).unwrap();
+        assert!(runtime.submit_job(job).is_err());
+    }
+
+    #[test]
+    fn test_worker_pool_parallelism() {
+        // 四个作业互相等待对方开始：只有真正并行执行时峰值才能达到 4
+        let backend = Arc::new(SlowBackend::new(10).with_rendezvous(4));
+        let mut runtime = pooled_runtime(backend.clone(), 4);
+        for q in 0..8 {
+            runtime.submit_job(single_qubit_job(q % 4, "slow")).unwrap();
+        }
+
+        let results = runtime.execute_all();
+        assert_eq!(results.len(), 8);
+        assert!(results.iter().all(|r| r.status == JobStatus::Completed));
+        assert_eq!(backend.peak.load(Ordering::SeqCst), 4);
+        assert_eq!(runtime.stats().total_jobs_completed, 8);
+        assert_eq!(runtime.stats().current_running_jobs, 0);
+        assert!(runtime.scheduler().running_jobs().is_empty());
+    }
+
+    #[test]
+    fn test_worker_pool_dependencies_and_failures() {
+        let backend = Arc::new(SlowBackend::new(30));
+        let mut runtime = pooled_runtime(backend.clone(), 2);
+        let first = runtime.submit_job(single_qubit_job(0, "slow")).unwrap();
+        let second = runtime.submit_job(single_qubit_job(1, "slow").with_dependency(first)).unwrap();
+        let mut panicking = single_qubit_job(2, "slow");
+        panicking.shots = 0;
+        let panicking = runtime.submit_job(panicking).unwrap();
+        let missing = runtime.submit_job(single_qubit_job(3, "missing")).unwrap();
+
+        let results = runtime.execute_all();
+        assert_eq!(results.len(), 4);
+        assert_eq!(runtime.get_job_status(first), Some(JobStatus::Completed));
+        assert_eq!(runtime.get_job_status(second), Some(JobStatus::Completed));
+        assert_eq!(runtime.get_job_status(panicking), Some(JobStatus::Failed));
+        assert_eq!(runtime.get_job_status(missing), Some(JobStatus::Failed));
+        assert_eq!(runtime.stats().total_jobs_failed, 2);
+
+        // 下游作业在上游结束之后才开始
+        let log = backend.log.lock().unwrap();
+        let end_first = log.iter().position(|e| *e == (first, "end")).unwrap();
+        let start_second = log.iter().position(|e| *e == (second, "start")).unwrap();
+        assert!(end_first < start_second);
+    }
+
+    #[test]
+    fn test_native_async_backend() {
+        let backend = Arc::new(DeferredBackend::new(500));
+        let jobs: Vec<Job> = (0..4).map(|q| single_qubit_job(q, "deferred")).collect();
+        let submitted: Vec<_> = jobs.iter().map(|job| Arc::clone(&backend).execute_async(job)).collect();
+        let backend_ids: Vec<_> = submitted.iter().map(|s| s.backend_job_id.unwrap()).collect();
+        assert_eq!(backend_ids, vec![5000, 5001, 5002, 5003]);
+        // 提交不等待执行：四个作业同时在后端运行
+        assert!(backend_ids.iter().all(|&id| backend.get_job_status(id).unwrap() == JobStatus::Running));
+        let results = block_on(crate::future::join_all(submitted.into_iter().map(|s| s.future)));
+        assert!(results.iter().all(|r| r.as_ref().unwrap().status == JobStatus::Completed));
+        // 同步接口轮询状态取得结果
+        assert_eq!(backend.execute(&jobs[0]).unwrap().status, JobStatus::Completed);
+    }
+
+    #[test]
+    fn test_async_handle_dependencies_and_cancel() {
+        let backend = Arc::new(SlowBackend::new(60));
+        let handle = pooled_runtime(backend, 2).into_async();
+        let first = handle.submit(single_qubit_job(0, "slow")).unwrap();
+        let second = handle.submit(single_qubit_job(1, "slow").with_dependency(first)).unwrap();
+        let third = handle.submit(single_qubit_job(2, "slow").with_dependency(second)).unwrap();
+        let independent = handle.execute(single_qubit_job(3, "slow"));
+        let waiters = vec![handle.wait(first), handle.wait(second), handle.wait(third)];
+
+        // 第一个作业仍在执行时取消其下游：等待者立即得到取消与级联失败的结果
+        assert!(handle.cancel(second));
+        let results: Vec<JobResult> = block_on(crate::future::join_all(waiters)).into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(results[0].status, JobStatus::Completed);
+        assert_eq!(results[1].status, JobStatus::Cancelled);
+        assert_eq!(results[2].status, JobStatus::Failed);
+        assert_eq!(block_on(independent).unwrap().status, JobStatus::Completed);
+
+        assert_eq!(block_on(handle.wait(first)).unwrap().status, JobStatus::Completed);
+        assert!(block_on(handle.wait(9999)).is_err());
+        assert!(block_on(handle.execute(single_qubit_job(4, "missing"))).unwrap().error.is_some());
+        handle.with_runtime(|runtime| {
+            assert_eq!(runtime.stats().total_jobs_completed, 2);
+            assert_eq!(runtime.stats().current_running_jobs, 0);
+        });
+    }
+
+    #[test]
+    fn test_async_handle_local_executor() {
+        let backend = Arc::new(SlowBackend::new(30));
+        let handle = pooled_runtime(backend.clone(), 4).into_async();
+        let mut executor = crate::future::LocalExecutor::new();
+        let tasks: Vec<_> = (0..4)
+            .map(|q| {
+                let handle = handle.clone();
+                executor.spawn(async move { handle.execute(single_qubit_job(q, "slow")).await })
+            })
+            .collect();
+        executor.run();
+        for task in tasks {
+            assert_eq!(block_on(task).unwrap().unwrap().status, JobStatus::Completed);
+        }
+        assert!(backend.peak.load(Ordering::SeqCst) > 1);
+    }
+
+    #[test]
+    fn test_async_handle_finishes_on_backend_wake() {
+        let backend = Arc::new(GatedBackend {
+            inner: MockBackendAdapter::new(),
+            pending: Mutex::new(HashMap::new()),
+            cancelled: Mutex::new(Vec::new()),
+        });
+        let mut runtime = QuantumRuntime::default();
+        runtime.register_backend("gated", backend.clone());
+        *runtime.scheduler_mut() = JobScheduler::new(2).with_qubits((0..8).map(LogicalQubitId::new).collect());
+        let handle = runtime.into_async();
+        let done = handle.submit(single_qubit_job(0, "gated")).unwrap();
+        let timed = handle.submit(single_qubit_job(1, "gated").with_timeout(Duration::from_millis(20))).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while backend.pending.lock().unwrap().len() < 2 {
+            assert!(Instant::now() < deadline);
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        // 超时由共享计时线程唤醒，并以后端作业 ID 取消
+        let result = block_on(handle.wait(timed)).unwrap();
+        assert!(result.timed_out);
+        let timed_backend_id = backend.pending.lock().unwrap()[&timed].0;
+        assert_eq!(*backend.cancelled.lock().unwrap(), vec![timed_backend_id]);
+
+        // 后端完成时在完成方线程上结束这次尝试：complete 返回时运行时已有结果
+        let (_, completer) = backend.pending.lock().unwrap().remove(&done).unwrap();
+        completer.complete(Ok(JobResult::success(done)));
+        handle.with_runtime(|runtime| {
+            assert_eq!(runtime.get_job_status(done), Some(JobStatus::Completed));
+            assert_eq!(runtime.stats().current_running_jobs, 0);
+        });
+    }
+
+    #[test]
+    fn test_job_timeout() {
+        // 后端作业远长于超时：run 在超时后返回而不等后端结束
+        let backend = Arc::new(DeferredBackend::new(30_000));
+        let mut runtime = QuantumRuntime::default();
+        runtime.register_backend("deferred", backend.clone());
+        let job = single_qubit_job(0, "deferred").with_timeout(Duration::from_millis(30));
+        let job_id = job.id;
+
+        let start = Instant::now();
+        let result = runtime.run(job).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert_eq!(result.status, JobStatus::Failed);
+        assert!(result.timed_out);
+        assert!(result.error.unwrap().contains("timeout"));
+        // 以后端分配的作业 ID 取消，而不是运行时的作业 ID
+        assert_ne!(job_id, 5000);
+        assert_eq!(*backend.cancelled.lock().unwrap(), vec![5000]);
+        assert_eq!(backend.get_job_status(5000).unwrap(), JobStatus::Cancelled);
+        assert_eq!(runtime.stats().total_jobs_timed_out, 1);
+        assert_eq!(runtime.stats().total_jobs_failed, 1);
+    }
+
+    #[test]
+    fn test_job_timeout_through_scheduler() {
+        assert_eq!(RuntimeConfig::new().with_timeout(0).job_timeout(), None);
+
+        let backend = Arc::new(SlowBackend::new(60));
+        let mut runtime = pooled_runtime(backend, 2);
+        let slow = runtime.submit_job(single_qubit_job(0, "slow").with_timeout(Duration::from_millis(10))).unwrap();
+        let normal = runtime.submit_job(single_qubit_job(1, "slow")).unwrap();
+        let dependent = runtime.submit_job(single_qubit_job(2, "slow").with_dependency(slow)).unwrap();
+
+        runtime.execute_all();
+        let result = runtime.get_job_result(slow).unwrap();
+        assert_eq!(result.status, JobStatus::Failed);
+        assert!(result.timed_out);
+        assert_eq!(runtime.get_job_status(normal), Some(JobStatus::Completed));
+        assert_eq!(runtime.get_job_status(dependent), Some(JobStatus::Failed));
+        assert_eq!(runtime.stats().total_jobs_timed_out, 1);
+    }
+
+    #[test]
+    fn test_runtime_quota_report() {
+        use crate::job::JobMetadata;
+        use crate::quota::{OverQuota, QuotaLimits};
+
+        let mut runtime = pooled_runtime(Arc::new(SlowBackend::new(5)), 2);
+        runtime.set_quotas(
+            QuotaManager::new()
+                .with_default_user_limits(QuotaLimits::new().with_shot_rate(25, Duration::from_secs(60)))
+                .with_action(OverQuota::Reject),
+        );
+        let alice = |q| single_qubit_job(q, "slow").with_metadata(JobMetadata::new().with_user("alice"));
+        runtime.submit_job(alice(0)).unwrap();
+        runtime.execute_all();
+        runtime.submit_job(alice(1)).unwrap();
+        runtime.execute_all();
+        assert!(matches!(runtime.submit_job(alice(2)), Err(IrError::QuotaExceeded(_))));
+        // 直接执行同样受配额约束
+        assert!(matches!(runtime.run(alice(3)), Err(IrError::QuotaExceeded(_))));
+
+        let usage = runtime.quota_usage(&QuotaKey::user("alice"));
+        assert_eq!((usage.total_jobs, usage.shots_in_window, usage.rejected), (2, 20, 2));
+        assert!(usage.qubit_seconds > 0.0);
+        assert_eq!(runtime.quota_report(), vec![usage]);
+        assert_eq!(runtime.quota_usage(&QuotaKey::user("bob")).total_jobs, 0);
+
+        // 直接执行的作业计入使用量
+        let bob = single_qubit_job(0, "slow").with_metadata(JobMetadata::new().with_user("bob"));
+        assert_eq!(runtime.run(bob).unwrap().status, JobStatus::Completed);
+        let usage = runtime.quota_usage(&QuotaKey::user("bob"));
+        assert_eq!((usage.total_jobs, usage.shots_in_window, usage.running_jobs), (1, 10, 0));
+        assert!(usage.qubit_seconds > 0.0);
+    }
+
+    fn stored_runtime(store: impl JobStore + 'static) -> QuantumRuntime {
+        let mut runtime = QuantumRuntime::new(RuntimeConfig::new().with_max_jobs(1));
+        runtime.register_backend("mock", Arc::new(MockBackendAdapter::new()));
+        *runtime.scheduler_mut() = JobScheduler::new(1).with_qubits((0..4).map(LogicalQubitId::new).collect());
+        runtime.set_store(store);
+        runtime
+    }
+
+    #[test]
+    fn test_store_write_through_and_recovery() {
+        use crate::store::InMemoryJobStore;
+
+        let store = InMemoryJobStore::new();
+        let (first, second, third) = {
+            let mut runtime = stored_runtime(store.clone());
+            let first = runtime.submit_job(single_qubit_job(0, "mock")).unwrap();
+            let second = runtime.submit_job(single_qubit_job(1, "mock").with_dependency(first)).unwrap();
+            let third = runtime.submit_job(single_qubit_job(2, "mock").with_priority(Priority::Low)).unwrap();
+            runtime.schedule_and_execute().unwrap();
+            // 第二个作业开始执行后进程退出
+            assert!(matches!(runtime.start_next(), Some(Dispatch::Run(..))));
+            (first, second, third)
+        };
+        let statuses: Vec<_> = store.load().unwrap().iter().map(|s| s.job.status).collect();
+        assert_eq!(statuses, vec![JobStatus::Completed, JobStatus::Running, JobStatus::Queued]);
+
+        let mut runtime = stored_runtime(store.clone());
+        let report = runtime.recover(RecoveryMode::Resubmit).unwrap();
+        assert_eq!(report.restored, vec![first]);
+        assert_eq!(report.resubmitted, vec![second]);
+        assert_eq!(report.requeued, vec![third]);
+        assert_eq!(runtime.get_job_status(first), Some(JobStatus::Completed));
+
+        assert_eq!(runtime.execute_all().len(), 2);
+        assert!(store.load().unwrap().iter().all(|s| s.result.as_ref().is_some_and(|r| r.status == JobStatus::Completed)));
+        assert!(single_qubit_job(3, "mock").id > third);
+        assert_eq!(runtime.stats().total_store_errors, 0);
+    }
+
+    #[test]
+    fn test_recovery_from_file_store() {
+        use crate::store::FileJobStore;
+
+        let dir = std::env::temp_dir().join(format!("quantum-ir-runtime-store-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let (running, cancelled, queued) = {
+            let mut runtime = stored_runtime(FileJobStore::open(&dir).unwrap());
+            let running = runtime.submit_job(single_qubit_job(0, "mock")).unwrap();
+            let cancelled = runtime.submit_job(single_qubit_job(1, "mock")).unwrap();
+            let queued = runtime.submit_job(single_qubit_job(2, "mock").with_priority(Priority::Low)).unwrap();
+            assert!(runtime.cancel_job(cancelled));
+            runtime.start_next().unwrap();
+            (running, cancelled, queued)
+        };
+
+        let mut runtime = stored_runtime(FileJobStore::open(&dir).unwrap());
+        let report = runtime.recover(RecoveryMode::MarkFailed).unwrap();
+        assert_eq!(report.failed, vec![running]);
+        assert_eq!(report.restored, vec![cancelled]);
+        assert_eq!(report.requeued, vec![queued]);
+        assert_eq!(report.total(), 3);
+        let interrupted = runtime.get_job_result(running).unwrap();
+        assert_eq!(interrupted.status, JobStatus::Failed);
+        assert_eq!(runtime.get_job_status(cancelled), Some(JobStatus::Cancelled));
+
+        assert_eq!(runtime.execute_all().len(), 1);
+        assert_eq!(runtime.get_job_status(queued), Some(JobStatus::Completed));
+        drop(runtime);
+
+        let stored = FileJobStore::open(&dir).unwrap().load().unwrap();
+        let statuses: Vec<_> = stored.iter().map(|s| s.job.status).collect();
+        assert_eq!(statuses, vec![JobStatus::Failed, JobStatus::Cancelled, JobStatus::Completed]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_retry_with_backoff() {
+        let retry = RetryPolicy::new(3).with_backoff(Duration::from_millis(5), 2.0);
+        let mut runtime = QuantumRuntime::new(RuntimeConfig::new().with_retry(retry));
+        *runtime.scheduler_mut() = JobScheduler::new(4).with_qubits(vec![LogicalQubitId::new(0)]);
+        runtime.register_backend("flaky", Arc::new(FlakyBackend::new(2, IrError::BackendUnavailable)));
+
+        let start = Instant::now();
+        let job_id = runtime.submit_job(single_qubit_job(0, "flaky")).unwrap();
+        let result = runtime.schedule_and_execute().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(15));
+        assert_eq!(result.status, JobStatus::Completed);
+        assert_eq!(result.retries(), 2);
+        let kinds: Vec<_> = result.attempts.iter().map(|a| a.error_kind).collect();
+        assert_eq!(kinds, vec![Some(ErrorKind::BackendUnavailable), Some(ErrorKind::BackendUnavailable), None]);
+        assert_eq!(result.attempts[2].status, JobStatus::Completed);
+        assert_eq!(runtime.get_job_result(job_id).unwrap().attempts.len(), 3);
+        assert_eq!(runtime.stats().total_retries, 2);
+        assert_eq!(runtime.stats().total_jobs_completed, 1);
+    }
+
+    #[test]
+    fn test_timed_out_attempt_cancelled_before_retry() {
+        let backend = Arc::new(DeferredBackend::new(300));
+        let retry = RetryPolicy::new(2).with_backoff(Duration::ZERO, 1.0);
+        let mut runtime = QuantumRuntime::new(RuntimeConfig::new().with_retry(retry));
+        *runtime.scheduler_mut() = JobScheduler::new(1).with_qubits(vec![LogicalQubitId::new(0)]);
+        runtime.register_backend("deferred", backend.clone());
+        runtime.submit_job(single_qubit_job(0, "deferred").with_timeout(Duration::from_millis(30))).unwrap();
+
+        let results = runtime.execute_all();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].timed_out);
+        assert_eq!(results[0].attempts.len(), 2);
+        assert!(results[0].attempts.iter().all(|a| a.error_kind == Some(ErrorKind::Timeout)));
+        // 每次超时的后端作业都在下一次提交前取消
+        assert_eq!(*backend.cancelled.lock().unwrap(), vec![5000, 5001]);
+        assert_eq!(backend.get_job_status(5000).unwrap(), JobStatus::Cancelled);
+        assert_eq!(runtime.stats().total_retries, 1);
+
+        // 后端自己报告超时时同样先取消
+        let backend = Arc::new(DeferredBackend::new(10).reporting_timeout());
+        runtime.register_backend("deferred", backend.clone());
+        let result = runtime.run(single_qubit_job(0, "deferred")).unwrap();
+        assert!(result.timed_out);
+        assert_eq!(result.attempts.len(), 2);
+        assert_eq!(*backend.cancelled.lock().unwrap(), vec![5000, 5001]);
+    }
+
+    #[test]
+    fn test_retry_backoff_does_not_block_other_jobs() {
+        let retry = RetryPolicy::new(2).with_backoff(Duration::from_millis(100), 1.0);
+        for mode in [ExecutionMode::Serial, ExecutionMode::WorkerPool] {
+            let config = RuntimeConfig::new().with_max_jobs(1).with_execution_mode(mode).with_retry(retry.clone());
+            let mut runtime = QuantumRuntime::new(config);
+            *runtime.scheduler_mut() = JobScheduler::new(1).with_qubits((0..2).map(LogicalQubitId::new).collect());
+            runtime.register_backend("flaky", Arc::new(FlakyBackend::new(1, IrError::BackendUnavailable)));
+            runtime.register_backend("mock", Arc::new(MockBackendAdapter::new()));
+            let flaky = runtime.submit_job(single_qubit_job(0, "flaky")).unwrap();
+            let other = runtime.submit_job(single_qubit_job(1, "mock")).unwrap();
+
+            // 退避期间作业回到队列，另一个作业先执行完
+            let start = Instant::now();
+            let results = runtime.execute_all();
+            assert!(start.elapsed() >= Duration::from_millis(100));
+            assert_eq!(results.len(), 2, "{:?}", mode);
+            assert_eq!(runtime.get_job_status(other), Some(JobStatus::Completed));
+            assert_eq!(results[1].status, JobStatus::Completed);
+            assert_eq!(results[1].attempts.len(), 2);
+            assert_eq!(runtime.get_job_result(flaky).unwrap().attempts.len(), 2);
+            assert_eq!(runtime.stats().total_retries, 1);
+            assert_eq!(runtime.stats().current_running_jobs, 0);
+        }
+
+        // 异步句柄由计时线程在重试时刻重新调度
+        let mut runtime = QuantumRuntime::new(RuntimeConfig::new().with_retry(retry));
+        *runtime.scheduler_mut() = JobScheduler::new(1).with_qubits(vec![LogicalQubitId::new(0)]);
+        runtime.register_backend("flaky", Arc::new(FlakyBackend::new(1, IrError::BackendUnavailable)));
+        let handle = runtime.into_async();
+        let result = block_on(handle.execute(single_qubit_job(0, "flaky"))).unwrap();
+        assert_eq!(result.status, JobStatus::Completed);
+        assert_eq!(result.attempts.len(), 2);
+    }
+
+    #[test]
+    fn test_retry_gives_up() {
+        let mut runtime = QuantumRuntime::new(RuntimeConfig::new().with_retry(RetryPolicy::new(5)));
+        runtime.register_backend("unsupported", Arc::new(FlakyBackend::new(1, IrError::UnsupportedOperation)));
+        runtime.register_backend("offline", Arc::new(FlakyBackend::new(usize::MAX, IrError::BackendUnavailable)));
+
+        // 不可重试的错误只尝试一次
+        let result = runtime.run(single_qubit_job(0, "unsupported")).unwrap();
+        assert_eq!(result.status, JobStatus::Failed);
+        assert_eq!(result.attempts.len(), 1);
+        assert_eq!(result.attempts[0].error_kind, Some(ErrorKind::UnsupportedOperation));
+
+        // 作业自己的策略覆盖 Runtime 默认值
+        let job = single_qubit_job(0, "offline").with_retry(RetryPolicy::new(2).with_backoff(Duration::ZERO, 1.0));
+        let result = runtime.run(job).unwrap();
+        assert_eq!(result.status, JobStatus::Failed);
+        assert_eq!(result.attempts.len(), 2);
+        assert!(result.error.unwrap().contains("Backend unavailable"));
+        assert_eq!(runtime.stats().total_retries, 1);
+        assert_eq!(runtime.stats().total_jobs_failed, 2);
+    }
+
+    #[test]
+    fn test_failover_to_capable_backend() {
+        let mut no_measurement = BackendCapabilities::ideal_simulator();
+        no_measurement.supports_measurement = false;
+        let mut runtime = QuantumRuntime::default();
+        runtime.register_backend("flaky", Arc::new(FlakyBackend::new(usize::MAX, IrError::BackendUnavailable)));
+        runtime.register_backend("a-no-measurement", Arc::new(MockBackendAdapter::with_capabilities(no_measurement)));
+        runtime.register_backend("backup", Arc::new(MockBackendAdapter::new()));
+
+        let job = single_qubit_job(0, "flaky").with_retry(RetryPolicy::new(3).with_failover(true));
+        let result = runtime.run(job).unwrap();
+        assert_eq!(result.status, JobStatus::Completed);
+        let backends: Vec<_> = result.attempts.iter().map(|a| a.backend.as_str()).collect();
+        assert_eq!(backends, vec!["flaky", "backup"]);
+        assert_eq!(result.final_backend(), Some("backup"));
+        assert_eq!(runtime.stats().total_failovers, 1);
+
+        // 没有启用故障转移时只在目标后端上重试
+        let job = single_qubit_job(0, "flaky").with_retry(RetryPolicy::new(2).with_backoff(Duration::ZERO, 1.0));
+        let result = runtime.run(job).unwrap();
+        assert_eq!(result.status, JobStatus::Failed);
+        assert!(result.attempts.iter().all(|a| a.backend == "flaky"));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_async_handle_on_tokio() {
+        let tokio_runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let handle = AsyncRuntimeHandle::with_tokio(pooled_runtime(Arc::new(SlowBackend::new(20)), 2), tokio_runtime.handle().clone());
+        let results = tokio_runtime.block_on(async {
+            let first = handle.execute(single_qubit_job(0, "slow"));
+            let second = handle.execute(single_qubit_job(1, "slow"));
+            (first.await, second.await)
+        });
+        assert_eq!(results.0.unwrap().status, JobStatus::Completed);
+        assert_eq!(results.1.unwrap().status, JobStatus::Completed);
+    }
+
     #[test]
     fn test_runtime_start_stop() {
         let mut runtime = QuantumRuntime::default();
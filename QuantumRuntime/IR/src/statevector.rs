@@ -0,0 +1,403 @@
+//! 态矢量模块
+//!
+//! 稠密态矢量与门作用，用于小规模电路的精确模拟和等价性检查。
+//!
+//! 约定：电路 qubit 列表中第 k 个 qubit 对应振幅下标的第 k 位（qubit 0 为最低位）；
+//! 多比特门矩阵中第一个操作数为最高位（如 CNOT 的 control）。
+
+use crate::circuit::CircuitDag;
+use crate::linalg::{inner_product, Complex, Matrix};
+use crate::operation::Operation;
+use crate::qubit::LogicalQubitId;
+use crate::rng::SplitMix64;
+use crate::{Result, IrError};
+
+/// CircuitDag::unitary 允许的最大 qubit 数
+pub const MAX_UNITARY_QUBITS: usize = 12;
+
+/// 态矢量模拟允许的最大 qubit 数
+pub const MAX_SIMULATION_QUBITS: usize = 24;
+
+/// 操作的酉矩阵；barrier 与延时返回 None，非酉操作返回错误
+fn unitary_of(op: &Operation) -> Result<Option<Matrix>> {
+    match op {
+        Operation::Barrier { .. } | Operation::Delay { .. } => Ok(None),
+        Operation::Measure { .. } | Operation::Reset { .. } => Err(IrError::UnsupportedOperation(
+            format!("{} is not a unitary operation", op.name()),
+        )),
+        Operation::Symbolic { .. } => Err(IrError::InvalidOperation(format!(
+            "{} has unbound parameters", op.name()
+        ))),
+        Operation::Custom(custom) => match &custom.matrix {
+            Some(matrix) if matrix.dim() == 1 << custom.qubits.len() => Ok(Some(matrix.clone())),
+            Some(matrix) => Err(IrError::InvalidOperation(format!(
+                "Custom operation {} has a {}x{} matrix for {} qubits",
+                custom.name, matrix.dim(), matrix.dim(), custom.qubits.len()
+            ))),
+            None => Err(IrError::UnsupportedOperation(format!(
+                "Custom operation {} has no known matrix", custom.name
+            ))),
+        },
+        _ => Ok(op.matrix()),
+    }
+}
+
+// ============================================================================
+// State Vector
+// ============================================================================
+
+/// 稠密态矢量
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateVector {
+    num_qubits: usize,
+    amplitudes: Vec<Complex>,
+}
+
+impl StateVector {
+    /// |0…0⟩
+    pub fn new(num_qubits: usize) -> Self {
+        Self::basis(num_qubits, 0)
+    }
+
+    /// 计算基态 |index⟩
+    pub fn basis(num_qubits: usize, index: usize) -> Self {
+        let mut amplitudes = vec![Complex::ZERO; 1 << num_qubits];
+        amplitudes[index] = Complex::ONE;
+        Self { num_qubits, amplitudes }
+    }
+
+    /// 由振幅构造（自动归一化）
+    pub fn from_amplitudes(amplitudes: Vec<Complex>) -> Result<Self> {
+        if !amplitudes.len().is_power_of_two() {
+            return Err(IrError::InvalidOperation(format!(
+                "State vector length {} is not a power of two", amplitudes.len()
+            )));
+        }
+        let norm = amplitudes.iter().map(|a| a.norm_sqr()).sum::<f64>().sqrt();
+        if norm <= 0.0 {
+            return Err(IrError::InvalidOperation("State vector has zero norm".to_string()));
+        }
+        Ok(Self {
+            num_qubits: amplitudes.len().trailing_zeros() as usize,
+            amplitudes: amplitudes.into_iter().map(|a| a / norm).collect(),
+        })
+    }
+
+    /// 直接由振幅构造，不做归一化（用于表示 O|ψ⟩ 等非归一向量）
+    pub(crate) fn from_raw(amplitudes: Vec<Complex>) -> Self {
+        debug_assert!(amplitudes.len().is_power_of_two());
+        Self {
+            num_qubits: amplitudes.len().trailing_zeros() as usize,
+            amplitudes,
+        }
+    }
+    
+    /// Haar 随机态（高斯振幅归一化）
+    pub fn random(num_qubits: usize, seed: u64) -> Self {
+        let mut rng = SplitMix64::new(seed);
+        let amplitudes = (0..1usize << num_qubits)
+            .map(|_| Complex::new(rng.next_gaussian(), rng.next_gaussian()))
+            .collect();
+        Self::from_amplitudes(amplitudes).expect("random state has non-zero norm")
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    pub fn amplitudes(&self) -> &[Complex] {
+        &self.amplitudes
+    }
+
+    pub fn into_amplitudes(self) -> Vec<Complex> {
+        self.amplitudes
+    }
+
+    /// 各计算基态的概率
+    pub fn probabilities(&self) -> Vec<f64> {
+        self.amplitudes.iter().map(|a| a.norm_sqr()).collect()
+    }
+
+    /// 按计算基概率采样 shots 次，返回基态下标
+    pub fn sample(&self, shots: usize, seed: u64) -> Vec<usize> {
+        let mut cumulative = Vec::with_capacity(self.amplitudes.len());
+        let mut total = 0.0;
+        for a in &self.amplitudes {
+            total += a.norm_sqr();
+            cumulative.push(total);
+        }
+        let mut rng = SplitMix64::new(seed);
+        (0..shots)
+            .map(|_| {
+                let r = rng.next_f64() * total;
+                cumulative.partition_point(|&c| c <= r).min(cumulative.len() - 1)
+            })
+            .collect()
+    }
+    
+    /// ⟨self|other⟩
+    pub fn inner(&self, other: &StateVector) -> Complex {
+        inner_product(&self.amplitudes, &other.amplitudes)
+    }
+
+    /// |⟨self|other⟩|²
+    pub fn fidelity(&self, other: &StateVector) -> f64 {
+        self.inner(other).norm_sqr()
+    }
+
+    /// 在 targets 上作用矩阵（targets[0] 对应矩阵下标最高位）
+    pub fn apply_matrix(&mut self, targets: &[usize], matrix: &Matrix) {
+        let k = targets.len();
+        assert_eq!(matrix.dim(), 1 << k, "matrix does not match target count");
+
+        let target_mask: usize = targets.iter().map(|&t| 1 << t).sum();
+        let offsets: Vec<usize> = (0..1usize << k)
+            .map(|sub| {
+                (0..k)
+                    .filter(|&j| sub >> (k - 1 - j) & 1 == 1)
+                    .map(|j| 1 << targets[j])
+                    .sum()
+            })
+            .collect();
+
+        let mut buffer = vec![Complex::ZERO; 1 << k];
+        for base in 0..self.amplitudes.len() {
+            if base & target_mask != 0 {
+                continue;
+            }
+            for (sub, &offset) in offsets.iter().enumerate() {
+                buffer[sub] = self.amplitudes[base | offset];
+            }
+            for (row, &offset) in offsets.iter().enumerate() {
+                let mut acc = Complex::ZERO;
+                for (col, &amp) in buffer.iter().enumerate() {
+                    acc += matrix.get(row, col) * amp;
+                }
+                self.amplitudes[base | offset] = acc;
+            }
+        }
+    }
+
+    /// 作用一个酉操作，index_of 把逻辑 qubit 映射到态矢量中的位
+    pub fn apply_operation<F>(&mut self, op: &Operation, index_of: F) -> Result<()>
+    where
+        F: Fn(LogicalQubitId) -> Option<usize>,
+    {
+        let Some(matrix) = unitary_of(op)? else {
+            return Ok(());
+        };
+        let targets = op
+            .qubits()
+            .into_iter()
+            .map(|q| {
+                index_of(q).ok_or_else(|| IrError::QubitNotFound(format!("{} is not simulated", q)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.apply_matrix(&targets, &matrix);
+        Ok(())
+    }
+
+    /// 按拓扑顺序作用整个电路（qubits 给出位的顺序）
+    pub fn apply_circuit(&mut self, circuit: &CircuitDag, qubits: &[LogicalQubitId]) -> Result<()> {
+        for op in circuit.resolved_operations()? {
+            self.apply_operation(&op, |q| qubits.iter().position(|&x| x == q))?;
+        }
+        Ok(())
+    }
+}
+
+/// 计算电路的完整酉矩阵（qubits[0] 为最低位）
+pub fn circuit_unitary(circuit: &CircuitDag, qubits: &[LogicalQubitId]) -> Result<Matrix> {
+    let n = qubits.len();
+    let dim = 1usize << n;
+    let mut unitary = Matrix::zeros(dim);
+    for col in 0..dim {
+        let mut state = StateVector::basis(n, col);
+        state.apply_circuit(circuit, qubits)?;
+        for (row, &amp) in state.amplitudes().iter().enumerate() {
+            unitary.set(row, col, amp);
+        }
+    }
+    Ok(unitary)
+}
+
+/// 剥离末尾测量后的酉操作序列（按拓扑顺序）
+/// 
+/// 测量之后不能再有作用于同一 qubit 的操作，且不支持重置。
+pub(crate) fn unitary_ops(circuit: &CircuitDag) -> Result<Vec<Operation>> {
+    strip_measurements(circuit.resolved_operations()?)
+}
+
+/// 剥离已排好序的操作序列末尾的测量
+pub(crate) fn strip_measurements(operations: impl IntoIterator<Item = Operation>) -> Result<Vec<Operation>> {
+    let mut measured = std::collections::BTreeSet::new();
+    let mut ops = Vec::new();
+    for op in operations {
+        match op {
+            Operation::Measure { qubit, .. } => {
+                measured.insert(qubit);
+            }
+            Operation::Reset { qubit } => {
+                return Err(IrError::UnsupportedOperation(format!("Reset on {} is not supported", qubit)));
+            }
+            op => {
+                if let Some(q) = op.qubits().into_iter().find(|q| measured.contains(q)) {
+                    return Err(IrError::UnsupportedOperation(format!(
+                        "Mid-circuit measurement on {} is not supported", q
+                    )));
+                }
+                ops.push(op);
+            }
+        }
+    }
+    Ok(ops)
+}
+
+/// 从 |0…0⟩ 模拟到测量前的末态，qubit 按 ID 升序（最小 ID 为最低位）
+pub fn final_state(circuit: &CircuitDag) -> Result<(Vec<LogicalQubitId>, StateVector)> {
+    let mut qubits = circuit.all_qubits();
+    qubits.sort();
+    let state = evolve(&qubits, &unitary_ops(circuit)?)?;
+    Ok((qubits, state))
+}
+
+/// 从 |0…0⟩ 依次作用酉操作，qubits 须按 ID 升序（最小 ID 为最低位）
+pub(crate) fn evolve(qubits: &[LogicalQubitId], ops: &[Operation]) -> Result<StateVector> {
+    if qubits.len() > MAX_SIMULATION_QUBITS {
+        return Err(IrError::UnsupportedOperation(format!(
+            "Simulation of {} qubits exceeds the limit of {}", qubits.len(), MAX_SIMULATION_QUBITS
+        )));
+    }
+    let mut state = StateVector::new(qubits.len());
+    for op in ops {
+        state.apply_operation(op, |q| qubits.binary_search(&q).ok())?;
+    }
+    Ok(state)
+}
+
+impl CircuitDag {
+    /// 电路的完整酉矩阵，qubit 按 ID 升序排列（最小 ID 为最低位）
+    pub fn unitary(&self) -> Result<Matrix> {
+        let mut qubits = self.all_qubits();
+        qubits.sort();
+        if qubits.len() > MAX_UNITARY_QUBITS {
+            return Err(IrError::UnsupportedOperation(format!(
+                "Unitary of {} qubits exceeds the limit of {}", qubits.len(), MAX_UNITARY_QUBITS
+            )));
+        }
+        circuit_unitary(self, &qubits)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::bell_state_dag;
+    use crate::operation::{cnot, h, x, CustomOp, SingleQubitGate, ThreeQubitGate, TwoQubitGate};
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    #[test]
+    fn test_bell_state() {
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(h(q(0)));
+        circuit.add_node(cnot(q(0), q(1)));
+        let mut state = StateVector::new(2);
+        state.apply_circuit(&circuit, &[q(0), q(1)]).unwrap();
+
+        let probs = state.probabilities();
+        assert!((probs[0] - 0.5).abs() < 1e-12);
+        assert!((probs[3] - 0.5).abs() < 1e-12);
+        assert!(state.amplitudes()[0].approx_eq(Complex::real(FRAC_1_SQRT_2), 1e-12));
+    }
+
+    #[test]
+    fn test_qubit_order() {
+        // qubit 0 为最低位：X(q1) 得到 |10⟩ = 下标 2
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(x(q(1)));
+        let mut state = StateVector::new(2);
+        state.apply_circuit(&circuit, &[q(0), q(1)]).unwrap();
+        assert_eq!(state.amplitudes()[2], Complex::ONE);
+
+        // control 在高位 qubit 上
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(x(q(1)));
+        circuit.add_node(cnot(q(1), q(0)));
+        let mut state = StateVector::new(2);
+        state.apply_circuit(&circuit, &[q(0), q(1)]).unwrap();
+        assert_eq!(state.amplitudes()[3], Complex::ONE);
+    }
+
+    #[test]
+    fn test_circuit_unitary_is_unitary() {
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(h(q(0)));
+        circuit.add_node(Operation::gate2(TwoQubitGate::SqrtSWAP, q(0), q(1)));
+        circuit.add_node(Operation::gate3(ThreeQubitGate::Fredkin, q(2), q(0), q(1)));
+        circuit.add_node(Operation::gate1(SingleQubitGate::U(0.3, 0.2, 0.1), q(2)));
+        let u = circuit_unitary(&circuit, &[q(0), q(1), q(2)]).unwrap();
+        assert!(u.is_unitary(1e-10));
+    }
+
+    #[test]
+    fn test_measurement_is_rejected() {
+        let circuit = bell_state_dag();
+        let mut state = StateVector::new(2);
+        assert!(state.apply_circuit(&circuit, &[q(0), q(1)]).is_err());
+    }
+
+    #[test]
+    fn test_final_state_and_sampling() {
+        let (qubits, state) = final_state(&bell_state_dag()).unwrap();
+        assert_eq!(qubits, vec![q(0), q(1)]);
+        assert!((state.probabilities()[3] - 0.5).abs() < 1e-12);
+
+        let samples = state.sample(1000, 3);
+        assert!(samples.iter().all(|&s| s == 0 || s == 3));
+        let ones = samples.iter().filter(|&&s| s == 3).count();
+        assert!((400..600).contains(&ones));
+
+        // 测量后再作用门
+        let mut circuit = bell_state_dag();
+        circuit.add_node(x(q(0)));
+        let circuit = circuit.rebuild_with(circuit.operations_in_order());
+        assert!(final_state(&circuit).is_err());
+    }
+
+    #[test]
+    fn test_random_state_normalized() {
+        let state = StateVector::random(3, 7);
+        let total: f64 = state.probabilities().iter().sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        assert_eq!(state, StateVector::random(3, 7));
+    }
+
+    #[test]
+    fn test_custom_matrix() {
+        let sqrt_x = Matrix::from_rows(vec![
+            vec![Complex::new(0.5, 0.5), Complex::new(0.5, -0.5)],
+            vec![Complex::new(0.5, -0.5), Complex::new(0.5, 0.5)],
+        ]);
+        let op = Operation::Custom(CustomOp::new("SX").with_qubits(vec![q(0)]).with_matrix(sqrt_x));
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(op.clone());
+        circuit.add_node(op);
+        let u = circuit.unitary().unwrap();
+        assert!(u.approx_eq(&SingleQubitGate::X.matrix(), 1e-12));
+
+        let bad = Operation::Custom(CustomOp::new("bad").with_qubits(vec![q(0), q(1)]).with_matrix(Matrix::identity(2)));
+        let mut state = StateVector::new(2);
+        assert!(matches!(
+            state.apply_operation(&bad, |q| Some(q.value() as usize)),
+            Err(IrError::InvalidOperation(_))
+        ));
+    }
+}
@@ -0,0 +1,1229 @@
+//! 作业存储模块
+//!
+//! 调度器只在内存中保存作业与结果，进程重启即全部丢失。`JobStore` 记录作业的提交、
+//! 开始执行与最终结果，`QuantumRuntime` 写穿到存储，并在启动时据此恢复：
+//! - `InMemoryJobStore`：进程内存储，克隆共享同一份数据；
+//! - `FileJobStore`：目录下的追加日志加快照，日志超过阈值时压缩为新快照。
+//!
+//! 文件格式为按行的文本记录。复合门定义是生成函数，无法保存，写入前电路先展开全部复合门；
+//! 显式添加的额外依赖边不保存，读回后按 qubit 使用顺序重新推导。截止时刻按墙上时钟保存。
+
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display, Write as _};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::circuit::{CircuitDag, CircuitDagBuilder, CircuitMetadata};
+use crate::job::{Job, JobAttempt, JobId, JobMetadata, JobResult, JobStatus, Priority};
+use crate::linalg::{Complex, Matrix};
+use crate::operation::{CustomOp, Operation, SingleQubitGate, ThreeQubitGate, TwoQubitGate};
+use crate::params::{Param, ParamBinding, ParamExpr};
+use crate::qubit::LogicalQubitId;
+use crate::retry::RetryPolicy;
+use crate::{Result, IrError, ErrorKind};
+
+// ============================================================================
+// Job Store
+// ============================================================================
+
+/// 存储中的作业
+#[derive(Debug, Clone)]
+pub struct StoredJob {
+    /// 作业，`status` 为最后记录的状态
+    pub job: Job,
+    /// 已结束作业的结果
+    pub result: Option<JobResult>,
+}
+
+/// 作业持久化接口
+///
+/// 同一作业的记录按发生顺序写入；`load` 返回每个作业最后的状态。
+pub trait JobStore: Send + Debug {
+    /// 作业被调度器接受
+    fn record_submitted(&mut self, job: &Job) -> Result<()>;
+
+    /// 作业状态变化（重新排队、开始执行）
+    fn record_status(&mut self, job_id: JobId, status: JobStatus) -> Result<()>;
+
+    /// 作业结束（结果中的 `job_id` 可能是后端的作业 ID，以参数为准）
+    fn record_result(&mut self, job_id: JobId, result: &JobResult) -> Result<()>;
+
+    /// 按 ID 升序读出全部作业
+    fn load(&self) -> Result<Vec<StoredJob>>;
+}
+
+/// 按作业 ID 索引的最新状态
+#[derive(Debug, Clone, Default)]
+struct JobTable {
+    jobs: BTreeMap<JobId, StoredJob>,
+}
+
+impl JobTable {
+    fn submitted(&mut self, job: Job) {
+        self.jobs.insert(job.id, StoredJob { job, result: None });
+    }
+
+    fn status(&mut self, job_id: JobId, status: JobStatus) -> Result<()> {
+        self.get_mut(job_id)?.job.status = status;
+        Ok(())
+    }
+
+    fn result(&mut self, job_id: JobId, result: JobResult) -> Result<()> {
+        let stored = self.get_mut(job_id)?;
+        stored.job.status = result.status;
+        stored.result = Some(result);
+        Ok(())
+    }
+
+    fn get_mut(&mut self, job_id: JobId) -> Result<&mut StoredJob> {
+        self.jobs
+            .get_mut(&job_id)
+            .ok_or_else(|| IrError::Storage(format!("Job {} is not in the store", job_id)))
+    }
+
+    fn snapshot(&self) -> Vec<StoredJob> {
+        self.jobs.values().cloned().collect()
+    }
+}
+
+// ============================================================================
+// In-Memory Store
+// ============================================================================
+
+/// 进程内作业存储
+///
+/// 克隆得到的句柄共享同一份数据，可以交给新的 Runtime 模拟重启后的恢复。
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryJobStore {
+    table: Arc<Mutex<JobTable>>,
+}
+
+impl InMemoryJobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 存储中的作业数
+    pub fn len(&self) -> usize {
+        self.table.lock().unwrap().jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl JobStore for InMemoryJobStore {
+    fn record_submitted(&mut self, job: &Job) -> Result<()> {
+        self.table.lock().unwrap().submitted(job.clone());
+        Ok(())
+    }
+
+    fn record_status(&mut self, job_id: JobId, status: JobStatus) -> Result<()> {
+        self.table.lock().unwrap().status(job_id, status)
+    }
+
+    fn record_result(&mut self, job_id: JobId, result: &JobResult) -> Result<()> {
+        self.table.lock().unwrap().result(job_id, result.clone())
+    }
+
+    fn load(&self) -> Result<Vec<StoredJob>> {
+        Ok(self.table.lock().unwrap().snapshot())
+    }
+}
+
+// ============================================================================
+// File Store
+// ============================================================================
+
+const SNAPSHOT_FILE: &str = "snapshot";
+const SNAPSHOT_TMP_FILE: &str = "snapshot.tmp";
+const JOURNAL_FILE: &str = "journal.log";
+
+/// 基于文件的作业存储
+///
+/// 每条记录追加到 `journal.log`；日志记录数达到阈值时把当前状态写成 `snapshot`
+/// （先写临时文件再改名）并清空日志。打开时先读快照再重放日志，
+/// 日志末尾因崩溃而写了一半的记录被丢弃。
+#[derive(Debug)]
+pub struct FileJobStore {
+    dir: PathBuf,
+    journal: File,
+    table: JobTable,
+    /// 日志中的记录数
+    journal_records: usize,
+    /// 自动压缩的阈值
+    compact_threshold: usize,
+    /// 每条记录后同步到磁盘
+    sync: bool,
+}
+
+impl FileJobStore {
+    /// 打开（或创建）目录下的存储并读入已有记录
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+
+        let mut table = JobTable::default();
+        let snapshot = dir.join(SNAPSHOT_FILE);
+        if snapshot.exists() {
+            for line in read_lines(&snapshot)? {
+                apply_line(&mut table, &line)?;
+            }
+        }
+
+        let journal_path = dir.join(JOURNAL_FILE);
+        let mut journal_records = 0;
+        let mut torn = false;
+        if journal_path.exists() {
+            let lines = read_lines(&journal_path)?;
+            let last = lines.len().saturating_sub(1);
+            for (i, line) in lines.iter().enumerate() {
+                match apply_line(&mut table, line) {
+                    Ok(()) => journal_records += 1,
+                    // 最后一行可能在写入时崩溃而不完整
+                    Err(_) if i == last => torn = true,
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        let journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&journal_path)
+            .map_err(|e| io_error(&journal_path, e))?;
+        let mut store = Self {
+            dir,
+            journal,
+            table,
+            journal_records,
+            compact_threshold: 1000,
+            sync: false,
+        };
+        if torn {
+            store.compact()?;
+        }
+        Ok(store)
+    }
+
+    /// 日志达到多少条记录时自动压缩（默认 1000，0 表示不自动压缩）
+    pub fn with_compaction_threshold(mut self, records: usize) -> Self {
+        self.compact_threshold = records;
+        self
+    }
+
+    /// 每条记录后是否 fsync（默认只刷新到操作系统）
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// 存储目录
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 日志中尚未压缩的记录数
+    pub fn journal_records(&self) -> usize {
+        self.journal_records
+    }
+
+    /// 把当前状态写成快照并清空日志
+    pub fn compact(&mut self) -> Result<()> {
+        let tmp = self.dir.join(SNAPSHOT_TMP_FILE);
+        let mut contents = String::new();
+        for stored in self.table.jobs.values() {
+            contents.push_str(&encode_job(&stored.job)?);
+            contents.push('\n');
+            if let Some(result) = &stored.result {
+                contents.push_str(&encode_result(stored.job.id, result));
+                contents.push('\n');
+            }
+        }
+        let mut file = File::create(&tmp).map_err(|e| io_error(&tmp, e))?;
+        file.write_all(contents.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| io_error(&tmp, e))?;
+
+        let snapshot = self.dir.join(SNAPSHOT_FILE);
+        fs::rename(&tmp, &snapshot).map_err(|e| io_error(&snapshot, e))?;
+        self.journal.set_len(0).map_err(|e| io_error(&self.dir.join(JOURNAL_FILE), e))?;
+        self.journal_records = 0;
+        Ok(())
+    }
+
+    /// 追加一条记录，必要时压缩
+    fn append(&mut self, line: String) -> Result<()> {
+        let path = self.dir.join(JOURNAL_FILE);
+        self.journal
+            .write_all(format!("{}\n", line).as_bytes())
+            .map_err(|e| io_error(&path, e))?;
+        if self.sync {
+            self.journal.sync_data().map_err(|e| io_error(&path, e))?;
+        }
+        self.journal_records += 1;
+        if self.compact_threshold > 0 && self.journal_records >= self.compact_threshold {
+            self.compact()?;
+        }
+        Ok(())
+    }
+}
+
+impl JobStore for FileJobStore {
+    fn record_submitted(&mut self, job: &Job) -> Result<()> {
+        let line = encode_job(job)?;
+        self.append(line)?;
+        self.table.submitted(job.clone());
+        Ok(())
+    }
+
+    fn record_status(&mut self, job_id: JobId, status: JobStatus) -> Result<()> {
+        self.table.status(job_id, status)?;
+        let mut enc = Encoder::new("status");
+        enc.u64(job_id);
+        enc.status(status);
+        self.append(enc.finish())
+    }
+
+    fn record_result(&mut self, job_id: JobId, result: &JobResult) -> Result<()> {
+        self.table.result(job_id, result.clone())?;
+        self.append(encode_result(job_id, result))
+    }
+
+    fn load(&self) -> Result<Vec<StoredJob>> {
+        Ok(self.table.snapshot())
+    }
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> IrError {
+    IrError::Storage(format!("{}: {}", path.display(), error))
+}
+
+fn read_lines(path: &Path) -> Result<Vec<String>> {
+    let file = File::open(path).map_err(|e| io_error(path, e))?;
+    BufReader::new(file)
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.is_empty()))
+        .collect::<std::io::Result<_>>()
+        .map_err(|e| io_error(path, e))
+}
+
+/// 解析一行记录并应用到表
+fn apply_line(table: &mut JobTable, line: &str) -> Result<()> {
+    let mut dec = Decoder::new(line);
+    match dec.token()? {
+        "job" => {
+            let job = decode_job(&mut dec)?;
+            dec.finish()?;
+            table.submitted(job);
+            Ok(())
+        }
+        "status" => {
+            let job_id = dec.u64()?;
+            let status = dec.status()?;
+            dec.finish()?;
+            table.status(job_id, status)
+        }
+        "result" => {
+            let job_id = dec.u64()?;
+            let result = decode_result(&mut dec)?;
+            dec.finish()?;
+            table.result(job_id, result)
+        }
+        other => Err(dec.error(&format!("unknown record {}", other))),
+    }
+}
+
+// ============================================================================
+// Record Encoding
+// ============================================================================
+
+/// 空格分隔的记录；字符串以 `'` 开头并转义空白与 `%`，可选值以 `-`/`+` 开头，列表先写长度
+struct Encoder {
+    out: String,
+}
+
+impl Encoder {
+    fn new(tag: &str) -> Self {
+        Self { out: tag.to_string() }
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+
+    fn token(&mut self, token: impl Display) {
+        let _ = write!(self.out, " {}", token);
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.token(value);
+    }
+
+    fn f64(&mut self, value: f64) {
+        // Display 输出最短的可精确往返的表示
+        self.token(value);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.token(u8::from(value));
+    }
+
+    fn str(&mut self, value: &str) {
+        self.out.push_str(" '");
+        for c in value.chars() {
+            match c {
+                '%' => self.out.push_str("%25"),
+                ' ' => self.out.push_str("%20"),
+                '\n' => self.out.push_str("%0A"),
+                '\r' => self.out.push_str("%0D"),
+                '\t' => self.out.push_str("%09"),
+                c => self.out.push(c),
+            }
+        }
+    }
+
+    fn opt<T>(&mut self, value: Option<T>, f: impl FnOnce(&mut Self, T)) {
+        match value {
+            Some(value) => {
+                self.token("+");
+                f(self, value);
+            }
+            None => self.token("-"),
+        }
+    }
+
+    fn list<I: IntoIterator>(&mut self, items: I, mut f: impl FnMut(&mut Self, I::Item))
+    where
+        I::IntoIter: ExactSizeIterator,
+    {
+        let items = items.into_iter();
+        self.u64(items.len() as u64);
+        for item in items {
+            f(self, item);
+        }
+    }
+
+    fn qubit(&mut self, qubit: LogicalQubitId) {
+        self.u64(qubit.value());
+    }
+
+    fn duration(&mut self, duration: Duration) {
+        self.u64(duration.as_secs());
+        self.u64(u64::from(duration.subsec_nanos()));
+    }
+
+    fn status(&mut self, status: JobStatus) {
+        self.token(format!("{:?}", status));
+    }
+
+    fn error_kind(&mut self, kind: ErrorKind) {
+        self.token(format!("{:?}", kind));
+    }
+
+    fn retry(&mut self, retry: &RetryPolicy) {
+        self.u64(u64::from(retry.max_attempts));
+        self.duration(retry.initial_backoff);
+        self.f64(retry.backoff_multiplier);
+        self.duration(retry.max_backoff);
+        self.list(&retry.retry_on, |enc, kind| enc.error_kind(*kind));
+        self.bool(retry.failover);
+    }
+
+    fn attempt(&mut self, attempt: &JobAttempt) {
+        self.u64(u64::from(attempt.attempt));
+        self.str(&attempt.backend);
+        self.status(attempt.status);
+        self.opt(attempt.error.as_deref(), Encoder::str);
+        self.opt(attempt.error_kind, Encoder::error_kind);
+        self.u64(attempt.duration_ms);
+    }
+
+    fn binding(&mut self, binding: &ParamBinding) {
+        let mut entries: Vec<_> = binding.iter().collect();
+        entries.sort_by(|a, b| a.0.name().cmp(b.0.name()));
+        self.list(entries, |enc, (param, value)| {
+            enc.str(param.name());
+            enc.f64(*value);
+        });
+    }
+
+    fn string_map(&mut self, map: &std::collections::HashMap<String, String>) {
+        let entries: BTreeMap<_, _> = map.iter().collect();
+        self.list(entries, |enc, (key, value)| {
+            enc.str(key);
+            enc.str(value);
+        });
+    }
+}
+
+/// 解析 `Encoder` 写出的记录
+struct Decoder<'a> {
+    line: &'a str,
+    tokens: std::str::Split<'a, char>,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(line: &'a str) -> Self {
+        Self { line, tokens: line.split(' ') }
+    }
+
+    fn error(&self, what: &str) -> IrError {
+        IrError::Storage(format!("Malformed record ({}): {}", what, self.line))
+    }
+
+    fn finish(mut self) -> Result<()> {
+        match self.tokens.next() {
+            None => Ok(()),
+            Some(_) => Err(self.error("trailing fields")),
+        }
+    }
+
+    fn token(&mut self) -> Result<&'a str> {
+        self.tokens.next().ok_or_else(|| self.error("unexpected end"))
+    }
+
+    fn parse<T: std::str::FromStr>(&mut self, what: &str) -> Result<T> {
+        let token = self.token()?;
+        token.parse().map_err(|_| self.error(&format!("expected {}, found {}", what, token)))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        self.parse("integer")
+    }
+
+    fn usize(&mut self) -> Result<usize> {
+        self.parse("integer")
+    }
+
+    fn f64(&mut self) -> Result<f64> {
+        self.parse("number")
+    }
+
+    fn bool(&mut self) -> Result<bool> {
+        match self.token()? {
+            "0" => Ok(false),
+            "1" => Ok(true),
+            other => Err(self.error(&format!("expected flag, found {}", other))),
+        }
+    }
+
+    fn str(&mut self) -> Result<String> {
+        let token = self.token()?;
+        let body = token.strip_prefix('\'').ok_or_else(|| self.error("expected string"))?;
+        let mut out = String::with_capacity(body.len());
+        let mut chars = body.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let code: String = chars.by_ref().take(2).collect();
+            let byte = u8::from_str_radix(&code, 16).map_err(|_| self.error("bad escape"))?;
+            out.push(char::from(byte));
+        }
+        Ok(out)
+    }
+
+    fn opt<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<Option<T>> {
+        match self.token()? {
+            "-" => Ok(None),
+            "+" => f(self).map(Some),
+            other => Err(self.error(&format!("expected option, found {}", other))),
+        }
+    }
+
+    fn list<T>(&mut self, mut f: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let len = self.usize()?;
+        (0..len).map(|_| f(self)).collect()
+    }
+
+    fn qubit(&mut self) -> Result<LogicalQubitId> {
+        self.u64().map(LogicalQubitId::new)
+    }
+
+    fn duration(&mut self) -> Result<Duration> {
+        let secs = self.u64()?;
+        let nanos = self.parse("integer")?;
+        Ok(Duration::new(secs, nanos))
+    }
+
+    fn status(&mut self) -> Result<JobStatus> {
+        use JobStatus::*;
+        match self.token()? {
+            "Pending" => Ok(Pending),
+            "Queued" => Ok(Queued),
+            "Ready" => Ok(Ready),
+            "Running" => Ok(Running),
+            "Completed" => Ok(Completed),
+            "Failed" => Ok(Failed),
+            "Cancelled" => Ok(Cancelled),
+            "Waiting" => Ok(Waiting),
+            other => Err(self.error(&format!("unknown status {}", other))),
+        }
+    }
+
+    fn error_kind(&mut self) -> Result<ErrorKind> {
+        use ErrorKind::*;
+        match self.token()? {
+            "QubitNotFound" => Ok(QubitNotFound),
+            "QubitAlreadyAllocated" => Ok(QubitAlreadyAllocated),
+            "InvalidOperation" => Ok(InvalidOperation),
+            "UnsupportedOperation" => Ok(UnsupportedOperation),
+            "BackendUnavailable" => Ok(BackendUnavailable),
+            "JobExecutionFailed" => Ok(JobExecutionFailed),
+            "CyclicDependency" => Ok(CyclicDependency),
+            "SchedulingConflict" => Ok(SchedulingConflict),
+            "Timeout" => Ok(Timeout),
+            "InvalidStateTransition" => Ok(InvalidStateTransition),
+            "QuotaExceeded" => Ok(QuotaExceeded),
+            "Storage" => Ok(Storage),
+            other => Err(self.error(&format!("unknown error kind {}", other))),
+        }
+    }
+
+    fn retry(&mut self) -> Result<RetryPolicy> {
+        Ok(RetryPolicy {
+            max_attempts: self.parse("attempts")?,
+            initial_backoff: self.duration()?,
+            backoff_multiplier: self.f64()?,
+            max_backoff: self.duration()?,
+            retry_on: self.list(Decoder::error_kind)?,
+            failover: self.bool()?,
+        })
+    }
+
+    fn attempt(&mut self) -> Result<JobAttempt> {
+        Ok(JobAttempt {
+            attempt: self.parse("attempt")?,
+            backend: self.str()?,
+            status: self.status()?,
+            error: self.opt(Decoder::str)?,
+            error_kind: self.opt(Decoder::error_kind)?,
+            duration_ms: self.u64()?,
+        })
+    }
+
+    fn binding(&mut self) -> Result<ParamBinding> {
+        let entries = self.list(|dec| Ok((Param::new(dec.str()?), dec.f64()?)))?;
+        Ok(entries.into_iter().collect())
+    }
+
+    fn string_map(&mut self) -> Result<std::collections::HashMap<String, String>> {
+        let entries = self.list(|dec| Ok((dec.str()?, dec.str()?)))?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+// ============================================================================
+// Jobs and Results
+// ============================================================================
+
+fn encode_job(job: &Job) -> Result<String> {
+    let mut enc = Encoder::new("job");
+    enc.u64(job.id);
+    enc.status(job.status);
+    enc.u64(u64::from(job.priority.to_u8()));
+    enc.u64(u64::from(job.shots));
+    enc.str(&job.target_backend);
+    enc.u64(job.created_at);
+    enc.opt(job.submitted_at, Encoder::u64);
+    enc.opt(job.started_at, Encoder::u64);
+    enc.opt(job.completed_at, Encoder::u64);
+
+    let metadata = &job.metadata;
+    enc.opt(metadata.user_id.as_deref(), Encoder::str);
+    enc.opt(metadata.project.as_deref(), Encoder::str);
+    enc.opt(metadata.experiment_name.as_deref(), Encoder::str);
+    enc.string_map(&metadata.custom);
+    enc.opt(metadata.timeout, Encoder::duration);
+
+    enc.list(&job.depends_on, |enc, id| enc.u64(*id));
+    enc.list(&job.allocated_qubits, |enc, q| enc.qubit(*q));
+    enc.opt(job.parameters.as_ref(), Encoder::binding);
+    enc.list(&job.sweep, Encoder::binding);
+    encode_circuit(&mut enc, &job.circuit)?;
+    enc.u64(job.batch_circuits.len() as u64);
+    for circuit in &job.batch_circuits {
+        encode_circuit(&mut enc, circuit)?;
+    }
+    enc.opt(job.deadline.map(instant_to_wall), Encoder::duration);
+    enc.opt(job.estimated_runtime, Encoder::duration);
+    enc.opt(job.retry.as_ref(), Encoder::retry);
+    Ok(enc.finish())
+}
+
+fn decode_job(dec: &mut Decoder) -> Result<Job> {
+    let id = dec.u64()?;
+    let status = dec.status()?;
+    let priority = Priority::from_u8(dec.parse("priority")?);
+    let shots = dec.parse("shots")?;
+    let target_backend = dec.str()?;
+    let created_at = dec.u64()?;
+    let submitted_at = dec.opt(Decoder::u64)?;
+    let started_at = dec.opt(Decoder::u64)?;
+    let completed_at = dec.opt(Decoder::u64)?;
+
+    let metadata = JobMetadata {
+        user_id: dec.opt(Decoder::str)?,
+        project: dec.opt(Decoder::str)?,
+        experiment_name: dec.opt(Decoder::str)?,
+        custom: dec.string_map()?,
+        timeout: dec.opt(Decoder::duration)?,
+    };
+
+    Ok(Job {
+        id,
+        status,
+        priority,
+        shots,
+        target_backend,
+        created_at,
+        submitted_at,
+        started_at,
+        completed_at,
+        metadata,
+        depends_on: dec.list(Decoder::u64)?,
+        allocated_qubits: dec.list(Decoder::qubit)?,
+        parameters: dec.opt(Decoder::binding)?,
+        sweep: dec.list(Decoder::binding)?,
+        circuit: decode_circuit(dec)?,
+        batch_circuits: dec.list(decode_circuit)?,
+        deadline: dec.opt(Decoder::duration)?.map(wall_to_instant),
+        estimated_runtime: dec.opt(Decoder::duration)?,
+        retry: dec.opt(Decoder::retry)?,
+    })
+}
+
+fn encode_result(job_id: JobId, result: &JobResult) -> String {
+    let mut enc = Encoder::new("result");
+    enc.u64(job_id);
+    encode_result_fields(&mut enc, result);
+    enc.finish()
+}
+
+fn encode_result_fields(enc: &mut Encoder, result: &JobResult) {
+    enc.u64(result.job_id);
+    enc.status(result.status);
+    let counts: BTreeMap<_, _> = result.counts.iter().collect();
+    enc.list(counts, |enc, (qubit, outcomes)| {
+        enc.qubit(*qubit);
+        enc.token(outcomes.iter().fold(String::from("x"), |mut s, b| {
+            let _ = write!(s, "{:02x}", b);
+            s
+        }));
+    });
+    let statistics: BTreeMap<_, _> = result.statistics.iter().collect();
+    enc.list(statistics, |enc, (qubit, p)| {
+        enc.qubit(*qubit);
+        enc.f64(*p);
+    });
+    enc.opt(result.execution_time_ms, Encoder::u64);
+    enc.opt(result.error.as_deref(), Encoder::str);
+    enc.opt(result.backend_data.as_deref(), Encoder::str);
+    enc.bool(result.timed_out);
+    enc.list(&result.points, encode_result_fields);
+    enc.list(&result.attempts, Encoder::attempt);
+}
+
+fn decode_result(dec: &mut Decoder) -> Result<JobResult> {
+    let job_id = dec.u64()?;
+    let status = dec.status()?;
+    let counts = dec.list(|dec| {
+        let qubit = dec.qubit()?;
+        let hex = dec.token()?;
+        let bytes = hex
+            .strip_prefix('x')
+            .filter(|h| h.len() % 2 == 0)
+            .and_then(|h| (0..h.len()).step_by(2).map(|i| u8::from_str_radix(&h[i..i + 2], 16).ok()).collect())
+            .ok_or_else(|| dec.error("bad outcome bytes"))?;
+        Ok((qubit, bytes))
+    })?;
+    let statistics = dec.list(|dec| Ok((dec.qubit()?, dec.f64()?)))?;
+    Ok(JobResult {
+        job_id,
+        status,
+        counts: counts.into_iter().collect(),
+        statistics: statistics.into_iter().collect(),
+        execution_time_ms: dec.opt(Decoder::u64)?,
+        error: dec.opt(Decoder::str)?,
+        backend_data: dec.opt(Decoder::str)?,
+        timed_out: dec.bool()?,
+        points: dec.list(decode_result)?,
+        attempts: dec.list(Decoder::attempt)?,
+    })
+}
+
+/// `Instant` 换算为自 Unix 纪元起的墙上时间
+fn instant_to_wall(instant: Instant) -> Duration {
+    let (now, wall) = (Instant::now(), SystemTime::now());
+    let at = if instant >= now { wall + (instant - now) } else { wall - (now - instant) };
+    at.duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+fn wall_to_instant(since_epoch: Duration) -> Instant {
+    let now = Instant::now();
+    match (UNIX_EPOCH + since_epoch).duration_since(SystemTime::now()) {
+        Ok(ahead) => now + ahead,
+        Err(behind) => now.checked_sub(behind.duration()).unwrap_or(now),
+    }
+}
+
+// ============================================================================
+// Circuits and Operations
+// ============================================================================
+
+fn encode_circuit(enc: &mut Encoder, circuit: &CircuitDag) -> Result<()> {
+    let inlined;
+    let circuit = if circuit.gate_library().is_empty() {
+        circuit
+    } else {
+        inlined = circuit.inlined()?;
+        &inlined
+    };
+    let metadata = circuit.metadata();
+    enc.opt(metadata.name.as_deref(), Encoder::str);
+    enc.opt(metadata.description.as_deref(), Encoder::str);
+    enc.list(&metadata.tags, |enc, tag| enc.str(tag));
+    enc.opt(metadata.created_at, Encoder::u64);
+    enc.list(circuit.inputs(), |enc, q| enc.qubit(*q));
+    enc.list(circuit.operations_in_order(), |enc, op| encode_op(enc, &op));
+    Ok(())
+}
+
+fn decode_circuit(dec: &mut Decoder) -> Result<CircuitDag> {
+    let metadata = CircuitMetadata {
+        name: dec.opt(Decoder::str)?,
+        description: dec.opt(Decoder::str)?,
+        tags: dec.list(Decoder::str)?,
+        created_at: dec.opt(Decoder::u64)?,
+    };
+    let inputs = dec.list(Decoder::qubit)?;
+    let mut builder = CircuitDagBuilder::new();
+    for op in dec.list(decode_op)? {
+        builder.add_op(op);
+    }
+    let mut circuit = builder.build().with_inputs(inputs);
+    circuit.set_metadata(metadata);
+    Ok(circuit)
+}
+
+fn encode_op(enc: &mut Encoder, op: &Operation) {
+    match op {
+        Operation::Gate1 { gate, target } => {
+            enc.token("g1");
+            enc.token(gate.name());
+            enc.list(gate.parameters(), Encoder::f64);
+            enc.qubit(*target);
+        }
+        Operation::Gate2 { gate, control, target } => {
+            enc.token("g2");
+            enc.token(gate.name());
+            enc.list(gate.parameters(), Encoder::f64);
+            enc.qubit(*control);
+            enc.qubit(*target);
+        }
+        Operation::Gate3 { gate, controls, target } => {
+            enc.token("g3");
+            enc.token(gate.name());
+            enc.qubit(controls[0]);
+            enc.qubit(controls[1]);
+            enc.qubit(*target);
+        }
+        Operation::Measure { qubit, classical_reg } => {
+            enc.token("measure");
+            enc.qubit(*qubit);
+            enc.opt(*classical_reg, |enc, reg| enc.u64(reg as u64));
+        }
+        Operation::Reset { qubit } => {
+            enc.token("reset");
+            enc.qubit(*qubit);
+        }
+        Operation::Barrier { qubits } => {
+            enc.token("barrier");
+            enc.list(qubits, |enc, q| enc.qubit(*q));
+        }
+        Operation::Delay { qubit, duration_ns } => {
+            enc.token("delay");
+            enc.qubit(*qubit);
+            enc.f64(*duration_ns);
+        }
+        Operation::Custom(custom) => {
+            enc.token("custom");
+            enc.str(&custom.name);
+            enc.list(&custom.qubits, |enc, q| enc.qubit(*q));
+            enc.list(&custom.params, |enc, p| enc.f64(*p));
+            enc.string_map(&custom.metadata);
+            enc.opt(custom.matrix.as_ref(), |enc, matrix| {
+                let dim = matrix.dim();
+                enc.u64(dim as u64);
+                for row in 0..dim {
+                    for col in 0..dim {
+                        let entry = matrix.get(row, col);
+                        enc.f64(entry.re);
+                        enc.f64(entry.im);
+                    }
+                }
+            });
+        }
+        Operation::Symbolic { op, params } => {
+            enc.token("symbolic");
+            encode_op(enc, op);
+            enc.list(params, encode_expr);
+        }
+    }
+}
+
+fn decode_op(dec: &mut Decoder) -> Result<Operation> {
+    match dec.token()? {
+        "g1" => {
+            let name = dec.token()?;
+            let params = dec.list(Decoder::f64)?;
+            let gate = single_qubit_gate(name)
+                .and_then(|g| g.with_parameters(&params))
+                .ok_or_else(|| dec.error(&format!("unknown gate {}", name)))?;
+            Ok(Operation::Gate1 { gate, target: dec.qubit()? })
+        }
+        "g2" => {
+            let name = dec.token()?;
+            let params = dec.list(Decoder::f64)?;
+            let gate = two_qubit_gate(name)
+                .and_then(|g| g.with_parameters(&params))
+                .ok_or_else(|| dec.error(&format!("unknown gate {}", name)))?;
+            Ok(Operation::Gate2 { gate, control: dec.qubit()?, target: dec.qubit()? })
+        }
+        "g3" => {
+            let gate = match dec.token()? {
+                "Toffoli" => ThreeQubitGate::Toffoli,
+                "Fredkin" => ThreeQubitGate::Fredkin,
+                "CCZ" => ThreeQubitGate::CCZ,
+                other => return Err(dec.error(&format!("unknown gate {}", other))),
+            };
+            Ok(Operation::Gate3 { gate, controls: [dec.qubit()?, dec.qubit()?], target: dec.qubit()? })
+        }
+        "measure" => Ok(Operation::Measure { qubit: dec.qubit()?, classical_reg: dec.opt(Decoder::usize)? }),
+        "reset" => Ok(Operation::Reset { qubit: dec.qubit()? }),
+        "barrier" => Ok(Operation::Barrier { qubits: dec.list(Decoder::qubit)? }),
+        "delay" => Ok(Operation::Delay { qubit: dec.qubit()?, duration_ns: dec.f64()? }),
+        "custom" => {
+            let mut custom = CustomOp::new(dec.str()?)
+                .with_qubits(dec.list(Decoder::qubit)?)
+                .with_params(dec.list(Decoder::f64)?);
+            custom.metadata = dec.string_map()?;
+            custom.matrix = dec.opt(|dec| {
+                let dim = dec.usize()?;
+                let mut matrix = Matrix::zeros(dim);
+                for row in 0..dim {
+                    for col in 0..dim {
+                        matrix.set(row, col, Complex::new(dec.f64()?, dec.f64()?));
+                    }
+                }
+                Ok(matrix)
+            })?;
+            Ok(Operation::Custom(custom))
+        }
+        "symbolic" => {
+            let op = Box::new(decode_op(dec)?);
+            Ok(Operation::Symbolic { op, params: dec.list(decode_expr)? })
+        }
+        other => Err(dec.error(&format!("unknown operation {}", other))),
+    }
+}
+
+/// 由门名得到参数占位为 0 的门
+fn single_qubit_gate(name: &str) -> Option<SingleQubitGate> {
+    use SingleQubitGate::*;
+    Some(match name {
+        "X" => X,
+        "Y" => Y,
+        "Z" => Z,
+        "H" => H,
+        "S" => S,
+        "T" => T,
+        "Sdg" => Sdg,
+        "Tdg" => Tdg,
+        "Rx" => Rx(0.0),
+        "Ry" => Ry(0.0),
+        "Rz" => Rz(0.0),
+        "P" => P(0.0),
+        "U" => U(0.0, 0.0, 0.0),
+        _ => return None,
+    })
+}
+
+fn two_qubit_gate(name: &str) -> Option<TwoQubitGate> {
+    use TwoQubitGate::*;
+    Some(match name {
+        "CNOT" => CNOT,
+        "CZ" => CZ,
+        "SWAP" => SWAP,
+        "CP" => CP(0.0),
+        "iSWAP" => ISWAP,
+        "√SWAP" => SqrtSWAP,
+        "MS" => MS(0.0),
+        _ => return None,
+    })
+}
+
+/// 参数表达式按前缀形式写出
+fn encode_expr(enc: &mut Encoder, expr: &ParamExpr) {
+    let binary = |enc: &mut Encoder, tag: &str, a: &ParamExpr, b: &ParamExpr| {
+        enc.token(tag);
+        encode_expr(enc, a);
+        encode_expr(enc, b);
+    };
+    match expr {
+        ParamExpr::Const(value) => {
+            enc.token("c");
+            enc.f64(*value);
+        }
+        ParamExpr::Param(param) => {
+            enc.token("p");
+            enc.str(param.name());
+        }
+        ParamExpr::Add(a, b) => binary(enc, "add", a, b),
+        ParamExpr::Sub(a, b) => binary(enc, "sub", a, b),
+        ParamExpr::Mul(a, b) => binary(enc, "mul", a, b),
+        ParamExpr::Div(a, b) => binary(enc, "div", a, b),
+        ParamExpr::Neg(a) => {
+            enc.token("neg");
+            encode_expr(enc, a);
+        }
+        ParamExpr::Sin(a) => {
+            enc.token("sin");
+            encode_expr(enc, a);
+        }
+        ParamExpr::Cos(a) => {
+            enc.token("cos");
+            encode_expr(enc, a);
+        }
+    }
+}
+
+fn decode_expr(dec: &mut Decoder) -> Result<ParamExpr> {
+    let operand = |dec: &mut Decoder| decode_expr(dec).map(Box::new);
+    Ok(match dec.token()? {
+        "c" => ParamExpr::Const(dec.f64()?),
+        "p" => ParamExpr::Param(Param::new(dec.str()?)),
+        "add" => ParamExpr::Add(operand(dec)?, operand(dec)?),
+        "sub" => ParamExpr::Sub(operand(dec)?, operand(dec)?),
+        "mul" => ParamExpr::Mul(operand(dec)?, operand(dec)?),
+        "div" => ParamExpr::Div(operand(dec)?, operand(dec)?),
+        "neg" => ParamExpr::Neg(operand(dec)?),
+        "sin" => ParamExpr::Sin(operand(dec)?),
+        "cos" => ParamExpr::Cos(operand(dec)?),
+        other => return Err(dec.error(&format!("unknown expression {}", other))),
+    })
+}
+
+// ============================================================================
+// Recovery
+// ============================================================================
+
+/// 恢复时如何处理重启前正在运行的作业
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecoveryMode {
+    /// 重新提交，从头执行
+    #[default]
+    Resubmit,
+    /// 以失败结束
+    MarkFailed,
+}
+
+/// `QuantumRuntime::recover` 的结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecoveryReport {
+    /// 重新排队的作业（重启前在排队或等待）
+    pub requeued: Vec<JobId>,
+    /// 重新提交的运行中作业
+    pub resubmitted: Vec<JobId>,
+    /// 恢复期间以失败结束的作业（中断的运行中作业或无法重新提交的作业）
+    pub failed: Vec<JobId>,
+    /// 恢复了结果的已结束作业
+    pub restored: Vec<JobId>,
+}
+
+impl RecoveryReport {
+    /// 恢复的作业总数
+    pub fn total(&self) -> usize {
+        self.requeued.len() + self.resubmitted.len() + self.failed.len() + self.restored.len()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::bell_state_dag;
+    use crate::composite::GateDefinition;
+    use crate::operation::{cnot, h, measure, rx};
+    use crate::params::rx_param;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("quantum-ir-store-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn sample_job() -> Job {
+        let mut builder = CircuitDagBuilder::with_name("sample circuit");
+        builder.add_op(h(LogicalQubitId::new(0)));
+        builder.add_op(rx(LogicalQubitId::new(1), 0.1 + 0.2));
+        builder.add_op(cnot(LogicalQubitId::new(0), LogicalQubitId::new(1)));
+        builder.add_op(rx_param(LogicalQubitId::new(0), ParamExpr::param("theta").sin() * ParamExpr::constant(2.0)));
+        builder.add_op(Operation::Custom(
+            CustomOp::new("phase flip")
+                .with_qubits(vec![LogicalQubitId::new(1)])
+                .with_params(vec![-1.5])
+                .with_metadata("note", "100% custom")
+                .with_matrix(Matrix::diagonal(&[Complex::ONE, Complex::new(0.0, -1.0)])),
+        ));
+        builder.add_op(measure(LogicalQubitId::new(0)));
+        let mut binding = ParamBinding::new();
+        binding.insert(Param::new("theta"), 0.25);
+        Job::new(builder.build(), 512, "mock backend")
+            .with_metadata(JobMetadata::new().with_user("alice").with_custom("tag", "a b"))
+            .with_timeout(Duration::from_millis(1500))
+            .with_deadline_in(Duration::from_secs(60))
+            .with_parameters(binding)
+            .with_dependency(7)
+            .with_retry(RetryPolicy::new(3).with_retry_on([ErrorKind::Timeout]).with_failover(true))
+    }
+
+    #[test]
+    fn test_job_round_trip() {
+        let job = sample_job();
+        let line = encode_job(&job).unwrap();
+        assert!(!line.contains('\n'));
+        let mut dec = Decoder::new(&line);
+        assert_eq!(dec.token().unwrap(), "job");
+        let decoded = decode_job(&mut dec).unwrap();
+        dec.finish().unwrap();
+
+        assert_eq!(decoded.id, job.id);
+        assert_eq!(decoded.target_backend, "mock backend");
+        assert_eq!(decoded.metadata.user_id.as_deref(), Some("alice"));
+        assert_eq!(decoded.metadata.custom["tag"], "a b");
+        assert_eq!(decoded.metadata.timeout, Some(Duration::from_millis(1500)));
+        assert_eq!(decoded.depends_on, vec![7]);
+        assert_eq!(decoded.parameters, job.parameters);
+        assert_eq!(decoded.retry, job.retry);
+        assert_eq!(decoded.circuit.metadata().name.as_deref(), Some("sample circuit"));
+        assert_eq!(decoded.circuit.operations_in_order(), job.circuit.operations_in_order());
+        let drift = instant_to_wall(decoded.deadline.unwrap()).abs_diff(instant_to_wall(job.deadline.unwrap()));
+        assert!(drift < Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_result_round_trip() {
+        let mut point = JobResult::success(1);
+        point.add_counts(LogicalQubitId::new(0), vec![0, 1, 1, 0]);
+        point.statistics.insert(LogicalQubitId::new(0), 0.5);
+        point.execution_time_ms = Some(12);
+        let mut result = JobResult::batch(1, vec![point, JobResult::timed_out(1, Duration::from_secs(2))]);
+        result.attempts.push(JobAttempt {
+            attempt: 1,
+            backend: "flaky backend".into(),
+            status: JobStatus::Failed,
+            error: Some("offline".into()),
+            error_kind: Some(ErrorKind::BackendUnavailable),
+            duration_ms: 3,
+        });
+
+        let line = encode_result(1, &result);
+        let mut dec = Decoder::new(&line);
+        assert_eq!(dec.token().unwrap(), "result");
+        assert_eq!(dec.u64().unwrap(), 1);
+        let decoded = decode_result(&mut dec).unwrap();
+        dec.finish().unwrap();
+
+        assert_eq!(decoded.status, JobStatus::Failed);
+        assert_eq!(decoded.error, result.error);
+        assert_eq!(decoded.points.len(), 2);
+        assert_eq!(decoded.points[0].get_counts(LogicalQubitId::new(0)), Some(&vec![0, 1, 1, 0]));
+        assert_eq!(decoded.points[0].statistics[&LogicalQubitId::new(0)], 0.5);
+        assert!(decoded.points[1].timed_out);
+        assert_eq!(decoded.attempts, result.attempts);
+    }
+
+    #[test]
+    fn test_composite_gates_are_inlined() {
+        let mut circuit = CircuitDag::new();
+        circuit.define_gate(GateDefinition::from_circuit("bell", &bell_state_dag()));
+        circuit
+            .add_composite("bell", vec![LogicalQubitId::new(0), LogicalQubitId::new(1)], vec![])
+            .unwrap();
+        let job = Job::new(circuit, 10, "sim");
+
+        let line = encode_job(&job).unwrap();
+        let mut dec = Decoder::new(&line);
+        dec.token().unwrap();
+        let decoded = decode_job(&mut dec).unwrap();
+        assert!(decoded.circuit.gate_library().is_empty());
+        assert_eq!(decoded.circuit.operations_in_order(), job.circuit.inlined().unwrap().operations_in_order());
+    }
+
+    #[test]
+    fn test_file_store_replay_and_compaction() {
+        let dir = temp_dir("replay");
+        let first = sample_job();
+        let second = Job::new(bell_state_dag(), 100, "sim");
+        {
+            let mut store = FileJobStore::open(&dir).unwrap().with_compaction_threshold(0);
+            store.record_submitted(&first).unwrap();
+            store.record_submitted(&second).unwrap();
+            store.record_status(first.id, JobStatus::Running).unwrap();
+            store.record_result(second.id, &JobResult::success(second.id)).unwrap();
+            assert_eq!(store.journal_records(), 4);
+        }
+
+        let mut store = FileJobStore::open(&dir).unwrap();
+        let jobs = store.load().unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].job.status, JobStatus::Running);
+        assert!(jobs[0].result.is_none());
+        assert_eq!(jobs[1].result.as_ref().unwrap().status, JobStatus::Completed);
+
+        store.compact().unwrap();
+        assert_eq!(store.journal_records(), 0);
+        store.record_result(first.id, &JobResult::failure(first.id, "boom")).unwrap();
+        drop(store);
+
+        let jobs = FileJobStore::open(&dir).unwrap().load().unwrap();
+        assert_eq!(jobs[0].job.status, JobStatus::Failed);
+        assert_eq!(jobs[0].result.as_ref().unwrap().error.as_deref(), Some("boom"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_store_torn_tail() {
+        let dir = temp_dir("torn");
+        let job = Job::new(bell_state_dag(), 100, "sim");
+        {
+            let mut store = FileJobStore::open(&dir).unwrap();
+            store.record_submitted(&job).unwrap();
+        }
+        let mut journal = OpenOptions::new().append(true).open(dir.join(JOURNAL_FILE)).unwrap();
+        journal.write_all(format!("result {} Compl", job.id).as_bytes()).unwrap();
+        drop(journal);
+
+        let store = FileJobStore::open(&dir).unwrap();
+        assert_eq!(store.journal_records(), 0);
+        let jobs = store.load().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].job.status, JobStatus::Pending);
+        assert!(jobs[0].result.is_none());
+
+        // 中间的损坏记录不能被静默跳过
+        fs::write(dir.join(JOURNAL_FILE), "bogus record\nstatus 1 Queued\n").unwrap();
+        assert!(matches!(FileJobStore::open(&dir), Err(IrError::Storage(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
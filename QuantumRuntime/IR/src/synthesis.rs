@@ -0,0 +1,601 @@
+//! Clifford+T 近似综合模块
+//!
+//! 容错目标只接受 Clifford+T 门集，本模块将任意角度的单比特旋转
+//! （Rx/Ry/Rz/P/U）改写为 H/S/T 序列，误差不超过给定 ε。
+//!
+//! 算法：Solovay-Kitaev
+//! - 基础网格：Matsumoto-Amano 范式 `(T|ε)(HT|SHT)* C` 枚举的全部元素
+//! - 递归：群交换子分解 `Δ ≈ V W V† W†`
+//! - 距离：算子范数距离（忽略全局相位）
+
+use std::sync::OnceLock;
+
+use crate::circuit::CircuitDag;
+use crate::linalg::{Complex, Matrix};
+use crate::operation::{Operation, SingleQubitGate};
+use crate::{Result, IrError};
+
+// ============================================================================
+// SU(2) 表示
+// ============================================================================
+
+/// SU(2) 元素的单位四元数表示
+///
+/// `U = w·I - i(x·X + y·Y + z·Z)`，q 与 -q 表示同一个门（全局相位）
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Quat {
+    w: f64,
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl Quat {
+    const IDENTITY: Quat = Quat { w: 1.0, x: 0.0, y: 0.0, z: 0.0 };
+
+    fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// 绕单位轴 (ax, ay, az) 旋转 theta
+    fn rotation(theta: f64, ax: f64, ay: f64, az: f64) -> Self {
+        let (s, c) = (theta / 2.0).sin_cos();
+        Self::new(c, s * ax, s * ay, s * az)
+    }
+
+    fn mul(&self, o: &Quat) -> Quat {
+        Quat::new(
+            self.w * o.w - self.x * o.x - self.y * o.y - self.z * o.z,
+            self.w * o.x + o.w * self.x + self.y * o.z - self.z * o.y,
+            self.w * o.y + o.w * self.y + self.z * o.x - self.x * o.z,
+            self.w * o.z + o.w * self.z + self.x * o.y - self.y * o.x,
+        )
+    }
+
+    fn conj(&self) -> Quat {
+        Quat::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    fn dot(&self, o: &Quat) -> f64 {
+        self.w * o.w + self.x * o.x + self.y * o.y + self.z * o.z
+    }
+
+    /// 忽略全局相位的算子范数距离
+    fn distance(&self, o: &Quat) -> f64 {
+        (2.0 - 2.0 * self.dot(o).abs().min(1.0)).max(0.0).sqrt()
+    }
+
+    /// 旋转角与旋转轴
+    fn angle_axis(&self) -> (f64, [f64; 3]) {
+        // 选择 w >= 0 的代表元，使角度落在 [0, π]
+        let q = if self.w < 0.0 {
+            Quat::new(-self.w, -self.x, -self.y, -self.z)
+        } else {
+            *self
+        };
+        let norm = (q.x * q.x + q.y * q.y + q.z * q.z).sqrt();
+        if norm < 1e-15 {
+            return (0.0, [0.0, 0.0, 1.0]);
+        }
+        let angle = 2.0 * norm.atan2(q.w);
+        (angle, [q.x / norm, q.y / norm, q.z / norm])
+    }
+
+    /// 把 from 轴转到 to 轴的旋转
+    fn rotation_between(from: [f64; 3], to: [f64; 3]) -> Quat {
+        let dot = from[0] * to[0] + from[1] * to[1] + from[2] * to[2];
+        let cross = [
+            from[1] * to[2] - from[2] * to[1],
+            from[2] * to[0] - from[0] * to[2],
+            from[0] * to[1] - from[1] * to[0],
+        ];
+        let norm = (cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]).sqrt();
+        if norm < 1e-12 {
+            if dot > 0.0 {
+                return Quat::IDENTITY;
+            }
+            // 反向：绕任一垂直轴转 π
+            let perp = if from[0].abs() < 0.9 { [1.0, 0.0, 0.0] } else { [0.0, 1.0, 0.0] };
+            let c = [
+                from[1] * perp[2] - from[2] * perp[1],
+                from[2] * perp[0] - from[0] * perp[2],
+                from[0] * perp[1] - from[1] * perp[0],
+            ];
+            let n = (c[0] * c[0] + c[1] * c[1] + c[2] * c[2]).sqrt();
+            return Quat::rotation(std::f64::consts::PI, c[0] / n, c[1] / n, c[2] / n);
+        }
+        let angle = norm.atan2(dot);
+        Quat::rotation(angle, cross[0] / norm, cross[1] / norm, cross[2] / norm)
+    }
+
+    /// 由 2×2 酉矩阵得到四元数（忽略全局相位）
+    ///
+    /// 去掉相位后 U = [[w - iz, -y - ix], [y - ix, w + iz]]
+    fn from_matrix(m: &Matrix) -> Quat {
+        let det = m.get(0, 0) * m.get(1, 1) - m.get(0, 1) * m.get(1, 0);
+        let su = m.scale(Complex::cis(-det.arg() / 2.0));
+        let (a, b) = (su.get(0, 0), su.get(0, 1));
+        Quat::new(a.re, -b.im, -b.re, -a.im)
+    }
+
+    /// 门对应的四元数，矩阵取自 SingleQubitGate::matrix
+    fn of_gate(gate: &SingleQubitGate) -> Quat {
+        Quat::from_matrix(&gate.matrix())
+    }
+
+    /// 按时间顺序作用的门序列对应的四元数
+    fn of_sequence(gates: &[SingleQubitGate]) -> Quat {
+        gates
+            .iter()
+            .fold(Quat::IDENTITY, |acc, g| Quat::of_gate(g).mul(&acc))
+    }
+
+    /// 去除符号歧义后量化，用于去重
+    fn key(&self) -> [i64; 4] {
+        let sign = [self.w, self.x, self.y, self.z]
+            .into_iter()
+            .find(|v| v.abs() > 1e-9)
+            .map(|v| v.signum())
+            .unwrap_or(1.0);
+        let quant = |v: f64| (v * sign * 1e8).round() as i64;
+        [quant(self.w), quant(self.x), quant(self.y), quant(self.z)]
+    }
+}
+
+// ============================================================================
+// Gate Sequence
+// ============================================================================
+
+/// 近似综合得到的 Clifford+T 门序列
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateSequence {
+    /// 按时间顺序排列的门
+    pub gates: Vec<SingleQubitGate>,
+    /// 与目标门的距离（算子范数，忽略全局相位）
+    pub error: f64,
+}
+
+impl GateSequence {
+    /// T/T† 门数量
+    pub fn t_count(&self) -> usize {
+        self.gates.iter().filter(|g| is_t_gate(g)).count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.gates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.gates.is_empty()
+    }
+}
+
+/// 是否是 T 或 T†
+pub fn is_t_gate(gate: &SingleQubitGate) -> bool {
+    matches!(gate, SingleQubitGate::T | SingleQubitGate::Tdg)
+}
+
+/// 是否属于 Clifford+T 门集
+pub fn is_clifford_t(gate: &SingleQubitGate) -> bool {
+    !gate.is_parametric()
+}
+
+/// 统计电路中的 T-count
+pub fn circuit_t_count(circuit: &CircuitDag) -> usize {
+    circuit
+        .nodes()
+        .iter()
+        .filter(|n| matches!(&n.op, Operation::Gate1 { gate, .. } if is_t_gate(gate)))
+        .count()
+}
+
+/// 门的逆
+fn adjoint_gate(gate: SingleQubitGate) -> SingleQubitGate {
+    match gate {
+        SingleQubitGate::S => SingleQubitGate::Sdg,
+        SingleQubitGate::Sdg => SingleQubitGate::S,
+        SingleQubitGate::T => SingleQubitGate::Tdg,
+        SingleQubitGate::Tdg => SingleQubitGate::T,
+        other => other,
+    }
+}
+
+/// 门序列的逆（时间顺序反转并逐个取逆）
+fn adjoint_sequence(gates: &[SingleQubitGate]) -> Vec<SingleQubitGate> {
+    gates.iter().rev().map(|&g| adjoint_gate(g)).collect()
+}
+
+/// 两个相邻门的精确合并，None 表示无法合并，Some(None) 表示互相抵消
+fn merge_pair(a: SingleQubitGate, b: SingleQubitGate) -> Option<Option<SingleQubitGate>> {
+    use SingleQubitGate::*;
+    match (a, b) {
+        (H, H) | (X, X) | (Y, Y) | (Z, Z) => Some(None),
+        (S, Sdg) | (Sdg, S) | (T, Tdg) | (Tdg, T) => Some(None),
+        (T, T) => Some(Some(S)),
+        (Tdg, Tdg) => Some(Some(Sdg)),
+        (S, S) | (Sdg, Sdg) => Some(Some(Z)),
+        (T, Sdg) | (Sdg, T) => Some(Some(Tdg)),
+        (Tdg, S) | (S, Tdg) => Some(Some(T)),
+        (S, Z) | (Z, S) => Some(Some(Sdg)),
+        (Sdg, Z) | (Z, Sdg) => Some(Some(S)),
+        _ => None,
+    }
+}
+
+/// 窥孔化简：反复合并相邻门
+fn simplify(gates: Vec<SingleQubitGate>) -> Vec<SingleQubitGate> {
+    let mut out: Vec<SingleQubitGate> = Vec::with_capacity(gates.len());
+    for gate in gates {
+        let mut current = Some(gate);
+        while let (Some(g), Some(&top)) = (current, out.last()) {
+            match merge_pair(top, g) {
+                Some(merged) => {
+                    out.pop();
+                    current = merged;
+                }
+                None => break,
+            }
+        }
+        if let Some(g) = current {
+            out.push(g);
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Base Net
+// ============================================================================
+
+/// 基础网格中的一个元素
+#[derive(Debug, Clone)]
+struct NetEntry {
+    quat: Quat,
+    gates: Vec<SingleQubitGate>,
+}
+
+/// 基础网格最大 T-count
+const BASE_NET_T_COUNT: usize = 10;
+
+/// 基础网格（首次使用时构建）
+fn base_net() -> &'static [NetEntry] {
+    static NET: OnceLock<Vec<NetEntry>> = OnceLock::new();
+    NET.get_or_init(|| build_base_net(BASE_NET_T_COUNT))
+}
+
+/// 24 个单比特 Clifford（H/S 最短字）
+fn clifford_group() -> Vec<NetEntry> {
+    let mut seen = std::collections::HashSet::new();
+    let mut group = vec![NetEntry { quat: Quat::IDENTITY, gates: Vec::new() }];
+    seen.insert(Quat::IDENTITY.key());
+
+    let mut frontier = 0;
+    while frontier < group.len() {
+        let entry = group[frontier].clone();
+        frontier += 1;
+        for gate in [SingleQubitGate::H, SingleQubitGate::S] {
+            let quat = Quat::of_gate(&gate).mul(&entry.quat);
+            if seen.insert(quat.key()) {
+                let mut gates = entry.gates.clone();
+                gates.push(gate);
+                group.push(NetEntry { quat, gates });
+            }
+        }
+    }
+    group
+}
+
+/// 按 Matsumoto-Amano 范式枚举 T-count 不超过 max_t 的全部元素
+fn build_base_net(max_t: usize) -> Vec<NetEntry> {
+    use SingleQubitGate::{H, S, T};
+
+    let cliffords = clifford_group();
+
+    // 矩阵乘积 (T|ε)(HT|SHT)^k，按时间顺序存储（最右侧的因子最先作用）
+    let mut prefixes: Vec<Vec<SingleQubitGate>> = vec![Vec::new()];
+    let mut layer: Vec<Vec<SingleQubitGate>> = vec![Vec::new()];
+    for _ in 0..max_t {
+        let mut next = Vec::with_capacity(layer.len() * 2);
+        for word in &layer {
+            // 新音节在矩阵乘积中位于左侧，即时间上最后作用
+            let mut ht = word.clone();
+            ht.extend([T, H]);
+            let mut sht = word.clone();
+            sht.extend([T, H, S]);
+            next.push(ht);
+            next.push(sht);
+        }
+        prefixes.extend(next.iter().cloned());
+        layer = next;
+    }
+
+    let mut net = Vec::new();
+    for syllables in &prefixes {
+        let t_count = syllables.iter().filter(|g| is_t_gate(g)).count();
+        for leading_t in [false, true] {
+            if leading_t && t_count >= max_t {
+                continue;
+            }
+            let mut word = syllables.clone();
+            if leading_t {
+                word.push(T);
+            }
+            for clifford in &cliffords {
+                let mut gates = clifford.gates.clone();
+                gates.extend(word.iter().copied());
+                let gates = simplify(gates);
+                let quat = Quat::of_sequence(&gates);
+                net.push(NetEntry { quat, gates });
+            }
+        }
+    }
+    net
+}
+
+/// 在基础网格中查找最近元素
+fn nearest_in_net(target: &Quat) -> &'static NetEntry {
+    base_net()
+        .iter()
+        .min_by(|a, b| {
+            a.quat
+                .distance(target)
+                .partial_cmp(&b.quat.distance(target))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .expect("base net is never empty")
+}
+
+// ============================================================================
+// Solovay-Kitaev
+// ============================================================================
+
+/// 平衡群交换子分解：给定接近单位元的 Δ，求 V、W 使 V W V† W† = Δ
+fn group_commutator_decompose(delta: &Quat) -> (Quat, Quat) {
+    let (theta, axis) = delta.angle_axis();
+
+    // sin(θ/2) = 2 sin²(φ/2) sqrt(1 - sin⁴(φ/2))
+    let lhs = (theta / 2.0).sin();
+    let s2 = ((1.0 - (1.0 - lhs * lhs).max(0.0).sqrt()) / 2.0).sqrt();
+    let phi = 2.0 * s2.sqrt().min(1.0).asin();
+
+    let v = Quat::rotation(phi, 1.0, 0.0, 0.0);
+    let w = Quat::rotation(phi, 0.0, 1.0, 0.0);
+    let commutator = v.mul(&w).mul(&v.conj()).mul(&w.conj());
+    let (_, commutator_axis) = commutator.angle_axis();
+
+    let similarity = Quat::rotation_between(commutator_axis, axis);
+    let v = similarity.mul(&v).mul(&similarity.conj());
+    let w = similarity.mul(&w).mul(&similarity.conj());
+    (v, w)
+}
+
+/// Solovay-Kitaev 递归，返回 (近似四元数, 时间顺序门序列)
+fn solovay_kitaev(target: &Quat, depth: usize) -> (Quat, Vec<SingleQubitGate>) {
+    if depth == 0 {
+        let entry = nearest_in_net(target);
+        return (entry.quat, entry.gates.clone());
+    }
+
+    let (approx, gates) = solovay_kitaev(target, depth - 1);
+    let delta = target.mul(&approx.conj());
+    let (v, w) = group_commutator_decompose(&delta);
+    let (v_approx, v_gates) = solovay_kitaev(&v, depth - 1);
+    let (w_approx, w_gates) = solovay_kitaev(&w, depth - 1);
+
+    // 矩阵：V W V† W† Ũ，时间顺序：Ũ, W†, V†, W, V
+    let quat = v_approx
+        .mul(&w_approx)
+        .mul(&v_approx.conj())
+        .mul(&w_approx.conj())
+        .mul(&approx);
+
+    let mut sequence = gates;
+    sequence.extend(adjoint_sequence(&w_gates));
+    sequence.extend(adjoint_sequence(&v_gates));
+    sequence.extend(w_gates);
+    sequence.extend(v_gates);
+    (quat, simplify(sequence))
+}
+
+// ============================================================================
+// Synthesizer
+// ============================================================================
+
+/// 综合报告
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SynthesisReport {
+    /// 被替换的旋转门数量
+    pub rotations_replaced: usize,
+    /// 输出电路的 T-count
+    pub t_count: usize,
+    /// 被替换旋转门中的最大近似误差
+    pub max_error: f64,
+    /// 输出电路的门数量
+    pub total_gates: usize,
+}
+
+/// Clifford+T 近似综合器
+#[derive(Debug, Clone)]
+pub struct CliffordTSynthesizer {
+    /// 每个旋转门允许的最大误差
+    epsilon: f64,
+    /// Solovay-Kitaev 最大递归深度
+    max_depth: usize,
+}
+
+impl CliffordTSynthesizer {
+    pub fn new(epsilon: f64) -> Self {
+        Self {
+            epsilon,
+            max_depth: 4,
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// 近似单个单比特门
+    ///
+    /// 逐层加深递归，直到误差不超过 ε；达到最大深度仍不满足则返回错误
+    pub fn approximate(&self, gate: &SingleQubitGate) -> Result<GateSequence> {
+        if self.epsilon.is_nan() || self.epsilon <= 0.0 {
+            return Err(IrError::InvalidOperation(format!(
+                "Synthesis epsilon must be positive, got {}", self.epsilon
+            )));
+        }
+
+        let target = Quat::of_gate(gate);
+        let mut best: Option<GateSequence> = None;
+
+        for depth in 0..=self.max_depth {
+            let (approx, gates) = solovay_kitaev(&target, depth);
+            let error = approx.distance(&target);
+            let candidate = GateSequence { gates, error };
+
+            let improved = best.as_ref().is_none_or(|b| candidate.error < b.error);
+            if improved {
+                best = Some(candidate);
+            }
+            if let Some(b) = &best {
+                if b.error <= self.epsilon {
+                    return Ok(b.clone());
+                }
+            }
+        }
+
+        let achieved = best.map(|b| b.error).unwrap_or(f64::INFINITY);
+        Err(IrError::UnsupportedOperation(format!(
+            "{} cannot be approximated within ε={} (best {:.3e} at depth {})",
+            gate.name(), self.epsilon, achieved, self.max_depth
+        )))
+    }
+
+    /// 将电路中的连续旋转门改写为 Clifford+T 序列
+    pub fn run(&self, circuit: &CircuitDag) -> Result<(CircuitDag, SynthesisReport)> {
+        let mut report = SynthesisReport::default();
+        let mut ops = Vec::with_capacity(circuit.num_nodes());
+
+        for op in circuit.operations_in_order() {
+            match op {
+                Operation::Gate1 { gate, target } if !is_clifford_t(&gate) => {
+                    let sequence = self.approximate(&gate)?;
+                    report.rotations_replaced += 1;
+                    report.max_error = report.max_error.max(sequence.error);
+                    ops.extend(
+                        sequence.gates.into_iter().map(|g| Operation::gate1(g, target)),
+                    );
+                }
+                other => ops.push(other),
+            }
+        }
+
+        let output = circuit.rebuild_with(ops);
+        report.t_count = circuit_t_count(&output);
+        report.total_gates = output.num_operations();
+        Ok((output, report))
+    }
+}
+
+impl Default for CliffordTSynthesizer {
+    fn default() -> Self {
+        Self::new(1e-2)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qubit::LogicalQubitId;
+    use crate::operation::{h, rz, rx, cnot, measure};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_exact_clifford_t_angle() {
+        let synth = CliffordTSynthesizer::new(1e-9);
+        let seq = synth.approximate(&SingleQubitGate::Rz(PI / 4.0)).unwrap();
+        assert!(seq.error < 1e-9);
+        assert_eq!(seq.t_count(), 1);
+    }
+
+    #[test]
+    fn test_arbitrary_rotation_within_epsilon() {
+        let synth = CliffordTSynthesizer::new(1e-2);
+        for gate in [
+            SingleQubitGate::Rz(0.1234),
+            SingleQubitGate::Rx(1.1),
+            SingleQubitGate::Ry(-2.5),
+            SingleQubitGate::P(0.7),
+            SingleQubitGate::U(0.3, 1.2, -0.4),
+        ] {
+            let seq = synth.approximate(&gate).unwrap();
+            assert!(seq.error <= 1e-2, "{:?}: {}", gate, seq.error);
+            assert!(seq.gates.iter().all(is_clifford_t));
+            // 独立验证：重新计算序列的四元数
+            let actual = Quat::of_sequence(&seq.gates).distance(&Quat::of_gate(&gate));
+            assert!((actual - seq.error).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_tighter_epsilon_uses_more_t_gates() {
+        let gate = SingleQubitGate::Rz(0.3);
+        let coarse = CliffordTSynthesizer::new(1e-1).approximate(&gate).unwrap();
+        let fine = CliffordTSynthesizer::new(3e-3).approximate(&gate).unwrap();
+        assert!(fine.error <= 3e-3);
+        assert!(fine.t_count() >= coarse.t_count());
+    }
+
+    #[test]
+    fn test_invalid_epsilon() {
+        let synth = CliffordTSynthesizer::new(0.0);
+        assert!(synth.approximate(&SingleQubitGate::Rz(0.5)).is_err());
+    }
+
+    #[test]
+    fn test_circuit_rewrite_reports_t_count() {
+        let q0 = LogicalQubitId::new(0);
+        let q1 = LogicalQubitId::new(1);
+        let mut dag = CircuitDag::with_name("rotations");
+        dag.add_node(h(q0));
+        dag.add_node(rz(q0, 0.42));
+        dag.add_node(cnot(q0, q1));
+        dag.add_node(rx(q1, PI / 2.0));
+        dag.add_node(measure(q0));
+
+        let (out, report) = CliffordTSynthesizer::new(1e-2).run(&dag).unwrap();
+
+        assert_eq!(report.rotations_replaced, 2);
+        assert_eq!(report.t_count, circuit_t_count(&out));
+        assert!(report.max_error <= 1e-2);
+        assert_eq!(out.metadata().name.as_deref(), Some("rotations"));
+        assert!(out.nodes().iter().all(|n| match &n.op {
+            Operation::Gate1 { gate, .. } => is_clifford_t(gate),
+            _ => true,
+        }));
+        // 测量仍然是最后一个作用在 q0 上的操作
+        let order = out.topological_sort();
+        let last_q0 = order
+            .iter()
+            .rev()
+            .find(|&&id| out.nodes()[id].qubits.contains(&q0))
+            .unwrap();
+        assert!(out.nodes()[*last_q0].op.is_measurement());
+    }
+
+    #[test]
+    fn test_simplify_cancels_pairs() {
+        use SingleQubitGate::*;
+        assert_eq!(simplify(vec![T, T, S, H, H]), vec![Z]);
+        assert_eq!(simplify(vec![T, Tdg, S, Sdg]), Vec::<SingleQubitGate>::new());
+    }
+}
@@ -0,0 +1,339 @@
+//! 时序调度模块
+//!
+//! `compute_parallel_groups` 只按层分组，不考虑时间。本模块按后端门时长
+//! 为每个 `OperationNode` 分配开始时间（ASAP / ALAP），并导出每个 qubit
+//! 的空闲窗口，供动态解耦、噪声模拟和退相干代价估算使用。
+
+use std::collections::BTreeMap;
+
+use crate::backend::GateDurations;
+use crate::circuit::CircuitDag;
+use crate::qubit::LogicalQubitId;
+
+// ============================================================================
+// Schedule Types
+// ============================================================================
+
+/// 调度模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScheduleMode {
+    /// 尽早开始
+    #[default]
+    Asap,
+    /// 尽晚开始（总时长不变）
+    Alap,
+}
+
+/// 已调度的操作
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledOp {
+    /// 对应的 DAG 节点 ID
+    pub node_id: usize,
+    /// 操作名称
+    pub name: String,
+    /// 涉及的 qubit
+    pub qubits: Vec<LogicalQubitId>,
+    /// 开始时间（纳秒）
+    pub start_ns: f64,
+    /// 持续时间（纳秒）
+    pub duration_ns: f64,
+    /// 是否是显式延时
+    pub is_delay: bool,
+}
+
+impl ScheduledOp {
+    pub fn end_ns(&self) -> f64 {
+        self.start_ns + self.duration_ns
+    }
+}
+
+/// qubit 的空闲窗口
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdleWindow {
+    pub qubit: LogicalQubitId,
+    /// 窗口开始时间
+    pub start_ns: f64,
+    /// 窗口结束时间
+    pub end_ns: f64,
+    /// 窗口前的操作节点
+    pub after_node: usize,
+    /// 窗口后的操作节点
+    pub before_node: usize,
+}
+
+impl IdleWindow {
+    pub fn duration_ns(&self) -> f64 {
+        self.end_ns - self.start_ns
+    }
+}
+
+/// 时序调度结果
+#[derive(Debug, Clone)]
+pub struct TimingSchedule {
+    pub mode: ScheduleMode,
+    /// 按节点 ID 索引的调度信息
+    ops: Vec<ScheduledOp>,
+    /// 电路总时长（纳秒）
+    pub total_duration_ns: f64,
+}
+
+/// 判断时间相等的容差（纳秒）
+const TIME_EPS: f64 = 1e-9;
+
+impl TimingSchedule {
+    /// 按给定门时长调度电路
+    pub fn compute(circuit: &CircuitDag, durations: &GateDurations, mode: ScheduleMode) -> Self {
+        let n = circuit.num_nodes();
+        let order = circuit.topological_sort();
+        let nodes = circuit.nodes();
+        let duration: Vec<f64> = nodes.iter().map(|node| durations.duration(&node.op)).collect();
+
+        // ASAP：依赖与 qubit 占用共同约束
+        let mut start = vec![0.0f64; n];
+        let mut qubit_free: BTreeMap<LogicalQubitId, f64> = BTreeMap::new();
+        for &id in &order {
+            let node = &nodes[id];
+            let dep_ready = node.depends_on
+                .iter()
+                .map(|&pred| start[pred] + duration[pred])
+                .fold(0.0, f64::max);
+            let qubit_ready = node.qubits
+                .iter()
+                .map(|q| qubit_free.get(q).copied().unwrap_or(0.0))
+                .fold(0.0, f64::max);
+            start[id] = dep_ready.max(qubit_ready);
+            for &q in &node.qubits {
+                qubit_free.insert(q, start[id] + duration[id]);
+            }
+        }
+        let total = (0..n).map(|id| start[id] + duration[id]).fold(0.0, f64::max);
+
+        if mode == ScheduleMode::Alap {
+            // 反向传播：后继节点与 qubit 的下一次占用共同约束最晚结束时间
+            let mut successors = vec![Vec::new(); n];
+            for node in nodes {
+                for &pred in &node.depends_on {
+                    successors[pred].push(node.id);
+                }
+            }
+            let mut qubit_busy_from: BTreeMap<LogicalQubitId, f64> = BTreeMap::new();
+            for &id in order.iter().rev() {
+                let node = &nodes[id];
+                let succ_limit = successors[id]
+                    .iter()
+                    .map(|&succ| start[succ])
+                    .fold(total, f64::min);
+                let qubit_limit = node.qubits
+                    .iter()
+                    .map(|q| qubit_busy_from.get(q).copied().unwrap_or(total))
+                    .fold(total, f64::min);
+                start[id] = succ_limit.min(qubit_limit) - duration[id];
+                for &q in &node.qubits {
+                    qubit_busy_from.insert(q, start[id]);
+                }
+            }
+        }
+
+        let ops = nodes
+            .iter()
+            .map(|node| ScheduledOp {
+                node_id: node.id,
+                name: node.op.name().to_string(),
+                qubits: node.qubits.clone(),
+                start_ns: start[node.id],
+                duration_ns: duration[node.id],
+                is_delay: node.op.is_delay(),
+            })
+            .collect();
+
+        Self {
+            mode,
+            ops,
+            total_duration_ns: total,
+        }
+    }
+
+    /// 节点的调度信息
+    pub fn get(&self, node_id: usize) -> Option<&ScheduledOp> {
+        self.ops.get(node_id)
+    }
+
+    /// 节点开始时间
+    pub fn start_time(&self, node_id: usize) -> Option<f64> {
+        self.ops.get(node_id).map(|op| op.start_ns)
+    }
+
+    /// 所有调度信息（按节点 ID）
+    pub fn ops(&self) -> &[ScheduledOp] {
+        &self.ops
+    }
+
+    /// 按开始时间排序的时间线
+    pub fn timeline(&self) -> Vec<&ScheduledOp> {
+        let mut ops: Vec<&ScheduledOp> = self.ops.iter().collect();
+        ops.sort_by(|a, b| {
+            a.start_ns
+                .partial_cmp(&b.start_ns)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.node_id.cmp(&b.node_id))
+        });
+        ops
+    }
+
+    /// 某个 qubit 上按时间排序的操作
+    pub fn qubit_timeline(&self, qubit: LogicalQubitId) -> Vec<&ScheduledOp> {
+        self.timeline()
+            .into_iter()
+            .filter(|op| op.qubits.contains(&qubit))
+            .collect()
+    }
+
+    /// 所有 qubit 的空闲窗口
+    ///
+    /// 只统计同一 qubit 上两个操作之间的空隙；显式 `Delay` 视为空闲。
+    /// 第一个操作之前和最后一个操作之后的时间不计入。
+    pub fn idle_windows(&self) -> Vec<IdleWindow> {
+        let mut qubits: Vec<LogicalQubitId> =
+            self.ops.iter().flat_map(|op| op.qubits.iter().copied()).collect();
+        qubits.sort();
+        qubits.dedup();
+
+        let mut windows = Vec::new();
+        for qubit in qubits {
+            let busy: Vec<&ScheduledOp> = self
+                .qubit_timeline(qubit)
+                .into_iter()
+                .filter(|op| !op.is_delay)
+                .collect();
+            for pair in busy.windows(2) {
+                let (prev, next) = (pair[0], pair[1]);
+                if next.start_ns - prev.end_ns() > TIME_EPS {
+                    windows.push(IdleWindow {
+                        qubit,
+                        start_ns: prev.end_ns(),
+                        end_ns: next.start_ns,
+                        after_node: prev.node_id,
+                        before_node: next.node_id,
+                    });
+                }
+            }
+        }
+        windows
+    }
+
+    /// 所有 qubit 的空闲总时长
+    pub fn total_idle_ns(&self) -> f64 {
+        self.idle_windows().iter().map(|w| w.duration_ns()).sum()
+    }
+
+    /// 按 qubit 渲染文本时间线
+    pub fn render(&self) -> String {
+        let mut lines = BTreeMap::new();
+        for op in self.timeline() {
+            for &q in &op.qubits {
+                lines
+                    .entry(q)
+                    .or_insert_with(Vec::new)
+                    .push(format!("{}[{:.0}-{:.0}]", op.name, op.start_ns, op.end_ns()));
+            }
+        }
+        let mut out = format!("{:?} schedule, total {:.0} ns\n", self.mode, self.total_duration_ns);
+        for (q, entries) in lines {
+            out.push_str(&format!("{}: {}\n", q, entries.join(" ")));
+        }
+        out
+    }
+}
+
+impl CircuitDag {
+    /// 按门时长进行时序调度
+    pub fn schedule_timing(&self, durations: &GateDurations, mode: ScheduleMode) -> TimingSchedule {
+        TimingSchedule::compute(self, durations, mode)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::CircuitDagBuilder;
+    use crate::operation::{cnot, delay, h, measure, x};
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    fn durations() -> GateDurations {
+        GateDurations::new(10.0, 100.0, 300.0, 500.0, 500.0)
+    }
+
+    /// q0: H - X - X - CNOT，q1: H ---------- CNOT - M
+    fn skewed_circuit() -> CircuitDag {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(0)));
+        builder.add_op(h(q(1)));
+        builder.add_op(x(q(0)));
+        builder.add_op(x(q(0)));
+        builder.add_op(cnot(q(0), q(1)));
+        builder.add_op(measure(q(1)));
+        builder.build()
+    }
+
+    #[test]
+    fn test_asap_schedule() {
+        let schedule = skewed_circuit().schedule_timing(&durations(), ScheduleMode::Asap);
+        assert_eq!(schedule.start_time(1), Some(0.0));
+        assert_eq!(schedule.start_time(3), Some(20.0));
+        assert_eq!(schedule.start_time(4), Some(30.0));
+        assert_eq!(schedule.start_time(5), Some(130.0));
+        assert_eq!(schedule.total_duration_ns, 630.0);
+    }
+
+    #[test]
+    fn test_alap_schedule() {
+        let schedule = skewed_circuit().schedule_timing(&durations(), ScheduleMode::Alap);
+        // q1 上的 H 被推迟到 CNOT 之前
+        assert_eq!(schedule.start_time(1), Some(20.0));
+        assert_eq!(schedule.start_time(0), Some(0.0));
+        assert_eq!(schedule.total_duration_ns, 630.0);
+    }
+
+    #[test]
+    fn test_idle_windows() {
+        let circuit = skewed_circuit();
+        let asap = circuit.schedule_timing(&durations(), ScheduleMode::Asap);
+        let windows = asap.idle_windows();
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].qubit, q(1));
+        assert_eq!(windows[0].duration_ns(), 20.0);
+        assert_eq!((windows[0].after_node, windows[0].before_node), (1, 4));
+
+        let alap = circuit.schedule_timing(&durations(), ScheduleMode::Alap);
+        assert!(alap.idle_windows().is_empty());
+    }
+
+    #[test]
+    fn test_delay_is_idle_time() {
+        let mut builder = CircuitDagBuilder::new();
+        builder.add_op(h(q(0)));
+        builder.add_op(delay(q(0), 250.0));
+        builder.add_op(h(q(0)));
+        let schedule = builder.build().schedule_timing(&durations(), ScheduleMode::Asap);
+
+        assert_eq!(schedule.start_time(2), Some(260.0));
+        let windows = schedule.idle_windows();
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].duration_ns(), 250.0);
+    }
+
+    #[test]
+    fn test_render_timeline() {
+        let schedule = skewed_circuit().schedule_timing(&durations(), ScheduleMode::Asap);
+        let text = schedule.render();
+        assert!(text.contains("LQ#0: H[0-10] X[10-20] X[20-30] CNOT[30-130]"));
+        assert!(text.contains("LQ#1: H[0-10] CNOT[30-130] M[130-630]"));
+    }
+}
@@ -0,0 +1,481 @@
+//! 变分算法模块
+//!
+//! VQE 与 QAOA 的混合循环：经典优化器提出参数，运行时在后端上估计能量，
+//! 梯度类优化器所需的梯度由参数移位电路（合为一个批量作业）给出。
+//!
+//! ansatz 的参数顺序为 `CircuitDag::parameters()`（按名字排序），
+//! 初始点与结果中的参数值都按此顺序排列。
+
+use crate::circuit::CircuitDag;
+use crate::estimator::Estimator;
+use crate::gradient::ParameterShift;
+use crate::operation::{cnot, h, Operation};
+use crate::optimizer::{NelderMead, Objective, OptimizationResult, Optimizer};
+use crate::params::{rx_param, rz_param, Param, ParamBinding, ParamExpr};
+use crate::pauli::{Pauli, PauliString, PauliSum};
+use crate::primitives::QuasiDistribution;
+use crate::qubit::LogicalQubitId;
+use crate::runtime::QuantumRuntime;
+use crate::{Result, IrError};
+
+/// 未指定初始点时各参数的取值（避开 0 处常见的梯度为零的驻点）
+const DEFAULT_INITIAL_VALUE: f64 = 0.1;
+
+// ============================================================================
+// Energy Objective
+// ============================================================================
+
+/// 以运行时估计的 ⟨H⟩ 为目标函数
+struct EnergyObjective<'a> {
+    runtime: &'a mut QuantumRuntime,
+    backend_id: &'a str,
+    estimator: &'a Estimator,
+    ansatz: &'a CircuitDag,
+    hamiltonian: &'a PauliSum,
+    parameters: &'a [Param],
+}
+
+impl EnergyObjective<'_> {
+    fn binding(&self, x: &[f64]) -> ParamBinding {
+        self.parameters.iter().cloned().zip(x.iter().copied()).collect()
+    }
+}
+
+impl Objective for EnergyObjective<'_> {
+    fn value(&mut self, x: &[f64]) -> Result<f64> {
+        let binding = self.binding(x);
+        let observables = std::slice::from_ref(self.hamiltonian);
+        let estimates = self.runtime.estimate_on(self.backend_id, self.estimator, self.ansatz, observables, &binding)?;
+        Ok(estimates[0].value)
+    }
+
+    /// 参数移位梯度：所有移位电路的期望值在一个批量作业中估计
+    fn gradient(&mut self, x: &[f64]) -> Result<Vec<f64>> {
+        let plan = ParameterShift::new().plan(self.ansatz, &self.binding(x))?;
+        debug_assert_eq!(plan.parameters, self.parameters);
+        let circuits = plan
+            .circuits
+            .iter()
+            .map(|circuit| self.runtime.transpile(self.backend_id, circuit))
+            .collect::<Result<Vec<_>>>()?;
+        let estimates = self.estimator.run_circuits(self.runtime, self.backend_id, &circuits, self.hamiltonian)?;
+        let values: Vec<f64> = estimates.iter().map(|e| e.value).collect();
+        Ok(plan.combine(&values))
+    }
+}
+
+/// 在运行时上最小化 ⟨ψ(θ)|H|ψ(θ)⟩
+fn minimize_energy(
+    runtime: &mut QuantumRuntime,
+    backend: Option<&str>,
+    optimizer: &dyn Optimizer,
+    estimator: &Estimator,
+    ansatz: &CircuitDag,
+    hamiltonian: &PauliSum,
+    initial_point: Option<&[f64]>,
+) -> Result<(Vec<Param>, OptimizationResult)> {
+    let parameters = ansatz.parameters();
+    let initial = match initial_point {
+        Some(point) if point.len() != parameters.len() => {
+            return Err(IrError::InvalidOperation(format!(
+                "Ansatz has {} parameters, initial point has {}", parameters.len(), point.len()
+            )));
+        }
+        Some(point) => point.to_vec(),
+        None => vec![DEFAULT_INITIAL_VALUE; parameters.len()],
+    };
+    let backend_id = backend.map_or_else(|| runtime.config().default_backend.clone(), str::to_string);
+
+    let mut objective = EnergyObjective {
+        runtime,
+        backend_id: &backend_id,
+        estimator,
+        ansatz,
+        hamiltonian,
+        parameters: &parameters,
+    };
+    let optimization = optimizer.minimize(&mut objective, &initial)?;
+    Ok((parameters, optimization))
+}
+
+// ============================================================================
+// VQE
+// ============================================================================
+
+/// VQE 结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct VqeResult {
+    /// 最优能量
+    pub energy: f64,
+    pub parameters: Vec<Param>,
+    /// 最优参数值（顺序同 `parameters`）
+    pub optimal_values: Vec<f64>,
+    /// 优化过程（含逐迭代历史）
+    pub optimization: OptimizationResult,
+}
+
+impl VqeResult {
+    /// 最优参数的绑定
+    pub fn binding(&self) -> ParamBinding {
+        self.parameters.iter().cloned().zip(self.optimal_values.iter().copied()).collect()
+    }
+}
+
+/// 变分量子本征求解器
+pub struct Vqe {
+    pub hamiltonian: PauliSum,
+    pub ansatz: CircuitDag,
+    optimizer: Box<dyn Optimizer>,
+    pub estimator: Estimator,
+    /// 目标后端（`None` 时使用运行时默认后端）
+    pub backend: Option<String>,
+    pub initial_point: Option<Vec<f64>>,
+}
+
+impl Vqe {
+    /// 默认使用 Nelder-Mead 与默认估计器
+    pub fn new(hamiltonian: PauliSum, ansatz: CircuitDag) -> Self {
+        Self {
+            hamiltonian,
+            ansatz,
+            optimizer: Box::new(NelderMead::new()),
+            estimator: Estimator::new(),
+            backend: None,
+            initial_point: None,
+        }
+    }
+
+    pub fn with_optimizer(mut self, optimizer: impl Optimizer + 'static) -> Self {
+        self.optimizer = Box::new(optimizer);
+        self
+    }
+
+    pub fn with_estimator(mut self, estimator: Estimator) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    pub fn with_backend(mut self, backend: impl Into<String>) -> Self {
+        self.backend = Some(backend.into());
+        self
+    }
+
+    pub fn with_initial_point(mut self, point: Vec<f64>) -> Self {
+        self.initial_point = Some(point);
+        self
+    }
+
+    pub fn optimizer(&self) -> &dyn Optimizer {
+        self.optimizer.as_ref()
+    }
+
+    pub fn run(&self, runtime: &mut QuantumRuntime) -> Result<VqeResult> {
+        let (parameters, optimization) = minimize_energy(
+            runtime,
+            self.backend.as_deref(),
+            self.optimizer.as_ref(),
+            &self.estimator,
+            &self.ansatz,
+            &self.hamiltonian,
+            self.initial_point.as_deref(),
+        )?;
+        Ok(VqeResult {
+            energy: optimization.value,
+            parameters,
+            optimal_values: optimization.parameters.clone(),
+            optimization,
+        })
+    }
+}
+
+// ============================================================================
+// QAOA
+// ============================================================================
+
+/// MaxCut 代价 Hamiltonian：Σ w/2·(Z_a Z_b − 1)，能量等于负的割权重
+pub fn maxcut_hamiltonian(edges: &[(LogicalQubitId, LogicalQubitId, f64)]) -> PauliSum {
+    let mut hamiltonian = PauliSum::new();
+    for &(a, b, weight) in edges {
+        hamiltonian.add_term(weight / 2.0, PauliString::new([(a, Pauli::Z), (b, Pauli::Z)]));
+        hamiltonian.add_term(-weight / 2.0, PauliString::identity());
+    }
+    hamiltonian
+}
+
+/// QAOA 结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct QaoaResult {
+    /// 最优参数下的 ⟨C⟩
+    pub energy: f64,
+    pub parameters: Vec<Param>,
+    pub optimal_values: Vec<f64>,
+    pub optimization: OptimizationResult,
+    /// 最优参数下采样得到的分布
+    pub distribution: QuasiDistribution,
+    /// 采样结果中代价最低者
+    pub best_outcome: u64,
+    pub best_cost: f64,
+}
+
+impl QaoaResult {
+    /// 最优结果的比特串（最大 qubit 在左）
+    pub fn best_bitstring(&self) -> String {
+        self.distribution.bitstring(self.best_outcome)
+    }
+}
+
+/// 量子近似优化算法
+///
+/// 第 k 层为 exp(−iγ_k C)·exp(−iβ_k Σ X)，参数名为 `gamma_k` 与 `beta_k`。
+pub struct Qaoa {
+    /// 代价 Hamiltonian（只含 Z 与恒等项）
+    pub cost: PauliSum,
+    pub depth: usize,
+    optimizer: Box<dyn Optimizer>,
+    pub estimator: Estimator,
+    pub backend: Option<String>,
+    pub initial_point: Option<Vec<f64>>,
+    /// 最终采样的 shots
+    pub shots: u32,
+}
+
+impl Qaoa {
+    pub fn new(cost: PauliSum, depth: usize) -> Self {
+        Self {
+            cost,
+            depth,
+            optimizer: Box::new(NelderMead::new()),
+            estimator: Estimator::new(),
+            backend: None,
+            initial_point: None,
+            shots: 1024,
+        }
+    }
+
+    /// 加权图上的 MaxCut
+    pub fn maxcut(edges: &[(LogicalQubitId, LogicalQubitId, f64)], depth: usize) -> Self {
+        Self::new(maxcut_hamiltonian(edges), depth)
+    }
+
+    pub fn with_optimizer(mut self, optimizer: impl Optimizer + 'static) -> Self {
+        self.optimizer = Box::new(optimizer);
+        self
+    }
+
+    pub fn with_estimator(mut self, estimator: Estimator) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    pub fn with_backend(mut self, backend: impl Into<String>) -> Self {
+        self.backend = Some(backend.into());
+        self
+    }
+
+    pub fn with_initial_point(mut self, point: Vec<f64>) -> Self {
+        self.initial_point = Some(point);
+        self
+    }
+
+    pub fn with_shots(mut self, shots: u32) -> Self {
+        self.shots = shots;
+        self
+    }
+
+    pub fn optimizer(&self) -> &dyn Optimizer {
+        self.optimizer.as_ref()
+    }
+
+    /// 构造 p 层 QAOA ansatz
+    pub fn ansatz(&self) -> Result<CircuitDag> {
+        if self.depth == 0 {
+            return Err(IrError::InvalidOperation("QAOA depth must be at least 1".to_string()));
+        }
+        if let Some((_, term)) = self.cost.terms().iter().find(|(_, s)| !s.is_diagonal()) {
+            return Err(IrError::UnsupportedOperation(format!(
+                "QAOA cost term {} is not diagonal", term
+            )));
+        }
+
+        let qubits = self.cost.qubits();
+        let mut ops: Vec<Operation> = qubits.iter().map(|&q| h(q)).collect();
+        for k in 0..self.depth {
+            let gamma = ParamExpr::from(Param::new(format!("gamma_{}", k)));
+            let beta = ParamExpr::from(Param::new(format!("beta_{}", k)));
+            for (coefficient, term) in self.cost.terms().iter().filter(|(_, s)| !s.is_identity()) {
+                // exp(−iγc Z…Z)：CNOT 阶梯把奇偶性汇集到最后一个 qubit 上再作 Rz(2γc)
+                let support = term.qubits();
+                let ladder: Vec<Operation> = support.windows(2).map(|w| cnot(w[0], w[1])).collect();
+                ops.extend(ladder.iter().cloned());
+                ops.push(rz_param(support[support.len() - 1], gamma.clone() * (2.0 * coefficient)));
+                ops.extend(ladder.into_iter().rev());
+            }
+            ops.extend(qubits.iter().map(|&q| rx_param(q, beta.clone() * 2.0)));
+        }
+
+        let mut circuit = CircuitDag::new();
+        for op in ops {
+            circuit.add_node(op);
+        }
+        Ok(circuit.rebuild_with(circuit.operations_in_order()))
+    }
+
+    /// 计算基结果的代价
+    pub fn cost_of(&self, outcome: u64, qubits: &[LogicalQubitId]) -> Result<f64> {
+        self.cost
+            .terms()
+            .iter()
+            .map(|(coefficient, term)| {
+                let mut parity = false;
+                for q in term.qubits() {
+                    let bit = qubits.iter().position(|&m| m == q).ok_or_else(|| {
+                        IrError::QubitNotFound(format!("{} is not in the outcome", q))
+                    })?;
+                    parity ^= outcome >> bit & 1 == 1;
+                }
+                Ok(if parity { -coefficient } else { *coefficient })
+            })
+            .sum()
+    }
+
+    pub fn run(&self, runtime: &mut QuantumRuntime) -> Result<QaoaResult> {
+        let ansatz = self.ansatz()?;
+        let (parameters, optimization) = minimize_energy(
+            runtime,
+            self.backend.as_deref(),
+            self.optimizer.as_ref(),
+            &self.estimator,
+            &ansatz,
+            &self.cost,
+            self.initial_point.as_deref(),
+        )?;
+
+        let binding: ParamBinding = parameters.iter().cloned().zip(optimization.parameters.iter().copied()).collect();
+        let backend_id = self.backend.clone().unwrap_or_else(|| runtime.config().default_backend.clone());
+        let distribution = runtime.sample_on(&backend_id, &[ansatz.bind(&binding)?], self.shots)?.remove(0);
+
+        let mut best: Option<(u64, f64)> = None;
+        for (outcome, _) in distribution.iter() {
+            let cost = self.cost_of(outcome, distribution.qubits())?;
+            if best.is_none_or(|(_, b)| cost < b) {
+                best = Some((outcome, cost));
+            }
+        }
+        let (best_outcome, best_cost) = best.ok_or_else(|| {
+            IrError::JobExecutionFailed("QAOA sampling returned no outcomes".to_string())
+        })?;
+
+        Ok(QaoaResult {
+            energy: optimization.value,
+            parameters,
+            optimal_values: optimization.parameters.clone(),
+            optimization,
+            distribution,
+            best_outcome,
+            best_cost,
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::IdealSimulatorBackend;
+    use crate::optimizer::{Cobyla, GradientDescent, Spsa};
+    use crate::params::ry_param;
+    use crate::runtime::RuntimeConfig;
+    use std::sync::Arc;
+
+    fn q(i: u64) -> LogicalQubitId {
+        LogicalQubitId::new(i)
+    }
+
+    fn runtime() -> QuantumRuntime {
+        let mut runtime = QuantumRuntime::new(RuntimeConfig::new().with_default_backend("ideal"));
+        runtime.register_backend("ideal", Arc::new(IdealSimulatorBackend::new()));
+        runtime
+    }
+
+    /// H = Z + X，基态能量 −√2
+    fn single_qubit_problem() -> (PauliSum, CircuitDag) {
+        let hamiltonian = PauliSum::new()
+            .with_term(1.0, PauliString::parse("Z0").unwrap())
+            .with_term(1.0, PauliString::parse("X0").unwrap());
+        let mut ansatz = CircuitDag::new();
+        ansatz.add_node(ry_param(q(0), Param::new("theta")));
+        (hamiltonian, ansatz)
+    }
+
+    #[test]
+    fn test_vqe_with_each_optimizer() {
+        let (hamiltonian, ansatz) = single_qubit_problem();
+        let vqe = || Vqe::new(hamiltonian.clone(), ansatz.clone());
+        let cases = [
+            (vqe().with_optimizer(NelderMead::new()), 1e-6),
+            (vqe().with_optimizer(Cobyla::new()), 1e-6),
+            (vqe().with_optimizer(GradientDescent::new().with_learning_rate(0.3)), 1e-6),
+            (vqe().with_optimizer(Spsa::new().with_max_iterations(400)), 1e-2),
+        ];
+        for (vqe, tolerance) in cases {
+            let mut runtime = runtime();
+            let result = vqe.run(&mut runtime).unwrap();
+            let name = vqe.optimizer().name();
+            assert!((result.energy + 2f64.sqrt()).abs() < tolerance, "{}: {}", name, result.energy);
+            assert!(!result.optimization.history.is_empty(), "{}", name);
+            assert_eq!(result.parameters, vec![Param::new("theta")]);
+        }
+    }
+
+    #[test]
+    fn test_vqe_sampled_gradient_descent() {
+        let (hamiltonian, ansatz) = single_qubit_problem();
+        let mut runtime = runtime();
+        let result = Vqe::new(hamiltonian, ansatz)
+            .with_optimizer(GradientDescent::new().with_learning_rate(0.3).with_max_iterations(25))
+            .with_estimator(Estimator::new().with_exact(false).with_shots(2000))
+            .with_initial_point(vec![0.5])
+            .run(&mut runtime)
+            .unwrap();
+        assert!(result.energy < -1.3);
+        // 每次迭代一个梯度作业和一个能量作业
+        assert_eq!(runtime.stats().total_jobs_submitted, 2 * result.optimization.iterations as u64);
+        let binding = result.binding();
+        assert_eq!(binding[&Param::new("theta")], result.optimal_values[0]);
+        assert!(Vqe::new(PauliSum::new(), CircuitDag::new())
+            .with_initial_point(vec![0.0])
+            .run(&mut runtime)
+            .is_err());
+    }
+
+    #[test]
+    fn test_qaoa_ansatz() {
+        let edges = [(q(0), q(1), 1.0), (q(1), q(2), 1.0)];
+        let qaoa = Qaoa::maxcut(&edges, 2);
+        let ansatz = qaoa.ansatz().unwrap();
+        assert_eq!(ansatz.parameters().len(), 4);
+        assert_eq!(qaoa.cost.constant(), -1.0);
+        // 0,1 割开而 1,2 未割开：代价为 −1
+        assert_eq!(qaoa.cost_of(0b001, &[q(0), q(1), q(2)]).unwrap(), -1.0);
+        assert_eq!(qaoa.cost_of(0b010, &[q(0), q(1), q(2)]).unwrap(), -2.0);
+
+        let not_diagonal = PauliSum::from(PauliString::parse("X0").unwrap());
+        assert!(Qaoa::new(not_diagonal, 1).ansatz().is_err());
+        assert!(Qaoa::maxcut(&edges, 0).ansatz().is_err());
+    }
+
+    #[test]
+    fn test_qaoa_maxcut_ring() {
+        let edges = [(q(0), q(1), 1.0), (q(1), q(2), 1.0), (q(2), q(3), 1.0), (q(3), q(0), 1.0)];
+        let mut runtime = runtime();
+        let result = Qaoa::maxcut(&edges, 1).with_shots(500).run(&mut runtime).unwrap();
+        // p = 1 的环上 ⟨C⟩ 最多为 3/4 的最大割
+        assert!(result.energy < -2.9, "{}", result.energy);
+        assert_eq!(result.best_cost, -4.0);
+        assert!(["0101", "1010"].contains(&result.best_bitstring().as_str()));
+        assert_eq!(result.parameters, vec![Param::new("beta_0"), Param::new("gamma_0")]);
+    }
+}
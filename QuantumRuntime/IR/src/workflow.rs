@@ -0,0 +1,668 @@
+//! 工作流模块
+//!
+//! 由作业组成的 DAG，下游节点可以读取上游的结果：
+//! - 电路节点由上游结果生成 `Job`（先校准再运行、自适应层析等），提交到 `QuantumRuntime` 执行；
+//! - 经典节点是普通 Rust 闭包，处理上游结果并产出任意值；
+//! - 节点失败时按 `FailurePolicy` 跳过下游、取消整个工作流或让下游继续执行。
+//!
+//! 电路节点在其上游全部结束后立即提交，由 Runtime 按其执行方式（串行或工作线程池）执行；
+//! Runtime 只执行本工作流的作业，队列中的其他作业不受影响。
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::job::{Job, JobId, JobResult, JobStatus};
+use crate::runtime::QuantumRuntime;
+use crate::{Result, IrError};
+
+/// 工作流节点标识（按添加顺序编号）
+pub type NodeId = usize;
+
+// ============================================================================
+// Status and Policies
+// ============================================================================
+
+/// 节点失败时对工作流其余部分的处理
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailurePolicy {
+    /// 跳过依赖它的下游节点，其余分支照常执行（默认）
+    #[default]
+    Skip,
+    /// 取消工作流中所有尚未结束的节点
+    Cancel,
+    /// 下游节点照常执行，可从输入中看到失败的上游
+    Continue,
+}
+
+/// 节点状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeStatus {
+    Pending,
+    /// 作业已提交到 Runtime
+    Running,
+    Completed,
+    Failed,
+    /// 上游失败而未执行
+    Skipped,
+    /// 工作流被取消时尚未结束
+    Cancelled,
+}
+
+impl NodeStatus {
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, NodeStatus::Pending | NodeStatus::Running)
+    }
+}
+
+/// 整个工作流的结束状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkflowStatus {
+    /// 所有节点都已完成
+    Completed,
+    /// 有节点失败或被跳过
+    Failed,
+    /// 因节点失败而被取消
+    Cancelled,
+}
+
+// ============================================================================
+// Node Outputs
+// ============================================================================
+
+/// 节点的输出
+#[derive(Clone)]
+pub enum NodeOutput {
+    /// 电路节点的作业结果（失败的作业也保留其结果）
+    Job(Box<JobResult>),
+    /// 经典节点产出的值
+    Value(Arc<dyn Any + Send + Sync>),
+}
+
+impl NodeOutput {
+    pub fn from_value<T: Any + Send + Sync>(value: T) -> Self {
+        NodeOutput::Value(Arc::new(value))
+    }
+
+    /// 作业结果
+    pub fn job_result(&self) -> Option<&JobResult> {
+        match self {
+            NodeOutput::Job(result) => Some(result),
+            NodeOutput::Value(_) => None,
+        }
+    }
+
+    /// 经典节点的值（类型不符时为 None）
+    pub fn downcast<T: Any>(&self) -> Option<&T> {
+        match self {
+            NodeOutput::Job(_) => None,
+            NodeOutput::Value(value) => value.downcast_ref(),
+        }
+    }
+}
+
+impl std::fmt::Debug for NodeOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeOutput::Job(result) => f.debug_tuple("Job").field(result).finish(),
+            NodeOutput::Value(_) => f.write_str("Value(..)"),
+        }
+    }
+}
+
+/// 节点的执行记录
+#[derive(Debug, Clone)]
+pub struct NodeReport {
+    pub name: String,
+    pub status: NodeStatus,
+    /// 电路节点提交的作业
+    pub job_id: Option<JobId>,
+    pub output: Option<NodeOutput>,
+    pub error: Option<String>,
+}
+
+impl NodeReport {
+    fn new(name: &str) -> Self {
+        Self { name: name.to_string(), status: NodeStatus::Pending, job_id: None, output: None, error: None }
+    }
+}
+
+/// 节点闭包可见的已结束节点
+pub struct WorkflowInputs<'a> {
+    nodes: &'a [NodeReport],
+}
+
+impl WorkflowInputs<'_> {
+    pub fn status(&self, node: NodeId) -> Option<NodeStatus> {
+        self.nodes.get(node).map(|n| n.status)
+    }
+
+    pub fn error(&self, node: NodeId) -> Option<&str> {
+        self.nodes.get(node)?.error.as_deref()
+    }
+
+    pub fn output(&self, node: NodeId) -> Option<&NodeOutput> {
+        self.nodes.get(node)?.output.as_ref()
+    }
+
+    /// 上游电路节点的作业结果
+    pub fn result(&self, node: NodeId) -> Result<&JobResult> {
+        self.output(node)
+            .and_then(NodeOutput::job_result)
+            .ok_or_else(|| IrError::InvalidOperation(format!("Workflow node {} has no job result", node)))
+    }
+
+    /// 上游经典节点的值
+    pub fn value<T: Any>(&self, node: NodeId) -> Result<&T> {
+        self.output(node)
+            .and_then(NodeOutput::downcast)
+            .ok_or_else(|| IrError::InvalidOperation(format!("Workflow node {} has no value of the requested type", node)))
+    }
+}
+
+/// 一次运行的结果
+#[derive(Debug, Clone)]
+pub struct WorkflowReport {
+    pub status: WorkflowStatus,
+    /// 按 `NodeId` 排列
+    pub nodes: Vec<NodeReport>,
+}
+
+impl WorkflowReport {
+    pub fn node(&self, node: NodeId) -> Option<&NodeReport> {
+        self.nodes.get(node)
+    }
+
+    pub fn result(&self, node: NodeId) -> Option<&JobResult> {
+        self.node(node)?.output.as_ref()?.job_result()
+    }
+
+    pub fn value<T: Any>(&self, node: NodeId) -> Option<&T> {
+        self.node(node)?.output.as_ref()?.downcast()
+    }
+
+    /// 处于给定状态的节点数
+    pub fn count(&self, status: NodeStatus) -> usize {
+        self.nodes.iter().filter(|n| n.status == status).count()
+    }
+}
+
+// ============================================================================
+// Workflow
+// ============================================================================
+
+type JobGenerator = Box<dyn Fn(&WorkflowInputs) -> Result<Job>>;
+type ClassicalStep = Box<dyn Fn(&WorkflowInputs) -> Result<NodeOutput>>;
+
+enum NodeKind {
+    Circuit(JobGenerator),
+    Classical(ClassicalStep),
+}
+
+struct WorkflowNode {
+    name: String,
+    depends_on: Vec<NodeId>,
+    kind: NodeKind,
+    on_failure: Option<FailurePolicy>,
+}
+
+/// 作业工作流
+///
+/// 节点只能依赖已添加的节点，因此总是无环的。工作流本身不保存运行状态，可以多次运行；
+/// 每次运行由生成函数重新创建作业。
+pub struct Workflow {
+    name: String,
+    nodes: Vec<WorkflowNode>,
+    on_failure: FailurePolicy,
+}
+
+impl Workflow {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), nodes: Vec::new(), on_failure: FailurePolicy::default() }
+    }
+
+    /// 节点未单独设置时的失败处理（默认 `Skip`）
+    pub fn with_failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.on_failure = policy;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// 添加固定作业
+    ///
+    /// 作业 ID 固定，同一 Runtime 上再次运行时提交会失败；需要重复运行时用 `add_circuit`。
+    pub fn add_job(&mut self, name: impl Into<String>, depends_on: &[NodeId], job: Job) -> Result<NodeId> {
+        self.add_circuit(name, depends_on, move |_| Ok(job.clone()))
+    }
+
+    /// 添加由上游输出生成作业的电路节点
+    pub fn add_circuit<F>(&mut self, name: impl Into<String>, depends_on: &[NodeId], generate: F) -> Result<NodeId>
+    where
+        F: Fn(&WorkflowInputs) -> Result<Job> + 'static,
+    {
+        self.add_node(name.into(), depends_on, NodeKind::Circuit(Box::new(generate)))
+    }
+
+    /// 添加经典计算节点，返回值可被下游以 `WorkflowInputs::value` 读取
+    pub fn add_classical<T, F>(&mut self, name: impl Into<String>, depends_on: &[NodeId], step: F) -> Result<NodeId>
+    where
+        T: Any + Send + Sync,
+        F: Fn(&WorkflowInputs) -> Result<T> + 'static,
+    {
+        let step = move |inputs: &WorkflowInputs| step(inputs).map(NodeOutput::from_value);
+        self.add_node(name.into(), depends_on, NodeKind::Classical(Box::new(step)))
+    }
+
+    /// 设置节点失败时的处理
+    pub fn set_failure_policy(&mut self, node: NodeId, policy: FailurePolicy) -> Result<()> {
+        let len = self.nodes.len();
+        let node = self
+            .nodes
+            .get_mut(node)
+            .ok_or_else(|| IrError::InvalidOperation(format!("Workflow node {} does not exist ({} nodes)", node, len)))?;
+        node.on_failure = Some(policy);
+        Ok(())
+    }
+
+    fn add_node(&mut self, name: String, depends_on: &[NodeId], kind: NodeKind) -> Result<NodeId> {
+        let id = self.nodes.len();
+        if let Some(&missing) = depends_on.iter().find(|&&dep| dep >= id) {
+            return Err(IrError::InvalidOperation(format!(
+                "Workflow node {} depends on unknown node {}", name, missing
+            )));
+        }
+        let mut depends_on = depends_on.to_vec();
+        depends_on.sort_unstable();
+        depends_on.dedup();
+        self.nodes.push(WorkflowNode { name, depends_on, kind, on_failure: None });
+        Ok(id)
+    }
+
+    fn policy(&self, node: NodeId) -> FailurePolicy {
+        self.nodes[node].on_failure.unwrap_or(self.on_failure)
+    }
+
+    /// 在 Runtime 上运行工作流
+    ///
+    /// 就绪的经典节点立即执行，就绪的电路节点提交后由 `QuantumRuntime::execute_jobs_with` 执行；
+    /// 每个作业结束时立即执行或提交因此就绪的下游节点，不等待同批的其他作业。
+    /// Runtime 中排队的其他作业不会被执行。提交后未能执行的作业被取消并视为失败。
+    pub fn run(&self, runtime: &mut QuantumRuntime) -> WorkflowReport {
+        let mut run = WorkflowRun {
+            nodes: self.nodes.iter().map(|n| NodeReport::new(&n.name)).collect(),
+            jobs: HashMap::new(),
+            cancelled: false,
+        };
+
+        let submitted = self.dispatch(runtime, &mut run);
+        if !submitted.is_empty() {
+            runtime.execute_jobs_with(submitted, |runtime, job_id| {
+                if let Some(&id) = run.jobs.get(&job_id) {
+                    self.record_job(runtime, &mut run, id);
+                }
+                self.dispatch(runtime, &mut run)
+            });
+        }
+        // 未被执行的作业（例如 Runtime 无法调度）在此收尾
+        for id in 0..run.nodes.len() {
+            if run.nodes[id].status == NodeStatus::Running {
+                self.record_job(runtime, &mut run, id);
+            }
+        }
+
+        let WorkflowRun { mut nodes, cancelled, .. } = run;
+        if cancelled {
+            for node in nodes.iter_mut().filter(|n| !n.status.is_terminal()) {
+                node.status = NodeStatus::Cancelled;
+            }
+        }
+        let status = if cancelled {
+            WorkflowStatus::Cancelled
+        } else if nodes.iter().all(|n| n.status == NodeStatus::Completed) {
+            WorkflowStatus::Completed
+        } else {
+            WorkflowStatus::Failed
+        };
+        WorkflowReport { status, nodes }
+    }
+
+    /// 执行或提交所有就绪的节点，直到没有新的就绪节点；返回新提交的作业
+    ///
+    /// 工作流已取消时不再派发，并取消本轮提交的作业。
+    fn dispatch(&self, runtime: &mut QuantumRuntime, run: &mut WorkflowRun) -> Vec<JobId> {
+        let mut submitted = Vec::new();
+        while !run.cancelled {
+            let nodes = &mut run.nodes;
+            let ready: Vec<NodeId> = (0..nodes.len())
+                .filter(|&id| nodes[id].status == NodeStatus::Pending)
+                .filter(|&id| self.nodes[id].depends_on.iter().all(|&dep| nodes[dep].status.is_terminal()))
+                .collect();
+            if ready.is_empty() {
+                break;
+            }
+            for id in ready {
+                if let Some(blocker) = self.blocking_dependency(id, nodes) {
+                    nodes[id].status = NodeStatus::Skipped;
+                    nodes[id].error = Some(format!(
+                        "Dependency {} ({}) ended as {:?}", blocker, nodes[blocker].name, nodes[blocker].status
+                    ));
+                    continue;
+                }
+                let inputs = WorkflowInputs { nodes };
+                match &self.nodes[id].kind {
+                    NodeKind::Circuit(generate) => match generate(&inputs).and_then(|job| runtime.submit_job(job)) {
+                        Ok(job_id) => {
+                            nodes[id].status = NodeStatus::Running;
+                            nodes[id].job_id = Some(job_id);
+                            run.jobs.insert(job_id, id);
+                            submitted.push(job_id);
+                        }
+                        Err(e) => run.cancelled |= self.fail(&mut nodes[id], id, e.to_string(), None),
+                    },
+                    NodeKind::Classical(step) => match step(&inputs) {
+                        Ok(output) => {
+                            nodes[id].status = NodeStatus::Completed;
+                            nodes[id].output = Some(output);
+                        }
+                        Err(e) => run.cancelled |= self.fail(&mut nodes[id], id, e.to_string(), None),
+                    },
+                }
+            }
+        }
+        if run.cancelled {
+            self.cancel_running(runtime, run);
+        }
+        submitted
+    }
+
+    /// 按 Runtime 中的作业结果更新电路节点
+    fn record_job(&self, runtime: &mut QuantumRuntime, run: &mut WorkflowRun, id: NodeId) {
+        let job_id = run.nodes[id].job_id.expect("submitted node has a job");
+        let node = &mut run.nodes[id];
+        match runtime.get_job_result(job_id) {
+            Some(result) if result.status == JobStatus::Completed => {
+                node.status = NodeStatus::Completed;
+                node.output = Some(NodeOutput::Job(Box::new(result)));
+            }
+            Some(result) if result.status == JobStatus::Cancelled && run.cancelled => {
+                node.status = NodeStatus::Cancelled;
+                node.output = Some(NodeOutput::Job(Box::new(result)));
+            }
+            Some(result) => {
+                let error = result.error.clone().unwrap_or_else(|| format!("Job ended as {:?}", result.status));
+                run.cancelled |= self.fail(node, id, error, Some(result));
+            }
+            None => {
+                runtime.cancel_job(job_id);
+                let error = format!("Job {} was not executed by the runtime", job_id);
+                run.cancelled |= self.fail(node, id, error, None);
+            }
+        }
+    }
+
+    /// 取消仍在 Runtime 中的作业
+    fn cancel_running(&self, runtime: &mut QuantumRuntime, run: &WorkflowRun) {
+        for node in run.nodes.iter().filter(|n| n.status == NodeStatus::Running) {
+            runtime.cancel_job(node.job_id.expect("submitted node has a job"));
+        }
+    }
+
+    /// 使节点不能执行的上游：未完成且不允许下游继续
+    fn blocking_dependency(&self, node: NodeId, nodes: &[NodeReport]) -> Option<NodeId> {
+        self.nodes[node].depends_on.iter().copied().find(|&dep| match nodes[dep].status {
+            NodeStatus::Completed => false,
+            NodeStatus::Failed => self.policy(dep) != FailurePolicy::Continue,
+            _ => true,
+        })
+    }
+
+    /// 标记节点失败，返回是否需要取消工作流
+    fn fail(&self, report: &mut NodeReport, node: NodeId, error: String, result: Option<JobResult>) -> bool {
+        report.status = NodeStatus::Failed;
+        report.error = Some(error);
+        report.output = result.map(|r| NodeOutput::Job(Box::new(r)));
+        self.policy(node) == FailurePolicy::Cancel
+    }
+}
+
+/// 一次运行的状态
+struct WorkflowRun {
+    nodes: Vec<NodeReport>,
+    /// 作业 ID 到电路节点
+    jobs: HashMap<JobId, NodeId>,
+    cancelled: bool,
+}
+
+impl std::fmt::Debug for Workflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self.nodes.iter().map(|n| n.name.as_str()).collect();
+        f.debug_struct("Workflow")
+            .field("name", &self.name)
+            .field("nodes", &names)
+            .field("on_failure", &self.on_failure)
+            .finish()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::IdealSimulatorBackend;
+    use crate::circuit::CircuitDag;
+    use crate::job::JobScheduler;
+    use crate::operation::{measure, rx, x};
+    use crate::qubit::LogicalQubitId;
+    use crate::backend::{BackendAdapter, BackendCapabilities, BackendCircuit};
+    use crate::runtime::{ExecutionMode, RuntimeConfig};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Condvar, Mutex};
+    use std::time::{Duration, Instant};
+
+    fn sim_runtime() -> QuantumRuntime {
+        let mut runtime = QuantumRuntime::new(RuntimeConfig::new().with_default_backend("sim"));
+        runtime.register_backend("sim", Arc::new(IdealSimulatorBackend::new()));
+        *runtime.scheduler_mut() = JobScheduler::new(4).with_qubits((0..4).map(LogicalQubitId::new).collect());
+        runtime
+    }
+
+    /// 作业等待闸门打开（最多 5 秒）后才在模拟器上执行
+    struct GatedBackend {
+        inner: IdealSimulatorBackend,
+        open: Mutex<bool>,
+        opened: Condvar,
+        timed_out: AtomicBool,
+    }
+
+    impl GatedBackend {
+        fn open(&self) {
+            *self.open.lock().unwrap() = true;
+            self.opened.notify_all();
+        }
+    }
+
+    impl BackendAdapter for GatedBackend {
+        fn id(&self) -> &str { "gated" }
+        fn capabilities(&self) -> BackendCapabilities { self.inner.capabilities() }
+        fn translate_circuit(&self, circuit: &CircuitDag) -> Result<BackendCircuit> { self.inner.translate_circuit(circuit) }
+        fn submit_job(&self, job: &Job) -> Result<JobId> { self.inner.submit_job(job) }
+        fn get_job_status(&self, job_id: JobId) -> Result<JobStatus> { self.inner.get_job_status(job_id) }
+        fn get_job_result(&self, job_id: JobId) -> Result<JobResult> { self.inner.get_job_result(job_id) }
+        fn cancel_job(&self, job_id: JobId) -> Result<()> { self.inner.cancel_job(job_id) }
+
+        fn execute(&self, job: &Job) -> Result<JobResult> {
+            let open = self.open.lock().unwrap();
+            let (_open, wait) = self.opened.wait_timeout_while(open, Duration::from_secs(5), |open| !*open).unwrap();
+            self.timed_out.store(wait.timed_out(), Ordering::SeqCst);
+            self.inner.execute(job)
+        }
+    }
+
+    fn flip_job(qubit: u64) -> Job {
+        let q = LogicalQubitId::new(qubit);
+        let mut circuit = CircuitDag::new();
+        circuit.add_node(x(q));
+        circuit.add_node(measure(q));
+        Job::new(circuit, 50, "sim")
+    }
+
+    /// 目标后端未注册的作业，执行时失败
+    fn unroutable_job(qubit: u64) -> Job {
+        let mut job = flip_job(qubit);
+        job.target_backend = "missing".to_string();
+        job
+    }
+
+    #[test]
+    fn test_calibrate_then_run() {
+        let q0 = LogicalQubitId::new(0);
+        let q1 = LogicalQubitId::new(1);
+        let mut workflow = Workflow::new("calibrate-then-run");
+        let calibrate = workflow.add_job("calibrate", &[], flip_job(0)).unwrap();
+        let angle = workflow
+            .add_classical("angle", &[calibrate], move |inputs| {
+                let p = inputs.result(calibrate)?.get_probability(q0).unwrap_or(0.0);
+                Ok(std::f64::consts::PI * p)
+            })
+            .unwrap();
+        let run = workflow
+            .add_circuit("run", &[angle], move |inputs| {
+                let theta = *inputs.value::<f64>(angle)?;
+                let mut circuit = CircuitDag::new();
+                circuit.add_node(rx(q1, theta));
+                circuit.add_node(measure(q1));
+                Ok(Job::new(circuit, 50, "sim"))
+            })
+            .unwrap();
+
+        let mut runtime = sim_runtime();
+        let report = workflow.run(&mut runtime);
+        assert_eq!(report.status, WorkflowStatus::Completed);
+        assert_eq!(report.value::<f64>(angle), Some(&std::f64::consts::PI));
+        assert_eq!(report.result(run).unwrap().get_probability(q1), Some(1.0));
+        assert_eq!(runtime.get_job_status(report.node(run).unwrap().job_id.unwrap()), Some(JobStatus::Completed));
+    }
+
+    #[test]
+    fn test_failure_skips_dependents() {
+        let mut workflow = Workflow::new("skip");
+        let broken = workflow
+            .add_classical::<f64, _>("broken", &[], |_| Err(IrError::InvalidOperation("fit diverged".into())))
+            .unwrap();
+        let downstream = workflow.add_job("downstream", &[broken], flip_job(0)).unwrap();
+        let after = workflow.add_classical("after", &[downstream], |_| Ok(())).unwrap();
+        let independent = workflow.add_job("independent", &[], flip_job(1)).unwrap();
+
+        let report = workflow.run(&mut sim_runtime());
+        assert_eq!(report.status, WorkflowStatus::Failed);
+        assert_eq!(report.node(broken).unwrap().status, NodeStatus::Failed);
+        assert!(report.node(broken).unwrap().error.as_ref().unwrap().contains("fit diverged"));
+        assert_eq!(report.node(downstream).unwrap().status, NodeStatus::Skipped);
+        assert_eq!(report.node(after).unwrap().status, NodeStatus::Skipped);
+        assert_eq!(report.node(independent).unwrap().status, NodeStatus::Completed);
+    }
+
+    #[test]
+    fn test_dispatch_on_completion() {
+        let gate = Arc::new(GatedBackend {
+            inner: IdealSimulatorBackend::new(),
+            open: Mutex::new(false),
+            opened: Condvar::new(),
+            timed_out: AtomicBool::new(false),
+        });
+        let config = RuntimeConfig::new().with_max_jobs(2).with_execution_mode(ExecutionMode::WorkerPool);
+        let mut runtime = QuantumRuntime::new(config);
+        runtime.register_backend("sim", Arc::new(IdealSimulatorBackend::new()));
+        runtime.register_backend("gated", gate.clone());
+        *runtime.scheduler_mut() = JobScheduler::new(2).with_qubits((0..4).map(LogicalQubitId::new).collect());
+        let unrelated = runtime.submit_job(flip_job(3)).unwrap();
+
+        // 慢作业等待闸门；闸门由快作业的下游节点打开，只有逐个派发时慢作业才不必等到超时
+        let mut workflow = Workflow::new("pipelined");
+        let fast = workflow.add_job("fast", &[], flip_job(0)).unwrap();
+        let mut slow_job = flip_job(1);
+        slow_job.target_backend = "gated".to_string();
+        let slow = workflow.add_job("slow", &[], slow_job).unwrap();
+        let opener = {
+            let gate = gate.clone();
+            workflow.add_classical("open", &[fast], move |_| {
+                gate.open();
+                Ok(())
+            }).unwrap()
+        };
+        let follow_up = workflow.add_job("follow-up", &[opener], flip_job(2)).unwrap();
+
+        let start = Instant::now();
+        let report = workflow.run(&mut runtime);
+        assert_eq!(report.status, WorkflowStatus::Completed);
+        assert!(!gate.timed_out.load(Ordering::SeqCst));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(report.result(slow).is_some() && report.result(follow_up).is_some());
+
+        // 工作流之外的作业留在队列中
+        assert_eq!(runtime.get_job_status(unrelated), Some(JobStatus::Queued));
+        assert_eq!(runtime.execute_all().len(), 1);
+        assert_eq!(runtime.get_job_status(unrelated), Some(JobStatus::Completed));
+    }
+
+    #[test]
+    fn test_failure_cancels_workflow() {
+        let mut workflow = Workflow::new("cancel");
+        let first = workflow.add_job("first", &[], flip_job(0)).unwrap();
+        let unavailable = workflow.add_job("unavailable", &[first], unroutable_job(1)).unwrap();
+        workflow.set_failure_policy(unavailable, FailurePolicy::Cancel).unwrap();
+        let next = workflow.add_job("next", &[unavailable], flip_job(2)).unwrap();
+        let sibling = workflow.add_classical("sibling", &[first], |_| Ok(1_u32)).unwrap();
+        let late = workflow.add_classical("late", &[sibling, first], |_| Ok(2_u32)).unwrap();
+
+        let report = workflow.run(&mut sim_runtime());
+        assert_eq!(report.status, WorkflowStatus::Cancelled);
+        assert_eq!(report.node(first).unwrap().status, NodeStatus::Completed);
+        assert_eq!(report.node(unavailable).unwrap().status, NodeStatus::Failed);
+        assert_eq!(report.node(unavailable).unwrap().output.as_ref().unwrap().job_result().unwrap().status, JobStatus::Failed);
+        assert_eq!(report.node(sibling).unwrap().status, NodeStatus::Completed);
+        assert_eq!(report.node(next).unwrap().status, NodeStatus::Cancelled);
+        // 经典节点在上游结束后立即执行，早于失败的作业结束
+        assert_eq!(report.node(late).unwrap().status, NodeStatus::Completed);
+    }
+
+    #[test]
+    fn test_continue_after_failure() {
+        let mut workflow = Workflow::new("continue").with_failure_policy(FailurePolicy::Continue);
+        let broken = workflow.add_job("broken", &[], unroutable_job(0)).unwrap();
+        let summary = workflow
+            .add_classical("summary", &[broken], move |inputs| {
+                Ok(inputs.status(broken) == Some(NodeStatus::Failed) && inputs.result(broken).is_ok())
+            })
+            .unwrap();
+
+        let report = workflow.run(&mut sim_runtime());
+        assert_eq!(report.status, WorkflowStatus::Failed);
+        assert_eq!(report.value::<bool>(summary), Some(&true));
+        assert_eq!(report.count(NodeStatus::Completed), 1);
+    }
+
+    #[test]
+    fn test_unknown_dependency() {
+        let mut workflow = Workflow::new("invalid");
+        assert!(matches!(workflow.add_job("orphan", &[0], flip_job(0)), Err(IrError::InvalidOperation(_))));
+        let first = workflow.add_classical("first", &[], |_| Ok(0_u8)).unwrap();
+        assert!(workflow.add_classical("second", &[first, first], |_| Ok(1_u8)).is_ok());
+        assert!(workflow.set_failure_policy(5, FailurePolicy::Cancel).is_err());
+        assert_eq!(workflow.len(), 2);
+    }
+}